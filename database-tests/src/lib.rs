@@ -0,0 +1,2 @@
+//! Tests of the column types of cron against an in-memory SQLite database, run with
+//! `cargo test --workspace` or `cargo test -p cron-database-tests`.
@@ -0,0 +1,51 @@
+use cron::{CronExpr, Schedule};
+use diesel::prelude::*;
+use std::str::FromStr;
+
+#[test]
+fn test_diesel() {
+    diesel::table! {
+        jobs (name) {
+            name -> Text,
+            schedule -> Text,
+        }
+    }
+
+    let mut connection = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query("CREATE TABLE jobs (name TEXT PRIMARY KEY, schedule TEXT NOT NULL)")
+        .execute(&mut connection)
+        .unwrap();
+    let schedule = Schedule::from_str("0 0 9 * * Mon-Fri # standup").unwrap();
+    diesel::insert_into(jobs::table)
+        .values((jobs::name.eq("standup"), jobs::schedule.eq(&schedule)))
+        .execute(&mut connection)
+        .unwrap();
+    let read: Schedule = jobs::table
+        .select(jobs::schedule)
+        .first(&mut connection)
+        .unwrap();
+    assert_eq!(schedule.source(), read.source());
+    let (name, expression): (String, CronExpr) = jobs::table.first(&mut connection).unwrap();
+    assert_eq!(
+        ("standup", "0 0 9 * * Mon-Fri # standup"),
+        (&*name, &*expression)
+    );
+    diesel::update(jobs::table)
+        .set(jobs::schedule.eq(CronExpr::from_str("0 0 10 * * *").unwrap()))
+        .execute(&mut connection)
+        .unwrap();
+
+    // Invalid expressions fail to load.
+    diesel::insert_into(jobs::table)
+        .values((jobs::name.eq("broken"), jobs::schedule.eq("0 0 25 * * *")))
+        .execute(&mut connection)
+        .unwrap();
+    let broken = jobs::table
+        .filter(jobs::name.eq("broken"))
+        .select(jobs::schedule)
+        .first::<Schedule>(&mut connection);
+    assert!(matches!(
+        broken,
+        Err(diesel::result::Error::DeserializationError(_))
+    ));
+}
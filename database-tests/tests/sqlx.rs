@@ -0,0 +1,37 @@
+use cron::{CronExpr, Schedule};
+use sqlx::{Connection, SqliteConnection};
+use std::str::FromStr;
+
+#[tokio::test]
+async fn test_sqlx() {
+    let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE jobs (name TEXT, schedule TEXT)")
+        .execute(&mut connection)
+        .await
+        .unwrap();
+    let schedule = Schedule::from_str("0 0 9 * * Mon-Fri # standup").unwrap();
+    sqlx::query("INSERT INTO jobs VALUES (?, ?)")
+        .bind("standup")
+        .bind(&schedule)
+        .execute(&mut connection)
+        .await
+        .unwrap();
+    let (name, read): (String, Schedule) = sqlx::query_as("SELECT name, schedule FROM jobs")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap();
+    assert_eq!(("standup", &schedule), (name.as_str(), &read));
+    assert_eq!(schedule.source(), read.source());
+    let expression: CronExpr = sqlx::query_scalar("SELECT schedule FROM jobs")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap();
+    assert_eq!("0 0 9 * * Mon-Fri # standup", &*expression);
+
+    // Invalid expressions fail to decode.
+    let error = sqlx::query_scalar::<_, Schedule>("SELECT '0 0 25 * * *'")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, sqlx::Error::ColumnDecode { .. }));
+}
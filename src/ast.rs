@@ -0,0 +1,354 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::Error;
+use crate::parsing::{self, Dialect, ScheduleParser};
+use crate::schedule::Schedule;
+use crate::specifier::{PointValue, RootSpecifier, Specifier};
+use crate::syntax::FieldSyntax;
+use crate::visit::{walk_ast, Transform, Visitor};
+
+/// A value of a field, given either as a number or as a name, e.g. `5` or `FRI`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Value {
+    Number(u32),
+    Name(String),
+}
+
+/// A comma separated item of a field, as written in the expression.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Item {
+    /// `*`
+    All,
+    /// `?`, only allowed in the day fields.
+    Any,
+    /// A single value, e.g. `5` or `MON`.
+    Value(Value),
+    /// A range of values, e.g. `1-5` or `MON-FRI`.
+    Range(Value, Value),
+    /// Values repeating from `*`, a value or a range, e.g. `*/15` or `10-40/10`.
+    Step(Box<Item>, u32),
+    /// A value drawn at random between optional bounds, e.g. `~` or `0~30`.
+    Random(Option<u32>, Option<u32>),
+    /// A value derived from the parser's hash key, within an optional range and with an
+    /// optional step, e.g. `H`, `H(0-29)` or `H/15`.
+    Hashed(Option<(u32, u32)>, Option<u32>),
+    /// The last occurrence of a day of the week in the month, counting back by an offset, e.g.
+    /// `6L` or `FRIL-1`.
+    LastOfMonth(Value, u32),
+    /// The nth occurrence of a day of the week in the month, e.g. `MON#3`.
+    NthOfMonth(Value, u32),
+    /// The last day of the month, counting back by an offset, e.g. `L` or `L-2`.
+    LastDayOfMonth(u32),
+    /// The weekday nearest to a day of the month, e.g. `15W`.
+    NearestWeekday(u32),
+    /// The last weekday of the month, `LW`.
+    LastWeekdayOfMonth,
+    /// The three months of a quarter of the year, e.g. `Q3`, only allowed in the month field.
+    /// Quarters count from the [fiscal year start](crate::ScheduleParser::fiscal_year_start) of
+    /// the parser compiling the tree.
+    Quarter(u32),
+    /// The nth business day of the month, e.g. `3B`.
+    BusinessDay(u32),
+}
+
+/// Which field of an expression an [AstField] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    Seconds,
+    Minutes,
+    Hours,
+    DaysOfMonth,
+    Months,
+    DaysOfWeek,
+    Years,
+}
+
+impl FieldKind {
+    /// All fields, in the order of a Quartz expression.
+    pub const ALL: [FieldKind; 7] = [
+        FieldKind::Seconds,
+        FieldKind::Minutes,
+        FieldKind::Hours,
+        FieldKind::DaysOfMonth,
+        FieldKind::Months,
+        FieldKind::DaysOfWeek,
+        FieldKind::Years,
+    ];
+}
+
+/// A field of an [Ast], as the list of its comma separated items.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AstField {
+    pub items: Vec<Item>,
+}
+
+/// The syntax tree of a cron expression, as returned by [parse_to_ast], which tooling can
+/// analyze or rewrite before compiling it into a [Schedule].
+///
+/// Fields the dialect leaves out, like the seconds in Unix cron, are `None`, as are the years
+/// when a Quartz expression doesn't give them. Shorthands such as `@daily` are expanded into the
+/// equivalent Quartz expression.
+/// # Example
+/// ```
+/// use cron::{parse_to_ast, Item, Value};
+///
+/// let mut ast = parse_to_ast("0 30 9 * * Mon-Fri").unwrap();
+/// ast.hours.items.push(Item::Value(Value::Number(17)));
+/// assert_eq!("0 30 9,17 * * Mon-Fri", ast.to_string());
+/// let schedule = ast.compile().unwrap();
+/// assert_eq!("0 30 9,17 * * Mon-Fri", schedule.source());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ast {
+    pub seconds: Option<AstField>,
+    pub minutes: AstField,
+    pub hours: AstField,
+    pub days_of_month: AstField,
+    pub months: AstField,
+    pub days_of_week: AstField,
+    pub years: Option<AstField>,
+    /// The trailing `# comment` of the expression, without the `#`.
+    pub comment: Option<String>,
+    dialect: Dialect,
+}
+
+/// Parses a Quartz cron expression into its [Ast]. Use [ScheduleParser::parse_to_ast] for other
+/// dialects.
+pub fn parse_to_ast(expression: &str) -> Result<Ast, Error> {
+    ScheduleParser::new().parse_to_ast(expression)
+}
+
+impl Ast {
+    // The tree of a schedule, which parsing has already validated.
+    pub(crate) fn from_schedule(schedule: &Schedule) -> Result<Ast, Error> {
+        let syntax = match schedule.syntax() {
+            Some(syntax) => syntax.clone(),
+            None => parsing::quartz_syntax(schedule.fields())?,
+        };
+        let dialect = syntax.dialect;
+        let field = |position: u64, syntax: &FieldSyntax| {
+            (!syntax.is_omitted()).then(|| AstField::parse(dialect, position, syntax.text()))
+        };
+        Ok(Ast {
+            seconds: field(0, &syntax.seconds),
+            minutes: AstField::parse(dialect, 1, syntax.minutes.text()),
+            hours: AstField::parse(dialect, 2, syntax.hours.text()),
+            days_of_month: AstField::parse(dialect, 3, syntax.days_of_month.text()),
+            months: AstField::parse(dialect, 4, syntax.months.text()),
+            days_of_week: AstField::parse(dialect, 5, syntax.days_of_week.text()),
+            years: field(6, &syntax.years),
+            comment: schedule.comment().map(str::to_string),
+            dialect,
+        })
+    }
+
+    /// The dialect the expression is written in.
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Sets the dialect the tree compiles in, e.g. to [Dialect::Robfig] after adding seconds to a
+    /// Unix expression. The values of the fields are kept as they are, so they have to mean the
+    /// same in both dialects.
+    pub fn set_dialect(&mut self, dialect: Dialect) {
+        self.dialect = dialect;
+    }
+
+    /// The given field, or `None` if the expression leaves it out.
+    pub fn field(&self, kind: FieldKind) -> Option<&AstField> {
+        match kind {
+            FieldKind::Seconds => self.seconds.as_ref(),
+            FieldKind::Minutes => Some(&self.minutes),
+            FieldKind::Hours => Some(&self.hours),
+            FieldKind::DaysOfMonth => Some(&self.days_of_month),
+            FieldKind::Months => Some(&self.months),
+            FieldKind::DaysOfWeek => Some(&self.days_of_week),
+            FieldKind::Years => self.years.as_ref(),
+        }
+    }
+
+    /// The given field, or `None` if the expression leaves it out.
+    pub fn field_mut(&mut self, kind: FieldKind) -> Option<&mut AstField> {
+        match kind {
+            FieldKind::Seconds => self.seconds.as_mut(),
+            FieldKind::Minutes => Some(&mut self.minutes),
+            FieldKind::Hours => Some(&mut self.hours),
+            FieldKind::DaysOfMonth => Some(&mut self.days_of_month),
+            FieldKind::Months => Some(&mut self.months),
+            FieldKind::DaysOfWeek => Some(&mut self.days_of_week),
+            FieldKind::Years => self.years.as_mut(),
+        }
+    }
+
+    /// Walks the tree with the given [Visitor].
+    pub fn accept<V>(&self, visitor: &mut V)
+    where
+        V: Visitor + ?Sized,
+    {
+        walk_ast(visitor, self);
+    }
+
+    /// Rewrites the tree with the given [Transform].
+    pub fn transform<T>(self, transform: &mut T) -> Ast
+    where
+        T: Transform + ?Sized,
+    {
+        transform.transform_ast(self)
+    }
+
+    /// Compiles the tree into a [Schedule], with the default options of a [ScheduleParser] for
+    /// its dialect. Use [ScheduleParser::compile] for other options.
+    pub fn compile(&self) -> Result<Schedule, Error> {
+        ScheduleParser::new().compile(self)
+    }
+}
+
+impl AstField {
+    fn parse(dialect: Dialect, position: u64, text: &str) -> AstField {
+        // `?` is parsed as `*`, so it's told apart by its text.
+        if text == "?" {
+            return AstField {
+                items: vec![Item::Any],
+            };
+        }
+        AstField {
+            items: parsing::specifiers_of(dialect, position, text)
+                .into_iter()
+                .map(Item::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<PointValue> for Value {
+    fn from(value: PointValue) -> Value {
+        match value {
+            PointValue::Ordinal(ordinal) => Value::Number(ordinal),
+            PointValue::Named(name) => Value::Name(name),
+        }
+    }
+}
+
+impl From<Specifier> for Item {
+    fn from(specifier: Specifier) -> Item {
+        match specifier {
+            Specifier::All => Item::All,
+            Specifier::Point(ordinal) => Item::Value(Value::Number(ordinal)),
+            Specifier::Range(start, end) => Item::Range(Value::Number(start), Value::Number(end)),
+            Specifier::NamedRange(start, end) => Item::Range(Value::Name(start), Value::Name(end)),
+        }
+    }
+}
+
+impl From<RootSpecifier> for Item {
+    fn from(specifier: RootSpecifier) -> Item {
+        match specifier {
+            RootSpecifier::Specifier(specifier) => Item::from(specifier),
+            RootSpecifier::Period(start, step) => Item::Step(Box::new(Item::from(start)), step),
+            RootSpecifier::NamedPoint(name) => Item::Value(Value::Name(name)),
+            RootSpecifier::Random(start, end, _) => Item::Random(start, end),
+            RootSpecifier::Hashed(range, step, _) => Item::Hashed(range, step),
+            RootSpecifier::LastOfMonth(value, offset) => Item::LastOfMonth(value.into(), offset),
+            RootSpecifier::NthOfMonth(value, nth) => Item::NthOfMonth(value.into(), nth),
+            RootSpecifier::LastDayOfMonth(offset) => Item::LastDayOfMonth(offset),
+            RootSpecifier::NearestWeekday(day) => Item::NearestWeekday(day),
+            RootSpecifier::LastWeekdayOfMonth => Item::LastWeekdayOfMonth,
+            RootSpecifier::Quarter(quarter, _) => Item::Quarter(quarter),
+            RootSpecifier::BusinessDay(nth) => Item::BusinessDay(nth),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{number}"),
+            Value::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+// Writes `-offset`, unless the offset is zero.
+fn write_offset(f: &mut Formatter<'_>, offset: u32) -> fmt::Result {
+    match offset {
+        0 => Ok(()),
+        offset => write!(f, "-{offset}"),
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::All => write!(f, "*"),
+            Item::Any => write!(f, "?"),
+            Item::Value(value) => write!(f, "{value}"),
+            Item::Range(start, end) => write!(f, "{start}-{end}"),
+            Item::Step(start, step) => write!(f, "{start}/{step}"),
+            Item::Random(start, end) => {
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                write!(f, "~")?;
+                if let Some(end) = end {
+                    write!(f, "{end}")?;
+                }
+                Ok(())
+            }
+            Item::Hashed(range, step) => {
+                write!(f, "H")?;
+                if let Some((start, end)) = range {
+                    write!(f, "({start}-{end})")?;
+                }
+                if let Some(step) = step {
+                    write!(f, "/{step}")?;
+                }
+                Ok(())
+            }
+            Item::LastOfMonth(value, offset) => {
+                write!(f, "{value}L")?;
+                write_offset(f, *offset)
+            }
+            Item::NthOfMonth(value, nth) => write!(f, "{value}#{nth}"),
+            Item::LastDayOfMonth(offset) => {
+                write!(f, "L")?;
+                write_offset(f, *offset)
+            }
+            Item::NearestWeekday(day) => write!(f, "{day}W"),
+            Item::LastWeekdayOfMonth => write!(f, "LW"),
+            Item::Quarter(quarter) => write!(f, "Q{quarter}"),
+            Item::BusinessDay(nth) => write!(f, "{nth}B"),
+        }
+    }
+}
+
+impl Display for AstField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the tree back out as an expression of its dialect.
+impl Display for Ast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let fields = FieldKind::ALL
+            .into_iter()
+            .filter_map(|kind| self.field(kind));
+        for (index, field) in fields.enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{field}")?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " # {comment}")?;
+        }
+        Ok(())
+    }
+}
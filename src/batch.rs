@@ -0,0 +1,36 @@
+use chrono::{DateTime, TimeZone};
+use rayon::prelude::*;
+
+use crate::occurrences::Occurrences;
+
+/// Returns the first time each schedule fires after the instant paired with it, computed in
+/// parallel on rayon's thread pool, e.g. for a control plane refreshing the next runs of many
+/// jobs at once. The results are in the order of the jobs.
+///
+/// Any [Occurrences] implementation can be used, including references to schedules and schedules
+/// shared through an `Arc`.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{next_after_batch, Schedule};
+/// use std::str::FromStr;
+///
+/// let hourly = Schedule::from_str("0 0 * * * *").unwrap();
+/// let daily = Schedule::from_str("0 0 0 * * *").unwrap();
+/// let last_run = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+/// let jobs = vec![(&hourly, last_run), (&daily, last_run), (&hourly, last_run)];
+/// let next = next_after_batch(&jobs);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap()), next[0]);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap()), next[1]);
+/// assert_eq!(next[0], next[2]);
+/// ```
+pub fn next_after_batch<O, Z>(jobs: &[(O, DateTime<Z>)]) -> Vec<Option<DateTime<Z>>>
+where
+    O: Occurrences<Z> + Sync,
+    Z: TimeZone + Sync,
+    Z::Offset: Send + Sync,
+{
+    jobs.par_iter()
+        .map(|(schedule, after)| schedule.next_after(after))
+        .collect()
+}
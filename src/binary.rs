@@ -0,0 +1,244 @@
+use crate::error::{Error, ErrorKind};
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::time_unit::{DayOfMonthSpec, DayOfWeekSpec, DaySpec, TimeUnitField, YEAR_LIMITS};
+use std::ops::RangeInclusive;
+
+// Every encoded schedule starts with these magic bytes followed by the format version, so that
+// stale caches are rejected instead of being misread after the layout changes.
+pub const MAGIC: &[u8; 3] = b"CRN";
+// The version goes up with every change to the layout: 2 added the day matching flag, 3 the leap
+// day policy, 4 the end of month clamping flag, 5 the nearest weekday policy and 6 the year range.
+pub const FORMAT_VERSION: u8 = 6;
+
+const TAG_ALL: u8 = 0;
+const TAG_ORDINALS: u8 = 1;
+
+// Specials like `FRIL` are stored among the ordinals of their field as their value tagged with a
+// flag in the high bits, with a small offset like the `1` in `FRIL-1` next to the value. Plain
+// ordinals never have any of these bits set.
+const IS_LAST_OCCURRENCE: Ordinal = 1 << 31;
+const IS_WEEKDAY: Ordinal = 1 << 30;
+// The flag of the first occurrence, `#1`, followed by those of the next four.
+const IS_NTH_1: Ordinal = 1 << 25;
+const SPECIAL_FLAGS: Ordinal = IS_LAST_OCCURRENCE | IS_WEEKDAY | (0x1F * IS_NTH_1);
+const VALUE_MASK: Ordinal = 0xFF;
+const OFFSET_SHIFT: u32 = 8;
+
+// Years are the largest plain ordinals, and must stay below the flags so that a year is never
+// read back as a special.
+const _: () = assert!(*YEAR_LIMITS.end() < IS_NTH_1);
+
+pub struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new() -> Encoder {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        Encoder { bytes }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    // LEB128: seven bits per byte, least significant group first.
+    pub fn write_varint(&mut self, mut value: u32) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_varint(value.len() as u32);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    pub fn write_flag(&mut self, value: bool) {
+        self.bytes.push(value as u8);
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_field<T: TimeUnitField>(&mut self, field: &T) {
+        let specs = field.day_specs();
+        if specs.is_empty() && *field.ordinals() == T::supported_ordinals() {
+            self.bytes.push(TAG_ALL);
+            return;
+        }
+        let mut ordinals = field.ordinals().clone();
+        ordinals.extend(specs.iter().map(encode_day_spec));
+        self.bytes.push(TAG_ORDINALS);
+        self.write_varint(ordinals.len() as u32);
+        // Ordinal sets are sorted, so storing the gaps keeps most values to a single byte.
+        let mut previous = 0;
+        for &ordinal in &ordinals {
+            self.write_varint(ordinal - previous);
+            previous = ordinal;
+        }
+    }
+}
+
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Decoder<'a>, Error> {
+        let mut decoder = Decoder { bytes };
+        if decoder.take(MAGIC.len())? != MAGIC {
+            return Err(invalid("missing header"));
+        }
+        match decoder.take(1)?[0] {
+            FORMAT_VERSION => Ok(decoder),
+            version => Err(invalid(&format!(
+                "unsupported format version {version} (expected {FORMAT_VERSION})"
+            ))),
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Error> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid("trailing bytes"))
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < count {
+            return Err(invalid("unexpected end of input"));
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    pub fn read_varint(&mut self) -> Result<u32, Error> {
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.take(1)?[0];
+            let group = (byte & 0x7F) as u32;
+            if shift == 28 && group > 0x0F {
+                break;
+            }
+            value |= group << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("integer overflow"))
+    }
+
+    pub fn read_str(&mut self) -> Result<String, Error> {
+        let len = self.read_varint()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| invalid("source is not valid UTF-8"))
+    }
+
+    pub fn read_flag(&mut self) -> Result<bool, Error> {
+        match self.take(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            flag => Err(invalid(&format!("invalid flag {flag}"))),
+        }
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_field<T: TimeUnitField>(&mut self, allow_specials: bool) -> Result<T, Error> {
+        self.read_field_within(allow_specials, &T::bounds())
+    }
+
+    pub fn read_field_within<T: TimeUnitField>(
+        &mut self,
+        allow_specials: bool,
+        bounds: &RangeInclusive<Ordinal>,
+    ) -> Result<T, Error> {
+        match self.take(1)?[0] {
+            TAG_ALL => Ok(T::all()),
+            TAG_ORDINALS => {
+                let count = self.read_varint()?;
+                let mut ordinals = OrdinalSet::new();
+                let mut specs = Vec::new();
+                let mut previous: Ordinal = 0;
+                for _ in 0..count {
+                    let ordinal = previous
+                        .checked_add(self.read_varint()?)
+                        .ok_or_else(|| invalid("integer overflow"))?;
+                    if allow_specials && ordinal & SPECIAL_FLAGS != 0 {
+                        specs.push(
+                            decode_day_spec(ordinal).ok_or_else(|| invalid("unknown special"))?,
+                        );
+                    } else {
+                        ordinals.insert(T::validate_ordinal_within(ordinal, bounds)?);
+                    }
+                    previous = ordinal;
+                }
+                T::from_ordinal_set(ordinals).with_day_specs(specs)
+            }
+            tag => Err(invalid(&format!("unknown field tag {tag}"))),
+        }
+    }
+}
+
+fn encode_day_spec(spec: &DaySpec) -> Ordinal {
+    match *spec {
+        DaySpec::OfMonth(DayOfMonthSpec::NearestWeekday { day }) => day | IS_WEEKDAY,
+        DaySpec::OfMonth(DayOfMonthSpec::LastDay { offset }) => {
+            IS_LAST_OCCURRENCE | offset << OFFSET_SHIFT
+        }
+        DaySpec::OfMonth(DayOfMonthSpec::LastWeekday) => IS_LAST_OCCURRENCE | IS_WEEKDAY,
+        DaySpec::OfMonth(DayOfMonthSpec::BusinessDay { nth }) => nth | IS_WEEKDAY | IS_NTH_1,
+        DaySpec::OfWeek(DayOfWeekSpec::Nth { weekday, nth }) => weekday | IS_NTH_1 << (nth - 1),
+        DaySpec::OfWeek(DayOfWeekSpec::Last { weekday, offset }) => {
+            weekday | IS_LAST_OCCURRENCE | offset << OFFSET_SHIFT
+        }
+    }
+}
+
+// Decodes a special, checking that its flags and values are those of one the parser accepts.
+fn decode_day_spec(ordinal: Ordinal) -> Option<DaySpec> {
+    let value = ordinal & VALUE_MASK;
+    let offset = (ordinal & !SPECIAL_FLAGS) >> OFFSET_SHIFT;
+    let spec = match ordinal & SPECIAL_FLAGS {
+        IS_WEEKDAY if (1..=31).contains(&value) && offset == 0 => {
+            DaySpec::OfMonth(DayOfMonthSpec::NearestWeekday { day: value })
+        }
+        IS_LAST_OCCURRENCE if value == 0 && offset <= 30 => {
+            DaySpec::OfMonth(DayOfMonthSpec::LastDay { offset })
+        }
+        flags if flags == IS_LAST_OCCURRENCE | IS_WEEKDAY && value == 0 && offset == 0 => {
+            DaySpec::OfMonth(DayOfMonthSpec::LastWeekday)
+        }
+        flags if flags == IS_WEEKDAY | IS_NTH_1 && (1..=23).contains(&value) && offset == 0 => {
+            DaySpec::OfMonth(DayOfMonthSpec::BusinessDay { nth: value })
+        }
+        IS_LAST_OCCURRENCE if (1..=7).contains(&value) && offset <= 4 => {
+            DaySpec::OfWeek(DayOfWeekSpec::Last {
+                weekday: value,
+                offset,
+            })
+        }
+        flags if (1..=7).contains(&value) && offset == 0 => {
+            let nth = (1..=5).find(|nth| flags == IS_NTH_1 << (nth - 1))?;
+            DaySpec::OfWeek(DayOfWeekSpec::Nth {
+                weekday: value,
+                nth,
+            })
+        }
+        _ => return None,
+    };
+    Some(spec)
+}
+
+pub fn invalid(reason: &str) -> Error {
+    ErrorKind::Decode(format!("Invalid encoded schedule: {reason}")).into()
+}
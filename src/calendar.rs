@@ -0,0 +1,218 @@
+//! The calendar arithmetic schedules are evaluated with, for code which needs to resolve days the
+//! same way, e.g. to show where `15W` or `FRI#3` falls before a schedule fires on it.
+//!
+//! Dates outside the range of [NaiveDate] resolve to `None`.
+//! # Example
+//! ```
+//! use chrono::{NaiveDate, Weekday};
+//! use cron::calendar;
+//! use cron::NearestWeekdayPolicy;
+//!
+//! assert_eq!(Some(29), calendar::days_in_month(2028, 2));
+//! // The 15th of August 2026 is a Saturday, so `15W` fires on Friday the 14th.
+//! let saturday = NaiveDate::from_ymd_opt(2026, 8, 15).unwrap();
+//! assert_eq!(
+//!     NaiveDate::from_ymd_opt(2026, 8, 14),
+//!     calendar::nearest_weekday(saturday, NearestWeekdayPolicy::WithinMonth)
+//! );
+//! // `FRI#3` in October 2026.
+//! assert_eq!(
+//!     NaiveDate::from_ymd_opt(2026, 10, 16),
+//!     calendar::nth_weekday_of_month(2026, 10, Weekday::Fri, 3)
+//! );
+//! ```
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::fmt::Debug;
+
+use crate::schedule::NearestWeekdayPolicy;
+
+#[cfg(feature = "icu")]
+pub use crate::icu::IcuCalendar;
+
+/// A calendar other than the Gregorian one, such as the Hebrew or the Hijri calendar, which the
+/// day of month and month fields of a schedule can be evaluated against with
+/// [Schedule::in_calendar_system](crate::Schedule::in_calendar_system).
+///
+/// With the `icu` feature, `IcuCalendar` implements it for the calendars of ICU4X.
+/// # Example
+/// ```
+/// use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+/// use cron::calendar::CalendarSystem;
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// // A calendar of twelve months of 30 days and five or six extra days, starting on the 1st of
+/// // January.
+/// #[derive(Debug)]
+/// struct ThirtyDayMonths;
+///
+/// impl CalendarSystem for ThirtyDayMonths {
+///     fn month_and_day(&self, date: NaiveDate) -> Option<(u32, u32)> {
+///         let day_of_year = date.ordinal0();
+///         Some(((day_of_year / 30 + 1).min(13), day_of_year % 30 + 1))
+///     }
+///
+///     fn days_in_month(&self, date: NaiveDate) -> Option<u32> {
+///         let (month, _) = self.month_and_day(date)?;
+///         let days_in_year = if date.leap_year() { 366 } else { 365 };
+///         Some(if month == 13 { days_in_year - 360 } else { 30 })
+///     }
+/// }
+///
+/// let schedule = Schedule::from_str("0 0 9 1 2 ?")
+///     .unwrap()
+///     .in_calendar_system(ThirtyDayMonths)
+///     .unwrap();
+/// let after = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+/// assert_eq!(
+///     Some(Utc.with_ymd_and_hms(2026, 1, 31, 9, 0, 0).unwrap()),
+///     schedule.after(&after).next()
+/// );
+/// ```
+pub trait CalendarSystem: Debug + Send + Sync {
+    /// The month and the day of the month, both starting at 1, which the given Gregorian date
+    /// falls on, or `None` if the calendar doesn't cover the date.
+    fn month_and_day(&self, date: NaiveDate) -> Option<(u32, u32)>;
+
+    /// The number of days in the month which the given Gregorian date falls in, to resolve `L`,
+    /// or `None` if the calendar doesn't cover the date.
+    fn days_in_month(&self, date: NaiveDate) -> Option<u32>;
+}
+
+/// Returns true if the year has a 29th of February, in the proleptic Gregorian calendar.
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// The number of days in the given month, from 1 for January to 12 for December, or `None` for
+/// any other month.
+pub fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    match month {
+        4 | 6 | 9 | 11 => Some(30),
+        2 if is_leap_year(year) => Some(29),
+        2 => Some(28),
+        1..=12 => Some(31),
+        _ => None,
+    }
+}
+
+/// The weekday closest to the given date, as `W` resolves it: a Saturday moves to the Friday
+/// before and a Sunday to the Monday after, while other days stay as they are.
+///
+/// With [NearestWeekdayPolicy::WithinMonth], a Saturday on the 1st moves to Monday the 3rd and a
+/// Sunday on the last day of the month to the Friday two days before, as in Quartz. With
+/// [NearestWeekdayPolicy::AcrossMonths] these move into the months around instead.
+pub fn nearest_weekday(date: NaiveDate, policy: NearestWeekdayPolicy) -> Option<NaiveDate> {
+    let within_month = policy == NearestWeekdayPolicy::WithinMonth;
+    match date.weekday() {
+        Weekday::Sat if within_month && date.day() == 1 => date.checked_add_days(Days::new(2)),
+        Weekday::Sat => date.checked_sub_days(Days::new(1)),
+        Weekday::Sun if within_month && date.succ_opt()?.day() == 1 => {
+            date.checked_sub_days(Days::new(2))
+        }
+        Weekday::Sun => date.checked_add_days(Days::new(1)),
+        _ => Some(date),
+    }
+}
+
+/// The nth occurrence of the day of the week in the given month, counting from 1 as `#` does, or
+/// `None` if the month doesn't have that many.
+pub fn nth_weekday_of_month(
+    year: i32,
+    month: u32,
+    weekday: Weekday,
+    nth: u32,
+) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let first_day = 1 + first_day_offset(first.weekday(), weekday);
+    let day = nth.checked_sub(1)?.checked_mul(7)?.checked_add(first_day)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// The last occurrence of the day of the week in the given month, as `L` after a day of the week
+/// resolves it.
+pub fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let last_day = days_in_month(year, month)?;
+    let last = NaiveDate::from_ymd_opt(year, month, last_day)?;
+    let days_back = first_day_offset(weekday, last.weekday());
+    last.checked_sub_days(Days::new(u64::from(days_back)))
+}
+
+/// The nth business day of the given month, counting from 1 as `B` does the days from Monday to
+/// Friday for which `is_holiday` returns false, or `None` if the month doesn't have that many.
+pub fn nth_business_day<F>(year: i32, month: u32, nth: u32, mut is_holiday: F) -> Option<NaiveDate>
+where
+    F: FnMut(NaiveDate) -> bool,
+{
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let index = usize::try_from(nth.checked_sub(1)?).ok()?;
+    first
+        .iter_days()
+        .take_while(|date| date.month() == month)
+        .filter(|date| date.weekday().number_from_monday() <= 5 && !is_holiday(*date))
+        .nth(index)
+}
+
+/// The quarter of the fiscal year a month falls in, from 1 to 4, as `Q1` to `Q4` resolve it for
+/// a year starting on the month `fiscal_year_start`, as set with
+/// [ScheduleParser::fiscal_year_start](crate::ScheduleParser::fiscal_year_start), or 1 for
+/// calendar quarters. `None` for a month or a start other than 1 to 12.
+/// # Example
+/// ```
+/// use cron::calendar;
+///
+/// assert_eq!(Some(4), calendar::quarter_of_month(10, 1));
+/// // A fiscal year starting in April ends with January to March.
+/// assert_eq!(Some(4), calendar::quarter_of_month(2, 4));
+/// ```
+pub fn quarter_of_month(month: u32, fiscal_year_start: u32) -> Option<u32> {
+    let months = 1..=12;
+    (months.contains(&month) && months.contains(&fiscal_year_start))
+        .then(|| (month + 12 - fiscal_year_start) % 12 / 3 + 1)
+}
+
+/// The first day of the given quarter, from 1 to 4, as `@quarterly` fires on it, in the fiscal
+/// year starting on the month `fiscal_year_start` of `year`.
+pub fn first_day_of_quarter(year: i32, quarter: u32, fiscal_year_start: u32) -> Option<NaiveDate> {
+    let (year, month) = fiscal_month(year, quarter, fiscal_year_start, 0)?;
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+/// The last day of the given quarter, from 1 to 4, as `@quarterend` fires on it, in the fiscal
+/// year starting on the month `fiscal_year_start` of `year`.
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use cron::calendar;
+///
+/// // The fiscal year starting in October 2026 ends on the 30th of September 2027.
+/// assert_eq!(
+///     NaiveDate::from_ymd_opt(2027, 9, 30),
+///     calendar::last_day_of_quarter(2026, 4, 10)
+/// );
+/// ```
+pub fn last_day_of_quarter(year: i32, quarter: u32, fiscal_year_start: u32) -> Option<NaiveDate> {
+    let (year, month) = fiscal_month(year, quarter, fiscal_year_start, 2)?;
+    NaiveDate::from_ymd_opt(year, month, days_in_month(year, month)?)
+}
+
+// The calendar year and month of the month `offset` months into a quarter, from 1 to 4, of the
+// fiscal year starting on the month `fiscal_year_start` of `year`.
+fn fiscal_month(
+    year: i32,
+    quarter: u32,
+    fiscal_year_start: u32,
+    offset: u32,
+) -> Option<(i32, u32)> {
+    if !(1..=4).contains(&quarter) || !(1..=12).contains(&fiscal_year_start) {
+        return None;
+    }
+    let months = fiscal_year_start - 1 + (quarter - 1) * 3 + offset;
+    Some((year.checked_add((months / 12) as i32)?, months % 12 + 1))
+}
+
+// The number of days from a day falling on `from` to the first day after it falling on `to`,
+// from 0 to 6.
+fn first_day_offset(from: Weekday, to: Weekday) -> u32 {
+    (to.num_days_from_sunday() + 7 - from.num_days_from_sunday()) % 7
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+
+use crate::error::{Error, ErrorCode, ErrorKind};
+
+/// Where a [ScheduleIterator](crate::ScheduleIterator) stopped, so that another process can
+/// resume it with [Schedule::resume](crate::Schedule::resume) after a restart.
+///
+/// A checkpoint holds the last instant the iterator yielded, or the one it started from, along
+/// with a hash of the schedule, and is written as text such as `1792152000:ad1093665ad44995`. It
+/// serializes as that text with the `serde` feature.
+///
+/// Resuming always carries on forwards, so iterators last moved with
+/// [next_back](DoubleEndedIterator::next_back) don't take checkpoints.
+///
+/// A checkpoint taken between the two instants of a wall clock time repeated by daylight saving
+/// time resumes after both of them.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{Checkpoint, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 30, 0).unwrap();
+/// let mut upcoming = schedule.after(&start);
+/// upcoming.next();
+/// let saved = upcoming.checkpoint().unwrap().to_string();
+///
+/// // After a restart.
+/// let checkpoint = Checkpoint::from_str(&saved).unwrap();
+/// let mut resumed = schedule.resume(&checkpoint, &Utc).unwrap();
+/// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap(), resumed.next().unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    instant: DateTime<Utc>,
+    schedule_hash: u64,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(instant: DateTime<Utc>, schedule_hash: u64) -> Checkpoint {
+        Checkpoint {
+            instant,
+            schedule_hash,
+        }
+    }
+
+    /// The instant iteration resumes from.
+    pub fn instant(&self) -> DateTime<Utc> {
+        self.instant
+    }
+
+    /// The hash of the schedule the checkpoint was taken from, stable across releases and
+    /// platforms as it's computed from the schedule's expression and resolved fields written out
+    /// as text, rather than from its binary encoding.
+    pub fn schedule_hash(&self) -> u64 {
+        self.schedule_hash
+    }
+}
+
+/// Writes the Unix timestamp of the instant, with nanoseconds only if it has any, and the hash of
+/// the schedule in hexadecimal, e.g. `1792152000.5:ad1093665ad44995`.
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.instant.timestamp())?;
+        let nanos = self.instant.timestamp_subsec_nanos();
+        if nanos > 0 {
+            let nanos = format!("{nanos:09}");
+            write!(f, ".{}", nanos.trim_end_matches('0'))?;
+        }
+        write!(f, ":{:016x}", self.schedule_hash)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ErrorKind::Decode(format!("Invalid checkpoint: '{text}'.")).with_code(ErrorCode::Decode)
+        };
+        let (instant, hash) = text.split_once(':').ok_or_else(invalid)?;
+        let (seconds, nanos) = match instant.split_once('.') {
+            Some((seconds, fraction)) if (1..=9).contains(&fraction.len()) => {
+                let nanos: u32 = fraction.parse().map_err(|_| invalid())?;
+                (seconds, nanos * 10u32.pow(9 - fraction.len() as u32))
+            }
+            Some(_) => return Err(invalid()),
+            None => (instant, 0),
+        };
+        let seconds = seconds.parse().map_err(|_| invalid())?;
+        let instant = DateTime::from_timestamp(seconds, nanos).ok_or_else(invalid)?;
+        if hash.len() != 16 {
+            return Err(invalid());
+        }
+        let schedule_hash = u64::from_str_radix(hash, 16).map_err(|_| invalid())?;
+        Ok(Checkpoint::new(instant, schedule_hash))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CheckpointVisitor;
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> for CheckpointVisitor {
+    type Value = Checkpoint;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a valid iterator checkpoint")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Checkpoint::from_str(v).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Checkpoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Checkpoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CheckpointVisitor)
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::schedule::Schedule;
+use crate::time_unit::{TimeUnitField, TimeUnitSpec};
+
+// The first year of the years mask, which is bit 0 of it. Years past its 192 bits, which only
+// widened year ranges include, are looked up in the schedule's years instead.
+const FIRST_YEAR: i32 = 1970;
+// The number of days between the 1st of January of year 1 and the Unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i64 = 719_163;
+
+/// A [Schedule] compiled into bitmasks of the values each field includes, as returned by
+/// [Schedule::compile], for testing large numbers of timestamps against it, e.g. to filter
+/// millions of log events down to those at times a job should have run.
+///
+/// It answers the same as [Schedule::includes], with each field checked by a single bit test.
+/// The days of the month are resolved once per month of the timestamps, so batches are fastest
+/// when their timestamps are grouped by month, as sorted logs are.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// let compiled = Schedule::from_str("0 */15 9-17 * * Mon-Fri").unwrap().compile();
+/// let friday = Utc.with_ymd_and_hms(2026, 10, 16, 9, 45, 0).unwrap();
+/// let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 9, 45, 0).unwrap();
+/// assert_eq!(vec![true, false], compiled.matches_batch(&[friday, saturday]));
+/// let timestamps = [friday.timestamp(), friday.timestamp() + 1, saturday.timestamp()];
+/// assert_eq!(vec![true, false, false], compiled.matches_timestamps(&timestamps));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompiledSchedule {
+    schedule: Schedule,
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    years: [u64; 3],
+}
+
+// The days of the month included in the last month looked at.
+#[derive(Clone, Copy)]
+struct DaysMask {
+    year: i32,
+    month: u32,
+    days: u32,
+}
+
+impl CompiledSchedule {
+    pub(crate) fn new(schedule: &Schedule) -> CompiledSchedule {
+        let fields = schedule.search_fields();
+        let mut years = [0; 3];
+        for &year in fields.years.ordinals() {
+            let bit = year as i32 - FIRST_YEAR;
+            if (0..192).contains(&bit) {
+                years[bit as usize / 64] |= 1 << (bit % 64);
+            }
+        }
+        CompiledSchedule {
+            schedule: schedule.clone(),
+            seconds: mask(fields.seconds.ordinals()),
+            minutes: mask(fields.minutes.ordinals()),
+            hours: mask(fields.hours.ordinals()),
+            years,
+        }
+    }
+
+    /// The schedule this was compiled from.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns true if the schedule fires at the given instant, like [Schedule::includes].
+    pub fn matches<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.schedule.is_active_at(date_time)
+            && self.matches_local(&date_time.naive_local(), &mut None)
+    }
+
+    /// Returns, for each of the given instants, whether the schedule fires at it.
+    pub fn matches_batch<Z>(&self, date_times: &[DateTime<Z>]) -> Vec<bool>
+    where
+        Z: TimeZone,
+    {
+        let mut days = None;
+        date_times
+            .iter()
+            .map(|date_time| {
+                self.schedule.is_active_at(date_time)
+                    && self.matches_local(&date_time.naive_local(), &mut days)
+            })
+            .collect()
+    }
+
+    /// Returns, for each of the given Unix timestamps in seconds, whether the schedule fires at
+    /// it when read in UTC.
+    pub fn matches_timestamps(&self, timestamps: &[i64]) -> Vec<bool> {
+        let mut days = None;
+        timestamps
+            .iter()
+            .map(|&timestamp| {
+                let seconds_of_day = timestamp.rem_euclid(86_400) as u32;
+                if !self.matches_time(
+                    seconds_of_day / 3600,
+                    seconds_of_day / 60 % 60,
+                    seconds_of_day % 60,
+                ) {
+                    return false;
+                }
+                let days_from_ce = timestamp.div_euclid(86_400) + UNIX_EPOCH_DAYS_FROM_CE;
+                let date = i32::try_from(days_from_ce)
+                    .ok()
+                    .and_then(NaiveDate::from_num_days_from_ce_opt);
+                let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds_of_day, 0);
+                match date.zip(time) {
+                    Some((date, time)) => {
+                        let date_time = date.and_time(time);
+                        self.matches_date(&date_time, &mut days)
+                            && !self.schedule.is_excluded(&date_time)
+                            && self.schedule.is_active_at(&date_time.and_utc())
+                    }
+                    None => false,
+                }
+            })
+            .collect()
+    }
+
+    fn matches_local(&self, date_time: &NaiveDateTime, days: &mut Option<DaysMask>) -> bool {
+        self.matches_time(date_time.hour(), date_time.minute(), date_time.second())
+            && self.matches_date(date_time, days)
+            && !self.schedule.is_excluded(date_time)
+    }
+
+    fn matches_time(&self, hour: u32, minute: u32, second: u32) -> bool {
+        self.hours & (1 << hour) != 0
+            && self.minutes & (1 << minute) != 0
+            && self.seconds & (1 << second) != 0
+    }
+
+    fn matches_date(&self, date_time: &NaiveDateTime, days: &mut Option<DaysMask>) -> bool {
+        let (year, month) = (date_time.year(), date_time.month());
+        let bit = year - FIRST_YEAR;
+        let included = match (0..192).contains(&bit) {
+            true => self.years[bit as usize / 64] & (1 << (bit % 64)) != 0,
+            false => {
+                year > 0
+                    && self
+                        .schedule
+                        .search_fields()
+                        .years
+                        .includes(year as Ordinal)
+            }
+        };
+        if !included {
+            return false;
+        }
+        let mask = match days {
+            Some(mask) if mask.year == year && mask.month == month => mask.days,
+            _ => {
+                let fields = self.schedule.search_fields();
+                let mask = DaysMask {
+                    year,
+                    month,
+                    days: mask(&fields.matching_days(month, year as Ordinal)) as u32,
+                };
+                *days = Some(mask);
+                mask.days
+            }
+        };
+        mask & (1 << date_time.day()) != 0
+    }
+}
+
+// The plain ordinals of the set below 64 as a bitmask.
+fn mask(ordinals: &OrdinalSet) -> u64 {
+    ordinals
+        .range(..64)
+        .fold(0, |mask, &ordinal| mask | 1 << ordinal)
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Offset, TimeZone, Timelike};
+use std::ops::ControlFlow;
+
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::schedule::{Schedule, ScheduleFields};
+use crate::time_unit::{Months, TimeUnitField};
+
+// The occurrences of a schedule on one day, as visited by `walk`.
+enum Day<Z>
+where
+    Z: TimeZone,
+{
+    // All the times of day from index `first` up to `end`, excluded, on a day on which the
+    // timezone keeps the same offset.
+    Run {
+        date: NaiveDate,
+        first: usize,
+        end: usize,
+    },
+    // A single occurrence on a day on which times may be skipped or repeated.
+    Single(DateTime<Z>),
+}
+
+// The k-th occurrence after `after`, counting from zero.
+pub(crate) fn nth_after<Z>(
+    schedule: &Schedule,
+    k: usize,
+    after: &DateTime<Z>,
+) -> Option<DateTime<Z>>
+where
+    Z: TimeZone,
+{
+    if !schedule.is_unconstrained() {
+        return schedule.after(after).nth(k);
+    }
+    let times = TimesOfDay::new(schedule.fields());
+    let timezone = after.timezone();
+    let mut remaining = k;
+    walk(schedule, &times, after, |day| match day {
+        Day::Run { date, first, end } if remaining < end - first => {
+            ControlFlow::Break(times.at(&timezone, date, first + remaining))
+        }
+        Day::Run { first, end, .. } => {
+            remaining -= end - first;
+            ControlFlow::Continue(())
+        }
+        Day::Single(occurrence) if remaining == 0 => ControlFlow::Break(Some(occurrence)),
+        Day::Single(_) => {
+            remaining -= 1;
+            ControlFlow::Continue(())
+        }
+    })
+    .flatten()
+}
+
+// The number of occurrences after `anchor` and before `date_time`, if the schedule fires at it.
+pub(crate) fn occurrence_index<Z>(
+    schedule: &Schedule,
+    anchor: &DateTime<Z>,
+    date_time: &DateTime<Z>,
+) -> Option<usize>
+where
+    Z: TimeZone,
+{
+    if date_time <= anchor || !schedule.fires_at(date_time) {
+        return None;
+    }
+    let local = date_time.naive_local();
+    if !schedule.is_unconstrained() {
+        return schedule
+            .after(anchor)
+            .take_while(|occurrence| occurrence.naive_local() <= local)
+            .position(|occurrence| occurrence == *date_time);
+    }
+    let times = TimesOfDay::new(schedule.fields());
+    let mut index = 0;
+    walk(schedule, &times, anchor, |day| match day {
+        Day::Run { date, first, end } if date < local.date() => {
+            index += end - first;
+            ControlFlow::Continue(())
+        }
+        Day::Run { date, first, .. } if date == local.date() => {
+            // The count includes `date_time` itself.
+            ControlFlow::Break(Some(index + times.count_until(local.time()) - 1 - first))
+        }
+        Day::Single(occurrence) if occurrence == *date_time => ControlFlow::Break(Some(index)),
+        // Occurrences come in the order of their local times, so both instants of a repeated
+        // time come before the next one.
+        Day::Single(occurrence) if occurrence.naive_local() <= local => {
+            index += 1;
+            ControlFlow::Continue(())
+        }
+        _ => ControlFlow::Break(None),
+    })
+    .flatten()
+}
+
+// Visits the occurrences after `after` in order, whole days at a time where the timezone allows
+// it, until `visit` breaks. Only the fields of the schedule are taken into account.
+fn walk<Z, B, F>(
+    schedule: &Schedule,
+    times: &TimesOfDay<'_>,
+    after: &DateTime<Z>,
+    mut visit: F,
+) -> Option<B>
+where
+    Z: TimeZone,
+    F: FnMut(Day<Z>) -> ControlFlow<B>,
+{
+    let fields = schedule.fields();
+    let timezone = after.timezone();
+    // The last occurrence visited, or `after` until one is.
+    let mut cursor = after.clone();
+    let start = after.naive_local().date();
+    for &year in fields
+        .years
+        .ordinals()
+        .range(start.year().max(0) as Ordinal..)
+    {
+        for month in Months::inclusive_min()..=Months::inclusive_max() {
+            for &day in fields.matching_days(month, year).iter() {
+                let Some(date) = NaiveDate::from_ymd_opt(year as i32, month, day) else {
+                    continue;
+                };
+                let local = cursor.naive_local();
+                if date < local.date() {
+                    continue;
+                }
+                if !has_constant_offset(&timezone, date) {
+                    // Times may be skipped or repeated on this day, so its occurrences are found
+                    // one by one.
+                    let mut occurrences = schedule.after(&cursor);
+                    while let Some(next) = occurrences
+                        .next()
+                        .filter(|next| next.naive_local().date() == date)
+                    {
+                        if let ControlFlow::Break(value) = visit(Day::Single(next.clone())) {
+                            return Some(value);
+                        }
+                        cursor = next;
+                    }
+                    continue;
+                }
+                let first = if date == local.date() {
+                    times.count_until(local.time())
+                } else {
+                    0
+                };
+                let end = times.len();
+                if first == end {
+                    continue;
+                }
+                if let ControlFlow::Break(value) = visit(Day::Run { date, first, end }) {
+                    return Some(value);
+                }
+                cursor = times.at(&timezone, date, end - 1)?;
+            }
+        }
+    }
+    None
+}
+
+// The times of day a schedule fires at, in order, for counting its occurrences within a day.
+struct TimesOfDay<'a> {
+    hours: &'a OrdinalSet,
+    minutes: &'a OrdinalSet,
+    seconds: &'a OrdinalSet,
+}
+
+impl<'a> TimesOfDay<'a> {
+    fn new(fields: &'a ScheduleFields) -> TimesOfDay<'a> {
+        TimesOfDay {
+            hours: fields.hours.ordinals(),
+            minutes: fields.minutes.ordinals(),
+            seconds: fields.seconds.ordinals(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.hours.len() * self.minutes.len() * self.seconds.len()
+    }
+
+    // The number of times of day which aren't after the given one.
+    fn count_until(&self, time: NaiveTime) -> usize {
+        let (hour, minute, second) = (time.hour(), time.minute(), time.second());
+        let per_hour = self.minutes.len() * self.seconds.len();
+        let mut count = self.hours.range(..hour).len() * per_hour;
+        if self.hours.contains(&hour) {
+            count += self.minutes.range(..minute).len() * self.seconds.len();
+            if self.minutes.contains(&minute) {
+                count += self.seconds.range(..=second).len();
+            }
+        }
+        count
+    }
+
+    // The time of day at the given index on a day on which the timezone keeps the same offset.
+    fn at<Z>(&self, timezone: &Z, date: NaiveDate, index: usize) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let per_hour = self.minutes.len() * self.seconds.len();
+        let hour = self.hours.iter().nth(index / per_hour)?;
+        let minute = self
+            .minutes
+            .iter()
+            .nth(index / self.seconds.len() % self.minutes.len())?;
+        let second = self.seconds.iter().nth(index % self.seconds.len())?;
+        let time = NaiveTime::from_hms_opt(*hour, *minute, *second)?;
+        timezone.from_local_datetime(&date.and_time(time)).single()
+    }
+}
+
+// Whether the timezone has the same offset at the start of the day as at the start of the next
+// one, so that every time of the day exists once.
+pub(crate) fn has_constant_offset<Z>(timezone: &Z, date: NaiveDate) -> bool
+where
+    Z: TimeZone,
+{
+    let offset_at = |date: NaiveDate| {
+        timezone
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .single()
+            .map(|midnight| midnight.offset().fix())
+    };
+    match (offset_at(date), date.succ_opt().and_then(offset_at)) {
+        (Some(start), Some(end)) => start == end,
+        _ => false,
+    }
+}
@@ -0,0 +1,191 @@
+use once_cell::sync::OnceCell;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+
+use crate::error::Error;
+use crate::schedule::Schedule;
+
+/// A cron expression known to be valid, for configuration structs which store expressions as
+/// strings but shouldn't hold invalid ones.
+///
+/// The expression is checked when the value is created, from [FromStr], [TryFrom] or serde, but
+/// only compiled into a [Schedule] the first time [CronExpr::schedule] is called. It derefs to
+/// the expression, and compares and hashes as the string.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::CronExpr;
+/// use std::str::FromStr;
+///
+/// let expression = CronExpr::from_str("0 30 9 * * Mon-Fri").unwrap();
+/// assert_eq!("0 30 9 * * Mon-Fri", &*expression);
+/// assert!(expression.starts_with("0 30"));
+/// let friday = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+/// assert_eq!(
+///     Some(Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap()),
+///     expression.schedule().after(&friday).next()
+/// );
+/// assert!(CronExpr::from_str("0 30 25 * * *").is_err());
+/// ```
+#[derive(Clone)]
+pub struct CronExpr {
+    expression: Box<str>,
+    schedule: OnceCell<Schedule>,
+}
+
+impl CronExpr {
+    /// Checks the expression, with the same syntax as [Schedule]'s [FromStr].
+    pub fn new(expression: impl Into<String>) -> Result<CronExpr, Error> {
+        let expression = expression.into();
+        // The schedule isn't kept, so that expressions which are never evaluated stay small.
+        Schedule::from_str(&expression)?;
+        Ok(CronExpr {
+            expression: expression.into_boxed_str(),
+            schedule: OnceCell::new(),
+        })
+    }
+
+    /// The expression.
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+
+    /// The schedule of the expression, compiled the first time it's asked for.
+    pub fn schedule(&self) -> &Schedule {
+        self.schedule.get_or_init(|| {
+            Schedule::from_str(&self.expression).expect("the expression was checked")
+        })
+    }
+
+    /// Returns the schedule of the expression, compiling it if it wasn't already.
+    pub fn into_schedule(self) -> Schedule {
+        match self.schedule.into_inner() {
+            Some(schedule) => schedule,
+            None => Schedule::from_str(&self.expression).expect("the expression was checked"),
+        }
+    }
+}
+
+impl Deref for CronExpr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl AsRef<str> for CronExpr {
+    fn as_ref(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        CronExpr::new(expression)
+    }
+}
+
+impl TryFrom<String> for CronExpr {
+    type Error = Error;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        CronExpr::new(expression)
+    }
+}
+
+impl TryFrom<&str> for CronExpr {
+    type Error = Error;
+
+    fn try_from(expression: &str) -> Result<Self, Self::Error> {
+        CronExpr::new(expression)
+    }
+}
+
+impl From<CronExpr> for String {
+    fn from(expression: CronExpr) -> String {
+        expression.expression.into()
+    }
+}
+
+impl Display for CronExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl fmt::Debug for CronExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CronExpr").field(&self.expression).finish()
+    }
+}
+
+impl PartialEq for CronExpr {
+    fn eq(&self, other: &CronExpr) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl Eq for CronExpr {}
+
+impl Hash for CronExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expression.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CronExprVisitor;
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> for CronExprVisitor {
+    type Value = CronExpr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a valid cron expression")
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        CronExpr::new(v).map_err(de::Error::custom)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        CronExpr::new(v).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CronExpr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.expression)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CronExpr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(CronExprVisitor)
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::ast::FieldKind;
+use crate::parsing;
+use crate::schedule::Schedule;
+use crate::syntax::FieldSyntax;
+use crate::time_unit::*;
+
+/// A [Schedule] laid out field by field for front-ends which render and edit schedules, as
+/// returned by [Schedule::description]. With the `serde` feature it serializes to a plain
+/// structure of strings, numbers and lists, which doesn't depend on how the crate stores fields.
+/// # Example
+/// ```
+/// use cron::{DayOfMonthSpec, DaySpec, FieldKind, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 */15 9-17 L * ? # Reports").unwrap();
+/// let description = schedule.description().with_timezone("Europe/Berlin");
+/// assert_eq!(Some("Reports"), description.comment.as_deref());
+/// let minutes = description.field(FieldKind::Minutes).unwrap();
+/// assert_eq!(Some("*/15"), minutes.text.as_deref());
+/// assert_eq!(vec![0, 15, 30, 45], minutes.values);
+/// let days = description.field(FieldKind::DaysOfMonth).unwrap();
+/// assert_eq!(vec![DaySpec::OfMonth(DayOfMonthSpec::LastDay { offset: 0 })], days.specials);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ScheduleDescription {
+    /// The expression the schedule was parsed from, without its comment.
+    pub expression: String,
+    /// The trailing comment of the expression, if any.
+    pub comment: Option<String>,
+    /// The name of the timezone the schedule runs in, which isn't part of an expression and is
+    /// only known once set with [ScheduleDescription::with_timezone].
+    pub timezone: Option<String>,
+    /// Whether a day matches if it's included by either of the day fields rather than by both,
+    /// as in Unix cron when both are restricted.
+    pub days_match_either: bool,
+    /// The fields, in the order of a Quartz expression.
+    pub fields: Vec<FieldDescription>,
+}
+
+/// A field of a [ScheduleDescription].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldDescription {
+    /// Which field this is.
+    pub kind: FieldKind,
+    /// The field as written, or `None` if it was left out of the expression or the schedule
+    /// doesn't keep how its fields were written, as with shorthands like `@daily`.
+    pub text: Option<String>,
+    /// Whether the field includes every value, as with `*`.
+    pub is_all: bool,
+    /// The plain values the field includes, in ascending order. The days of the week go from 1
+    /// for Sunday to 7 for Saturday.
+    pub values: Vec<u32>,
+    /// The values which depend on the month, like `L` or `6#3`.
+    pub specials: Vec<DaySpec>,
+}
+
+impl ScheduleDescription {
+    pub(crate) fn new(schedule: &Schedule) -> ScheduleDescription {
+        let fields = schedule.fields();
+        let syntax = schedule.syntax();
+        let (expression, comment) = parsing::split_comment(schedule.source());
+        ScheduleDescription {
+            expression: expression.trim().to_string(),
+            comment: comment.map(str::to_string),
+            timezone: None,
+            days_match_either: fields.days_match_either,
+            fields: vec![
+                describe(
+                    FieldKind::Seconds,
+                    &fields.seconds,
+                    syntax.map(|s| &s.seconds),
+                ),
+                describe(
+                    FieldKind::Minutes,
+                    &fields.minutes,
+                    syntax.map(|s| &s.minutes),
+                ),
+                describe(FieldKind::Hours, &fields.hours, syntax.map(|s| &s.hours)),
+                describe(
+                    FieldKind::DaysOfMonth,
+                    &fields.days_of_month,
+                    syntax.map(|s| &s.days_of_month),
+                ),
+                describe(FieldKind::Months, &fields.months, syntax.map(|s| &s.months)),
+                describe(
+                    FieldKind::DaysOfWeek,
+                    &fields.days_of_week,
+                    syntax.map(|s| &s.days_of_week),
+                ),
+                describe(FieldKind::Years, &fields.years, syntax.map(|s| &s.years)),
+            ],
+        }
+    }
+
+    /// Sets the name of the timezone the schedule runs in, e.g. `Europe/Berlin`.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> ScheduleDescription {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// The description of the given field.
+    pub fn field(&self, kind: FieldKind) -> Option<&FieldDescription> {
+        self.fields.iter().find(|field| field.kind == kind)
+    }
+}
+
+fn describe<T: TimeUnitField>(
+    kind: FieldKind,
+    field: &T,
+    syntax: Option<&FieldSyntax>,
+) -> FieldDescription {
+    FieldDescription {
+        kind,
+        text: syntax
+            .filter(|syntax| !syntax.is_omitted())
+            .map(|syntax| syntax.text().to_string()),
+        is_all: field.is_all(),
+        values: field.iter().collect(),
+        specials: field.day_specs(),
+    }
+}
+
+// Field kinds are written in snake case, e.g. `days_of_month`.
+#[cfg(feature = "serde")]
+fn kind_name(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::Seconds => "seconds",
+        FieldKind::Minutes => "minutes",
+        FieldKind::Hours => "hours",
+        FieldKind::DaysOfMonth => "days_of_month",
+        FieldKind::Months => "months",
+        FieldKind::DaysOfWeek => "days_of_week",
+        FieldKind::Years => "years",
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ScheduleDescription {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ScheduleDescription", 5)?;
+        state.serialize_field("expression", &self.expression)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("timezone", &self.timezone)?;
+        state.serialize_field("days_match_either", &self.days_match_either)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for FieldDescription {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("FieldDescription", 5)?;
+        state.serialize_field("kind", kind_name(self.kind))?;
+        state.serialize_field("text", &self.text)?;
+        state.serialize_field("is_all", &self.is_all)?;
+        state.serialize_field("values", &self.values)?;
+        let specials: Vec<_> = self.specials.iter().map(SerializedSpec).collect();
+        state.serialize_field("specials", &specials)?;
+        state.end()
+    }
+}
+
+// Specials are written as structures tagged with their kind in snake case, e.g.
+// `{"kind": "nth_of_month", "weekday": 6, "nth": 3}`.
+#[cfg(feature = "serde")]
+struct SerializedSpec<'a>(&'a DaySpec);
+
+#[cfg(feature = "serde")]
+impl Serialize for SerializedSpec<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (kind, first, second) = match *self.0 {
+            DaySpec::OfMonth(DayOfMonthSpec::LastDay { offset }) => {
+                ("last_day_of_month", Some(("offset", offset)), None)
+            }
+            DaySpec::OfMonth(DayOfMonthSpec::NearestWeekday { day }) => {
+                ("nearest_weekday", Some(("day", day)), None)
+            }
+            DaySpec::OfMonth(DayOfMonthSpec::LastWeekday) => ("last_weekday_of_month", None, None),
+            DaySpec::OfMonth(DayOfMonthSpec::BusinessDay { nth }) => {
+                ("business_day", Some(("nth", nth)), None)
+            }
+            DaySpec::OfWeek(DayOfWeekSpec::Last { weekday, offset }) => (
+                "last_of_month",
+                Some(("weekday", weekday)),
+                Some(("offset", offset)),
+            ),
+            DaySpec::OfWeek(DayOfWeekSpec::Nth { weekday, nth }) => (
+                "nth_of_month",
+                Some(("weekday", weekday)),
+                Some(("nth", nth)),
+            ),
+        };
+        let length = 1 + first.iter().count() + second.iter().count();
+        let mut state = serializer.serialize_struct("DaySpec", length)?;
+        state.serialize_field("kind", kind)?;
+        for (name, value) in first.into_iter().chain(second) {
+            state.serialize_field(name, &value)?;
+        }
+        state.end()
+    }
+}
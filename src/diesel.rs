@@ -0,0 +1,52 @@
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::cron_expr::CronExpr;
+use crate::schedule::Schedule;
+
+// Derives the expression and row impls for the types themselves, as Diesel does for the types of
+// other crates it supports.
+#[derive(AsExpression, FromSqlRow)]
+#[diesel(foreign_derive)]
+#[diesel(sql_type = Text)]
+#[allow(dead_code)]
+struct ScheduleProxy(Schedule);
+
+#[derive(AsExpression, FromSqlRow)]
+#[diesel(foreign_derive)]
+#[diesel(sql_type = Text)]
+#[allow(dead_code)]
+struct CronExprProxy(CronExpr);
+
+// Schedules and expressions are stored as the text of their expression, and parsed again when
+// read, so that a row holding an invalid expression fails to load, as with sqlx.
+macro_rules! impl_text {
+    ($type:ty, $expression:ident) => {
+        impl<DB> ToSql<Text, DB> for $type
+        where
+            DB: Backend,
+            str: ToSql<Text, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                self.$expression().to_sql(out)
+            }
+        }
+
+        impl<DB> FromSql<Text, DB> for $type
+        where
+            DB: Backend,
+            String: FromSql<Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                let expression = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+                Ok(<$type>::try_from(expression)?)
+            }
+        }
+    };
+}
+
+impl_text!(Schedule, source);
+impl_text!(CronExpr, as_str);
@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::ordinal::Ordinal;
+use crate::time_unit::TimeUnitField;
+
+/// How a single field differs between two [Schedule](crate::Schedule)s.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The values only included by the new schedule, in ascending order.
+    pub added: Vec<Ordinal>,
+    /// The values only included by the old schedule, in ascending order.
+    pub removed: Vec<Ordinal>,
+    /// Whether the special constraints of the field, such as `L`, `W` or `#`, differ.
+    pub specials_changed: bool,
+}
+
+impl FieldDiff {
+    pub(crate) fn between<T: TimeUnitField>(old: &T, new: &T) -> FieldDiff {
+        let (old_ordinals, new_ordinals) = (old.ordinals(), new.ordinals());
+        FieldDiff {
+            added: new_ordinals.difference(old_ordinals).copied().collect(),
+            removed: old_ordinals.difference(new_ordinals).copied().collect(),
+            specials_changed: old.day_specs() != new.day_specs(),
+        }
+    }
+
+    /// Returns true if the field is the same in both schedules.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && !self.specials_changed
+    }
+}
+
+/// A per-field description of how two [Schedule](crate::Schedule)s differ, as returned by
+/// [Schedule::diff](crate::Schedule::diff).
+///
+/// Its [Display] implementation lists the changed fields only, e.g.
+/// `Hours: +13 -12; Days of Week: special constraints changed`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScheduleDiff {
+    pub seconds: FieldDiff,
+    pub minutes: FieldDiff,
+    pub hours: FieldDiff,
+    pub days_of_month: FieldDiff,
+    pub months: FieldDiff,
+    pub days_of_week: FieldDiff,
+    pub years: FieldDiff,
+}
+
+impl ScheduleDiff {
+    /// Returns true if both schedules fire at exactly the same times.
+    pub fn is_empty(&self) -> bool {
+        self.fields().all(|(_, field)| field.is_empty())
+    }
+
+    /// Iterates over the name and difference of every field, from seconds to years.
+    pub fn fields(&self) -> impl Iterator<Item = (Cow<'static, str>, &FieldDiff)> {
+        use crate::time_unit::*;
+        [
+            (Seconds::name(), &self.seconds),
+            (Minutes::name(), &self.minutes),
+            (Hours::name(), &self.hours),
+            (DaysOfMonth::name(), &self.days_of_month),
+            (Months::name(), &self.months),
+            (DaysOfWeek::name(), &self.days_of_week),
+            (Years::name(), &self.years),
+        ]
+        .into_iter()
+    }
+}
+
+impl Display for ScheduleDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut separator = "";
+        for (name, field) in self.fields().filter(|(_, field)| !field.is_empty()) {
+            write!(f, "{separator}{name}:")?;
+            for ordinal in &field.added {
+                write!(f, " +{ordinal}")?;
+            }
+            for ordinal in &field.removed {
+                write!(f, " -{ordinal}")?;
+            }
+            if field.specials_changed {
+                write!(f, " special constraints changed")?;
+            }
+            separator = "; ";
+        }
+        Ok(())
+    }
+}
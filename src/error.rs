@@ -1,9 +1,11 @@
+use chrono::Duration;
 use std::{error, fmt};
 
 /// A cron error
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
+    code: ErrorCode,
 }
 
 /// The kind of cron error that occurred
@@ -11,12 +13,132 @@ pub struct Error {
 pub enum ErrorKind {
     /// Failed to parse an expression
     Expression(String),
+    /// Failed to decode a binary encoded schedule
+    Decode(String),
+    /// A schedule doesn't fire within the given horizon
+    NoUpcomingWithin(Duration),
+    /// Failed to parse some lines of a multi-line input, given with their line number
+    Lines(Vec<(usize, Error)>),
+    /// Failed to encode a schedule in binary form
+    Encode(String),
+}
+
+/// A stable identifier of the cause of an error, for mapping errors to documented messages
+/// without matching on their text, which may change between releases.
+///
+/// Codes are never reused or renumbered, and new ones may be added.
+/// # Example
+/// ```
+/// use cron::error::ErrorCode;
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// let error = Schedule::from_str("0 0 25 * * *").unwrap_err();
+/// assert_eq!(ErrorCode::FieldOutOfRange, error.code());
+/// assert_eq!("E001", error.code().as_str());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// `E001`: a value is outside of the values its field allows, e.g. hour `25`.
+    FieldOutOfRange,
+    /// `E002`: a range ends before it starts, e.g. `20-10`.
+    InvalidRange,
+    /// `E003`: a step is zero or larger than its field allows, e.g. `*/0`.
+    InvalidStep,
+    /// `E004`: a name isn't a day of the week or month, or its field doesn't take names.
+    UnknownName,
+    /// `E005`: the offset or occurrence of a special character is out of range, e.g. `6#6`.
+    InvalidSpecial,
+    /// `E006`: neither day field is `?` where the dialect requires one of them to be.
+    DayFieldConflict,
+    /// `E007`: the dialect has no such field, e.g. the seconds in Unix cron.
+    UnsupportedField,
+    /// `E008`: the expression exceeds a limit set on the parser, e.g. its length.
+    LimitExceeded,
+    /// `E009`: the datetime of an `@once` expression is invalid.
+    InvalidDateTime,
+    /// `E010`: the expression doesn't follow the cron syntax.
+    Syntax,
+    /// `E011`: a binary encoded schedule couldn't be decoded.
+    Decode,
+    /// `E012`: a schedule doesn't fire within the given horizon.
+    NoUpcomingWithin,
+    /// `E013`: some lines of a multi-line input couldn't be parsed.
+    Lines,
+    /// `E014`: an iterator checkpoint was taken from another schedule than the one resuming it.
+    CheckpointMismatch,
+    /// `E015`: a schedule couldn't be encoded, as the encoding can't represent a part of it.
+    Encode,
+}
+
+impl ErrorCode {
+    /// The code as written in documentation, e.g. `E001`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::FieldOutOfRange => "E001",
+            ErrorCode::InvalidRange => "E002",
+            ErrorCode::InvalidStep => "E003",
+            ErrorCode::UnknownName => "E004",
+            ErrorCode::InvalidSpecial => "E005",
+            ErrorCode::DayFieldConflict => "E006",
+            ErrorCode::UnsupportedField => "E007",
+            ErrorCode::LimitExceeded => "E008",
+            ErrorCode::InvalidDateTime => "E009",
+            ErrorCode::Syntax => "E010",
+            ErrorCode::Decode => "E011",
+            ErrorCode::NoUpcomingWithin => "E012",
+            ErrorCode::Lines => "E013",
+            ErrorCode::CheckpointMismatch => "E014",
+            ErrorCode::Encode => "E015",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Error {
+    /// Returns the kind of error that occurred.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Returns the stable code of the error's cause.
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+}
+
+impl ErrorKind {
+    // An error of this kind with a more specific code than the one the kind implies.
+    pub(crate) fn with_code(self, code: ErrorCode) -> Error {
+        Error { kind: self, code }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             ErrorKind::Expression(ref expr) => write!(f, "{expr}"),
+            ErrorKind::Decode(ref reason) | ErrorKind::Encode(ref reason) => write!(f, "{reason}"),
+            ErrorKind::NoUpcomingWithin(horizon) => write!(
+                f,
+                "No upcoming time within {} seconds",
+                horizon.num_seconds()
+            ),
+            ErrorKind::Lines(ref lines) => {
+                for (index, (line, error)) in lines.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "Line {line}: {error}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -25,6 +147,13 @@ impl error::Error for Error {}
 
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
-        Error { kind }
+        let code = match kind {
+            ErrorKind::Expression(_) => ErrorCode::Syntax,
+            ErrorKind::Decode(_) => ErrorCode::Decode,
+            ErrorKind::NoUpcomingWithin(_) => ErrorCode::NoUpcomingWithin,
+            ErrorKind::Lines(_) => ErrorCode::Lines,
+            ErrorKind::Encode(_) => ErrorCode::Encode,
+        };
+        Error { kind, code }
     }
 }
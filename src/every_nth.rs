@@ -0,0 +1,165 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::occurrences::{OccurrenceIterator, Occurrences};
+use crate::schedule::Schedule;
+
+/// Keeps every nth occurrence of a [Schedule], counted from an anchor, as returned by
+/// [Schedule::every_nth], e.g. every third Friday, which cron expressions can't express.
+///
+/// The first occurrence at or after the anchor is kept, as is every nth occurrence before and
+/// after it. Queries walk the occurrences between the anchor and the queried instant, so the
+/// anchor should be reasonably recent.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// let fridays = Schedule::from_str("0 0 9 * * Fri").unwrap();
+/// let anchor = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+/// let every_third = fridays.every_nth(3, &anchor);
+/// let upcoming: Vec<_> = every_third.after(&anchor).take(2).collect();
+/// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap(), upcoming[0]);
+/// assert_eq!(Utc.with_ymd_and_hms(2026, 11, 6, 9, 0, 0).unwrap(), upcoming[1]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EveryNth {
+    schedule: Schedule,
+    n: u32,
+    anchor: DateTime<Utc>,
+}
+
+impl EveryNth {
+    pub(crate) fn new<Z>(schedule: Schedule, n: u32, anchor: &DateTime<Z>) -> EveryNth
+    where
+        Z: TimeZone,
+    {
+        assert!(n > 0, "Every nth occurrence requires n to be positive.");
+        EveryNth {
+            schedule,
+            n,
+            anchor: anchor.with_timezone(&Utc),
+        }
+    }
+
+    /// The schedule being downsampled.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// How many occurrences of the schedule there are between two kept ones.
+    pub fn n(&self) -> u32 {
+        self.n
+    }
+
+    /// The instant occurrences are counted from.
+    pub fn anchor(&self) -> DateTime<Utc> {
+        self.anchor
+    }
+
+    /// Provides an iterator over the kept occurrences after the given instant.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> OccurrenceIterator<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        OccurrenceIterator::new(self, after)
+    }
+
+    // The occurrences from the anchor onwards, along with their index.
+    fn forward<Z>(&self, timezone: &Z) -> impl Iterator<Item = (i64, DateTime<Z>)>
+    where
+        Z: TimeZone,
+    {
+        let start = self.start(timezone);
+        self.schedule
+            .after_owned(start)
+            .enumerate()
+            .map(|(index, occurrence)| (index as i64, occurrence))
+    }
+
+    // The occurrences before the anchor, latest first, along with their negative index.
+    fn backward<Z>(&self, timezone: &Z) -> impl Iterator<Item = (i64, DateTime<Z>)>
+    where
+        Z: TimeZone,
+    {
+        let start = self.start(timezone);
+        self.schedule
+            .after_owned(start)
+            .rev()
+            .enumerate()
+            .map(|(index, occurrence)| (-(index as i64) - 1, occurrence))
+    }
+
+    // Just before the anchor, so that an occurrence at the anchor itself counts as the first. No
+    // occurrence can fall on the earliest instant chrono supports, so it's its own start.
+    fn start<Z>(&self, timezone: &Z) -> DateTime<Z>
+    where
+        Z: TimeZone,
+    {
+        self.anchor
+            .checked_sub_signed(Duration::nanoseconds(1))
+            .unwrap_or(self.anchor)
+            .with_timezone(timezone)
+    }
+
+    fn is_kept(&self, index: i64) -> bool {
+        index.rem_euclid(i64::from(self.n)) == 0
+    }
+}
+
+impl<Z> Occurrences<Z> for EveryNth
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let timezone = after.timezone();
+        let first = self.forward(&timezone).next().map(|(_, first)| first);
+        match first {
+            Some(first) if first <= *after => self
+                .forward(&timezone)
+                .skip_while(|(_, occurrence)| occurrence <= after)
+                .find(|&(index, _)| self.is_kept(index))
+                .map(|(_, occurrence)| occurrence),
+            _ => self
+                .backward(&timezone)
+                .take_while(|(_, occurrence)| occurrence > after)
+                .filter(|&(index, _)| self.is_kept(index))
+                .last()
+                .map(|(_, occurrence)| occurrence)
+                .or(first),
+        }
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let timezone = before.timezone();
+        match self.forward(&timezone).next() {
+            Some((_, first)) if first < *before => self
+                .forward(&timezone)
+                .take_while(|(_, occurrence)| occurrence < before)
+                .filter(|&(index, _)| self.is_kept(index))
+                .last()
+                .map(|(_, occurrence)| occurrence),
+            _ => self
+                .backward(&timezone)
+                .find(|(index, occurrence)| occurrence < before && self.is_kept(*index))
+                .map(|(_, occurrence)| occurrence),
+        }
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        if !self.schedule.includes(date_time.clone()) {
+            return false;
+        }
+        let timezone = date_time.timezone();
+        let index = if date_time >= self.anchor {
+            self.forward(&timezone)
+                .find(|(_, occurrence)| *occurrence >= date_time)
+                .map(|(index, _)| index)
+        } else {
+            self.backward(&timezone)
+                .find(|(_, occurrence)| *occurrence <= date_time)
+                .map(|(index, _)| index)
+        };
+        index.is_some_and(|index| self.is_kept(index))
+    }
+}
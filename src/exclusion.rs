@@ -0,0 +1,205 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::calendar::CalendarSystem;
+use crate::error::{Error, ErrorCode, ErrorKind};
+use crate::ordinal::Ordinal;
+use crate::schedule::{LeapDayPolicy, Schedule, ScheduleFields};
+use crate::time_unit::{
+    DayOfMonthSpec, DaysOfMonth, DaysOfWeek, Months, TimeUnitField, TimeUnitSpec,
+};
+
+/// A set of times at which a [Schedule] must not fire, mirroring Quartz's calendars.
+///
+/// Calendars are attached to a schedule with [Schedule::with_calendar] and are evaluated against
+/// the local date and time of each candidate firing.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc, Weekday};
+/// use cron::{AnnualCalendar, Schedule, WeeklyCalendar};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 12 * * *")
+///     .unwrap()
+///     .with_calendar(WeeklyCalendar::new().excluding(Weekday::Sat).excluding(Weekday::Sun))
+///     .with_calendar(AnnualCalendar::new().excluding(12, 25));
+///
+/// // Friday, 2026-12-18
+/// let friday = Utc.with_ymd_and_hms(2026, 12, 18, 12, 0, 0).unwrap();
+/// let mut upcoming = schedule.after(&friday);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 12, 21, 12, 0, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 12, 22, 12, 0, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 12, 23, 12, 0, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 12, 24, 12, 0, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 12, 28, 12, 0, 0).unwrap()), upcoming.next());
+/// ```
+pub trait Calendar: Debug + Send + Sync {
+    /// Returns true if a firing at the given local date and time must be skipped.
+    fn excludes(&self, datetime: &NaiveDateTime) -> bool;
+
+    /// Returns true if every firing on the given local date must be skipped, so that searches
+    /// can move on to the next day at once rather than try each time of the day in turn.
+    /// Defaults to `false`, which is always correct but slower for calendars excluding whole
+    /// days.
+    fn excludes_day(&self, date: &NaiveDate) -> bool {
+        let _ = date;
+        false
+    }
+}
+
+/// Excludes whole days of the year, such as public holidays, regardless of the year.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnnualCalendar {
+    days: BTreeSet<(Ordinal, Ordinal)>,
+}
+
+impl AnnualCalendar {
+    /// Creates a calendar which doesn't exclude any day.
+    pub fn new() -> AnnualCalendar {
+        AnnualCalendar::default()
+    }
+
+    /// Excludes the given day of the given month (both starting at 1) every year.
+    pub fn excluding(mut self, month: Ordinal, day_of_month: Ordinal) -> AnnualCalendar {
+        self.days.insert((month, day_of_month));
+        self
+    }
+}
+
+impl Calendar for AnnualCalendar {
+    fn excludes(&self, datetime: &NaiveDateTime) -> bool {
+        self.excludes_day(&datetime.date())
+    }
+
+    fn excludes_day(&self, date: &NaiveDate) -> bool {
+        self.days.contains(&(date.month(), date.day()))
+    }
+}
+
+/// Excludes whole days of the week, such as weekends.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WeeklyCalendar {
+    days: BTreeSet<Ordinal>,
+}
+
+impl WeeklyCalendar {
+    /// Creates a calendar which doesn't exclude any day.
+    pub fn new() -> WeeklyCalendar {
+        WeeklyCalendar::default()
+    }
+
+    /// Excludes the given day every week.
+    pub fn excluding(mut self, day_of_week: Weekday) -> WeeklyCalendar {
+        self.days.insert(day_of_week.number_from_sunday());
+        self
+    }
+}
+
+impl Calendar for WeeklyCalendar {
+    fn excludes(&self, datetime: &NaiveDateTime) -> bool {
+        self.excludes_day(&datetime.date())
+    }
+
+    fn excludes_day(&self, date: &NaiveDate) -> bool {
+        self.days.contains(&date.weekday().number_from_sunday())
+    }
+}
+
+/// Excludes every time matched by a cron [Schedule], e.g. `* * 0-6 * * ?` to exclude nights.
+#[derive(Clone, Debug)]
+pub struct CronCalendar {
+    schedule: Schedule,
+}
+
+impl CronCalendar {
+    /// Creates a calendar excluding the times included in the given schedule.
+    pub fn new(schedule: Schedule) -> CronCalendar {
+        CronCalendar { schedule }
+    }
+}
+
+impl Calendar for CronCalendar {
+    fn excludes(&self, datetime: &NaiveDateTime) -> bool {
+        self.schedule.includes_local(datetime)
+    }
+}
+
+// Picks the days whose month and day in a calendar system match the day fields of a schedule,
+// for schedules evaluated against that calendar with [Schedule::in_calendar_system].
+#[derive(Clone, Debug)]
+pub(crate) struct CalendarSystemDays {
+    pub(crate) system: Arc<dyn CalendarSystem>,
+    days_of_month: DaysOfMonth,
+    months: Months,
+    // The days of the week, when a day matches if it's included by either of the day fields.
+    days_of_week: Option<DaysOfWeek>,
+    // The fields the Gregorian search goes through, which include every day and leave the
+    // calendar system to pick them.
+    pub(crate) fields: ScheduleFields,
+}
+
+impl CalendarSystemDays {
+    // Fails for the day specials which depend on the Gregorian month.
+    pub(crate) fn new(
+        system: Arc<dyn CalendarSystem>,
+        fields: &ScheduleFields,
+    ) -> Result<Self, Error> {
+        let gregorian_specs = fields.days_of_month.has_nearest_weekdays()
+            || fields.days_of_month.business_days().next().is_some()
+            || !fields.days_of_week.specs().is_empty();
+        if gregorian_specs {
+            return Err(ErrorKind::Expression(
+                "'W', 'LW', 'B', '#' and 'L' after a day of the week can't be evaluated against \
+                 another calendar system."
+                    .to_string(),
+            )
+            .with_code(ErrorCode::InvalidSpecial));
+        }
+        let mut search = fields.clone();
+        search.days_of_month = DaysOfMonth::all();
+        search.months = Months::all();
+        if search.days_match_either {
+            search.days_of_week = DaysOfWeek::all();
+            search.days_match_either = false;
+        }
+        search.leap_day = LeapDayPolicy::Skip;
+        search.clamp_to_end_of_month = false;
+        Ok(CalendarSystemDays {
+            system,
+            days_of_month: fields.days_of_month.clone(),
+            months: fields.months.clone(),
+            days_of_week: fields
+                .days_match_either
+                .then(|| fields.days_of_week.clone()),
+            fields: search,
+        })
+    }
+
+    pub(crate) fn excludes_day(&self, date: NaiveDate) -> bool {
+        !self.includes(date)
+    }
+
+    fn includes(&self, date: NaiveDate) -> bool {
+        let Some((month, day)) = self.system.month_and_day(date) else {
+            return false;
+        };
+        let last_day = || self.system.days_in_month(date);
+        let day_of_month = self.days_of_month.includes(day)
+            || self.days_of_month.specs().iter().any(|spec| match *spec {
+                DayOfMonthSpec::LastDay { offset } => {
+                    last_day().and_then(|last_day| last_day.checked_sub(offset)) == Some(day)
+                }
+                // Days counted in weekdays are rejected when the calendar system is set.
+                DayOfMonthSpec::NearestWeekday { .. }
+                | DayOfMonthSpec::LastWeekday
+                | DayOfMonthSpec::BusinessDay { .. } => false,
+            });
+        let day_of_week = self
+            .days_of_week
+            .as_ref()
+            .is_some_and(|days_of_week| days_of_week.includes(date.weekday().number_from_sunday()));
+        self.months.includes(month) && (day_of_month || day_of_week)
+    }
+}
@@ -0,0 +1,294 @@
+use std::borrow::Cow;
+
+use crate::ordinal::*;
+use crate::schedule::ScheduleFields;
+use crate::time_unit::*;
+
+/// The result of translating a [Schedule](crate::Schedule) into another scheduler's syntax.
+///
+/// Parts of the schedule the target syntax can't express are left out of the expression, which
+/// then fires more often than the schedule, and are listed in [Translation::untranslatable].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Translation {
+    /// The translated expression.
+    pub expression: String,
+    /// The parts of the schedule which were left out of the expression.
+    pub untranslatable: Vec<Untranslatable>,
+}
+
+impl Translation {
+    /// Returns true if the expression fires exactly when the schedule does.
+    pub fn is_exact(&self) -> bool {
+        self.untranslatable.is_empty()
+    }
+}
+
+/// A part of a [Schedule](crate::Schedule) which couldn't be translated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Untranslatable {
+    /// The name of the field holding the part, e.g. `Days of Week`.
+    pub field: Cow<'static, str>,
+    /// Why the part couldn't be translated.
+    pub reason: String,
+}
+
+impl Untranslatable {
+    pub(crate) fn new<T: TimeUnitField>(reason: impl Into<String>) -> Untranslatable {
+        Untranslatable {
+            field: T::name(),
+            reason: reason.into(),
+        }
+    }
+}
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+// Writes plain ordinals as a list of single values and ranges, e.g. `1,3..5` with a `..`
+// separator.
+pub(crate) fn format_list(
+    ordinals: &[Ordinal],
+    range_separator: &str,
+    format: impl Fn(Ordinal) -> String,
+) -> String {
+    let mut items = Vec::new();
+    let mut index = 0;
+    while index < ordinals.len() {
+        let mut end = index;
+        while end + 1 < ordinals.len() && ordinals[end + 1] == ordinals[end] + 1 {
+            end += 1;
+        }
+        match end - index {
+            0 => items.push(format(ordinals[index])),
+            1 => items.push(format!(
+                "{},{}",
+                format(ordinals[index]),
+                format(ordinals[end])
+            )),
+            _ => items.push(format!(
+                "{}{range_separator}{}",
+                format(ordinals[index]),
+                format(ordinals[end])
+            )),
+        }
+        index = end + 1;
+    }
+    items.join(",")
+}
+
+// Returns the plain ordinals of the field, or `None` if it's unrestricted.
+fn restricted<T: TimeUnitField>(field: &T) -> Option<Vec<Ordinal>> {
+    if field.is_all() {
+        return None;
+    }
+    Some(field.iter().collect())
+}
+
+// A systemd calendar component, e.g. `09` or `00/15`.
+fn oncalendar_component<T: TimeUnitField>(field: &T, width: usize) -> String {
+    let pad = |ordinal: Ordinal| format!("{ordinal:0width$}");
+    match restricted(field) {
+        None => "*".to_string(),
+        // Only specials, which are reported separately.
+        Some(ordinals) if ordinals.is_empty() => "*".to_string(),
+        Some(ordinals) => {
+            if let Some((start, step)) = progression::<T>(&ordinals) {
+                return format!("{}/{step}", pad(start));
+            }
+            format_list(&ordinals, "..", pad)
+        }
+    }
+}
+
+// Returns the start and step of ordinals which repeat until the end of the field, e.g. `0,15,30,45`.
+pub(crate) fn progression<T: TimeUnitField>(ordinals: &[Ordinal]) -> Option<(Ordinal, Ordinal)> {
+    if ordinals.len() < 3 {
+        return None;
+    }
+    let step = ordinals[1] - ordinals[0];
+    let is_progression = ordinals.windows(2).all(|pair| pair[1] - pair[0] == step);
+    (is_progression && step > 1 && ordinals[ordinals.len() - 1] + step > T::inclusive_max())
+        .then_some((ordinals[0], step))
+}
+
+// Writes plain ordinals as a cron field, preferring `*`, steps and ranges over plain lists. Years
+// of a range other than the default one are written out rather than as `*`.
+pub(crate) fn format_cron_field<T: TimeUnitField>(ordinals: &[Ordinal]) -> String {
+    if ordinals
+        .iter()
+        .copied()
+        .eq(T::inclusive_min()..=T::inclusive_max())
+    {
+        return "*".to_string();
+    }
+    match progression::<T>(ordinals) {
+        Some((start, step)) if start == T::inclusive_min() => format!("*/{step}"),
+        Some((start, step)) => format!("{start}/{step}"),
+        None => format_list(ordinals, "-", |ordinal| ordinal.to_string()),
+    }
+}
+
+// A Quartz field, where `L`, `W` and `#` can't be combined with other values.
+fn quartz_field<T: TimeUnitField, S>(
+    field: &T,
+    specs: &[S],
+    format_spec: impl Fn(&S) -> Result<String, &'static str>,
+    untranslatable: &mut Vec<Untranslatable>,
+) -> Option<String> {
+    let plain: Vec<Ordinal> = field.iter().collect();
+    match (&plain[..], specs) {
+        (_, []) => Some(format_cron_field::<T>(&plain)),
+        ([], [spec]) => match format_spec(spec) {
+            Ok(formatted) => Some(formatted),
+            Err(reason) => {
+                untranslatable.push(Untranslatable::new::<T>(reason));
+                None
+            }
+        },
+        _ => {
+            untranslatable.push(Untranslatable::new::<T>(
+                "Quartz can't combine L, W or # with other values",
+            ));
+            None
+        }
+    }
+}
+
+pub(crate) fn to_quartz(fields: &ScheduleFields) -> Translation {
+    let mut untranslatable = Vec::new();
+    // Fields which can't be translated are widened to `*`, as in `to_oncalendar`.
+    let days_of_month = quartz_field(
+        &fields.days_of_month,
+        fields.days_of_month.specs(),
+        |spec| match *spec {
+            DayOfMonthSpec::BusinessDay { .. } => {
+                Err("Quartz has no equivalent for the nth business day of the month")
+            }
+            spec => Ok(spec.to_string()),
+        },
+        &mut untranslatable,
+    )
+    .unwrap_or_else(|| "*".to_string());
+    let days_of_week = quartz_field(
+        &fields.days_of_week,
+        fields.days_of_week.specs(),
+        |spec| match *spec {
+            DayOfWeekSpec::Last { offset, .. } if offset != 0 => {
+                Err("Quartz has no equivalent for an offset from the last occurrence of a day")
+            }
+            spec => Ok(spec.to_string()),
+        },
+        &mut untranslatable,
+    )
+    .unwrap_or_else(|| "*".to_string());
+
+    // Quartz requires exactly one of the day fields to be `?`, and can't restrict both.
+    let (days_of_month, days_of_week) = match (days_of_month.as_str(), days_of_week.as_str()) {
+        _ if fields.days_match_either => {
+            untranslatable.push(Untranslatable::new::<DaysOfWeek>(
+                "Quartz can't match days included by either of the day fields",
+            ));
+            ("*".to_string(), "?".to_string())
+        }
+        (_, "*") => (days_of_month, "?".to_string()),
+        ("*", _) => ("?".to_string(), days_of_week),
+        _ => {
+            untranslatable.push(Untranslatable::new::<DaysOfWeek>(
+                "Quartz can't restrict both the days of month and the days of week",
+            ));
+            (days_of_month, "?".to_string())
+        }
+    };
+
+    let mut expression = format!(
+        "{} {} {} {days_of_month} {} {days_of_week}",
+        format_cron_field::<Seconds>(&fields.seconds.iter().collect::<Vec<_>>()),
+        format_cron_field::<Minutes>(&fields.minutes.iter().collect::<Vec<_>>()),
+        format_cron_field::<Hours>(&fields.hours.iter().collect::<Vec<_>>()),
+        format_cron_field::<Months>(&fields.months.iter().collect::<Vec<_>>()),
+    );
+    if let Some(years) = restricted(&fields.years) {
+        expression.push(' ');
+        expression.push_str(&format_cron_field::<Years>(&years));
+    }
+    Translation {
+        expression,
+        untranslatable,
+    }
+}
+
+pub(crate) fn to_oncalendar(fields: &ScheduleFields) -> Translation {
+    let mut untranslatable = Vec::new();
+
+    // Leaving out a special alone would stop the schedule from firing on the days it matches,
+    // so the whole field is widened to `*` instead.
+    let mut weekdays = String::new();
+    let weekday_specs = fields.days_of_week.specs();
+    for spec in weekday_specs {
+        let weekday = WEEKDAY_NAMES[spec.weekday() as usize - 1];
+        untranslatable.push(Untranslatable::new::<DaysOfWeek>(format!(
+            "systemd has no equivalent for a specific occurrence of {weekday} in the month"
+        )));
+    }
+    if let (Some(ordinals), true) = (restricted(&fields.days_of_week), weekday_specs.is_empty()) {
+        weekdays = format_list(&ordinals, "..", |ordinal| {
+            WEEKDAY_NAMES[ordinal as usize - 1].to_string()
+        });
+        weekdays.push(' ');
+    }
+
+    let mut days = oncalendar_component(&fields.days_of_month, 2);
+    let mut day_separator = "-";
+    let day_specs = fields.days_of_month.specs();
+    match day_specs {
+        [] => {}
+        // `~01` is the last day of the month, `~02` the one before and so on.
+        [DayOfMonthSpec::LastDay { offset }] if fields.days_of_month.iter().next().is_none() => {
+            days = format!("{:02}", offset + 1);
+            day_separator = "~";
+        }
+        _ => {
+            days = "*".to_string();
+            let last_days = day_specs
+                .iter()
+                .any(|spec| matches!(spec, DayOfMonthSpec::LastDay { .. }));
+            if last_days {
+                untranslatable.push(Untranslatable::new::<DaysOfMonth>(
+                    "systemd can only express a single day counted from the end of the month",
+                ));
+            }
+            if day_specs.iter().any(DayOfMonthSpec::is_nearest_weekday) {
+                untranslatable.push(Untranslatable::new::<DaysOfMonth>(
+                    "systemd has no equivalent for the nearest weekday to a day",
+                ));
+            }
+            if fields.days_of_month.business_days().next().is_some() {
+                untranslatable.push(Untranslatable::new::<DaysOfMonth>(
+                    "systemd has no equivalent for the nth business day of the month",
+                ));
+            }
+        }
+    }
+
+    if fields.days_match_either {
+        untranslatable.push(Untranslatable::new::<DaysOfWeek>(
+            "systemd can't match days included by either of the day fields",
+        ));
+        weekdays.clear();
+        days = "*".to_string();
+        day_separator = "-";
+    }
+
+    let expression = format!(
+        "{weekdays}{}-{}{day_separator}{} {}:{}:{}",
+        oncalendar_component(&fields.years, 4),
+        oncalendar_component(&fields.months, 2),
+        days,
+        oncalendar_component(&fields.hours, 2),
+        oncalendar_component(&fields.minutes, 2),
+        oncalendar_component(&fields.seconds, 2),
+    );
+    Translation {
+        expression,
+        untranslatable,
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::{Month, Weekday};
+use std::ops::RangeInclusive;
+
+use crate::export::format_list;
+use crate::ordinal::Ordinal;
+
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+pub(crate) const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Values of a field given as typed enums rather than as text, so that they can't be out of
+/// range: a single value such as `Weekday::Mon`, an inclusive range such as
+/// `Weekday::Mon..=Weekday::Fri`, or a slice, array or vector of values.
+///
+/// Ranges wrap around the end of the week or year, so `Weekday::Fri..=Weekday::Mon` is the
+/// weekend along with the days around it. Values are taken by
+/// [Schedule::with_weekdays](crate::Schedule::with_weekdays) and
+/// [Schedule::with_months_of_year](crate::Schedule::with_months_of_year).
+pub trait FieldValues<T> {
+    /// The values, in any order and possibly repeated.
+    fn values(self) -> Vec<T>;
+}
+
+// A typed value of a field, with its ordinal in a Quartz expression.
+pub(crate) trait FieldValue: Copy + PartialEq {
+    fn ordinal(self) -> Ordinal;
+    fn succ(self) -> Self;
+    fn name(ordinal: Ordinal) -> &'static str;
+}
+
+impl FieldValue for Weekday {
+    fn ordinal(self) -> Ordinal {
+        self.number_from_sunday()
+    }
+    fn succ(self) -> Self {
+        Weekday::succ(&self)
+    }
+    fn name(ordinal: Ordinal) -> &'static str {
+        WEEKDAY_NAMES[ordinal as usize - 1]
+    }
+}
+
+impl FieldValue for Month {
+    fn ordinal(self) -> Ordinal {
+        self.number_from_month()
+    }
+    fn succ(self) -> Self {
+        Month::succ(&self)
+    }
+    fn name(ordinal: Ordinal) -> &'static str {
+        MONTH_NAMES[ordinal as usize - 1]
+    }
+}
+
+macro_rules! field_values {
+    ($value:ty) => {
+        impl FieldValues<$value> for $value {
+            fn values(self) -> Vec<$value> {
+                vec![self]
+            }
+        }
+
+        impl FieldValues<$value> for RangeInclusive<$value> {
+            fn values(self) -> Vec<$value> {
+                let (start, end) = self.into_inner();
+                let mut values = vec![start];
+                while *values.last().unwrap() != end {
+                    values.push(FieldValue::succ(*values.last().unwrap()));
+                }
+                values
+            }
+        }
+
+        impl FieldValues<$value> for &[$value] {
+            fn values(self) -> Vec<$value> {
+                self.to_vec()
+            }
+        }
+
+        impl<const N: usize> FieldValues<$value> for [$value; N] {
+            fn values(self) -> Vec<$value> {
+                self.to_vec()
+            }
+        }
+
+        impl FieldValues<$value> for Vec<$value> {
+            fn values(self) -> Vec<$value> {
+                self
+            }
+        }
+    };
+}
+
+field_values!(Weekday);
+field_values!(Month);
+
+// Writes the values as a field with names, e.g. `MON-FRI`, which every dialect reads alike.
+pub(crate) fn field_text<T: FieldValue>(values: impl FieldValues<T>) -> String {
+    let mut ordinals: Vec<Ordinal> = values.values().into_iter().map(T::ordinal).collect();
+    ordinals.sort_unstable();
+    ordinals.dedup();
+    format_list(&ordinals, "-", |ordinal| T::name(ordinal).to_string())
+}
@@ -0,0 +1,210 @@
+use crate::export::{self, Translation, Untranslatable};
+use crate::field_values::{MONTH_NAMES, WEEKDAY_NAMES};
+use crate::ordinal::*;
+use crate::parsing;
+use crate::schedule::Schedule;
+use crate::time_unit::*;
+
+/// The number of fields a [ScheduleFormatter] writes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FieldCount {
+    /// `min hour dom month dow`, read as classic crontab expressions: Sunday is `0`, and a day
+    /// matches if either of the day fields includes it when both are restricted.
+    Five,
+    /// `sec min hour dom month dow`, read as by [Dialect::Quartz](crate::Dialect::Quartz).
+    #[default]
+    Six,
+    /// `sec min hour dom month dow year`, read as by [Dialect::Quartz](crate::Dialect::Quartz).
+    Seven,
+}
+
+/// Writes [Schedule]s out as cron expressions in the shape a downstream system accepts, rather
+/// than as they were written.
+///
+/// By default, expressions have six fields and values are written as numbers. Parts of a schedule
+/// the chosen shape can't express are left out, and listed in [Translation::untranslatable]:
+/// specials such as `L`, `W` or `#` in five fields, where their whole field is widened to `*`,
+/// seconds other than `0` in five fields and restricted years in fewer than seven.
+/// # Example
+/// ```
+/// use cron::{FieldCount, Schedule, ScheduleFormatter};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 30 9 * 1-3 2-6").unwrap();
+/// let formatter = ScheduleFormatter::new().names(true).field_count(FieldCount::Five);
+/// assert_eq!("30 9 * JAN-MAR MON-FRI", formatter.format(&schedule).expression);
+///
+/// let nightly = Schedule::from_str("0 0 0 * * *").unwrap();
+/// let formatter = ScheduleFormatter::new().nicknames(true);
+/// assert_eq!("@daily", formatter.format(&nightly).expression);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleFormatter {
+    names: bool,
+    nicknames: bool,
+    field_count: FieldCount,
+}
+
+impl ScheduleFormatter {
+    /// Creates a formatter with the default options.
+    pub fn new() -> ScheduleFormatter {
+        ScheduleFormatter::default()
+    }
+
+    /// Writes months and days of the week as names such as `JAN` and `MON` rather than as
+    /// numbers. Disabled by default.
+    pub fn names(mut self, names: bool) -> ScheduleFormatter {
+        self.names = names;
+        self
+    }
+
+    /// Writes `@yearly`, `@monthly`, `@weekly`, `@daily` or `@hourly` instead of the fields when
+    /// one of them fires exactly when the schedule does. Disabled by default.
+    pub fn nicknames(mut self, nicknames: bool) -> ScheduleFormatter {
+        self.nicknames = nicknames;
+        self
+    }
+
+    /// Sets the number of fields to write. Defaults to [FieldCount::Six].
+    pub fn field_count(mut self, field_count: FieldCount) -> ScheduleFormatter {
+        self.field_count = field_count;
+        self
+    }
+
+    /// Writes the schedule out with the options of the formatter. Calendars and the policies set
+    /// when parsing, such as the [leap day policy](crate::ScheduleParser::leap_day), aren't
+    /// written.
+    pub fn format(&self, schedule: &Schedule) -> Translation {
+        let fields = schedule.fields();
+        if self.nicknames {
+            if let Some(nickname) = parsing::nickname_of(fields) {
+                return Translation {
+                    expression: nickname.to_string(),
+                    untranslatable: Vec::new(),
+                };
+            }
+        }
+
+        let mut untranslatable = Vec::new();
+        let five = self.field_count == FieldCount::Five;
+        let month = |ordinal: Ordinal| MONTH_NAMES[ordinal as usize - 1].to_string();
+        let weekday = |ordinal: Ordinal| WEEKDAY_NAMES[ordinal as usize - 1].to_string();
+        let unix_weekday = |ordinal: Ordinal| (ordinal - 1).to_string();
+        let month_label: Option<&dyn Fn(Ordinal) -> String> = match self.names {
+            true => Some(&month),
+            false => None,
+        };
+        let weekday_label: Option<&dyn Fn(Ordinal) -> String> = match (self.names, five) {
+            (true, _) => Some(&weekday),
+            (false, true) => Some(&unix_weekday),
+            (false, false) => None,
+        };
+
+        let mut days_of_month = self.format_days(&fields.days_of_month, None, &mut untranslatable);
+        let mut days_of_week =
+            self.format_days(&fields.days_of_week, weekday_label, &mut untranslatable);
+        if days_of_month != "*" && days_of_week != "*" {
+            if five && !fields.days_match_either {
+                untranslatable.push(Untranslatable::new::<DaysOfWeek>(
+                    "Five field expressions match days included by either of the day fields",
+                ));
+                days_of_week = "*".to_string();
+            } else if !five && fields.days_match_either {
+                untranslatable.push(Untranslatable::new::<DaysOfWeek>(
+                    "Six and seven field expressions match days included by both day fields",
+                ));
+                days_of_month = "*".to_string();
+                days_of_week = "*".to_string();
+            }
+        }
+
+        let mut items = Vec::new();
+        if !five {
+            items.push(format_field(&fields.seconds, None));
+        } else if fields.seconds.iter().ne([0]) {
+            untranslatable.push(Untranslatable::new::<Seconds>(
+                "Five field expressions always fire at second 0",
+            ));
+        }
+        items.push(format_field(&fields.minutes, None));
+        items.push(format_field(&fields.hours, None));
+        items.push(days_of_month);
+        items.push(format_field(&fields.months, month_label));
+        items.push(days_of_week);
+        if self.field_count == FieldCount::Seven {
+            items.push(format_field(&fields.years, None));
+        } else if !fields.every_year() {
+            untranslatable.push(Untranslatable::new::<Years>(format!(
+                "{} field expressions have no year",
+                if five { "Five" } else { "Six" }
+            )));
+        }
+        Translation {
+            expression: items.join(" "),
+            untranslatable,
+        }
+    }
+
+    // Writes one of the day fields, whose specials five fields can't express.
+    fn format_days<T: TimeUnitField>(
+        &self,
+        field: &T,
+        label: Option<&dyn Fn(Ordinal) -> String>,
+        untranslatable: &mut Vec<Untranslatable>,
+    ) -> String {
+        let specs = field.day_specs();
+        if self.field_count == FieldCount::Five && !specs.is_empty() {
+            untranslatable.push(Untranslatable::new::<T>(
+                "Five field expressions have no equivalent for L, W, # or B",
+            ));
+            return "*".to_string();
+        }
+        let weekday = |ordinal: Ordinal| match label {
+            Some(label) => label(ordinal),
+            None => ordinal.to_string(),
+        };
+        let mut items = Vec::new();
+        if field.iter().next().is_some() {
+            items.push(format_field(field, label));
+        }
+        items.extend(specs.iter().map(|spec| match *spec {
+            DaySpec::OfWeek(DayOfWeekSpec::Nth { weekday: day, nth }) => {
+                format!("{}#{nth}", weekday(day))
+            }
+            DaySpec::OfWeek(DayOfWeekSpec::Last {
+                weekday: day,
+                offset: 0,
+            }) => {
+                format!("{}L", weekday(day))
+            }
+            DaySpec::OfWeek(DayOfWeekSpec::Last {
+                weekday: day,
+                offset,
+            }) => format!("{}L-{offset}", weekday(day)),
+            DaySpec::OfMonth(spec) => spec.to_string(),
+        }));
+        items.join(",")
+    }
+}
+
+// Writes the plain values of a field, with the given labels rather than numbers if any.
+// Labelled steps which don't start at the first value are written over a range, since a name such
+// as `FEB/3` doesn't parse and a shifted start such as `1/2` for the Unix days of the week would
+// include Sunday again as `7`.
+fn format_field<T: TimeUnitField>(field: &T, label: Option<&dyn Fn(Ordinal) -> String>) -> String {
+    let ordinals: Vec<Ordinal> = field.iter().collect();
+    let Some(label) = label else {
+        return export::format_cron_field::<T>(&ordinals);
+    };
+    if field.is_all() {
+        return "*".to_string();
+    }
+    match export::progression::<T>(&ordinals) {
+        Some((start, step)) if start == T::inclusive_min() => format!("*/{step}"),
+        Some((start, step)) => {
+            let last = ordinals[ordinals.len() - 1];
+            format!("{}-{}/{step}", label(start), label(last))
+        }
+        None => export::format_list(&ordinals, "-", label),
+    }
+}
@@ -0,0 +1,24 @@
+/// A coarse classification of how often a [Schedule](crate::Schedule) fires, as returned by
+/// [Schedule::frequency](crate::Schedule::frequency).
+///
+/// Variants are ordered from the most to the least frequent, with [Frequency::Irregular] last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Frequency {
+    /// Fires on several seconds of the same minute.
+    PerSecond,
+    /// Fires on several minutes of the same hour, once per minute.
+    PerMinute,
+    /// Fires on several hours of the same day, once per hour.
+    Hourly,
+    /// Fires once on several days of the same week.
+    Daily,
+    /// Fires once on a single day of the week.
+    Weekly,
+    /// Fires once on a single day of several months.
+    Monthly,
+    /// Fires once on a single day of a single month.
+    Yearly,
+    /// Fires at most once a day, on days that don't follow any of the cadences above, e.g.
+    /// `0 0 12 1,15 * ?`.
+    Irregular,
+}
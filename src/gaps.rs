@@ -0,0 +1,44 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::schedule::ScheduleIterator;
+
+/// An iterator over the occurrences of a [Schedule](crate::Schedule) along with the time elapsed
+/// since the occurrence before each of them, as returned by
+/// [Schedule::gaps_after](crate::Schedule::gaps_after).
+pub struct Gaps<'a, Z>
+where
+    Z: TimeZone,
+{
+    previous: Option<DateTime<Z>>,
+    occurrences: ScheduleIterator<'a, Z>,
+}
+
+impl<'a, Z> Gaps<'a, Z>
+where
+    Z: TimeZone,
+{
+    pub(crate) fn new(previous: Option<DateTime<Z>>, occurrences: ScheduleIterator<'a, Z>) -> Self {
+        Gaps {
+            previous,
+            occurrences,
+        }
+    }
+}
+
+impl<Z> Iterator for Gaps<'_, Z>
+where
+    Z: TimeZone,
+{
+    type Item = (DateTime<Z>, Duration);
+
+    fn next(&mut self) -> Option<(DateTime<Z>, Duration)> {
+        loop {
+            let next = self.occurrences.next()?;
+            // The first occurrence of a schedule only serves as the start of the next gap.
+            if let Some(previous) = self.previous.replace(next.clone()) {
+                let gap = next.clone() - previous;
+                return Some((next, gap));
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+
+/// How many times a [Schedule](crate::Schedule) fires within a span of time, bucketed by hour
+/// of the day, day of the week and month, as returned by
+/// [Schedule::histogram](crate::Schedule::histogram), e.g. to render a heatmap of a schedule.
+///
+/// Buckets use the local time of the timezone the span was given in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Histogram {
+    /// Firings per hour of the day, from midnight to 23:00.
+    pub hours: [u64; 24],
+    /// Firings per day of the week, from Monday to Sunday.
+    pub days_of_week: [u64; 7],
+    /// Firings per month, from January to December.
+    pub months: [u64; 12],
+    /// Firings in total.
+    pub total: u64,
+}
+
+impl Histogram {
+    pub(crate) fn record<Z>(&mut self, date_time: &DateTime<Z>)
+    where
+        Z: TimeZone,
+    {
+        self.hours[date_time.hour() as usize] += 1;
+        self.days_of_week[date_time.weekday().num_days_from_monday() as usize] += 1;
+        self.months[date_time.month0() as usize] += 1;
+        self.total += 1;
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, Offset, TimeZone, Utc};
+use std::fmt::Write;
+
+use crate::counting;
+use crate::ordinal::Ordinal;
+use crate::schedule::{LeapDayPolicy, Schedule};
+use crate::time_unit::*;
+
+const WEEKDAY_CODES: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+// Content lines longer than this many bytes are folded, as RFC 5545 requires.
+const MAX_LINE_LENGTH: usize = 75;
+
+pub(crate) fn to_ics<Z>(schedule: &Schedule, n: usize, after: &DateTime<Z>) -> String
+where
+    Z: TimeZone,
+{
+    let occurrences: Vec<DateTime<Z>> = schedule.after(after).take(n).collect();
+    let uid = uid_suffix(schedule.source());
+    let stamp = format_utc(&Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//cron//cron {}//EN", env!("CARGO_PKG_VERSION")),
+    ];
+    let mut event = |start: &DateTime<Z>, rule: Option<&str>| {
+        let start = format_utc(start);
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{start}-{uid}@cron"));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART:{start}"));
+        lines.push(format!("SUMMARY:{}", escape_text(schedule.source())));
+        if let Some(rule) = rule {
+            lines.push(format!("RRULE:{rule}"));
+        }
+        lines.push("END:VEVENT".to_string());
+    };
+    match (occurrences.first(), occurrences.last()) {
+        (Some(first), Some(last)) if is_utc_between(first, last) => {
+            match rule(schedule, occurrences.len()) {
+                Some(rule) => event(first, Some(&rule)),
+                None => occurrences.iter().for_each(|start| event(start, None)),
+            }
+        }
+        _ => occurrences.iter().for_each(|start| event(start, None)),
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = String::new();
+    for line in lines {
+        fold(&mut calendar, &line);
+    }
+    calendar
+}
+
+// The recurrence rule firing `count` times exactly when the schedule does in UTC, if it can be
+// written as one.
+fn rule(schedule: &Schedule, count: usize) -> Option<String> {
+    let fields = schedule.fields();
+    if !schedule.is_unconstrained()
+        || !fields.years.is_all()
+        || fields.days_match_either
+        || fields.clamp_to_end_of_month
+        || (fields.leap_day != LeapDayPolicy::Skip && fields.days_of_month.includes(29))
+    {
+        return None;
+    }
+    let mut monthly = false;
+    let mut days_of_month: Vec<String> = fields
+        .days_of_month
+        .iter()
+        .map(|day| day.to_string())
+        .collect();
+    for spec in fields.days_of_month.specs() {
+        let DayOfMonthSpec::LastDay { offset } = spec else {
+            return None;
+        };
+        // `L-2` is the third day from the end of the month.
+        days_of_month.push(format!("-{}", offset + 1));
+        monthly = true;
+    }
+    let code = |weekday: Ordinal| WEEKDAY_CODES[weekday as usize - 1];
+    let mut days_of_week: Vec<String> = fields
+        .days_of_week
+        .iter()
+        .map(|weekday| code(weekday).to_string())
+        .collect();
+    for spec in fields.days_of_week.specs() {
+        let weekday = code(spec.weekday());
+        match *spec {
+            DayOfWeekSpec::Last { offset: 0, .. } => days_of_week.push(format!("-1{weekday}")),
+            DayOfWeekSpec::Last { .. } => return None,
+            DayOfWeekSpec::Nth { nth, .. } => days_of_week.push(format!("{nth}{weekday}")),
+        }
+        monthly = true;
+    }
+
+    // Days counted within the month need a monthly rule, in which the days are expanded rather
+    // than limited.
+    let mut rule = format!("FREQ={}", if monthly { "MONTHLY" } else { "DAILY" });
+    if !fields.months.is_all() {
+        write!(rule, ";BYMONTH={}", join(fields.months.iter())).ok()?;
+    }
+    if !fields.days_of_month.is_all() {
+        write!(rule, ";BYMONTHDAY={}", days_of_month.join(",")).ok()?;
+    }
+    if !fields.days_of_week.is_all() {
+        write!(rule, ";BYDAY={}", days_of_week.join(",")).ok()?;
+    }
+    write!(
+        rule,
+        ";BYHOUR={};BYMINUTE={};BYSECOND={};COUNT={count}",
+        join(fields.hours.iter()),
+        join(fields.minutes.iter()),
+        join(fields.seconds.iter()),
+    )
+    .ok()?;
+    Some(rule)
+}
+
+fn join(ordinals: impl Iterator<Item = Ordinal>) -> String {
+    ordinals
+        .map(|ordinal| ordinal.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Whether the timezone is at UTC all the way from `first` to `last`, so that the schedule fires
+// on the same days and at the same times of day in UTC as it does locally.
+fn is_utc_between<Z>(first: &DateTime<Z>, last: &DateTime<Z>) -> bool
+where
+    Z: TimeZone,
+{
+    let timezone = first.timezone();
+    first.offset().fix().local_minus_utc() == 0
+        && first
+            .date_naive()
+            .iter_days()
+            .take_while(|date| *date <= last.date_naive())
+            .all(|date| counting::has_constant_offset(&timezone, date))
+}
+
+fn format_utc<Z>(date_time: &DateTime<Z>) -> String
+where
+    Z: TimeZone,
+{
+    date_time
+        .with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+// Escapes the characters with a meaning in TEXT values.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// A stable suffix for the UIDs of the events of a schedule, so that calendars subscribed to it
+// recognize the events they already have. It's the 64-bit FNV-1a hash of the expression.
+fn uid_suffix(source: &str) -> String {
+    let hash = source
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+// Appends the line, folded into lines of at most `MAX_LINE_LENGTH` bytes continued with a
+// leading space, and its CRLF ending.
+fn fold(calendar: &mut String, line: &str) {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            calendar.push_str("\r\n ");
+            length = 1;
+        }
+        calendar.push(c);
+        length += c.len_utf8();
+    }
+    calendar.push_str("\r\n");
+}
@@ -0,0 +1,70 @@
+use chrono::{Datelike, NaiveDate};
+use icu_calendar::{AnyCalendar, AnyCalendarKind, Date, Ref};
+
+use crate::calendar::CalendarSystem;
+
+/// A [CalendarSystem] backed by one of the calendars of ICU4X, such as the Hebrew or the Hijri
+/// calendar. Only available with the `icu` feature.
+///
+/// Months are numbered as in ICU4X, from 1 for the first month of the year. A leap month shares
+/// the number of the month before it, so that the other months keep their number in leap years,
+/// e.g. Nisan is always month 7 of the Hebrew calendar.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::calendar::IcuCalendar;
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// // The first day of Ramadan, the ninth month of the Hijri calendar.
+/// let schedule = Schedule::from_str("0 0 9 1 9 ?")
+///     .unwrap()
+///     .in_calendar_system(IcuCalendar::hijri())
+///     .unwrap();
+/// let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+/// assert_eq!(
+///     Some(Utc.with_ymd_and_hms(2027, 2, 8, 9, 0, 0).unwrap()),
+///     schedule.after(&after).next()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct IcuCalendar {
+    calendar: AnyCalendar,
+}
+
+impl IcuCalendar {
+    /// Creates the calendar of the given kind, with the data compiled into ICU4X.
+    pub fn new(kind: AnyCalendarKind) -> IcuCalendar {
+        IcuCalendar {
+            calendar: AnyCalendar::new(kind),
+        }
+    }
+
+    /// The Hebrew calendar, whose year starts with Tishrei.
+    pub fn hebrew() -> IcuCalendar {
+        IcuCalendar::new(AnyCalendarKind::Hebrew)
+    }
+
+    /// The Umm al-Qura Hijri calendar, as used in Saudi Arabia.
+    pub fn hijri() -> IcuCalendar {
+        IcuCalendar::new(AnyCalendarKind::HijriUmmAlQura)
+    }
+
+    fn date(&self, date: NaiveDate) -> Option<Date<Ref<'_, AnyCalendar>>> {
+        let month = u8::try_from(date.month()).ok()?;
+        let day = u8::try_from(date.day()).ok()?;
+        let iso = Date::try_new_iso(date.year(), month, day).ok()?;
+        Some(iso.to_calendar(Ref(&self.calendar)))
+    }
+}
+
+impl CalendarSystem for IcuCalendar {
+    fn month_and_day(&self, date: NaiveDate) -> Option<(u32, u32)> {
+        let date = self.date(date)?;
+        Some((date.month().number().into(), date.day_of_month().0.into()))
+    }
+
+    fn days_in_month(&self, date: NaiveDate) -> Option<u32> {
+        Some(self.date(date)?.days_in_month().into())
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
+use std::str::FromStr;
+
+use crate::export::format_cron_field;
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::schedule::Schedule;
+use crate::time_unit::*;
+
+// See `Schedule::infer`.
+pub(crate) fn infer<Z>(samples: &[DateTime<Z>]) -> Option<Schedule>
+where
+    Z: TimeZone,
+{
+    let mut samples = samples.to_vec();
+    samples.sort();
+    samples.dedup();
+    let first = samples.first()?;
+
+    let observed = |value: fn(&DateTime<Z>) -> Ordinal| -> Vec<Ordinal> {
+        samples
+            .iter()
+            .map(value)
+            .collect::<OrdinalSet>()
+            .into_iter()
+            .collect()
+    };
+    let seconds = format_cron_field::<Seconds>(&observed(|dt| dt.second()));
+    let minutes = format_cron_field::<Minutes>(&observed(|dt| dt.minute()));
+    let hours = format_cron_field::<Hours>(&observed(|dt| dt.hour()));
+    let days_of_month = format_cron_field::<DaysOfMonth>(&observed(|dt| dt.day()));
+    let months = format_cron_field::<Months>(&observed(|dt| dt.month()));
+    let days_of_week =
+        format_cron_field::<DaysOfWeek>(&observed(|dt| dt.weekday().number_from_sunday()));
+
+    // Restricting both days of month and days of week would only match days satisfying both,
+    // which is rarely what produced the samples, so each one is tried on its own first. Of the
+    // candidates fitting the samples, the shortest expression is the most likely to be the
+    // original one.
+    let candidates = [
+        (days_of_month.as_str(), "*"),
+        ("*", days_of_week.as_str()),
+        (days_of_month.as_str(), days_of_week.as_str()),
+    ];
+    candidates
+        .iter()
+        .filter_map(|(days_of_month, days_of_week)| {
+            let expression =
+                format!("{seconds} {minutes} {hours} {days_of_month} {months} {days_of_week}");
+            let schedule = Schedule::from_str(&expression).ok()?;
+            let fired = schedule.after(&first.clone().checked_sub_signed(Duration::seconds(1))?);
+            fired
+                .take(samples.len())
+                .eq(samples.iter().cloned())
+                .then_some(schedule)
+        })
+        .min_by_key(|schedule| schedule.source().len())
+}
@@ -0,0 +1,91 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+use crate::error::Error;
+use crate::schedule::Schedule;
+
+static GLOBAL: Lazy<ScheduleInterner> = Lazy::new(ScheduleInterner::new);
+
+/// Hands out a single shared [Schedule] for each distinct expression, so that services reading
+/// the same few expressions from thousands of configurations parse and store each of them once.
+///
+/// Schedules are only held weakly: one is freed once the last `Arc` handed out for it is dropped,
+/// and parsed again if its expression comes up later.
+/// # Example
+/// ```
+/// use cron::ScheduleInterner;
+/// use std::sync::Arc;
+///
+/// let first = ScheduleInterner::global().intern("0 0 9 * * Mon-Fri").unwrap();
+/// let second = ScheduleInterner::global().intern("0 0 9 * * Mon-Fri").unwrap();
+/// assert!(Arc::ptr_eq(&first, &second));
+/// ```
+#[derive(Debug, Default)]
+pub struct ScheduleInterner {
+    schedules: Mutex<Schedules>,
+}
+
+#[derive(Debug, Default)]
+struct Schedules {
+    by_expression: HashMap<Box<str>, Weak<Schedule>>,
+    // The number of entries at which those whose schedule was freed are next cleared out.
+    next_purge: usize,
+}
+
+impl ScheduleInterner {
+    /// Creates an empty interner, for when interning shouldn't be shared by the whole process.
+    pub fn new() -> ScheduleInterner {
+        ScheduleInterner::default()
+    }
+
+    /// The interner shared by the whole process.
+    pub fn global() -> &'static ScheduleInterner {
+        &GLOBAL
+    }
+
+    /// Returns the schedule for the given expression, which is only parsed if no schedule for
+    /// the exact same string is still in use. Invalid expressions aren't remembered.
+    pub fn intern(&self, expression: &str) -> Result<Arc<Schedule>, Error> {
+        let mut schedules = self.lock();
+        let interned = schedules
+            .by_expression
+            .get(expression)
+            .and_then(Weak::upgrade);
+        if let Some(schedule) = interned {
+            return Ok(schedule);
+        }
+        let schedule = Arc::new(Schedule::from_str(expression)?);
+        if schedules.by_expression.len() >= schedules.next_purge {
+            schedules
+                .by_expression
+                .retain(|_, schedule| schedule.strong_count() > 0);
+            schedules.next_purge = (schedules.by_expression.len() * 2).max(64);
+        }
+        schedules
+            .by_expression
+            .insert(expression.into(), Arc::downgrade(&schedule));
+        Ok(schedule)
+    }
+
+    /// The number of expressions whose schedule is still in use.
+    pub fn len(&self) -> usize {
+        let schedules = self.lock();
+        let live = schedules.by_expression.values();
+        live.filter(|schedule| schedule.strong_count() > 0).count()
+    }
+
+    /// Returns true if no interned schedule is still in use.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // A panic while the lock was held can't have left the map inconsistent, so a poisoned lock
+    // is used as is.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Schedules> {
+        self.schedules
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
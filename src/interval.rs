@@ -0,0 +1,110 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::occurrences::{OccurrenceIterator, Occurrences};
+
+/// A schedule firing at a fixed interval from a starting instant, such as every 90 minutes, which
+/// cron expressions can't express when the interval doesn't divide an hour or a day.
+///
+/// The interval is elapsed time, so it isn't affected by daylight saving time transitions.
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron::IntervalSchedule;
+///
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+/// let schedule = IntervalSchedule::new(&start, Duration::minutes(90));
+/// let mut upcoming = schedule.after(&start);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 30, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()), upcoming.next());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSchedule {
+    start: DateTime<Utc>,
+    every: Duration,
+}
+
+impl IntervalSchedule {
+    /// Creates a schedule firing at `start` and then every `every` after it.
+    ///
+    /// # Panics
+    /// Panics if `every` isn't positive.
+    pub fn new<Z>(start: &DateTime<Z>, every: Duration) -> IntervalSchedule
+    where
+        Z: TimeZone,
+    {
+        assert!(every > Duration::zero(), "The interval must be positive.");
+        IntervalSchedule {
+            start: start.with_timezone(&Utc),
+            every,
+        }
+    }
+
+    /// The first time the schedule fires at.
+    pub fn start(&self) -> DateTime<Utc> {
+        self.start
+    }
+
+    /// The time between two consecutive firings.
+    pub fn every(&self) -> Duration {
+        self.every
+    }
+
+    /// Provides an iterator over the times the schedule fires at after the given instant.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> OccurrenceIterator<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        OccurrenceIterator::new(self, after)
+    }
+
+    // The number of whole intervals between the start and the given instant, rounded down, and
+    // whether the instant falls exactly on a firing.
+    fn intervals_until<Z>(&self, date_time: &DateTime<Z>) -> (i128, bool)
+    where
+        Z: TimeZone,
+    {
+        let elapsed = nanoseconds(date_time.with_timezone(&Utc) - self.start);
+        let every = nanoseconds(self.every);
+        (elapsed.div_euclid(every), elapsed.rem_euclid(every) == 0)
+    }
+
+    fn nth<Z>(&self, intervals: i128, timezone: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let offset = nanoseconds(self.every).checked_mul(intervals)?;
+        let offset = Duration::try_seconds(i64::try_from(offset.div_euclid(1_000_000_000)).ok()?)?
+            + Duration::nanoseconds(offset.rem_euclid(1_000_000_000) as i64);
+        self.start
+            .checked_add_signed(offset)
+            .map(|date_time| date_time.with_timezone(timezone))
+    }
+}
+
+fn nanoseconds(duration: Duration) -> i128 {
+    i128::from(duration.num_seconds()) * 1_000_000_000 + i128::from(duration.subsec_nanos())
+}
+
+impl<Z> Occurrences<Z> for IntervalSchedule
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let (intervals, _) = self.intervals_until(after);
+        self.nth(intervals.max(-1) + 1, &after.timezone())
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let (intervals, exact) = self.intervals_until(before);
+        let intervals = if exact { intervals - 1 } else { intervals };
+        if intervals < 0 {
+            return None;
+        }
+        self.nth(intervals, &before.timezone())
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        let (intervals, exact) = self.intervals_until(&date_time);
+        exact && intervals >= 0
+    }
+}
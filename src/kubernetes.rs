@@ -0,0 +1,187 @@
+//! Scheduling decisions made the same way as the Kubernetes CronJob controller, for operators
+//! which need to agree with it.
+//!
+//! Kubernetes schedules are five field Unix expressions, which can be parsed with
+//! [Dialect::Unix](crate::Dialect::Unix).
+//! # Example
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use cron::kubernetes::CronJob;
+//! use cron::{Dialect, ScheduleParser};
+//!
+//! let schedule = ScheduleParser::new().dialect(Dialect::Unix).parse("*/5 * * * *").unwrap();
+//! let job = CronJob::new(schedule, Utc).starting_deadline_seconds(120);
+//!
+//! let created = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+//! let now = Utc.with_ymd_and_hms(2026, 10, 16, 9, 21, 0).unwrap();
+//! let most_recent = job.most_recent_schedule_time(&created, None, &now).unwrap();
+//! // Only the run at 09:20 is still within the starting deadline.
+//! assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 9, 20, 0).unwrap(), most_recent.time);
+//! assert_eq!(1, most_recent.missed);
+//! ```
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::schedule::Schedule;
+
+/// The number of missed runs above which the controller warns that a CronJob is missing too many
+/// start times.
+pub const TOO_MANY_MISSED: u64 = 100;
+
+/// A [Schedule] evaluated like a Kubernetes CronJob's `schedule`, `timeZone` and
+/// `startingDeadlineSeconds`.
+#[derive(Clone, Debug)]
+pub struct CronJob<Z>
+where
+    Z: TimeZone,
+{
+    schedule: Schedule,
+    timezone: Z,
+    starting_deadline_seconds: Option<i64>,
+}
+
+/// The most recent time a [CronJob] should have started at, as returned by
+/// [CronJob::most_recent_schedule_time].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MostRecentSchedule<Z>
+where
+    Z: TimeZone,
+{
+    /// The latest scheduled time which isn't after now.
+    pub time: DateTime<Z>,
+    /// The controller's estimate of the number of scheduled times since the job last started,
+    /// including `time`.
+    pub missed: u64,
+}
+
+impl<Z> MostRecentSchedule<Z>
+where
+    Z: TimeZone,
+{
+    /// Returns true if the controller would warn about too many missed start times.
+    pub fn too_many_missed(&self) -> bool {
+        self.missed > TOO_MANY_MISSED
+    }
+}
+
+impl<Z> CronJob<Z>
+where
+    Z: TimeZone,
+{
+    /// Creates a CronJob evaluating the schedule in the given timezone, without a starting
+    /// deadline.
+    pub fn new(schedule: Schedule, timezone: Z) -> CronJob<Z> {
+        CronJob {
+            schedule,
+            timezone,
+            starting_deadline_seconds: None,
+        }
+    }
+
+    /// Sets `startingDeadlineSeconds`: runs which can't start within this many seconds of their
+    /// scheduled time are skipped.
+    pub fn starting_deadline_seconds(mut self, seconds: i64) -> CronJob<Z> {
+        self.starting_deadline_seconds = Some(seconds);
+        self
+    }
+
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    // Runs are looked for after the last one, or the creation of the job, but no further back
+    // than the starting deadline.
+    fn earliest_time(
+        &self,
+        creation_time: &DateTime<Utc>,
+        last_schedule_time: Option<&DateTime<Utc>>,
+        now: &DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let earliest_time = *last_schedule_time.unwrap_or(creation_time);
+        // A deadline reaching past the range of dates is as good as none.
+        let deadline = self
+            .starting_deadline_seconds
+            .and_then(Duration::try_seconds)
+            .and_then(|deadline| now.checked_sub_signed(deadline));
+        match deadline {
+            Some(deadline) => earliest_time.max(deadline),
+            None => earliest_time,
+        }
+    }
+
+    /// Returns the latest scheduled time which isn't after `now`, if the job should have started
+    /// since it was created or last scheduled, and no earlier than its starting deadline.
+    ///
+    /// Like the controller, the number of missed runs is estimated from the interval between the
+    /// first two scheduled times, rather than counted.
+    pub fn most_recent_schedule_time(
+        &self,
+        creation_time: &DateTime<Utc>,
+        last_schedule_time: Option<&DateTime<Utc>>,
+        now: &DateTime<Utc>,
+    ) -> Option<MostRecentSchedule<Z>> {
+        let earliest_time = self.earliest_time(creation_time, last_schedule_time, now);
+        let now = now.with_timezone(&self.timezone);
+        let mut upcoming = self
+            .schedule
+            .after(&earliest_time.with_timezone(&self.timezone));
+        let t1 = upcoming.next().filter(|t1| *t1 <= now)?;
+        let t2 = match upcoming.next() {
+            Some(t2) if t2 <= now => t2,
+            _ => {
+                return Some(MostRecentSchedule {
+                    time: t1,
+                    missed: 1,
+                })
+            }
+        };
+
+        let time_between_two_schedules = (t2 - t1.clone()).num_seconds().max(1);
+        let time_elapsed = (now.clone() - t1.clone()).num_seconds();
+        let missed = time_elapsed / time_between_two_schedules + 1;
+        // Skipping ahead by whole intervals keeps this cheap after a long outage, while starting
+        // one interval early still finds the right time for irregular schedules.
+        let potential_earliest =
+            t1.clone() + Duration::seconds((missed - 2) * time_between_two_schedules);
+        let time = self
+            .schedule
+            .after(&potential_earliest)
+            .take_while(|time| *time <= now)
+            .last()
+            .unwrap_or(t1);
+        Some(MostRecentSchedule {
+            time,
+            missed: missed as u64,
+        })
+    }
+
+    /// Returns true if a run scheduled at `scheduled_time` can no longer start at `now` because
+    /// its starting deadline has passed.
+    pub fn is_too_late(&self, scheduled_time: &DateTime<Z>, now: &DateTime<Utc>) -> bool {
+        self.starting_deadline_seconds
+            .and_then(Duration::try_seconds)
+            .and_then(|deadline| scheduled_time.clone().checked_add_signed(deadline))
+            .is_some_and(|deadline| deadline < *now)
+    }
+
+    /// Returns the next scheduled time after `now`, when the controller requeues the job.
+    pub fn next_schedule_time(&self, now: &DateTime<Utc>) -> Option<DateTime<Z>> {
+        self.schedule
+            .after(&now.with_timezone(&self.timezone))
+            .next()
+    }
+
+    /// Iterates over every scheduled time since the job was created or last scheduled, up to and
+    /// including `now`, and no earlier than its starting deadline.
+    pub fn missed_runs<'a>(
+        &'a self,
+        creation_time: &DateTime<Utc>,
+        last_schedule_time: Option<&DateTime<Utc>>,
+        now: &DateTime<Utc>,
+    ) -> impl Iterator<Item = DateTime<Z>> + 'a {
+        let earliest_time = self.earliest_time(creation_time, last_schedule_time, now);
+        let now = now.with_timezone(&self.timezone);
+        self.schedule
+            .after_owned(earliest_time.with_timezone(&self.timezone))
+            .take_while(move |time| *time <= now)
+    }
+}
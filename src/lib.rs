@@ -32,16 +32,135 @@
 //! -> 2018-08-15 09:30:00 UTC
 //! */
 //! ```
+//!
+//! # Panics
+//! Invalid expressions are reported as errors, and queries which would go past the range of
+//! dates chrono supports return `None`, so the crate doesn't panic whatever the input. The only
+//! exceptions are arguments documented as invalid in a `# Panics` section, such as a zero `n` for
+//! [Schedule::every_nth].
+
+// Emits a `tracing` event when the tracing feature is enabled, and nothing otherwise.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+// Enters a `tracing` span until the end of the enclosing block when the tracing feature is
+// enabled, and does nothing otherwise.
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($arg)+).entered();
+    };
+}
 
 /// Error types used by this crate.
 pub mod error;
 
+pub mod calendar;
+
+pub mod kubernetes;
+
+mod ast;
+#[cfg(feature = "rayon")]
+mod batch;
+mod binary;
+mod checkpoint;
+mod compiled;
+mod counting;
+mod cron_expr;
+mod description;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod diff;
+mod every_nth;
+mod exclusion;
+mod export;
+mod field_values;
+mod formatter;
+mod frequency;
+mod gaps;
+mod histogram;
+mod ics;
+#[cfg(feature = "icu")]
+mod icu;
+mod inference;
+#[cfg(feature = "intern")]
+mod intern;
+mod interval;
+mod lint;
+#[cfg(feature = "locales")]
+mod locale;
+mod misfire;
+mod occurrences;
+mod offset;
 mod ordinal;
 mod parsing;
+#[cfg(feature = "python")]
+mod python;
 mod queries;
+mod rewrite;
+mod sanitize;
 mod schedule;
+mod schedule_expr;
+mod schedule_set;
+mod solar;
 mod specifier;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+mod syntax;
+#[cfg(feature = "tokio")]
+mod tick;
 mod time_unit;
+mod trace;
+mod visit;
+mod window;
+mod windowed;
 
-pub use crate::schedule::{OwnedScheduleIterator, Schedule, ScheduleIterator};
-pub use crate::time_unit::TimeUnitSpec;
+pub use crate::ast::{parse_to_ast, Ast, AstField, FieldKind, Item, Value};
+#[cfg(feature = "rayon")]
+pub use crate::batch::next_after_batch;
+pub use crate::checkpoint::Checkpoint;
+pub use crate::compiled::CompiledSchedule;
+pub use crate::cron_expr::CronExpr;
+pub use crate::description::{FieldDescription, ScheduleDescription};
+pub use crate::diff::{FieldDiff, ScheduleDiff};
+pub use crate::every_nth::EveryNth;
+pub use crate::exclusion::{AnnualCalendar, Calendar, CronCalendar, WeeklyCalendar};
+pub use crate::export::{Translation, Untranslatable};
+pub use crate::field_values::FieldValues;
+pub use crate::formatter::{FieldCount, ScheduleFormatter};
+pub use crate::frequency::Frequency;
+pub use crate::gaps::Gaps;
+pub use crate::histogram::Histogram;
+#[cfg(feature = "intern")]
+pub use crate::intern::ScheduleInterner;
+pub use crate::interval::IntervalSchedule;
+pub use crate::lint::{LintKind, LintWarning};
+#[cfg(feature = "locales")]
+pub use crate::locale::Locale;
+pub use crate::misfire::{Misfire, MisfirePolicy};
+pub use crate::occurrences::{OccurrenceIterator, Occurrences};
+pub use crate::offset::OffsetSchedule;
+pub use crate::parsing::{Dialect, ScheduleParser};
+pub use crate::rewrite::{RewriteReason, Suggestion};
+pub use crate::sanitize::{sanitize, Correction, CorrectionKind, Sanitized};
+pub use crate::schedule::{
+    LeapDayPolicy, NearestWeekdayPolicy, OwnedScheduleIterator, Schedule, ScheduleIterator,
+};
+pub use crate::schedule_expr::ScheduleExpr;
+pub use crate::schedule_set::{Overlap, ScheduleSet, ScheduleSetIterator, Simulation};
+pub use crate::solar::{SolarEvent, SolarProvider, SolarSchedule, SunriseEquation};
+pub use crate::syntax::{FieldSyntax, ScheduleSyntax, SpecifierForm};
+#[cfg(feature = "tokio")]
+pub use crate::tick::{MissedTickBehavior, Ticks};
+pub use crate::time_unit::{DayOfMonthSpec, DayOfWeekSpec, DaySpec, TimeUnitSpec};
+pub use crate::trace::{Trace, TraceStep};
+pub use crate::visit::{
+    transform_ast, transform_field, transform_item, walk_ast, walk_field, walk_item, Transform,
+    Visitor,
+};
+pub use crate::window::Window;
+pub use crate::windowed::{WindowedSchedule, Windows};
@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use crate::parsing::{self, Dialect};
+use crate::schedule::{days_in_month, Schedule};
+use crate::specifier::{RootSpecifier, Specifier};
+use crate::syntax::FieldSyntax;
+use crate::time_unit::*;
+
+/// A suspicious part of a [Schedule], as returned by [Schedule::lint], which parses but likely
+/// doesn't do what its author meant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintWarning {
+    /// The kind of pattern found.
+    pub kind: LintKind,
+    /// The name of the field holding the pattern, e.g. `Minutes`, if it's confined to one.
+    pub field: Option<Cow<'static, str>>,
+    /// A description of the pattern.
+    pub message: String,
+}
+
+/// The suspicious patterns [Schedule::lint] looks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// A step at least as large as the values it steps through, e.g. `*/12` in the months or
+    /// `20/5` in the hours, which only includes the first value.
+    StepCoversField,
+    /// Both the day of month and the day of week fields are restricted, which dialects combine
+    /// differently.
+    BothDaysRestricted,
+    /// Days of the month which none of the included months have, e.g. the 31st in `31 4,6`.
+    DaysMissingFromMonths,
+    /// A wildcard in the seconds, firing every second.
+    EverySecond,
+}
+
+impl LintWarning {
+    fn new(kind: LintKind, field: Option<Cow<'static, str>>, message: String) -> LintWarning {
+        LintWarning {
+            kind,
+            field,
+            message,
+        }
+    }
+}
+
+pub(crate) fn lint(schedule: &Schedule) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    if let Some(syntax) = schedule.syntax() {
+        let dialect = syntax.dialect;
+        lint_steps::<Seconds>(dialect, 0, &syntax.seconds, &mut warnings);
+        lint_steps::<Minutes>(dialect, 1, &syntax.minutes, &mut warnings);
+        lint_steps::<Hours>(dialect, 2, &syntax.hours, &mut warnings);
+        lint_steps::<DaysOfMonth>(dialect, 3, &syntax.days_of_month, &mut warnings);
+        lint_steps::<Months>(dialect, 4, &syntax.months, &mut warnings);
+        lint_steps::<DaysOfWeek>(dialect, 5, &syntax.days_of_week, &mut warnings);
+        lint_steps::<Years>(dialect, 6, &syntax.years, &mut warnings);
+    }
+
+    let fields = schedule.fields();
+    if fields.seconds.is_all() {
+        warnings.push(LintWarning::new(
+            LintKind::EverySecond,
+            Some(Seconds::name()),
+            "Fires every second, rather than once a minute.".to_string(),
+        ));
+    }
+    if !fields.days_of_month.is_all() && !fields.days_of_week.is_all() {
+        let message = if fields.days_match_either {
+            "Both day fields are restricted, so days matching either of them are included."
+        } else {
+            "Both day fields are restricted, so only days matching both of them are included."
+        };
+        warnings.push(LintWarning::new(
+            LintKind::BothDaysRestricted,
+            None,
+            message.to_string(),
+        ));
+    }
+    if !fields.clamp_to_end_of_month {
+        // Leap years are checked so that the 29th of February isn't reported.
+        let missing: Vec<String> = fields
+            .days_of_month
+            .iter()
+            .filter(|&day| {
+                fields
+                    .months
+                    .iter()
+                    .all(|month| day > days_in_month(month, 2000))
+            })
+            .map(|day| day.to_string())
+            .collect();
+        if !missing.is_empty() {
+            warnings.push(LintWarning::new(
+                LintKind::DaysMissingFromMonths,
+                Some(DaysOfMonth::name()),
+                format!(
+                    "None of the included months have day {}.",
+                    missing.join(", ")
+                ),
+            ));
+        }
+    }
+    warnings
+}
+
+// Reports steps which are at least as large as the values they step through.
+fn lint_steps<T: TimeUnitField>(
+    dialect: Dialect,
+    position: u64,
+    syntax: &FieldSyntax,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let (min, max) = if position == 5 && dialect.sunday_is_zero() {
+        (
+            UnixDaysOfWeek::inclusive_min(),
+            UnixDaysOfWeek::inclusive_max(),
+        )
+    } else {
+        (T::inclusive_min(), T::inclusive_max())
+    };
+    for specifier in parsing::specifiers_of(dialect, position, syntax.text()) {
+        let RootSpecifier::Period(start, step) = specifier else {
+            continue;
+        };
+        let values = match start {
+            Specifier::Point(start) => max.saturating_sub(start) + 1,
+            Specifier::Range(start, end) => end.saturating_sub(start) + 1,
+            _ => max - min + 1,
+        };
+        if step >= values {
+            warnings.push(LintWarning::new(
+                LintKind::StepCoversField,
+                Some(T::name()),
+                format!(
+                    "The step of {step} is at least as large as the {values} values it steps \
+                     through, so only the first one is included."
+                ),
+            ));
+        }
+    }
+}
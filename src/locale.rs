@@ -0,0 +1,112 @@
+use phf::phf_map;
+
+/// Languages whose month and day of the week names a [ScheduleParser](crate::ScheduleParser)
+/// accepts alongside the English ones, as set with
+/// [ScheduleParser::locale](crate::ScheduleParser::locale).
+///
+/// Names are matched case insensitively, without accents, in full or abbreviated, e.g. `janv`
+/// or `janvier` and `lun` or `lundi` in French.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Locale {
+    French,
+    German,
+    Spanish,
+}
+
+// Localized names are mapped to English ones, which the fields already know how to resolve.
+static FRENCH_MONTHS: phf::Map<&'static str, &'static str> = phf_map! {
+    "janv" => "jan", "janvier" => "jan",
+    "fev" => "feb", "fevr" => "feb", "fevrier" => "feb",
+    "mars" => "mar",
+    "avr" => "apr", "avril" => "apr",
+    "mai" => "may",
+    "juin" => "jun",
+    "juil" => "jul", "juillet" => "jul",
+    "aout" => "aug",
+    "sept" => "sep", "septembre" => "sep",
+    "octobre" => "oct",
+    "novembre" => "nov",
+    "decembre" => "dec",
+};
+
+static FRENCH_DAYS: phf::Map<&'static str, &'static str> = phf_map! {
+    "dim" => "sun", "dimanche" => "sun",
+    "lun" => "mon", "lundi" => "mon",
+    "mar" => "tue", "mardi" => "tue",
+    "mer" => "wed", "mercredi" => "wed",
+    "jeu" => "thu", "jeudi" => "thu",
+    "ven" => "fri", "vendredi" => "fri",
+    "sam" => "sat", "samedi" => "sat",
+};
+
+static GERMAN_MONTHS: phf::Map<&'static str, &'static str> = phf_map! {
+    "januar" => "jan",
+    "februar" => "feb",
+    "mrz" => "mar", "maerz" => "mar",
+    "mai" => "may",
+    "juni" => "jun",
+    "juli" => "jul",
+    "okt" => "oct", "oktober" => "oct",
+    "dez" => "dec", "dezember" => "dec",
+};
+
+static GERMAN_DAYS: phf::Map<&'static str, &'static str> = phf_map! {
+    "so" => "sun", "sonntag" => "sun",
+    "mo" => "mon", "montag" => "mon",
+    "di" => "tue", "dienstag" => "tue",
+    "mi" => "wed", "mittwoch" => "wed",
+    "do" => "thu", "donnerstag" => "thu",
+    "fr" => "fri", "freitag" => "fri",
+    "sa" => "sat", "samstag" => "sat",
+};
+
+static SPANISH_MONTHS: phf::Map<&'static str, &'static str> = phf_map! {
+    "ene" => "jan", "enero" => "jan",
+    "febrero" => "feb",
+    "marzo" => "mar",
+    "abr" => "apr", "abril" => "apr",
+    "mayo" => "may",
+    "junio" => "jun",
+    "julio" => "jul",
+    "ago" => "aug", "agosto" => "aug",
+    "septiembre" => "sep",
+    "octubre" => "oct",
+    "noviembre" => "nov",
+    "dic" => "dec", "diciembre" => "dec",
+};
+
+static SPANISH_DAYS: phf::Map<&'static str, &'static str> = phf_map! {
+    "dom" => "sun", "domingo" => "sun",
+    "lun" => "mon", "lunes" => "mon",
+    "mar" => "tue", "martes" => "tue",
+    "mie" => "wed", "miercoles" => "wed",
+    "jue" => "thu", "jueves" => "thu",
+    "vie" => "fri", "viernes" => "fri",
+    "sab" => "sat", "sabado" => "sat",
+};
+
+impl Locale {
+    fn names(self, position: u64) -> Option<&'static phf::Map<&'static str, &'static str>> {
+        match (self, position) {
+            (Locale::French, 4) => Some(&FRENCH_MONTHS),
+            (Locale::French, 5) => Some(&FRENCH_DAYS),
+            (Locale::German, 4) => Some(&GERMAN_MONTHS),
+            (Locale::German, 5) => Some(&GERMAN_DAYS),
+            (Locale::Spanish, 4) => Some(&SPANISH_MONTHS),
+            (Locale::Spanish, 5) => Some(&SPANISH_DAYS),
+            _ => None,
+        }
+    }
+
+    // The English name for a name of the field at `position`, in the order of a Quartz
+    // expression. Names the locale doesn't know are kept, so that English ones still work.
+    pub(crate) fn translate(self, position: u64, name: &mut String) {
+        let english = self
+            .names(position)
+            .and_then(|names| names.get(name.to_lowercase().as_str()));
+        if let Some(english) = english {
+            *name = english.to_string();
+        }
+    }
+}
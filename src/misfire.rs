@@ -0,0 +1,131 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::occurrences::Occurrences;
+
+/// What a scheduler does with the occurrences it missed, e.g. while it was down, mirroring the
+/// misfire instructions of Quartz's cron triggers.
+///
+/// An occurrence only counts as missed once it was due at least a threshold ago, as with Quartz's
+/// `misfireThreshold`. Later ones are merely running late and are always run.
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron::{MisfirePolicy, Schedule};
+/// use std::str::FromStr;
+///
+/// let hourly = Schedule::from_str("0 0 * * * *").unwrap();
+/// let last_fire = Utc.with_ymd_and_hms(2026, 10, 16, 6, 0, 0).unwrap();
+/// let now = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+/// let threshold = Duration::minutes(1);
+///
+/// let misfire = MisfirePolicy::FireOnceNow.resolve(&hourly, &last_fire, &now, threshold);
+/// assert_eq!(vec![Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap()], misfire.runs);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap()), misfire.next);
+///
+/// let misfire = MisfirePolicy::RescheduleNext.resolve(&hourly, &last_fire, &now, threshold);
+/// assert!(misfire.misfired && misfire.runs.is_empty());
+///
+/// let misfire = MisfirePolicy::Ignore.resolve(&hourly, &last_fire, &now, threshold);
+/// assert_eq!(3, misfire.runs.len());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MisfirePolicy {
+    /// Runs once right away for all the missed occurrences, then waits for the next one, as
+    /// Quartz's `MISFIRE_INSTRUCTION_FIRE_ONCE_NOW`.
+    #[default]
+    FireOnceNow,
+    /// Drops the missed occurrences and waits for the next one, as Quartz's
+    /// `MISFIRE_INSTRUCTION_DO_NOTHING`.
+    RescheduleNext,
+    /// Runs every missed occurrence right away, as Quartz's
+    /// `MISFIRE_INSTRUCTION_IGNORE_MISFIRE_POLICY`. They are all listed, so schedules which fire
+    /// often may have a lot of them to catch up on.
+    Ignore,
+}
+
+/// What to run after the occurrences of a schedule were missed, as returned by
+/// [MisfirePolicy::resolve].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misfire<Z>
+where
+    Z: TimeZone,
+{
+    /// The occurrences to run right away, oldest first. Each is the time the run was due at,
+    /// which may be earlier than when it actually runs.
+    pub runs: Vec<DateTime<Z>>,
+    /// The first occurrence after now, to wait for once the runs are done.
+    pub next: Option<DateTime<Z>>,
+    /// Whether any occurrence was missed, rather than running late.
+    pub misfired: bool,
+}
+
+impl MisfirePolicy {
+    /// Works out what to run for the occurrences of `schedule` after `last_fire` which are due
+    /// at `now`, counting those due at least `threshold` ago as missed.
+    pub fn resolve<O, Z>(
+        self,
+        schedule: &O,
+        last_fire: &DateTime<Z>,
+        now: &DateTime<Z>,
+        threshold: Duration,
+    ) -> Misfire<Z>
+    where
+        O: Occurrences<Z> + ?Sized,
+        Z: TimeZone,
+    {
+        let next = schedule.next_after(now);
+        // The occurrences due at or before the cutoff were missed, the later ones are running late.
+        let cutoff = now.clone().checked_sub_signed(threshold);
+        let oldest = schedule
+            .next_after(last_fire)
+            .filter(|oldest| oldest <= now);
+        let misfired = match (&oldest, &cutoff) {
+            (Some(oldest), Some(cutoff)) => oldest <= cutoff,
+            _ => false,
+        };
+        let runs = match self {
+            MisfirePolicy::FireOnceNow if misfired => {
+                latest_due(schedule, now).into_iter().collect()
+            }
+            MisfirePolicy::Ignore => due_between(schedule, last_fire, now),
+            // Only the occurrences running late are left to run.
+            MisfirePolicy::FireOnceNow | MisfirePolicy::RescheduleNext => match &cutoff {
+                Some(cutoff) if misfired => due_between(schedule, cutoff, now),
+                _ => due_between(schedule, last_fire, now),
+            },
+        };
+        Misfire {
+            runs,
+            next,
+            misfired,
+        }
+    }
+}
+
+// The occurrences after `after` and at or before `now`, oldest first.
+fn due_between<O, Z>(schedule: &O, after: &DateTime<Z>, now: &DateTime<Z>) -> Vec<DateTime<Z>>
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    let mut due = Vec::new();
+    let mut previous = after.clone();
+    while let Some(next) = schedule.next_after(&previous).filter(|next| next <= now) {
+        due.push(next.clone());
+        previous = next;
+    }
+    due
+}
+
+// The last occurrence at or before `now`.
+fn latest_due<O, Z>(schedule: &O, now: &DateTime<Z>) -> Option<DateTime<Z>>
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    if schedule.includes(now.clone()) {
+        return Some(now.clone());
+    }
+    schedule.prev_before(now)
+}
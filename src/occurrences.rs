@@ -0,0 +1,119 @@
+use chrono::{DateTime, TimeZone};
+use std::sync::Arc;
+
+/// The queries shared by every kind of schedule, such as a cron [Schedule](crate::Schedule) or an
+/// [IntervalSchedule](crate::IntervalSchedule), so that they can be stored and iterated over
+/// alike.
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron::{IntervalSchedule, Occurrences, Schedule};
+/// use std::str::FromStr;
+///
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+/// let schedules: Vec<Box<dyn Occurrences<Utc>>> = vec![
+///     Box::new(Schedule::from_str("0 0 12 * * *").unwrap()),
+///     Box::new(IntervalSchedule::new(&start, Duration::minutes(90))),
+/// ];
+/// let next: Vec<_> = schedules.iter().map(|schedule| schedule.next_after(&start)).collect();
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()), next[0]);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 30, 0).unwrap()), next[1]);
+/// ```
+pub trait Occurrences<Z>
+where
+    Z: TimeZone,
+{
+    /// Returns the first time the schedule fires after the given instant.
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>;
+
+    /// Returns the last time the schedule fires before the given instant.
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>>;
+
+    /// Returns true if the schedule fires at the given instant.
+    fn includes(&self, date_time: DateTime<Z>) -> bool;
+}
+
+impl<O, Z> Occurrences<Z> for &O
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        (**self).next_after(after)
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        (**self).prev_before(before)
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        (**self).includes(date_time)
+    }
+}
+
+impl<O, Z> Occurrences<Z> for Arc<O>
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        (**self).next_after(after)
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        (**self).prev_before(before)
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        (**self).includes(date_time)
+    }
+}
+
+/// Iterates over the times an [Occurrences] implementation fires at, in either direction from a
+/// starting instant.
+pub struct OccurrenceIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    occurrences: &'a dyn Occurrences<Z>,
+    previous_datetime: Option<DateTime<Z>>,
+}
+
+impl<'a, Z> OccurrenceIterator<'a, Z>
+where
+    Z: TimeZone,
+{
+    /// Creates an iterator starting from, but excluding, `after`.
+    pub fn new(occurrences: &'a dyn Occurrences<Z>, after: &DateTime<Z>) -> Self {
+        OccurrenceIterator {
+            occurrences,
+            previous_datetime: Some(after.clone()),
+        }
+    }
+}
+
+impl<Z> Iterator for OccurrenceIterator<'_, Z>
+where
+    Z: TimeZone,
+{
+    type Item = DateTime<Z>;
+
+    fn next(&mut self) -> Option<DateTime<Z>> {
+        let previous = self.previous_datetime.take()?;
+        let next = self.occurrences.next_after(&previous)?;
+        self.previous_datetime = Some(next.clone());
+        Some(next)
+    }
+}
+
+impl<Z> DoubleEndedIterator for OccurrenceIterator<'_, Z>
+where
+    Z: TimeZone,
+{
+    fn next_back(&mut self) -> Option<DateTime<Z>> {
+        let previous = self.previous_datetime.take()?;
+        let prev = self.occurrences.prev_before(&previous)?;
+        self.previous_datetime = Some(prev.clone());
+        Some(prev)
+    }
+}
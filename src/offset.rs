@@ -0,0 +1,73 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::occurrences::{OccurrenceIterator, Occurrences};
+use crate::schedule::Schedule;
+
+/// A [Schedule] whose occurrences are all shifted by a fixed duration, as returned by
+/// [Schedule::offset_by], e.g. to run a dependent job a few minutes after the one it depends on.
+///
+/// The offset is elapsed time, so a schedule firing at midnight and offset by a day fires at
+/// midnight the next day, even across daylight saving time transitions.
+/// # Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// let hourly = Schedule::from_str("0 0 * * * *").unwrap();
+/// let staggered = hourly.offset_by(Duration::minutes(7));
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+/// let mut upcoming = staggered.after(&start);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 9, 7, 0).unwrap()), upcoming.next());
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 7, 0).unwrap()), upcoming.next());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OffsetSchedule {
+    schedule: Schedule,
+    offset: Duration,
+}
+
+impl OffsetSchedule {
+    pub(crate) fn new(schedule: Schedule, offset: Duration) -> OffsetSchedule {
+        OffsetSchedule { schedule, offset }
+    }
+
+    /// The schedule being shifted.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// The duration every occurrence is shifted by, which may be negative.
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+
+    /// Provides an iterator over the shifted occurrences after the given instant.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> OccurrenceIterator<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        OccurrenceIterator::new(self, after)
+    }
+}
+
+impl<Z> Occurrences<Z> for OffsetSchedule
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let after = after.clone().checked_sub_signed(self.offset)?;
+        Occurrences::next_after(&self.schedule, &after)?.checked_add_signed(self.offset)
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let before = before.clone().checked_sub_signed(self.offset)?;
+        Occurrences::prev_before(&self.schedule, &before)?.checked_add_signed(self.offset)
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        date_time
+            .checked_sub_signed(self.offset)
+            .is_some_and(|date_time| self.schedule.includes(date_time))
+    }
+}
@@ -1,8 +1,305 @@
-use std::collections::BTreeSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Bound, RangeBounds};
+use std::slice;
 
 pub type Ordinal = u32;
-// TODO: Make OrdinalSet an enum.
-// It should either be a BTreeSet of ordinals or an `All` option to save space.
-// `All` can iterate from inclusive_min to inclusive_max and answer membership
-// queries
-pub type OrdinalSet = BTreeSet<Ordinal>;
+
+// The number of ordinals a set keeps inline before spilling onto the heap, which covers most
+// fields, like `0`, `9-17`, `MON-FRI` or `*/15`.
+const INLINE_CAPACITY: usize = 8;
+
+// A sorted set of ordinals. Most fields only include a handful of values, which are stored inline
+// so that building a schedule doesn't allocate for them and searching it stays within a cache
+// line; larger sets spill onto the heap.
+#[derive(Clone)]
+pub struct OrdinalSet {
+    storage: Storage,
+}
+
+#[derive(Clone)]
+enum Storage {
+    Inline {
+        len: u8,
+        ordinals: [Ordinal; INLINE_CAPACITY],
+    },
+    Heap(Vec<Ordinal>),
+}
+
+impl OrdinalSet {
+    pub const fn new() -> OrdinalSet {
+        OrdinalSet {
+            storage: Storage::Inline {
+                len: 0,
+                ordinals: [0; INLINE_CAPACITY],
+            },
+        }
+    }
+
+    fn as_slice(&self) -> &[Ordinal] {
+        match &self.storage {
+            Storage::Inline { len, ordinals } => &ordinals[..*len as usize],
+            Storage::Heap(ordinals) => ordinals,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    pub fn contains(&self, ordinal: &Ordinal) -> bool {
+        self.as_slice().binary_search(ordinal).is_ok()
+    }
+
+    pub fn first(&self) -> Option<&Ordinal> {
+        self.as_slice().first()
+    }
+
+    pub fn last(&self) -> Option<&Ordinal> {
+        self.as_slice().last()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Ordinal> {
+        self.as_slice().iter()
+    }
+
+    pub fn range<R>(&self, range: R) -> slice::Iter<'_, Ordinal>
+    where
+        R: RangeBounds<Ordinal>,
+    {
+        let ordinals = self.as_slice();
+        let start = match range.start_bound() {
+            Bound::Included(start) => ordinals.partition_point(|ordinal| ordinal < start),
+            Bound::Excluded(start) => ordinals.partition_point(|ordinal| ordinal <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => ordinals.partition_point(|ordinal| ordinal <= end),
+            Bound::Excluded(end) => ordinals.partition_point(|ordinal| ordinal < end),
+            Bound::Unbounded => ordinals.len(),
+        };
+        ordinals[start..end.max(start)].iter()
+    }
+
+    // Returns true if the ordinal wasn't in the set yet.
+    pub fn insert(&mut self, ordinal: Ordinal) -> bool {
+        let Err(index) = self.as_slice().binary_search(&ordinal) else {
+            return false;
+        };
+        match &mut self.storage {
+            Storage::Inline { len, ordinals } if (*len as usize) < INLINE_CAPACITY => {
+                ordinals.copy_within(index..*len as usize, index + 1);
+                ordinals[index] = ordinal;
+                *len += 1;
+            }
+            Storage::Inline { ordinals, .. } => {
+                let mut spilled = Vec::with_capacity(INLINE_CAPACITY * 2);
+                spilled.extend_from_slice(ordinals);
+                spilled.insert(index, ordinal);
+                self.storage = Storage::Heap(spilled);
+            }
+            Storage::Heap(ordinals) => ordinals.insert(index, ordinal),
+        }
+        true
+    }
+
+    // Returns true if the ordinal was in the set.
+    pub fn remove(&mut self, ordinal: &Ordinal) -> bool {
+        let Ok(index) = self.as_slice().binary_search(ordinal) else {
+            return false;
+        };
+        match &mut self.storage {
+            Storage::Inline { len, ordinals } => {
+                ordinals.copy_within(index + 1..*len as usize, index);
+                *len -= 1;
+            }
+            Storage::Heap(ordinals) => {
+                ordinals.remove(index);
+            }
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        *self = OrdinalSet::new();
+    }
+
+    // Removes the ordinals from `ordinal` upwards and returns them.
+    pub fn split_off(&mut self, ordinal: &Ordinal) -> OrdinalSet {
+        let ordinals = self.as_slice();
+        let index = ordinals.partition_point(|value| value < ordinal);
+        let upper = ordinals[index..].iter().copied().collect();
+        *self = ordinals[..index].iter().copied().collect();
+        upper
+    }
+
+    pub fn intersection<'a>(&'a self, other: &'a OrdinalSet) -> impl Iterator<Item = &'a Ordinal> {
+        self.iter().filter(|ordinal| other.contains(ordinal))
+    }
+
+    pub fn difference<'a>(&'a self, other: &'a OrdinalSet) -> impl Iterator<Item = &'a Ordinal> {
+        self.iter().filter(|ordinal| !other.contains(ordinal))
+    }
+
+    // Builds a set from ordinals which are already sorted and distinct.
+    fn from_sorted(ordinals: Vec<Ordinal>) -> OrdinalSet {
+        if ordinals.len() > INLINE_CAPACITY {
+            return OrdinalSet {
+                storage: Storage::Heap(ordinals),
+            };
+        }
+        let mut inline = [0; INLINE_CAPACITY];
+        inline[..ordinals.len()].copy_from_slice(&ordinals);
+        OrdinalSet {
+            storage: Storage::Inline {
+                len: ordinals.len() as u8,
+                ordinals: inline,
+            },
+        }
+    }
+}
+
+impl Default for OrdinalSet {
+    fn default() -> OrdinalSet {
+        OrdinalSet::new()
+    }
+}
+
+impl PartialEq for OrdinalSet {
+    fn eq(&self, other: &OrdinalSet) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for OrdinalSet {}
+
+impl Hash for OrdinalSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl fmt::Debug for OrdinalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Ordinal> for OrdinalSet {
+    fn from_iter<I: IntoIterator<Item = Ordinal>>(iter: I) -> OrdinalSet {
+        let mut ordinals: Vec<Ordinal> = iter.into_iter().collect();
+        ordinals.sort_unstable();
+        ordinals.dedup();
+        OrdinalSet::from_sorted(ordinals)
+    }
+}
+
+impl<'a> FromIterator<&'a Ordinal> for OrdinalSet {
+    fn from_iter<I: IntoIterator<Item = &'a Ordinal>>(iter: I) -> OrdinalSet {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Ordinal> for OrdinalSet {
+    fn extend<I: IntoIterator<Item = Ordinal>>(&mut self, iter: I) {
+        for ordinal in iter {
+            self.insert(ordinal);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Ordinal> for OrdinalSet {
+    fn extend<I: IntoIterator<Item = &'a Ordinal>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a> IntoIterator for &'a OrdinalSet {
+    type Item = &'a Ordinal;
+    type IntoIter = slice::Iter<'a, Ordinal>;
+
+    fn into_iter(self) -> slice::Iter<'a, Ordinal> {
+        self.iter()
+    }
+}
+
+impl IntoIterator for OrdinalSet {
+    type Item = Ordinal;
+    type IntoIter = std::vec::IntoIter<Ordinal>;
+
+    fn into_iter(self) -> std::vec::IntoIter<Ordinal> {
+        match self.storage {
+            Storage::Heap(ordinals) => ordinals.into_iter(),
+            Storage::Inline { .. } => self.as_slice().to_vec().into_iter(),
+        }
+    }
+}
+
+// Marks values of a `LookupTable` which have no included value after or before them.
+const NO_ORDINAL: u8 = u8::MAX;
+
+// For each value of a unit with fewer than 64 values, the first included value at or after it
+// and the last one at or before it. It's built along with the unit's ordinal set, so that the
+// search for an occurrence hops from one included value to the next without walking the set.
+#[derive(Clone, PartialEq, Eq)]
+pub struct LookupTable {
+    at_or_after: [u8; 64],
+    at_or_before: [u8; 64],
+}
+
+impl LookupTable {
+    pub fn new(ordinals: &OrdinalSet) -> LookupTable {
+        let mut table = LookupTable {
+            at_or_after: [NO_ORDINAL; 64],
+            at_or_before: [NO_ORDINAL; 64],
+        };
+        let mut next = NO_ORDINAL;
+        for value in (0..64).rev() {
+            if ordinals.contains(&(value as Ordinal)) {
+                next = value;
+            }
+            table.at_or_after[value as usize] = next;
+        }
+        let mut prev = NO_ORDINAL;
+        for value in 0..64 {
+            if ordinals.contains(&(value as Ordinal)) {
+                prev = value;
+            }
+            table.at_or_before[value as usize] = prev;
+        }
+        table
+    }
+
+    pub fn contains(&self, value: Ordinal) -> bool {
+        self.at_or_after(value) == Some(value)
+    }
+
+    pub fn at_or_after(&self, value: Ordinal) -> Option<Ordinal> {
+        let next = *self.at_or_after.get(value as usize)?;
+        (next != NO_ORDINAL).then_some(next as Ordinal)
+    }
+
+    pub fn at_or_before(&self, value: Ordinal) -> Option<Ordinal> {
+        let index = (value as usize).min(self.at_or_before.len() - 1);
+        let prev = self.at_or_before[index];
+        (prev != NO_ORDINAL).then_some(prev as Ordinal)
+    }
+
+    // The included values from `value` upwards.
+    pub fn ascending_from(&self, value: Ordinal) -> impl Iterator<Item = Ordinal> + '_ {
+        std::iter::successors(self.at_or_after(value), |&value| {
+            self.at_or_after(value + 1)
+        })
+    }
+
+    // The included values from `value` downwards.
+    pub fn descending_from(&self, value: Ordinal) -> impl Iterator<Item = Ordinal> + '_ {
+        std::iter::successors(self.at_or_before(value), |&value| {
+            self.at_or_before(value.checked_sub(1)?)
+        })
+    }
+}
@@ -1,25 +1,610 @@
-use winnow::ascii::{alpha1, digit1, multispace0};
-use winnow::combinator::{alt, delimited, eof, opt, separated, separated_pair, terminated};
+use winnow::ascii::{alpha1, digit1, multispace0, Caseless};
+use winnow::combinator::{
+    alt, delimited, eof, not, opt, preceded, separated, separated_pair, terminated,
+};
+use winnow::error::{ContextError, FromExternalError, ParseError};
 use winnow::prelude::*;
+use winnow::stream::Stream;
 
+use chrono::Duration;
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::convert::TryFrom;
+use std::ops::RangeInclusive;
 use std::str::{self, FromStr};
 
-use crate::error::{Error, ErrorKind};
+use crate::ast::Ast;
+use crate::error::{Error, ErrorCode, ErrorKind};
+use crate::export::format_cron_field;
+#[cfg(feature = "locales")]
+use crate::locale::Locale;
 use crate::ordinal::*;
-use crate::schedule::{Schedule, ScheduleFields};
+use crate::schedule::{LeapDayPolicy, NearestWeekdayPolicy, Schedule, ScheduleFields};
+use crate::schedule_set::ScheduleSet;
 use crate::specifier::*;
+use crate::syntax::{FieldSyntax, ScheduleSyntax};
 use crate::time_unit::*;
+use crate::windowed::WindowedSchedule;
+
+/// Parses cron expressions into [Schedule]s with non-default options.
+///
+/// Parsing through [FromStr] or [TryFrom] is equivalent to using `ScheduleParser::new()`.
+/// # Example
+/// ```
+/// use cron::ScheduleParser;
+///
+/// // Each host of the fleet picks its own, stable, minute within the hour.
+/// let host_id = 17;
+/// let schedule = ScheduleParser::new()
+///     .seed(host_id)
+///     .parse("0 ~ * * * *")
+///     .expect("Failed to parse expression.");
+/// let again = ScheduleParser::new().seed(host_id).parse("0 ~ * * * *").unwrap();
+/// assert!(schedule.timeunitspec_eq(&again));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleParser {
+    dialect: Dialect,
+    leap_day: LeapDayPolicy,
+    clamp_to_end_of_month: bool,
+    nearest_weekday: NearestWeekdayPolicy,
+    year_range: Option<RangeInclusive<Ordinal>>,
+    fiscal_year_start: Option<Ordinal>,
+    seed: Option<u64>,
+    hash_key: Option<u64>,
+    max_length: Option<usize>,
+    max_list_items: Option<usize>,
+    #[cfg(feature = "locales")]
+    locale: Option<Locale>,
+}
+
+impl ScheduleParser {
+    /// Creates a parser with the default options.
+    pub fn new() -> ScheduleParser {
+        ScheduleParser::default()
+    }
+
+    /// Sets the flavour of cron expressions to parse. Defaults to [Dialect::Quartz].
+    pub fn dialect(mut self, dialect: Dialect) -> ScheduleParser {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets what schedules including the 29th of February do in common years. Defaults to
+    /// [LeapDayPolicy::Skip].
+    pub fn leap_day(mut self, policy: LeapDayPolicy) -> ScheduleParser {
+        self.leap_day = policy;
+        self
+    }
+
+    /// Makes days of the month past the end of a shorter month fire on its last day instead of
+    /// being skipped, so that `0 0 0 31 * *` fires on the 30th of April. The 29th of February
+    /// follows the [leap day policy](ScheduleParser::leap_day) rather than being clamped. Disabled
+    /// by default.
+    pub fn clamp_to_end_of_month(mut self, clamp: bool) -> ScheduleParser {
+        self.clamp_to_end_of_month = clamp;
+        self
+    }
+
+    /// Sets whether the weekday nearest to a day of the month (`W`) may fall in another month.
+    /// Defaults to [NearestWeekdayPolicy::WithinMonth], as in Quartz.
+    pub fn nearest_weekday(mut self, policy: NearestWeekdayPolicy) -> ScheduleParser {
+        self.nearest_weekday = policy;
+        self
+    }
+
+    /// Sets the years searched when an expression doesn't restrict them, e.g. `1970..=9999`.
+    /// Years given in expressions must fall within the range, which must itself be within 1 to
+    /// 9999. Defaults to 1970 to 2100.
+    pub fn year_range(mut self, years: RangeInclusive<u32>) -> ScheduleParser {
+        self.year_range = Some(years);
+        self
+    }
+
+    /// Sets the month the fiscal year starts on, from 1 for January to 12 for December, so that
+    /// quarters and the shorthands marking the boundaries of the year and its quarters follow
+    /// the fiscal calendar. `Q1` then spans the first three months of the fiscal year, `@yearly`
+    /// fires on its first day and `@yearend` on its last. Years given in expressions are still
+    /// calendar years. Defaults to January.
+    /// # Example
+    /// ```
+    /// use cron::{ScheduleParser, TimeUnitSpec};
+    ///
+    /// let parser = ScheduleParser::new().fiscal_year_start(4);
+    /// let first_quarter = parser.parse("0 0 9 1 Q1 ?").unwrap();
+    /// assert_eq!(vec![4, 5, 6], first_quarter.months().iter().collect::<Vec<_>>());
+    /// let last_quarter = parser.parse("0 0 9 1 Q4 ?").unwrap();
+    /// assert_eq!(vec![1, 2, 3], last_quarter.months().iter().collect::<Vec<_>>());
+    /// let year_end = parser.parse("@yearend").unwrap();
+    /// assert_eq!(vec![3], year_end.months().iter().collect::<Vec<_>>());
+    /// ```
+    pub fn fiscal_year_start(mut self, month: u32) -> ScheduleParser {
+        self.fiscal_year_start = Some(month);
+        self
+    }
+
+    /// Sets the seed used to resolve random (`~` and `R`) specifiers, so that an expression
+    /// always resolves to the same values. Without a seed, values are drawn from a hash of the
+    /// expression, so that they're stable for each expression but differ across expressions.
+    pub fn seed(mut self, seed: u64) -> ScheduleParser {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the key, typically the name of a job, hashed to resolve `H` specifiers, so that the
+    /// same expression resolves to stable but distinct values for each job, as in Jenkins.
+    /// Without a key, `H` specifiers are resolved like random ones.
+    /// # Example
+    /// ```
+    /// use cron::{ScheduleParser, TimeUnitSpec};
+    ///
+    /// let backup = ScheduleParser::new().hash_key("backup").parse("0 H H(0-5) * * *").unwrap();
+    /// let again = ScheduleParser::new().hash_key("backup").parse("0 H H(0-5) * * *").unwrap();
+    /// assert!(backup.timeunitspec_eq(&again));
+    /// assert_eq!(1, backup.minutes().count());
+    /// assert!(backup.hours().iter().all(|hour| hour <= 5));
+    /// ```
+    pub fn hash_key(mut self, key: &str) -> ScheduleParser {
+        self.hash_key = Some(fnv1a(key.as_bytes()));
+        self
+    }
+
+    /// Rejects expressions longer than `max_length` bytes before parsing them. Unlimited by
+    /// default.
+    pub fn max_length(mut self, max_length: usize) -> ScheduleParser {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Rejects expressions with more than `max_list_items` comma separated items in a single
+    /// field. Unlimited by default.
+    pub fn max_list_items(mut self, max_list_items: usize) -> ScheduleParser {
+        self.max_list_items = Some(max_list_items);
+        self
+    }
+
+    /// Accepts month and day of the week names in the given language, as well as English ones.
+    /// Only available with the `locales` feature.
+    /// # Example
+    /// ```
+    /// use cron::{Locale, Schedule, ScheduleParser};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = ScheduleParser::new()
+    ///     .locale(Locale::French)
+    ///     .parse("0 0 9 * janv-mars lun-ven")
+    ///     .unwrap();
+    /// let english = Schedule::from_str("0 0 9 * Jan-Mar Mon-Fri").unwrap();
+    /// assert!(schedule.timeunitspec_eq(&english));
+    /// ```
+    #[cfg(feature = "locales")]
+    pub fn locale(mut self, locale: Locale) -> ScheduleParser {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Parses the given cron expression.
+    pub fn parse(&self, expression: &str) -> Result<Schedule, Error> {
+        self.parse_cow(Cow::Borrowed(expression))
+    }
+
+    /// Parses the given cron expression into its [Ast], after checking that it would compile.
+    pub fn parse_to_ast(&self, expression: &str) -> Result<Ast, Error> {
+        Ast::from_schedule(&self.parse(expression)?)
+    }
+
+    /// Parses a cron expression followed by a `for` suffix, such as `0 0 9 * * Mon-Fri for 8h`,
+    /// into a [WindowedSchedule]. See [WindowedSchedule] for the syntax of the duration.
+    pub fn parse_windowed(&self, expression: &str) -> Result<WindowedSchedule, Error> {
+        let (schedule, duration) = split_window(expression)?;
+        Ok(WindowedSchedule::new(self.parse(&schedule)?, duration))
+    }
+
+    /// Compiles an [Ast] into a [Schedule] with the options of this parser, in the dialect of
+    /// the tree rather than the one of the parser.
+    pub fn compile(&self, ast: &Ast) -> Result<Schedule, Error> {
+        self.clone().dialect(ast.dialect()).parse(&ast.to_string())
+    }
+
+    /// Parses one expression per line, e.g. from a text area, into a [ScheduleSet] keyed by
+    /// line number, starting from 1. Blank lines and lines holding only a `# comment` are
+    /// skipped.
+    ///
+    /// Fails with [ErrorKind::Lines] listing every line which couldn't be parsed.
+    /// # Example
+    /// ```
+    /// use cron::ScheduleParser;
+    ///
+    /// let input = "# Nightly jobs\n0 0 2 * * *\n\n0 30 3 * * * # cleanup\n";
+    /// let schedules = ScheduleParser::new().parse_lines(input).unwrap();
+    /// let lines: Vec<_> = schedules.iter().map(|(line, _)| *line).collect();
+    /// assert_eq!(vec![2, 4], lines);
+    ///
+    /// let error = ScheduleParser::new().parse_lines("0 0 2 * * *\n0 0 25 * * *").unwrap_err();
+    /// assert!(error.to_string().starts_with("Line 2: "));
+    /// ```
+    pub fn parse_lines(&self, input: &str) -> Result<ScheduleSet<usize>, Error> {
+        let mut schedules = ScheduleSet::new();
+        let mut errors = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            if split_comment(line).0.trim().is_empty() {
+                continue;
+            }
+            match self.parse(line) {
+                Ok(schedule) => schedules.insert(index + 1, schedule),
+                Err(error) => errors.push((index + 1, error)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(schedules)
+        } else {
+            Err(ErrorKind::Lines(errors).into())
+        }
+    }
+
+    fn parse_cow(&self, expression: Cow<'_, str>) -> Result<Schedule, Error> {
+        trace_span!(DEBUG, "parse", expression = %expression, dialect = ?self.dialect);
+        let parsed = self.parse_cow_unchecked(expression);
+        if let Err(_error) = &parsed {
+            trace_event!(debug, error = %_error, "Failed to parse the expression");
+        }
+        parsed
+    }
+
+    fn parse_cow_unchecked(&self, expression: Cow<'_, str>) -> Result<Schedule, Error> {
+        self.limit_length(&expression)?;
+        if let Some(years) = &self.year_range {
+            if years.is_empty()
+                || years.start() < YEAR_LIMITS.start()
+                || years.end() > YEAR_LIMITS.end()
+            {
+                return Err(ErrorKind::Expression(format!(
+                    "The year range {}-{} must be a range within {}-{}.",
+                    years.start(),
+                    years.end(),
+                    YEAR_LIMITS.start(),
+                    YEAR_LIMITS.end()
+                ))
+                .with_code(ErrorCode::FieldOutOfRange));
+            }
+        }
+        if let Some(month) = self.fiscal_year_start {
+            if !(1..=12).contains(&month) {
+                return Err(ErrorKind::Expression(format!(
+                    "The fiscal year must start on a month between 1 and 12. ('{}' specified.)",
+                    month
+                ))
+                .with_code(ErrorCode::FieldOutOfRange));
+            }
+        }
+        let fields = if self.dialect.allows_comments() {
+            split_comment(&expression).0
+        } else {
+            &expression
+        };
+        match schedule_with(self).parse(fields) {
+            Ok((mut schedule_fields, syntax)) => {
+                schedule_fields.leap_day = self.leap_day;
+                schedule_fields.clamp_to_end_of_month = self.clamp_to_end_of_month;
+                schedule_fields.nearest_weekday = self.nearest_weekday;
+                schedule_fields.year_range = self.year_range.clone().unwrap_or(Years::bounds());
+                Ok(Schedule::new(expression.into_owned(), schedule_fields).with_syntax(syntax))
+            }
+            Err(parse_error) => Err(parse_failure(parse_error)),
+        }
+    }
+
+    fn limit_length(&self, expression: &str) -> Result<(), Error> {
+        match self.max_length {
+            Some(max_length) if expression.len() > max_length => {
+                Err(ErrorKind::Expression(format!(
+                    "Expression is {} bytes long, exceeding the limit of {}.",
+                    expression.len(),
+                    max_length
+                ))
+                .with_code(ErrorCode::LimitExceeded))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // An empty syntax carrying the options of this parser, so that fields replaced later are
+    // resolved as this parser would have.
+    fn new_syntax(&self) -> ScheduleSyntax {
+        let mut syntax = ScheduleSyntax::new(self.dialect);
+        syntax.fiscal_year_start = self.fiscal_year_start;
+        syntax.seed = self.seed;
+        syntax.hash_key = self.hash_key;
+        #[cfg(feature = "locales")]
+        {
+            syntax.locale = self.locale;
+        }
+        syntax.max_length = self.max_length;
+        syntax.max_list_items = self.max_list_items;
+        syntax
+    }
+
+    // The parser a schedule with the given syntax and fields was parsed with, as far as resolving
+    // its fields goes.
+    fn from_syntax(syntax: &ScheduleSyntax, fields: &ScheduleFields) -> ScheduleParser {
+        let mut parser = ScheduleParser::new().dialect(syntax.dialect);
+        parser.year_range = Some(fields.year_range.clone());
+        parser.fiscal_year_start = syntax.fiscal_year_start;
+        parser.seed = syntax.seed;
+        parser.hash_key = syntax.hash_key;
+        #[cfg(feature = "locales")]
+        {
+            parser.locale = syntax.locale;
+        }
+        parser.max_length = syntax.max_length;
+        parser.max_list_items = syntax.max_list_items;
+        parser
+    }
+
+    fn limit_list_items(&self, field: Field) -> Result<Field, Error> {
+        match self.max_list_items {
+            Some(max_list_items) if field.specifiers.len() > max_list_items => {
+                Err(ErrorKind::Expression(format!(
+                    "Field has {} list items, exceeding the limit of {}.",
+                    field.specifiers.len(),
+                    max_list_items
+                ))
+                .with_code(ErrorCode::LimitExceeded))
+            }
+            _ => Ok(field),
+        }
+    }
+
+    // Rejects the values the upstream crate doesn't know of when parsing its dialect.
+    fn reject_extensions(&self, field: Field) -> Result<Field, Error> {
+        let extension = field.specifiers.iter().any(|specifier| {
+            matches!(
+                specifier,
+                RootSpecifier::Random(..) | RootSpecifier::Hashed(..)
+            )
+        });
+        if self.dialect == Dialect::Legacy && extension {
+            return Err(ErrorKind::Expression(
+                "Random and hashed values aren't supported by the Legacy dialect.".to_string(),
+            )
+            .with_code(ErrorCode::Syntax));
+        }
+        Ok(field)
+    }
+
+    // Resolves the year field against the configured year range.
+    fn years_from_field(&self, field: Field) -> Result<Years, Error> {
+        match &self.year_range {
+            Some(years) => Years::from_field_within(field, years),
+            None => Years::from_field(field),
+        }
+    }
+
+    // The years searched when the expression doesn't restrict them.
+    fn all_years(&self) -> Years {
+        match &self.year_range {
+            Some(years) => Years::from_ordinal_set(years.clone().collect()),
+            None => Years::all(),
+        }
+    }
+
+    // Replaces the localized names of the field at `position` with English ones.
+    #[cfg(feature = "locales")]
+    fn translate_names(&self, mut field: Field, position: u64) -> Field {
+        let Some(locale) = self.locale else {
+            return field;
+        };
+        let translate = |name: &mut String| locale.translate(position, name);
+        for specifier in field.specifiers.iter_mut() {
+            match specifier {
+                RootSpecifier::NamedPoint(name)
+                | RootSpecifier::LastOfMonth(PointValue::Named(name), _)
+                | RootSpecifier::NthOfMonth(PointValue::Named(name), _) => translate(name),
+                RootSpecifier::Specifier(Specifier::NamedRange(start, end))
+                | RootSpecifier::Period(Specifier::NamedRange(start, end), _) => {
+                    translate(start);
+                    translate(end);
+                }
+                _ => {}
+            }
+        }
+        field
+    }
+
+    #[cfg(not(feature = "locales"))]
+    fn translate_names(&self, field: Field, _position: u64) -> Field {
+        field
+    }
+
+    // Fills in the month the fiscal year starts on for the quarters of the field.
+    fn fiscal_quarters(&self, mut field: Field) -> Field {
+        for specifier in field.specifiers.iter_mut() {
+            if let RootSpecifier::Quarter(_, year_start) = specifier {
+                *year_start = self.fiscal_year_start.unwrap_or(1);
+            }
+        }
+        field
+    }
+
+    // Moves the months of a shorthand, given for a year starting in January, into the fiscal
+    // year.
+    fn fiscal_months(&self, months: Months) -> Months {
+        let shift = self.fiscal_year_start.unwrap_or(1) - 1;
+        if shift == 0 || months.is_all() {
+            return months;
+        }
+        Months::from_ordinal_set(
+            months
+                .iter()
+                .map(|month| (month - 1 + shift) % 12 + 1)
+                .collect(),
+        )
+    }
+
+    // Fills in the values drawn for the random and hashed specifiers of the field at `position`.
+    // Without a seed, values are drawn from `expression_hash`, the hash of the expression's fields.
+    fn draw_random(&self, mut field: Field, position: u64, expression_hash: u64) -> Field {
+        let seed = self.seed.unwrap_or(expression_hash);
+        for (index, specifier) in field.specifiers.iter_mut().enumerate() {
+            let key = (position << 32) | index as u64;
+            match specifier {
+                RootSpecifier::Random(_, _, draw) => *draw = draw_with(seed, key),
+                RootSpecifier::Hashed(_, _, draw) => {
+                    *draw = draw_with(self.hash_key.unwrap_or(seed), key)
+                }
+                _ => {}
+            }
+        }
+        field
+    }
+}
+
+/// The flavours of cron expressions a [ScheduleParser] understands.
+///
+/// | Dialect   | Fields                                    | Sunday | `L` `W` `#` | `?`      | Day fields match |
+/// |-----------|-------------------------------------------|--------|-------------|----------|------------------|
+/// | `Quartz`  | sec min hour dom month dow \[year\]        | 1      | yes         | yes      | both             |
+/// | `Unix`    | min hour dom month dow                    | 0 or 7 | no          | no       | either           |
+/// | `Jenkins` | min hour dom month dow                    | 0 or 7 | no          | no       | both             |
+/// | `Aws`     | min hour dom month dow year               | 1      | yes         | required | both             |
+/// | `Robfig`  | sec min hour dom month dow                | 0      | no          | yes      | either           |
+/// | `Legacy`  | sec min hour dom month dow \[year\]        | 1      | no          | yes      | both             |
+///
+/// Where day fields match "either", a day matches if it's included by the day of month field or
+/// by the day of week field, as long as both are restricted. Otherwise it must be included by
+/// both. All dialects but `Aws` accept shorthands such as `@daily`, and all of them accept random
+/// values (`~` and `R`), hashed values (`H`) and quarters (`Q1` to `Q4`) in the month field.
+///
+/// Besides the shorthands of Unix cron, `@quarterly` fires at midnight on the first day of each
+/// quarter, `@quarterend` at midnight on the last day of each quarter and `@yearend` at midnight
+/// on the last day of the year. Quarters and years start in January unless the parser is given
+/// a [fiscal year start](ScheduleParser::fiscal_year_start).
+///
+/// `Legacy` accepts what the upstream `cron` crate does, so that its users can switch to this one
+/// without any expression changing meaning and move to another dialect at their own pace. It only
+/// takes the `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` shorthands, and rejects
+/// random and hashed values, quarters and comments.
+/// # Example
+/// ```
+/// use cron::{Dialect, ScheduleParser};
+///
+/// let schedule = ScheduleParser::new()
+///     .dialect(Dialect::Unix)
+///     .parse("30 9 * * 1-5")
+///     .expect("Failed to parse expression.");
+/// assert_eq!("30 9 * * 1-5", schedule.source());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// Quartz-style expressions, with seconds and an optional year.
+    #[default]
+    Quartz,
+    /// Classic five field crontab expressions.
+    Unix,
+    /// Jenkins build triggers.
+    Jenkins,
+    /// Amazon EventBridge cron expressions.
+    Aws,
+    /// The `robfig/cron` Go library, with seconds.
+    Robfig,
+    /// The upstream `cron` crate, without the extensions of this one.
+    Legacy,
+}
+
+impl Dialect {
+    fn has_seconds(self) -> bool {
+        matches!(self, Dialect::Quartz | Dialect::Robfig | Dialect::Legacy)
+    }
+
+    pub(crate) fn sunday_is_zero(self) -> bool {
+        matches!(self, Dialect::Unix | Dialect::Jenkins | Dialect::Robfig)
+    }
+
+    fn days_match_either(self) -> bool {
+        matches!(self, Dialect::Unix | Dialect::Robfig)
+    }
+
+    fn allows_shorthands(self) -> bool {
+        self != Dialect::Aws
+    }
+
+    fn allows_comments(self) -> bool {
+        self != Dialect::Legacy
+    }
+
+    // Whether the year field may follow the day of week field.
+    fn has_years(self) -> bool {
+        matches!(self, Dialect::Quartz | Dialect::Aws | Dialect::Legacy)
+    }
+
+    fn days_of_month_field(self) -> fn(&mut &str) -> winnow::Result<Field> {
+        match self {
+            Dialect::Quartz | Dialect::Aws => field_days_of_month,
+            Dialect::Robfig | Dialect::Legacy => field_with_any,
+            Dialect::Unix | Dialect::Jenkins => field,
+        }
+    }
+
+    fn days_of_week_field(self) -> fn(&mut &str) -> winnow::Result<Field> {
+        match self {
+            Dialect::Quartz | Dialect::Aws => field_days_of_week,
+            Dialect::Robfig | Dialect::Legacy => field_with_any,
+            Dialect::Unix | Dialect::Jenkins => field,
+        }
+    }
+
+    // The grammar of the field at `position`, in the order of a Quartz expression.
+    fn field_at(self, position: u64) -> fn(&mut &str) -> winnow::Result<Field> {
+        match position {
+            3 => self.days_of_month_field(),
+            4 if self == Dialect::Legacy => field,
+            4 => field_months,
+            5 => self.days_of_week_field(),
+            _ => field,
+        }
+    }
+}
+
+fn draw_with(seed: u64, key: u64) -> u64 {
+    mix(seed ^ mix(key))
+}
+
+// The 64 bit FNV-1a hash, which unlike the standard library's hasher is stable across releases,
+// so that hash keys and checkpoints resolve to the same values everywhere.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+// The SplitMix64 finalizer, used so that seeded values don't depend on the standard library's
+// unspecified hashing algorithm.
+pub(crate) fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+// Jump consistent hashing (Lamping and Veach), which spreads keys evenly over the buckets and,
+// when buckets are added, only moves keys to the new ones.
+pub(crate) fn jump_hash(mut key: u64, buckets: u32) -> u32 {
+    let mut bucket = 0;
+    let mut next = 0i64;
+    while next < i64::from(buckets) {
+        bucket = next;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        next = ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    bucket as u32
+}
 
 impl TryFrom<Cow<'_, str>> for Schedule {
     type Error = Error;
 
     fn try_from(expression: Cow<'_, str>) -> Result<Self, Self::Error> {
-        match schedule.parse(&expression) {
-            Ok(schedule_fields) => Ok(Schedule::new(expression.into_owned(), schedule_fields)), // Extract from winnow tuple
-            Err(parse_error) => Err(ErrorKind::Expression(format!("{parse_error}")).into()),
-        }
+        ScheduleParser::new().parse_cow(expression)
     }
 }
 
@@ -58,6 +643,7 @@ where
 {
     //TODO: Replace with std::convert::TryFrom when stable
     fn from_field(field: Field) -> Result<Self, Error>;
+    fn from_field_within(field: Field, bounds: &RangeInclusive<Ordinal>) -> Result<Self, Error>;
 }
 
 impl<T> FromField for T
@@ -65,19 +651,30 @@ where
     T: TimeUnitField,
 {
     fn from_field(field: Field) -> Result<T, Error> {
+        T::from_field_within(field, &T::bounds())
+    }
+
+    fn from_field_within(field: Field, bounds: &RangeInclusive<Ordinal>) -> Result<T, Error> {
         if field.specifiers.len() == 1
             && field.specifiers.first().unwrap() == &RootSpecifier::from(Specifier::All)
+            && *bounds == T::bounds()
         {
             return Ok(T::all());
         }
         let mut ordinals = OrdinalSet::new();
+        let mut specs = Vec::new();
         for specifier in field.specifiers {
-            let specifier_ordinals: OrdinalSet = T::ordinals_from_root_specifier(&specifier)?;
-            for ordinal in specifier_ordinals {
-                ordinals.insert(T::validate_ordinal(ordinal)?);
+            match T::resolve_root_specifier_within(&specifier, bounds)? {
+                Resolved::Ordinals(specifier_ordinals) => {
+                    for ordinal in specifier_ordinals {
+                        ordinals.insert(T::validate_ordinal_within(ordinal, bounds)?);
+                    }
+                }
+                // Specials are validated when they are resolved from their specifier.
+                Resolved::Day(spec) => specs.push(spec),
             }
         }
-        Ok(T::from_ordinal_set(ordinals))
+        T::from_ordinal_set(ordinals).with_day_specs(specs)
     }
 }
 
@@ -125,6 +722,93 @@ fn named_range(i: &mut &str) -> winnow::Result<Specifier> {
         .parse_next(i)
 }
 
+fn random(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    // Unlike other specifiers, whitespace isn't allowed around the '~' to avoid ambiguity with
+    // neighbouring fields, e.g. in '0 ~ * * * *'.
+    let bound = || opt(digit1.try_map(u32::from_str));
+    let bounded = separated_pair(bound(), "~", bound());
+    let unbounded = terminated(Caseless("R"), not(alpha1)).map(|_| (None, None));
+    delimited(multispace0, alt((bounded, unbounded)), multispace0)
+        .map(|(start, end)| RootSpecifier::Random(start, end, 0))
+        .parse_next(i)
+}
+
+fn hashed(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let bound = || digit1.try_map(u32::from_str);
+    let range = delimited("(", separated_pair(bound(), "-", bound()), ")");
+    let step = preceded("/", bound());
+    let hashed = preceded(
+        terminated(Caseless("H"), not(alpha1)),
+        (opt(range), opt(step)),
+    );
+    delimited(multispace0, hashed, multispace0)
+        .map(|(range, step)| RootSpecifier::Hashed(range, step, 0))
+        .parse_next(i)
+}
+
+fn last_of_month(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    // Names are matched greedily, so the 'L' of e.g. 'FRIL' has to be split off afterwards.
+    let named_value = alpha1.verify_map(|name: &str| {
+        name.strip_suffix(['L', 'l'])
+            .filter(|name| !name.is_empty())
+            .map(|name| PointValue::Named(name.to_owned()))
+    });
+    let ordinal_value = terminated(digit1.try_map(u32::from_str), Caseless("L"));
+    let value = alt((ordinal_value.map(PointValue::Ordinal), named_value));
+    let offset = opt(preceded("-", digit1.try_map(u32::from_str)));
+    delimited(multispace0, (value, offset), multispace0)
+        .map(|(value, offset)| RootSpecifier::LastOfMonth(value, offset.unwrap_or(0)))
+        .parse_next(i)
+}
+
+fn nth_of_month(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let value = alt((
+        digit1.try_map(u32::from_str).map(PointValue::Ordinal),
+        alpha1.map(|name: &str| PointValue::Named(name.to_owned())),
+    ));
+    delimited(
+        multispace0,
+        separated_pair(value, "#", digit1.try_map(u32::from_str)),
+        multispace0,
+    )
+    .map(|(value, nth)| RootSpecifier::NthOfMonth(value, nth))
+    .parse_next(i)
+}
+
+fn last_day_of_month(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let offset = opt(preceded("-", digit1.try_map(u32::from_str)));
+    delimited(multispace0, preceded(Caseless("L"), offset), multispace0)
+        .map(|offset| RootSpecifier::LastDayOfMonth(offset.unwrap_or(0)))
+        .parse_next(i)
+}
+
+fn nearest_weekday(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let day = terminated(digit1.try_map(u32::from_str), Caseless("W"));
+    delimited(multispace0, day, multispace0)
+        .map(RootSpecifier::NearestWeekday)
+        .parse_next(i)
+}
+
+fn business_day(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let nth = terminated(digit1.try_map(u32::from_str), Caseless("B"));
+    delimited(multispace0, nth, multispace0)
+        .map(RootSpecifier::BusinessDay)
+        .parse_next(i)
+}
+
+fn last_weekday_of_month(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    delimited(multispace0, Caseless("LW"), multispace0)
+        .map(|_| RootSpecifier::LastWeekdayOfMonth)
+        .parse_next(i)
+}
+
+fn quarter(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    let quarter = preceded(Caseless("Q"), digit1.try_map(u32::from_str));
+    delimited(multispace0, quarter, multispace0)
+        .map(|quarter| RootSpecifier::Quarter(quarter, 1))
+        .parse_next(i)
+}
+
 fn all(i: &mut &str) -> winnow::Result<Specifier> {
     "*".map(|_| Specifier::All).parse_next(i)
 }
@@ -142,11 +826,20 @@ fn specifier_with_any(i: &mut &str) -> winnow::Result<Specifier> {
 }
 
 fn root_specifier(i: &mut &str) -> winnow::Result<RootSpecifier> {
-    alt((period, specifier.map(RootSpecifier::from), named_point)).parse_next(i)
+    alt((
+        random,
+        hashed,
+        period,
+        specifier.map(RootSpecifier::from),
+        named_point,
+    ))
+    .parse_next(i)
 }
 
 fn root_specifier_with_any(i: &mut &str) -> winnow::Result<RootSpecifier> {
     alt((
+        random,
+        hashed,
         period_with_any,
         specifier_with_any.map(RootSpecifier::from),
         named_point,
@@ -154,6 +847,25 @@ fn root_specifier_with_any(i: &mut &str) -> winnow::Result<RootSpecifier> {
     .parse_next(i)
 }
 
+fn root_specifier_days_of_month(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    alt((
+        last_weekday_of_month,
+        last_day_of_month,
+        nearest_weekday,
+        business_day,
+        root_specifier_with_any,
+    ))
+    .parse_next(i)
+}
+
+fn root_specifier_months(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    alt((quarter, root_specifier)).parse_next(i)
+}
+
+fn root_specifier_days_of_week(i: &mut &str) -> winnow::Result<RootSpecifier> {
+    alt((last_of_month, nth_of_month, root_specifier_with_any)).parse_next(i)
+}
+
 fn root_specifier_list(i: &mut &str) -> winnow::Result<Vec<RootSpecifier>> {
     let list = separated(1.., root_specifier, ",");
     let single_item = root_specifier.map(|spec| vec![spec]);
@@ -166,6 +878,24 @@ fn root_specifier_list_with_any(i: &mut &str) -> winnow::Result<Vec<RootSpecifie
     delimited(multispace0, alt((list, single_item)), multispace0).parse_next(i)
 }
 
+fn root_specifier_list_days_of_month(i: &mut &str) -> winnow::Result<Vec<RootSpecifier>> {
+    let list = separated(1.., root_specifier_days_of_month, ",");
+    let single_item = root_specifier_days_of_month.map(|spec| vec![spec]);
+    delimited(multispace0, alt((list, single_item)), multispace0).parse_next(i)
+}
+
+fn root_specifier_list_months(i: &mut &str) -> winnow::Result<Vec<RootSpecifier>> {
+    let list = separated(1.., root_specifier_months, ",");
+    let single_item = root_specifier_months.map(|spec| vec![spec]);
+    delimited(multispace0, alt((list, single_item)), multispace0).parse_next(i)
+}
+
+fn root_specifier_list_days_of_week(i: &mut &str) -> winnow::Result<Vec<RootSpecifier>> {
+    let list = separated(1.., root_specifier_days_of_week, ",");
+    let single_item = root_specifier_days_of_week.map(|spec| vec![spec]);
+    delimited(multispace0, alt((list, single_item)), multispace0).parse_next(i)
+}
+
 fn field(i: &mut &str) -> winnow::Result<Field> {
     let specifiers = root_specifier_list.parse_next(i)?;
     Ok(Field { specifiers })
@@ -176,6 +906,21 @@ fn field_with_any(i: &mut &str) -> winnow::Result<Field> {
     Ok(Field { specifiers })
 }
 
+fn field_days_of_month(i: &mut &str) -> winnow::Result<Field> {
+    let specifiers = root_specifier_list_days_of_month.parse_next(i)?;
+    Ok(Field { specifiers })
+}
+
+fn field_months(i: &mut &str) -> winnow::Result<Field> {
+    let specifiers = root_specifier_list_months.parse_next(i)?;
+    Ok(Field { specifiers })
+}
+
+fn field_days_of_week(i: &mut &str) -> winnow::Result<Field> {
+    let specifiers = root_specifier_list_days_of_week.parse_next(i)?;
+    Ok(Field { specifiers })
+}
+
 fn shorthand_yearly(i: &mut &str) -> winnow::Result<ScheduleFields> {
     "@yearly".parse_next(i)?;
     let fields = ScheduleFields::new(
@@ -190,6 +935,48 @@ fn shorthand_yearly(i: &mut &str) -> winnow::Result<ScheduleFields> {
     Ok(fields)
 }
 
+fn shorthand_yearend(i: &mut &str) -> winnow::Result<ScheduleFields> {
+    "@yearend".parse_next(i)?;
+    let fields = ScheduleFields::new(
+        Seconds::from_ordinal(0),
+        Minutes::from_ordinal(0),
+        Hours::from_ordinal(0),
+        DaysOfMonth::from_spec(DayOfMonthSpec::LastDay { offset: 0 }),
+        Months::from_ordinal(12),
+        DaysOfWeek::all(),
+        Years::all(),
+    );
+    Ok(fields)
+}
+
+fn shorthand_quarterly(i: &mut &str) -> winnow::Result<ScheduleFields> {
+    "@quarterly".parse_next(i)?;
+    let fields = ScheduleFields::new(
+        Seconds::from_ordinal(0),
+        Minutes::from_ordinal(0),
+        Hours::from_ordinal(0),
+        DaysOfMonth::from_ordinal(1),
+        Months::from_ordinal_set([1, 4, 7, 10].into_iter().collect()),
+        DaysOfWeek::all(),
+        Years::all(),
+    );
+    Ok(fields)
+}
+
+fn shorthand_quarterend(i: &mut &str) -> winnow::Result<ScheduleFields> {
+    "@quarterend".parse_next(i)?;
+    let fields = ScheduleFields::new(
+        Seconds::from_ordinal(0),
+        Minutes::from_ordinal(0),
+        Hours::from_ordinal(0),
+        DaysOfMonth::from_spec(DayOfMonthSpec::LastDay { offset: 0 }),
+        Months::from_ordinal_set([3, 6, 9, 12].into_iter().collect()),
+        DaysOfWeek::all(),
+        Years::all(),
+    );
+    Ok(fields)
+}
+
 fn shorthand_monthly(i: &mut &str) -> winnow::Result<ScheduleFields> {
     "@monthly".parse_next(i)?;
     let fields = ScheduleFields::new(
@@ -249,6 +1036,9 @@ fn shorthand_hourly(i: &mut &str) -> winnow::Result<ScheduleFields> {
 fn shorthand(i: &mut &str) -> winnow::Result<ScheduleFields> {
     let keywords = alt((
         shorthand_yearly,
+        shorthand_yearend,
+        shorthand_quarterly,
+        shorthand_quarterend,
         shorthand_monthly,
         shorthand_weekly,
         shorthand_daily,
@@ -257,44 +1047,438 @@ fn shorthand(i: &mut &str) -> winnow::Result<ScheduleFields> {
     delimited(multispace0, keywords, multispace0).parse_next(i)
 }
 
-fn longhand(i: &mut &str) -> winnow::Result<ScheduleFields> {
-    let seconds = field.try_map(Seconds::from_field);
-    let minutes = field.try_map(Minutes::from_field);
-    let hours = field.try_map(Hours::from_field);
-    let days_of_month = field_with_any.try_map(DaysOfMonth::from_field);
-    let months = field.try_map(Months::from_field);
-    let days_of_week = field_with_any.try_map(DaysOfWeek::from_field);
-    let years = opt(field.try_map(Years::from_field));
-    let fields = (
-        seconds,
-        minutes,
-        hours,
-        days_of_month,
-        months,
-        days_of_week,
-        years,
-    );
+// The classic shorthand firing exactly when the fields do, if any.
+pub(crate) fn nickname_of(fields: &ScheduleFields) -> Option<&'static str> {
+    ["@yearly", "@monthly", "@weekly", "@daily", "@hourly"]
+        .into_iter()
+        .find(|&nickname| {
+            let Ok(shorthand) = legacy_shorthand.parse(nickname) else {
+                return false;
+            };
+            fields.seconds == shorthand.seconds
+                && fields.minutes == shorthand.minutes
+                && fields.hours == shorthand.hours
+                && fields.days_of_month == shorthand.days_of_month
+                && fields.months == shorthand.months
+                && fields.days_of_week == shorthand.days_of_week
+                && fields.every_year()
+        })
+}
+
+// The shorthands the upstream crate knows of.
+fn legacy_shorthand(i: &mut &str) -> winnow::Result<ScheduleFields> {
+    let keywords = alt((
+        shorthand_yearly,
+        shorthand_monthly,
+        shorthand_weekly,
+        shorthand_daily,
+        shorthand_hourly,
+    ));
+    delimited(multispace0, keywords, multispace0).parse_next(i)
+}
+
+fn longhand<'p>(
+    parser: &'p ScheduleParser,
+) -> impl FnMut(&mut &str) -> winnow::Result<(ScheduleFields, Option<ScheduleSyntax>)> + 'p {
+    move |i: &mut &str| {
+        let dialect = parser.dialect;
+        let expression_hash = fnv1a(i.trim().as_bytes());
+        let syntax = RefCell::new(parser.new_syntax());
+        let prepare = |position| {
+            let syntax = &syntax;
+            move |(field, text): (Field, &str)| {
+                *syntax.borrow_mut().field_mut(position) =
+                    FieldSyntax::new(text, &field.specifiers);
+                parser
+                    .limit_list_items(field)
+                    .and_then(|field| parser.reject_extensions(field))
+                    .map(|field| parser.translate_names(field, position))
+                    .map(|field| parser.draw_random(field, position, expression_hash))
+                    .map(|field| parser.fiscal_quarters(field))
+            }
+        };
+        let seconds = if dialect.has_seconds() {
+            field
+                .with_taken()
+                .try_map(prepare(0))
+                .try_map(Seconds::from_field)
+                .parse_next(i)?
+        } else {
+            Seconds::from_ordinal(0)
+        };
+        let minutes = field
+            .with_taken()
+            .try_map(prepare(1))
+            .try_map(Minutes::from_field)
+            .parse_next(i)?;
+        let hours = field
+            .with_taken()
+            .try_map(prepare(2))
+            .try_map(Hours::from_field)
+            .parse_next(i)?;
+        let (days_of_month, days_of_month_source) = dialect
+            .days_of_month_field()
+            .with_taken()
+            .try_map(prepare(3))
+            .try_map(DaysOfMonth::from_field)
+            .with_taken()
+            .parse_next(i)?;
+        let months = dialect
+            .field_at(4)
+            .with_taken()
+            .try_map(prepare(4))
+            .try_map(Months::from_field)
+            .parse_next(i)?;
+        let days_of_week_start = i.checkpoint();
+        let (days_of_week, days_of_week_source) = if dialect.sunday_is_zero() {
+            dialect
+                .days_of_week_field()
+                .with_taken()
+                .try_map(prepare(5))
+                .try_map(UnixDaysOfWeek::from_field)
+                .map(DaysOfWeek::from)
+                .with_taken()
+                .parse_next(i)?
+        } else {
+            dialect
+                .days_of_week_field()
+                .with_taken()
+                .try_map(prepare(5))
+                .try_map(DaysOfWeek::from_field)
+                .with_taken()
+                .parse_next(i)?
+        };
+        if dialect == Dialect::Aws
+            && days_of_month_source.trim() != "?"
+            && days_of_week_source.trim() != "?"
+        {
+            i.reset(&days_of_week_start);
+            let error: Error = ErrorKind::Expression(
+                "One of the day of month and day of week fields must be '?'.".to_string(),
+            )
+            .with_code(ErrorCode::DayFieldConflict);
+            return Err(ContextError::from_external_error(i, error));
+        }
+        let years = match dialect {
+            // The field is optional, so an invalid year would otherwise be reported as
+            // unexpected input rather than with its own error.
+            Dialect::Quartz | Dialect::Legacy => {
+                let years_start = i.checkpoint();
+                match opt(field.with_taken()).parse_next(i)? {
+                    Some(field) => prepare(6)(field)
+                        .and_then(|field| parser.years_from_field(field))
+                        .map_err(|error| {
+                            i.reset(&years_start);
+                            ContextError::from_external_error(i, error)
+                        })?,
+                    None => parser.all_years(),
+                }
+            }
+            Dialect::Aws => field
+                .with_taken()
+                .try_map(prepare(6))
+                .try_map(|field| parser.years_from_field(field))
+                .parse_next(i)?,
+            _ => parser.all_years(),
+        };
+        eof.parse_next(i)?;
+
+        let fields = ScheduleFields::new(
+            seconds,
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            years,
+        );
+        let fields = if dialect.days_match_either() {
+            fields.matching_either_day()
+        } else {
+            fields
+        };
+        Ok((fields, Some(syntax.into_inner())))
+    }
+}
+
+// Parses an expression, along with how its fields were written unless it's a shorthand.
+fn schedule_with<'p>(
+    parser: &'p ScheduleParser,
+) -> impl FnMut(&mut &str) -> winnow::Result<(ScheduleFields, Option<ScheduleSyntax>)> + 'p {
+    move |i: &mut &str| {
+        if parser.dialect.allows_shorthands() {
+            let shorthand: fn(&mut &str) -> winnow::Result<ScheduleFields> =
+                if parser.dialect == Dialect::Legacy {
+                    legacy_shorthand
+                } else {
+                    shorthand
+                };
+            let shorthand = shorthand.map(|mut fields| {
+                fields.years = parser.all_years();
+                fields.months = parser.fiscal_months(fields.months);
+                let mut syntax = parser.new_syntax();
+                syntax.shorthand = true;
+                (fields, Some(syntax))
+            });
+            alt((shorthand, longhand(parser))).parse_next(i)
+        } else {
+            longhand(parser).parse_next(i)
+        }
+    }
+}
+
+// Splits the `for <duration>` suffix off an expression, keeping its comment if it has one.
+fn split_window(expression: &str) -> Result<(String, Duration), Error> {
+    let invalid = || {
+        ErrorKind::Expression(format!(
+            "Invalid windowed expression: '{expression}'. Expected e.g. 0 0 9 * * Mon-Fri for 8h."
+        ))
+        .with_code(ErrorCode::Syntax)
+    };
+    let body = split_comment(expression).0;
+    let (rest, duration) = body
+        .trim_end()
+        .rsplit_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+    let (rest, keyword) = rest
+        .trim_end()
+        .rsplit_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+    if !keyword.eq_ignore_ascii_case("for") {
+        return Err(invalid());
+    }
+    let duration = parse_duration(duration).ok_or_else(invalid)?;
+    if duration <= Duration::zero() {
+        return Err(ErrorKind::Expression(format!(
+            "The duration of the windows of '{expression}' must be positive."
+        ))
+        .with_code(ErrorCode::InvalidRange));
+    }
+    let rest = rest.trim_end();
+    let schedule = match &expression[body.len()..] {
+        "" => rest.to_string(),
+        comment => format!("{rest} {comment}"),
+    };
+    Ok((schedule, duration))
+}
 
-    terminated(fields, eof)
-        .map(
-            |(seconds, minutes, hours, days_of_month, months, days_of_week, years)| {
-                let years = years.unwrap_or_else(Years::all);
-                ScheduleFields::new(
-                    seconds,
-                    minutes,
-                    hours,
-                    days_of_month,
-                    months,
-                    days_of_week,
-                    years,
-                )
-            },
+// Splits a trailing `# comment` off an expression. The `#` must start the expression or follow
+// whitespace, as it's also used by the `6#3` form of the day of week field.
+pub(crate) fn split_comment(expression: &str) -> (&str, Option<&str>) {
+    let start = expression.char_indices().find_map(|(index, c)| {
+        let after_whitespace = expression[..index]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        (c == '#' && after_whitespace).then_some(index)
+    });
+    match start {
+        Some(start) => (&expression[..start], Some(expression[start + 1..].trim())),
+        None => (expression, None),
+    }
+}
+
+// The specifiers of a field as written, at `position` in the order of a Quartz expression, for
+// analyses which need more than the values they resolved to. Empty if the field doesn't parse.
+pub(crate) fn specifiers_of(dialect: Dialect, position: u64, text: &str) -> Vec<RootSpecifier> {
+    terminated(dialect.field_at(position), eof)
+        .parse(text)
+        .map(|field| field.specifiers)
+        .unwrap_or_default()
+}
+
+// Replaces the field at `position`, in the order of a Quartz expression, with the given text,
+// keeping the way the other fields were written. Shorthands are written out in full in their
+// dialect first, or as a Quartz expression if the dialect can't express them, as with `@yearend`
+// in Unix cron. Schedules which didn't keep their syntax, such as those decoded from bytes, are
+// written out as a Quartz expression.
+pub(crate) fn replace_field(
+    schedule: &Schedule,
+    position: u64,
+    text: &str,
+) -> Result<Schedule, Error> {
+    let mut syntax = match schedule.parsed_syntax() {
+        Some(syntax) if syntax.shorthand => {
+            let parser = ScheduleParser::from_syntax(syntax, schedule.fields());
+            written_syntax(schedule.fields(), &parser).or_else(|_| {
+                written_syntax(schedule.fields(), &parser.clone().dialect(Dialect::Quartz))
+            })?
+        }
+        Some(syntax) => syntax.clone(),
+        None => quartz_syntax(schedule.fields())?,
+    };
+    let dialect = syntax.dialect;
+    let has_field = match position {
+        0 => dialect.has_seconds(),
+        6 => dialect.has_years(),
+        _ => true,
+    };
+    if !has_field {
+        return Err(ErrorKind::Expression(format!(
+            "The {dialect:?} dialect has no {} field.",
+            if position == 0 { "seconds" } else { "years" }
+        ))
+        .with_code(ErrorCode::UnsupportedField));
+    }
+
+    let grammar = dialect.field_at(position);
+    let (field, taken) = terminated(grammar.with_taken(), eof)
+        .parse(text)
+        .map_err(parse_failure)?;
+    *syntax.field_mut(position) = FieldSyntax::new(taken, &field.specifiers);
+    if dialect == Dialect::Aws
+        && syntax.days_of_month.text() != "?"
+        && syntax.days_of_week.text() != "?"
+    {
+        return Err(ErrorKind::Expression(
+            "One of the day of month and day of week fields must be '?'.".to_string(),
         )
-        .parse_next(i)
+        .with_code(ErrorCode::DayFieldConflict));
+    }
+
+    // Resolves the field with the options the schedule was parsed with.
+    let mut fields = schedule.fields().clone();
+    let parser = ScheduleParser::from_syntax(&syntax, &fields);
+    let source = schedule.source_from(&syntax);
+    parser.limit_length(&source)?;
+    let field = parser.limit_list_items(field)?;
+    let field = parser.translate_names(parser.reject_extensions(field)?, position);
+    let expression_hash = fnv1a(syntax.expression().as_bytes());
+    let field = parser.fiscal_quarters(parser.draw_random(field, position, expression_hash));
+    match position {
+        0 => fields.seconds = Seconds::from_field(field)?,
+        1 => fields.minutes = Minutes::from_field(field)?,
+        2 => fields.hours = Hours::from_field(field)?,
+        3 => fields.days_of_month = DaysOfMonth::from_field(field)?,
+        4 => fields.months = Months::from_field(field)?,
+        5 if dialect.sunday_is_zero() => {
+            fields.days_of_week = UnixDaysOfWeek::from_field(field)?.into()
+        }
+        5 => fields.days_of_week = DaysOfWeek::from_field(field)?,
+        _ => fields.years = parser.years_from_field(field)?,
+    }
+    if dialect.days_match_either() {
+        fields = fields.matching_either_day();
+    }
+    schedule.with_fields(source, fields, syntax)
+}
+
+// Parses a duration written as whole days, hours, minutes and seconds, in that order, e.g. `1h30m`
+// or `90s`. At least one of them must be given.
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    fn part<'i>(unit: &'static str) -> impl Parser<&'i str, Option<i64>, ContextError> {
+        opt(terminated(digit1.try_map(i64::from_str), Caseless(unit)))
+    }
+    let parts = (part("d"), part("h"), part("m"), part("s"));
+    let (days, hours, minutes, seconds) = terminated(parts, eof).parse(text).ok()?;
+    if [days, hours, minutes, seconds].iter().all(Option::is_none) {
+        return None;
+    }
+    [(days, 86_400), (hours, 3_600), (minutes, 60), (seconds, 1)]
+        .iter()
+        .try_fold(0i64, |total, (count, unit)| {
+            total.checked_add(count.unwrap_or(0).checked_mul(*unit)?)
+        })
+        .and_then(Duration::try_seconds)
+}
+
+// Writes a duration the way `parse_duration` reads it, e.g. `1h30m`, leaving out its sign and
+// anything below a second.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let mut seconds = duration.num_seconds().unsigned_abs();
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+    let mut text = String::new();
+    for (unit, length) in [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)] {
+        let count = seconds / length;
+        seconds %= length;
+        if count > 0 {
+            text.push_str(&format!("{count}{unit}"));
+        }
+    }
+    text
+}
+
+// The error for an expression which failed to parse, keeping the code of the error which caused
+// the failure, e.g. a value out of its field's range.
+fn parse_failure(parse_error: ParseError<&str, ContextError>) -> Error {
+    let code = parse_error
+        .inner()
+        .cause()
+        .and_then(|cause| cause.downcast_ref::<Error>())
+        .map_or(ErrorCode::Syntax, Error::code);
+    ErrorKind::Expression(format!("{parse_error}")).with_code(code)
+}
+
+// The syntax of the Quartz expression equivalent to the given fields.
+// Writes the fields out in the dialect of the parser, e.g. `0 0 * * 0` for `@weekly` in Unix
+// cron, and parses them back for their syntax.
+fn written_syntax(
+    fields: &ScheduleFields,
+    parser: &ScheduleParser,
+) -> Result<ScheduleSyntax, Error> {
+    let dialect = parser.dialect;
+    let mut items = Vec::new();
+    if dialect.has_seconds() {
+        items.push(quartz_text(&fields.seconds));
+    }
+    items.push(quartz_text(&fields.minutes));
+    items.push(quartz_text(&fields.hours));
+    let mut days_of_month = quartz_text(&fields.days_of_month);
+    let mut days_of_week = match dialect.sunday_is_zero() {
+        true if !fields.days_of_week.is_all() => {
+            let days: Vec<String> = fields
+                .days_of_week
+                .iter()
+                .map(|day| (day - 1).to_string())
+                .collect();
+            days.join(",")
+        }
+        _ => quartz_text(&fields.days_of_week),
+    };
+    if dialect == Dialect::Aws {
+        match days_of_month == "*" {
+            true => days_of_month = "?".to_string(),
+            false => days_of_week = "?".to_string(),
+        }
+    }
+    items.push(days_of_month);
+    items.push(quartz_text(&fields.months));
+    items.push(days_of_week);
+    if !fields.every_year() {
+        items.push(quartz_text(&fields.years));
+    } else if dialect == Dialect::Aws {
+        items.push("*".to_string());
+    }
+    let expression = items.join(" ");
+    match longhand(parser).parse(&expression) {
+        Ok((_, syntax)) => Ok(syntax.unwrap_or_default()),
+        Err(parse_error) => Err(parse_failure(parse_error)),
+    }
 }
 
+pub(crate) fn quartz_syntax(fields: &ScheduleFields) -> Result<ScheduleSyntax, Error> {
+    let mut parser = ScheduleParser::new();
+    parser.year_range = Some(fields.year_range.clone());
+    written_syntax(fields, &parser)
+}
+
+fn quartz_text<T: TimeUnitField>(field: &T) -> String {
+    let plain: Vec<Ordinal> = field.iter().collect();
+    let mut items = Vec::new();
+    if !plain.is_empty() {
+        items.push(format_cron_field::<T>(&plain));
+    }
+    items.extend(field.day_specs().iter().map(DaySpec::to_string));
+    items.join(",")
+}
+
+#[cfg(test)]
 fn schedule(i: &mut &str) -> winnow::Result<ScheduleFields> {
-    alt((shorthand, longhand)).parse_next(i)
+    schedule_with(&ScheduleParser::new())
+        .map(|(fields, _)| fields)
+        .parse_next(i)
 }
 
 #[cfg(test)]
@@ -341,20 +1525,23 @@ mod test {
     fn test_nom_valid_number_list() {
         let expression = "1,2";
         field.parse(expression).unwrap();
-        field_with_any.parse(expression).unwrap();
+        field_days_of_month.parse(expression).unwrap();
+        field_days_of_week.parse(expression).unwrap();
     }
 
     #[test]
     fn test_nom_invalid_number_list() {
         let expression = ",1,2";
         assert!(field.parse(expression).is_err());
-        assert!(field_with_any.parse(expression).is_err());
+        assert!(field_days_of_month.parse(expression).is_err());
+        assert!(field_days_of_week.parse(expression).is_err());
     }
 
     #[test]
     fn test_nom_field_with_any_valid_any() {
         let expression = "?";
-        field_with_any.parse(expression).unwrap();
+        field_days_of_month.parse(expression).unwrap();
+        field_days_of_week.parse(expression).unwrap();
     }
 
     #[test]
@@ -555,6 +1742,228 @@ mod test {
         assert!(schedule.parse(expression).is_err());
     }
 
+    #[test]
+    fn test_nom_valid_last_of_month() {
+        for expression in ["6L", "FRIL", "friL", "FRIDAYL", "6L-1", "FRIL-1"] {
+            last_of_month.parse(expression).unwrap();
+        }
+        assert_eq!(
+            last_of_month.parse("FRIL-1").unwrap(),
+            RootSpecifier::LastOfMonth(PointValue::Named("FRI".to_owned()), 1)
+        );
+    }
+
+    #[test]
+    fn test_nom_invalid_last_of_month() {
+        for expression in ["L", "FRI", "6", "L-1", "FRIL-", "6L/2"] {
+            assert!(last_of_month.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_days_of_week_last_of_month() {
+        for expression in ["* * * ? * FRIL", "* * * ? * 6L-1", "* * * ? * MON,FRIL-2"] {
+            schedule.parse(expression).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_nom_invalid_days_of_week_last_of_month() {
+        for expression in [
+            "* * * ? * FRIL-5",
+            "* * * ? * 8L",
+            "* * * ? * TURTLEL",
+            "FRIL * * ? * *",
+        ] {
+            assert!(schedule.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_nth_of_month() {
+        for expression in ["2#1", "MON#3", "monday#5"] {
+            nth_of_month.parse(expression).unwrap();
+        }
+        assert_eq!(
+            nth_of_month.parse("MON#3").unwrap(),
+            RootSpecifier::NthOfMonth(PointValue::Named("MON".to_owned()), 3)
+        );
+    }
+
+    #[test]
+    fn test_nom_invalid_nth_of_month() {
+        for expression in ["#1", "MON#", "MON#L", "2#1/2"] {
+            assert!(nth_of_month.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_days_of_week_nth_of_month_list() {
+        for expression in ["* * * ? * MON#1,MON#3,FRI#2", "* * * ? * 2#1,TUE,FRIL"] {
+            schedule.parse(expression).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_nom_invalid_days_of_week_nth_of_month() {
+        for expression in [
+            "* * * ? * MON#0",
+            "* * * ? * MON#6",
+            "* * * ? * 9#1",
+            "MON#1 * * ? * *",
+        ] {
+            assert!(schedule.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_days_of_month_specials() {
+        assert_eq!(
+            last_day_of_month.parse("L").unwrap(),
+            RootSpecifier::LastDayOfMonth(0)
+        );
+        assert_eq!(
+            last_day_of_month.parse("L-2").unwrap(),
+            RootSpecifier::LastDayOfMonth(2)
+        );
+        assert_eq!(
+            nearest_weekday.parse("15W").unwrap(),
+            RootSpecifier::NearestWeekday(15)
+        );
+        assert_eq!(
+            last_weekday_of_month.parse("LW").unwrap(),
+            RootSpecifier::LastWeekdayOfMonth
+        );
+    }
+
+    #[test]
+    fn test_nom_invalid_days_of_month_specials() {
+        assert!(last_day_of_month.parse("L-").is_err());
+        assert!(last_day_of_month.parse("2L").is_err());
+        assert!(nearest_weekday.parse("W").is_err());
+        assert!(nearest_weekday.parse("W15").is_err());
+    }
+
+    #[test]
+    fn test_nom_valid_days_of_month_mixed_specials() {
+        for expression in ["* * * 1,15,L * ?", "* * * 1W,L-2 * ?", "* * * LW,10-12 * ?"] {
+            schedule.parse(expression).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_nom_invalid_schedule_days_of_month_specials() {
+        for expression in [
+            "* * * L-31 * ?",
+            "* * * 32W * ?",
+            "* * * 0W * ?",
+            "* * * L/2 * ?",
+            "L * * * * ?",
+            "* * * * * L-2",
+        ] {
+            assert!(schedule.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_quarter() {
+        assert_eq!(quarter.parse("Q1").unwrap(), RootSpecifier::Quarter(1, 1));
+        assert_eq!(quarter.parse(" q4 ").unwrap(), RootSpecifier::Quarter(4, 1));
+        assert!(quarter.parse("Q").is_err());
+        assert_eq!(
+            field_months.parse("Q2,DEC").unwrap().specifiers,
+            vec![
+                RootSpecifier::Quarter(2, 1),
+                RootSpecifier::NamedPoint("DEC".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nom_valid_business_day() {
+        assert_eq!(
+            business_day.parse("3B").unwrap(),
+            RootSpecifier::BusinessDay(3)
+        );
+        assert_eq!(
+            business_day.parse(" 10b ").unwrap(),
+            RootSpecifier::BusinessDay(10)
+        );
+        assert!(business_day.parse("B").is_err());
+        assert_eq!(
+            field_days_of_month.parse("1,3B").unwrap().specifiers,
+            vec![
+                RootSpecifier::Specifier(Specifier::Point(1)),
+                RootSpecifier::BusinessDay(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nom_valid_random() {
+        assert_eq!(
+            random.parse("0~30").unwrap(),
+            RootSpecifier::Random(Some(0), Some(30), 0)
+        );
+        assert_eq!(
+            random.parse("~").unwrap(),
+            RootSpecifier::Random(None, None, 0)
+        );
+        assert_eq!(
+            random.parse("10~").unwrap(),
+            RootSpecifier::Random(Some(10), None, 0)
+        );
+        assert_eq!(
+            random.parse("R").unwrap(),
+            RootSpecifier::Random(None, None, 0)
+        );
+    }
+
+    #[test]
+    fn test_nom_invalid_random() {
+        for expression in ["0-30", "~~", "Rx", "0~30/2"] {
+            assert!(random.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_random_fields() {
+        for expression in [
+            "0~30 ~ R * * ?",
+            "0 ~ * * * *",
+            "0 0 0 1~28 * ?",
+            "0 0 0 ? * 2~6",
+        ] {
+            schedule.parse(expression).unwrap();
+        }
+        for expression in ["30~0 * * * * *", "0~60 * * * * *", "0 0 0 ? * 0~6"] {
+            assert!(schedule.parse(expression).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nom_valid_hashed() {
+        assert_eq!(
+            hashed.parse("H").unwrap(),
+            RootSpecifier::Hashed(None, None, 0)
+        );
+        assert_eq!(
+            hashed.parse("H(0-29)").unwrap(),
+            RootSpecifier::Hashed(Some((0, 29)), None, 0)
+        );
+        assert_eq!(
+            hashed.parse("h/15").unwrap(),
+            RootSpecifier::Hashed(None, Some(15), 0)
+        );
+        assert_eq!(
+            hashed.parse("H(10-40)/10").unwrap(),
+            RootSpecifier::Hashed(Some((10, 40)), Some(10), 0)
+        );
+        for expression in ["HH", "H(0-)", "H(0~29)", "H/", "Hourly"] {
+            assert!(hashed.parse(expression).is_err());
+        }
+    }
+
     #[test]
     fn test_nom_invalid_period_with_range_specifier() {
         let expression = "10-12/10-12 * * * * ?";
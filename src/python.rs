@@ -0,0 +1,189 @@
+use chrono::{DateTime, FixedOffset};
+use chrono_tz::Tz;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error::Error;
+use crate::occurrences::Occurrences;
+use crate::parsing::{Dialect, ScheduleParser};
+use crate::schedule::Schedule;
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+/// A parsed cron expression.
+///
+/// Times are computed in the timezone of the given datetime, which must be aware. With a
+/// `zoneinfo.ZoneInfo` timezone, daylight saving time is handled as for a `chrono_tz::Tz`.
+#[pyclass(name = "Schedule", module = "cron", frozen)]
+struct PySchedule {
+    schedule: Schedule,
+}
+
+#[pymethods]
+impl PySchedule {
+    #[new]
+    #[pyo3(signature = (expression, dialect = "quartz"))]
+    fn new(expression: &str, dialect: &str) -> PyResult<PySchedule> {
+        parse(expression, dialect)
+    }
+
+    /// The expression the schedule was parsed from.
+    #[getter]
+    fn source(&self) -> &str {
+        self.schedule.source()
+    }
+
+    /// The first time the schedule fires at after the given datetime, or None.
+    fn next_after(&self, after: AwareDateTime) -> Option<AwareDateTime> {
+        match after {
+            AwareDateTime::Zoned(after) => {
+                self.schedule.next_after(&after).map(AwareDateTime::Zoned)
+            }
+            AwareDateTime::Fixed(after) => {
+                self.schedule.next_after(&after).map(AwareDateTime::Fixed)
+            }
+        }
+    }
+
+    /// The last time the schedule fires at before the given datetime, or None.
+    fn prev_before(&self, before: AwareDateTime) -> Option<AwareDateTime> {
+        match before {
+            AwareDateTime::Zoned(before) => {
+                self.schedule.prev_before(&before).map(AwareDateTime::Zoned)
+            }
+            AwareDateTime::Fixed(before) => {
+                self.schedule.prev_before(&before).map(AwareDateTime::Fixed)
+            }
+        }
+    }
+
+    /// The expression along with the values each field resolves to.
+    fn describe(&self) -> String {
+        format!("{:#}", self.schedule)
+    }
+
+    fn __str__(&self) -> &str {
+        self.schedule.source()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Schedule({:?})", self.schedule.source())
+    }
+}
+
+// An aware datetime, in a `zoneinfo.ZoneInfo` timezone if it has one so that daylight saving
+// time is taken into account, and otherwise at its fixed offset.
+#[derive(FromPyObject, IntoPyObject)]
+enum AwareDateTime {
+    Zoned(DateTime<Tz>),
+    Fixed(DateTime<FixedOffset>),
+}
+
+/// Parses a cron expression in the given dialect: "quartz", "unix", "jenkins", "aws", "robfig"
+/// or "legacy".
+#[pyfunction]
+#[pyo3(signature = (expression, dialect = "quartz"))]
+fn parse(expression: &str, dialect: &str) -> PyResult<PySchedule> {
+    let dialect = match dialect.to_ascii_lowercase().as_str() {
+        "quartz" => Dialect::Quartz,
+        "unix" => Dialect::Unix,
+        "jenkins" => Dialect::Jenkins,
+        "aws" => Dialect::Aws,
+        "robfig" => Dialect::Robfig,
+        "legacy" => Dialect::Legacy,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown dialect: '{dialect}'."
+            )))
+        }
+    };
+    let schedule = ScheduleParser::new().dialect(dialect).parse(expression)?;
+    Ok(PySchedule { schedule })
+}
+
+/// The `cron` Python module.
+#[pymodule]
+fn cron(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySchedule>()?;
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    // Runs Python code with the module imported as `cron`, failing the test on exceptions.
+    fn run(code: &std::ffi::CStr) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = pyo3::wrap_pymodule!(super::cron)(py);
+            let globals = PyDict::new(py);
+            globals.set_item("cron", module).unwrap();
+            py.run(code, Some(&globals), None)
+                .unwrap_or_else(|error| panic!("{error}"));
+        });
+    }
+
+    #[test]
+    fn test_python_queries() {
+        run(c"
+from datetime import datetime, timedelta, timezone
+from zoneinfo import ZoneInfo
+
+schedule = cron.parse('0 30 9 * * Mon-Fri')
+assert schedule.source == '0 30 9 * * Mon-Fri'
+assert str(schedule) == schedule.source
+assert repr(schedule) == 'Schedule(\"0 30 9 * * Mon-Fri\")', repr(schedule)
+friday = datetime(2026, 10, 16, 12, tzinfo=timezone.utc)
+assert schedule.next_after(friday) == datetime(2026, 10, 19, 9, 30, tzinfo=timezone.utc), schedule.next_after(friday)
+assert schedule.prev_before(friday) == datetime(2026, 10, 16, 9, 30, tzinfo=timezone.utc)
+assert 'Mon-Fri' in schedule.describe(), schedule.describe()
+
+# Daylight saving time is followed in zoneinfo timezones.
+berlin = ZoneInfo('Europe/Berlin')
+daily = cron.Schedule('0 0 12 * * *')
+after = datetime(2026, 10, 24, 13, tzinfo=berlin)
+upcoming = daily.next_after(after)
+assert upcoming == datetime(2026, 10, 25, 12, tzinfo=berlin), upcoming
+utc = upcoming.astimezone(timezone.utc) - after.astimezone(timezone.utc)
+assert utc == timedelta(hours=24), utc
+
+assert cron.parse('0 0 0 1 1 ? 2020').next_after(friday) is None
+");
+    }
+
+    #[test]
+    fn test_python_dialects() {
+        run(c"
+from datetime import datetime, timezone
+
+friday = datetime(2026, 10, 16, 12, tzinfo=timezone.utc)
+sunday = datetime(2026, 10, 18, 9, 30, tzinfo=timezone.utc)
+for dialect, expression in [
+    ('quartz', '0 30 9 ? * SUN'),
+    ('unix', '30 9 * * 0'),
+    ('jenkins', '30 9 * * 0'),
+    ('aws', '30 9 ? * 1 *'),
+    ('robfig', '0 30 9 * * 0'),
+    ('legacy', '0 30 9 * * 1'),
+    ('LEGACY', '0 30 9 * * 1'),
+]:
+    assert cron.parse(expression, dialect).next_after(friday) == sunday, dialect
+    assert cron.Schedule(expression, dialect=dialect).source == expression
+
+for expression, dialect in [('0 30 9 * * *', 'cobol'), ('0 30 9 * *', 'quartz')]:
+    try:
+        cron.parse(expression, dialect)
+    except ValueError:
+        pass
+    else:
+        raise AssertionError(expression)
+");
+    }
+}
@@ -22,20 +22,22 @@ impl<Z> NextAfterQuery<Z>
 where
     Z: TimeZone,
 {
-    pub fn from(after: &DateTime<Z>) -> NextAfterQuery<Z> {
-        NextAfterQuery {
-            initial_datetime: after.clone() + Duration::seconds(1),
+    // Returns `None` if there is no second after the given instant within chrono's range.
+    pub fn from(after: &DateTime<Z>) -> Option<NextAfterQuery<Z>> {
+        Some(NextAfterQuery {
+            initial_datetime: after.clone().checked_add_signed(Duration::seconds(1))?,
             first_month: true,
             first_day_of_month: true,
             first_hour: true,
             first_minute: true,
             first_second: true,
-        }
+        })
     }
 
     pub fn year_lower_bound(&self) -> Ordinal {
-        // Unlike the other units, years will never wrap around.
-        self.initial_datetime.year() as u32
+        // Unlike the other units, years will never wrap around. Years before the common era come
+        // before any supported year.
+        self.initial_datetime.year().max(0) as u32
     }
 
     pub fn month_lower_bound(&mut self) -> Ordinal {
@@ -119,25 +121,27 @@ impl<Z> PrevFromQuery<Z>
 where
     Z: TimeZone,
 {
-    pub fn from(before: &DateTime<Z>) -> PrevFromQuery<Z> {
+    // Returns `None` if there is no second before the given instant within chrono's range.
+    pub fn from(before: &DateTime<Z>) -> Option<PrevFromQuery<Z>> {
         let initial_datetime = if before.timestamp_subsec_nanos() > 0 {
             before.clone()
         } else {
-            before.clone() - Duration::seconds(1)
+            before.clone().checked_sub_signed(Duration::seconds(1))?
         };
-        PrevFromQuery {
+        Some(PrevFromQuery {
             initial_datetime,
             first_month: true,
             first_day_of_month: true,
             first_hour: true,
             first_minute: true,
             first_second: true,
-        }
+        })
     }
 
     pub fn year_upper_bound(&self) -> Ordinal {
-        // Unlike the other units, years will never wrap around.
-        self.initial_datetime.year() as u32
+        // Unlike the other units, years will never wrap around. Years before the common era come
+        // before any supported year.
+        self.initial_datetime.year().max(0) as u32
     }
 
     pub fn month_upper_bound(&mut self) -> Ordinal {
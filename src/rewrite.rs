@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+use crate::export::format_list;
+use crate::ordinal::Ordinal;
+use crate::schedule::Schedule;
+use crate::time_unit::*;
+
+/// Why a [Suggestion] is simpler than the field it rewrites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RewriteReason {
+    /// The field includes every value, e.g. `0-59` in the minutes, so it can be `*`.
+    Wildcard,
+    /// The values repeat at a fixed step until the end of the field, e.g. `0,30` is `*/30`.
+    Step,
+    /// Consecutive values can be written as ranges, e.g. `1,2,3,4,5` is `1-5`.
+    Range,
+}
+
+/// A simpler way to write a field of a [Schedule], which includes the same values, as returned
+/// by [Schedule::suggestions].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The name of the field to rewrite, e.g. `Minutes`.
+    pub field: Cow<'static, str>,
+    /// The field as written.
+    pub original: String,
+    /// The simpler way to write the field.
+    pub replacement: String,
+    /// Why the replacement is simpler.
+    pub reason: RewriteReason,
+    /// The whole expression, with the field rewritten.
+    pub expression: String,
+}
+
+pub(crate) fn suggestions(schedule: &Schedule) -> Vec<Suggestion> {
+    let Some(syntax) = schedule.syntax() else {
+        return Vec::new();
+    };
+    let fields = schedule.fields();
+    let mut suggestions = Vec::new();
+    let mut suggest = |position: u64, name: Cow<'static, str>, values: Vec<Ordinal>, bounds| {
+        let original = match position {
+            0 => syntax.seconds.text(),
+            1 => syntax.minutes.text(),
+            2 => syntax.hours.text(),
+            3 => syntax.days_of_month.text(),
+            4 => syntax.months.text(),
+            5 => syntax.days_of_week.text(),
+            _ => syntax.years.text(),
+        };
+        // Names, specials and random or hashed values are left as written.
+        if original.is_empty() || !original.chars().all(|c| "0123456789,-/*".contains(c)) {
+            return;
+        }
+        let (replacement, reason) = simplest(&values, bounds);
+        let items = |text: &str| text.split(',').count();
+        let is_simpler = items(&replacement) < items(original)
+            || (items(&replacement) == items(original) && replacement.len() < original.len());
+        if is_simpler {
+            suggestions.push(Suggestion {
+                field: name,
+                original: original.to_string(),
+                expression: schedule.source_from(&syntax.replacing_text(position, &replacement)),
+                replacement,
+                reason,
+            });
+        }
+    };
+    suggest(
+        0,
+        Seconds::name(),
+        fields.seconds.iter().collect(),
+        bounds::<Seconds>(),
+    );
+    suggest(
+        1,
+        Minutes::name(),
+        fields.minutes.iter().collect(),
+        bounds::<Minutes>(),
+    );
+    suggest(
+        2,
+        Hours::name(),
+        fields.hours.iter().collect(),
+        bounds::<Hours>(),
+    );
+    suggest(
+        3,
+        DaysOfMonth::name(),
+        fields.days_of_month.iter().collect(),
+        bounds::<DaysOfMonth>(),
+    );
+    suggest(
+        4,
+        Months::name(),
+        fields.months.iter().collect(),
+        bounds::<Months>(),
+    );
+    let days_of_week: Vec<Ordinal> = fields.days_of_week.iter().collect();
+    if syntax.dialect.sunday_is_zero() {
+        // Sunday is 0 rather than 1, and can also be written 7.
+        let days_of_week = days_of_week.iter().map(|day| day - 1).collect();
+        suggest(5, DaysOfWeek::name(), days_of_week, (0, 6));
+    } else {
+        suggest(5, DaysOfWeek::name(), days_of_week, bounds::<DaysOfWeek>());
+    }
+    suggest(
+        6,
+        Years::name(),
+        fields.years.iter().collect(),
+        bounds::<Years>(),
+    );
+    suggestions
+}
+
+fn bounds<T: TimeUnitField>() -> (Ordinal, Ordinal) {
+    (T::inclusive_min(), T::inclusive_max())
+}
+
+// The simplest way to write the values of a field within the given bounds.
+fn simplest(values: &[Ordinal], (min, max): (Ordinal, Ordinal)) -> (String, RewriteReason) {
+    if values.len() as Ordinal == max - min + 1 {
+        return ("*".to_string(), RewriteReason::Wildcard);
+    }
+    if let [first, second, ..] = values {
+        let step = second - first;
+        let is_progression = values.windows(2).all(|pair| pair[1] - pair[0] == step);
+        if is_progression && step > 1 && first - min < step && values[values.len() - 1] + step > max
+        {
+            let start = if *first == min {
+                "*".to_string()
+            } else {
+                first.to_string()
+            };
+            return (format!("{start}/{step}"), RewriteReason::Step);
+        }
+    }
+    let list = format_list(values, "-", |ordinal| ordinal.to_string());
+    (list, RewriteReason::Range)
+}
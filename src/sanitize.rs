@@ -0,0 +1,124 @@
+/// What [sanitize] did to a character of an expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CorrectionKind {
+    /// A non-breaking space was replaced with a space.
+    NonBreakingSpace,
+    /// Any other non-ASCII whitespace, e.g. an ideographic space, was replaced with a space.
+    Whitespace,
+    /// A character which isn't displayed, e.g. a zero width space, was removed.
+    Invisible,
+    /// A full-width form of an ASCII character, e.g. `１` or `＊`, was replaced with it.
+    FullWidth,
+    /// A typographic dash, e.g. the en dash of `MON–FRI`, was replaced with a hyphen.
+    Dash,
+    /// A curly quote was replaced with a straight one.
+    SmartQuote,
+    /// A quote enclosing the whole expression was removed.
+    EnclosingQuote,
+}
+
+/// A character [sanitize] replaced or removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Correction {
+    /// What was done to the character.
+    pub kind: CorrectionKind,
+    /// The byte offset of the character in the expression as given.
+    pub offset: usize,
+    /// The character as given.
+    pub found: char,
+}
+
+/// An expression cleaned up by [sanitize], along with what was done to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sanitized {
+    /// The expression, ready to be parsed.
+    pub expression: String,
+    /// The corrections made, in the order of the characters they apply to.
+    pub corrections: Vec<Correction>,
+}
+
+/// Cleans up the characters which copying an expression from a document or a chat commonly
+/// brings along, e.g. non-breaking spaces, full-width digits or the quotes around it, so that it
+/// can be parsed. Expressions without any of them are returned unchanged.
+///
+/// Only the characters are normalized: a sanitized expression may still be invalid.
+/// # Example
+/// ```
+/// use cron::{sanitize, CorrectionKind, Schedule};
+/// use std::str::FromStr;
+///
+/// // Quoted, with a non-breaking space, a full-width 9 and an en dash.
+/// let sanitized = sanitize("\u{201c}0\u{a0}0 \u{ff19} * * MON\u{2013}FRI\u{201d}");
+/// assert_eq!("0 0 9 * * MON-FRI", sanitized.expression);
+/// let kinds: Vec<_> = sanitized.corrections.iter().map(|c| c.kind).collect();
+/// assert_eq!(
+///     vec![
+///         CorrectionKind::EnclosingQuote,
+///         CorrectionKind::NonBreakingSpace,
+///         CorrectionKind::FullWidth,
+///         CorrectionKind::Dash,
+///         CorrectionKind::EnclosingQuote,
+///     ],
+///     kinds
+/// );
+/// assert!(Schedule::from_str(&sanitized.expression).is_ok());
+/// ```
+pub fn sanitize(expression: &str) -> Sanitized {
+    let mut characters: Vec<(usize, char, Option<char>, Option<CorrectionKind>)> = expression
+        .char_indices()
+        .map(|(offset, found)| {
+            let (replacement, kind) = normalize(found);
+            (offset, found, replacement, kind)
+        })
+        .collect();
+
+    // The whole expression is only enclosed in quotes if nothing but whitespace is around them.
+    let is_visible = |&(_, _, replacement, _): &(_, _, Option<char>, _)| {
+        replacement.is_some_and(|c| !c.is_whitespace())
+    };
+    let first = characters.iter().position(is_visible);
+    let last = characters.iter().rposition(is_visible);
+    if let (Some(first), Some(last)) = (first, last) {
+        let quote = characters[first].2;
+        if first < last && matches!(quote, Some('"' | '\'' | '`')) && characters[last].2 == quote {
+            for index in [first, last] {
+                characters[index].2 = None;
+                characters[index].3 = Some(CorrectionKind::EnclosingQuote);
+            }
+        }
+    }
+
+    Sanitized {
+        expression: characters.iter().filter_map(|c| c.2).collect(),
+        corrections: characters
+            .iter()
+            .filter_map(|&(offset, found, _, kind)| {
+                Some(Correction {
+                    kind: kind?,
+                    offset,
+                    found,
+                })
+            })
+            .collect(),
+    }
+}
+
+// The character to put in place of the given one, if any, and why it was changed.
+fn normalize(c: char) -> (Option<char>, Option<CorrectionKind>) {
+    match c {
+        '\u{a0}' | '\u{2007}' | '\u{202f}' => (Some(' '), Some(CorrectionKind::NonBreakingSpace)),
+        c if c.is_whitespace() && !c.is_ascii() => (Some(' '), Some(CorrectionKind::Whitespace)),
+        '\u{ad}' | '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => {
+            (None, Some(CorrectionKind::Invisible))
+        }
+        '\u{ff01}'..='\u{ff5e}' => (
+            char::from_u32(c as u32 - 0xfee0),
+            Some(CorrectionKind::FullWidth),
+        ),
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => (Some('-'), Some(CorrectionKind::Dash)),
+        '\u{2018}'..='\u{201b}' => (Some('\''), Some(CorrectionKind::SmartQuote)),
+        '\u{201c}'..='\u{201f}' => (Some('"'), Some(CorrectionKind::SmartQuote)),
+        c => (Some(c), None),
+    }
+}
@@ -1,7 +1,14 @@
 use chrono::offset::{LocalResult, TimeZone};
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Month, NaiveDate, NaiveDateTime, Timelike, Utc,
+    Weekday,
+};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::ops::Bound::{Included, Unbounded};
+use std::ops::{Range, RangeInclusive};
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "serde")]
 use core::fmt;
@@ -11,57 +18,718 @@ use serde::{
     Deserialize, Serialize, Serializer,
 };
 
+use crate::binary::{invalid, Decoder, Encoder};
+use crate::calendar::{self, CalendarSystem};
+use crate::checkpoint::Checkpoint;
+use crate::compiled::CompiledSchedule;
+use crate::counting;
+use crate::description::ScheduleDescription;
+use crate::diff::{FieldDiff, ScheduleDiff};
+use crate::error::{Error, ErrorCode, ErrorKind};
+use crate::every_nth::EveryNth;
+use crate::exclusion::{Calendar, CalendarSystemDays};
+use crate::export::{self, Translation};
+use crate::field_values::{self, FieldValues};
+use crate::frequency::Frequency;
+use crate::gaps::Gaps;
+use crate::histogram::Histogram;
+use crate::ics;
+use crate::inference;
+use crate::lint::{self, LintWarning};
+use crate::occurrences::Occurrences;
+use crate::offset::OffsetSchedule;
 use crate::ordinal::*;
+use crate::parsing;
 use crate::queries::*;
+use crate::rewrite::{self, Suggestion};
+use crate::syntax::ScheduleSyntax;
+#[cfg(feature = "tokio")]
+use crate::tick::{self, Ticks};
 use crate::time_unit::*;
+use crate::trace::{self, Trace};
+use crate::window::{self, Window};
 
 impl From<Schedule> for String {
     fn from(schedule: Schedule) -> String {
-        schedule.source
+        schedule.source.to_string()
     }
 }
 
-#[derive(Clone, Debug, Eq)]
+// A parsed schedule never changes, so its parts are shared between clones: handing a copy to
+// every worker is O(1) rather than a deep copy of each field's ordinal set.
+#[derive(Clone, Debug)]
 pub struct Schedule {
-    source: String,
-    fields: ScheduleFields,
+    source: Arc<str>,
+    fields: Arc<ScheduleFields>,
+    calendars: Arc<Vec<Arc<dyn Calendar>>>,
+    blackouts: Arc<Vec<Range<DateTime<Utc>>>>,
+    syntax: Option<Arc<ScheduleSyntax>>,
+    not_before: Option<DateTime<Utc>>,
+    not_after: Option<DateTime<Utc>>,
+    calendar_system: Option<Arc<CalendarSystemDays>>,
+    // The hash identifying the schedule in checkpoints and to workers, computed when first used.
+    hash: OnceLock<u64>,
 }
 
 impl Schedule {
     pub(crate) fn new(source: String, fields: ScheduleFields) -> Schedule {
-        Schedule { source, fields }
+        Schedule {
+            source: source.into(),
+            fields: Arc::new(fields),
+            calendars: Arc::default(),
+            blackouts: Arc::default(),
+            syntax: None,
+            not_before: None,
+            not_after: None,
+            calendar_system: None,
+            hash: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn with_syntax(mut self, syntax: Option<ScheduleSyntax>) -> Schedule {
+        self.syntax = syntax.map(Arc::new);
+        self
+    }
+
+    pub(crate) fn fields(&self) -> &ScheduleFields {
+        &self.fields
+    }
+
+    // The fields searches for occurrences go through, which for a schedule evaluated against
+    // another calendar system include every day of the Gregorian calendar.
+    pub(crate) fn search_fields(&self) -> &ScheduleFields {
+        match &self.calendar_system {
+            Some(days) => &days.fields,
+            None => &self.fields,
+        }
+    }
+
+    // A schedule with new fields, keeping the calendars, blackouts, bounds and calendar system
+    // attached to this one.
+    pub(crate) fn with_fields(
+        &self,
+        source: String,
+        fields: ScheduleFields,
+        syntax: ScheduleSyntax,
+    ) -> Result<Schedule, Error> {
+        let mut fields = fields;
+        if !self.calendars.is_empty() {
+            fields.days_of_month.skip_holidays();
+        }
+        let calendar_system = match &self.calendar_system {
+            Some(days) => Some(Arc::new(CalendarSystemDays::new(
+                Arc::clone(&days.system),
+                &fields,
+            )?)),
+            None => None,
+        };
+        Ok(Schedule {
+            source: source.into(),
+            fields: Arc::new(fields),
+            calendars: Arc::clone(&self.calendars),
+            blackouts: Arc::clone(&self.blackouts),
+            syntax: Some(Arc::new(syntax)),
+            not_before: self.not_before,
+            not_after: self.not_after,
+            calendar_system,
+            hash: OnceLock::new(),
+        })
+    }
+
+    // The source of a schedule whose fields are written as in `syntax`, keeping the comment of
+    // this one.
+    pub(crate) fn source_from(&self, syntax: &ScheduleSyntax) -> String {
+        match self.comment() {
+            Some(comment) => format!("{} # {comment}", syntax.expression()),
+            None => syntax.expression(),
+        }
+    }
+
+    /// Describes how each field of the expression was written, e.g. whether the minutes were
+    /// given as a step, a range or a list, rather than the values they resolved to.
+    ///
+    /// Returns `None` for shorthands like `@daily` and for schedules decoded with
+    /// [Schedule::from_bytes], which don't keep the syntax of their fields.
+    pub fn syntax(&self) -> Option<&ScheduleSyntax> {
+        self.syntax.as_deref().filter(|syntax| !syntax.shorthand)
+    }
+
+    // The syntax kept when parsing, including that of shorthands, which only carries the options
+    // of the parser.
+    pub(crate) fn parsed_syntax(&self) -> Option<&ScheduleSyntax> {
+        self.syntax.as_deref()
+    }
+
+    /// Attaches a [Calendar] to this [Schedule], so that it never fires at the times the calendar
+    /// excludes. Business days, e.g. `3B`, don't count the days the calendar excludes at the time
+    /// of the firing, so that holidays push them back.
+    ///
+    /// Calendars are not part of the source expression: they are neither compared by
+    /// [PartialEq] nor serialized.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::{AnnualCalendar, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// // The third business day of the month, after New Year's Day.
+    /// let schedule = Schedule::from_str("0 0 9 3B * ?")
+    ///     .unwrap()
+    ///     .with_calendar(AnnualCalendar::new().excluding(1, 1));
+    /// let start = Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap();
+    /// // The 1st of January 2027 is a Friday.
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2027, 1, 6, 9, 0, 0).unwrap()),
+    ///     schedule.after(&start).next()
+    /// );
+    /// ```
+    pub fn with_calendar<C>(mut self, calendar: C) -> Schedule
+    where
+        C: Calendar + 'static,
+    {
+        Arc::make_mut(&mut self.calendars).push(Arc::new(calendar));
+        if self.fields.days_of_month.business_days().next().is_some() {
+            Arc::make_mut(&mut self.fields)
+                .days_of_month
+                .skip_holidays();
+        }
+        self
+    }
+
+    /// Evaluates the day of month and month fields of this [Schedule] against another
+    /// [CalendarSystem], such as the Hijri calendar, rather than the Gregorian one, e.g. so that
+    /// `0 0 9 1 9 ?` fires on the first day of Ramadan. The other fields keep their meaning, and
+    /// the years are still Gregorian years.
+    ///
+    /// `L` and `L-2` resolve against the length of the month in the calendar system. Fails with
+    /// [ErrorCode::InvalidSpecial](crate::error::ErrorCode::InvalidSpecial) if the schedule
+    /// uses `W`, `LW`, `B`, `#` or `L` after a day of the week, which depend on the Gregorian
+    /// month.
+    ///
+    /// The fields keep the values they were written with, so that e.g. [Schedule::months] still
+    /// lists the months of the calendar system. Like calendars, the calendar system is not part
+    /// of the source expression and isn't compared by [PartialEq], while [Schedule::to_bytes]
+    /// fails rather than encode the schedule without it. Days are checked one at a time, so
+    /// schedules firing many times a day are slower to search.
+    pub fn in_calendar_system<S>(&self, system: S) -> Result<Schedule, Error>
+    where
+        S: CalendarSystem + 'static,
+    {
+        let days = CalendarSystemDays::new(Arc::new(system), &self.fields)?;
+        let mut schedule = self.clone();
+        schedule.calendar_system = Some(Arc::new(days));
+        schedule.hash = OnceLock::new();
+        Ok(schedule)
+    }
+
+    /// Limits this [Schedule] to the times at or after `not_before`, like the start time of a
+    /// Quartz trigger: the schedule's iterators, [Schedule::includes] and the queries built on
+    /// them leave out every earlier time.
+    ///
+    /// Like calendars, bounds are neither compared by [PartialEq] nor serialized. Queries on wall
+    /// clock times without a timezone, like [Schedule::after_naive], aren't limited by them.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2026, 11, 30, 0, 0, 0).unwrap();
+    /// let schedule = Schedule::from_str("0 0 9 ? * MON")
+    ///     .unwrap()
+    ///     .with_not_before(&start)
+    ///     .with_not_after(&end);
+    /// let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// let mondays: Vec<_> = schedule.after(&now).map(|monday| monday.day()).collect();
+    /// # use chrono::Datelike;
+    /// assert_eq!(vec![2, 9, 16, 23], mondays);
+    /// assert!(!schedule.includes(Utc.with_ymd_and_hms(2026, 10, 26, 9, 0, 0).unwrap()));
+    /// ```
+    pub fn with_not_before<Z>(mut self, not_before: &DateTime<Z>) -> Schedule
+    where
+        Z: TimeZone,
+    {
+        self.not_before = Some(not_before.with_timezone(&Utc));
+        self
+    }
+
+    /// Limits this [Schedule] to the times at or before `not_after`, like the end time of a
+    /// Quartz trigger. See [Schedule::with_not_before].
+    pub fn with_not_after<Z>(mut self, not_after: &DateTime<Z>) -> Schedule
+    where
+        Z: TimeZone,
+    {
+        self.not_after = Some(not_after.with_timezone(&Utc));
+        self
+    }
+
+    /// The earliest time this [Schedule] may fire at, as set with [Schedule::with_not_before].
+    pub fn not_before(&self) -> Option<DateTime<Utc>> {
+        self.not_before
+    }
+
+    /// The latest time this [Schedule] may fire at, as set with [Schedule::with_not_after].
+    pub fn not_after(&self) -> Option<DateTime<Utc>> {
+        self.not_after
+    }
+
+    /// Keeps this [Schedule] from firing from the start of `blackout` until its end, which is
+    /// excluded, e.g. during a maintenance freeze known by its dates. Searches jump over a
+    /// blackout at once rather than through every occurrence within it.
+    ///
+    /// Like calendars, blackouts are neither compared by [PartialEq] nor serialized, and don't
+    /// apply to queries on wall clock times without a timezone.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let freeze_start = Utc.with_ymd_and_hms(2026, 12, 20, 0, 0, 0).unwrap();
+    /// let freeze_end = Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 0).unwrap();
+    /// let deploys = Schedule::from_str("0 * * * * *")
+    ///     .unwrap()
+    ///     .with_blackout(freeze_start..freeze_end);
+    /// let now = Utc.with_ymd_and_hms(2026, 12, 19, 23, 59, 0).unwrap();
+    /// assert_eq!(Some(freeze_end), deploys.after(&now).next());
+    /// assert!(!deploys.includes(Utc.with_ymd_and_hms(2026, 12, 25, 12, 0, 0).unwrap()));
+    /// ```
+    pub fn with_blackout<Z>(mut self, blackout: Range<DateTime<Z>>) -> Schedule
+    where
+        Z: TimeZone,
+    {
+        let blackout = blackout.start.with_timezone(&Utc)..blackout.end.with_timezone(&Utc);
+        Arc::make_mut(&mut self.blackouts).push(blackout);
+        self
+    }
+
+    /// The ranges of time attached with [Schedule::with_blackout].
+    pub fn blackouts(&self) -> &[Range<DateTime<Utc>>] {
+        &self.blackouts
+    }
+
+    // A blackout the instant falls within, if any.
+    pub(crate) fn blackout_at<Z>(&self, date_time: &DateTime<Z>) -> Option<Range<DateTime<Utc>>>
+    where
+        Z: TimeZone,
+    {
+        self.blackouts
+            .iter()
+            .find(|blackout| *date_time >= blackout.start && *date_time < blackout.end)
+            .cloned()
+    }
+
+    // The blackout to skip an occurrence over, if every instant of it falls within one, which for
+    // an ambiguous local time is that of the instant furthest along in the search's direction.
+    // An occurrence with an instant outside of blackouts is kept and clipped instead.
+    pub(crate) fn blackout_over<Z>(
+        &self,
+        occurrence: &LocalResult<DateTime<Z>>,
+        forward: bool,
+    ) -> Option<Range<DateTime<Utc>>>
+    where
+        Z: TimeZone,
+    {
+        match occurrence {
+            LocalResult::Single(single) => self.blackout_at(single),
+            LocalResult::Ambiguous(earliest, latest) => match forward {
+                true => self.blackout_at(earliest).and(self.blackout_at(latest)),
+                false => self.blackout_at(latest).and(self.blackout_at(earliest)),
+            },
+            LocalResult::None => None,
+        }
+    }
+
+    // Whether only the fields decide when the schedule fires, without calendars, blackouts,
+    // bounds or another calendar system.
+    pub(crate) fn is_unconstrained(&self) -> bool {
+        self.calendars.is_empty()
+            && self.calendar_system.is_none()
+            && self.blackouts.is_empty()
+            && self.not_before.is_none()
+            && self.not_after.is_none()
+    }
+
+    // Whether the schedule may fire at the instant, as far as its bounds and blackouts go.
+    pub(crate) fn is_active_at<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.is_within_bounds(date_time) && self.blackout_at(date_time).is_none()
+    }
+
+    fn is_within_bounds<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.not_before
+            .is_none_or(|not_before| *date_time >= not_before)
+            && self
+                .not_after
+                .is_none_or(|not_after| *date_time <= not_after)
+    }
+
+    // Drops the instants of an occurrence at which the schedule isn't active.
+    pub(crate) fn clip<Z>(&self, occurrence: LocalResult<DateTime<Z>>) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match occurrence {
+            LocalResult::Single(single) if self.is_active_at(&single) => {
+                LocalResult::Single(single)
+            }
+            LocalResult::Ambiguous(earliest, latest) => {
+                match (self.is_active_at(&earliest), self.is_active_at(&latest)) {
+                    (true, true) => LocalResult::Ambiguous(earliest, latest),
+                    (true, false) => LocalResult::Single(earliest),
+                    (false, true) => LocalResult::Single(latest),
+                    (false, false) => LocalResult::None,
+                }
+            }
+            _ => LocalResult::None,
+        }
+    }
+
+    /// Encodes this [Schedule] and its resolved fields into a compact, versioned binary form
+    /// which [Schedule::from_bytes] can load back without parsing the expression again.
+    ///
+    /// Attached calendars are not encoded. Fails with [ErrorCode::Encode] for schedules
+    /// evaluated against another calendar system with [Schedule::in_calendar_system], which
+    /// would otherwise decode to a schedule firing on other days.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.calendar_system.is_some() {
+            return Err(ErrorKind::Encode(format!(
+                "The schedule '{}' is evaluated against another calendar system, which can't be \
+                 encoded.",
+                self.source
+            ))
+            .into());
+        }
+        let mut encoder = Encoder::new();
+        encoder.write_str(&self.source);
+        self.fields.encode(&mut encoder);
+        Ok(encoder.finish())
+    }
+
+    /// Provides an iterator carrying on from a [Checkpoint] taken from an iterator over this
+    /// schedule, e.g. by a previous run of the process.
+    ///
+    /// Fails with [ErrorCode::CheckpointMismatch] if the checkpoint was taken from a schedule with
+    /// another expression or other fields. Calendars, blackouts and bounds aren't part of the
+    /// check.
+    pub fn resume<Z>(
+        &self,
+        checkpoint: &Checkpoint,
+        timezone: &Z,
+    ) -> Result<ScheduleIterator<'_, Z>, Error>
+    where
+        Z: TimeZone,
+    {
+        if checkpoint.schedule_hash() != self.checkpoint_hash() {
+            return Err(ErrorKind::Decode(format!(
+                "The checkpoint was taken from another schedule than '{}'.",
+                self.source
+            ))
+            .with_code(ErrorCode::CheckpointMismatch));
+        }
+        Ok(self.after(&checkpoint.instant().with_timezone(timezone)))
+    }
+
+    // Identifies the schedule in checkpoints and to workers, through its source, its fields written
+    // out as text and the calendar system it's evaluated against, rather than through its binary
+    // encoding, whose layout changes between releases.
+    fn checkpoint_hash(&self) -> u64 {
+        *self.hash.get_or_init(|| {
+            let mut text = format!("{}\n{}", self.source, self.fields.canonical_form());
+            if let Some(days) = &self.calendar_system {
+                text.push_str(&format!("\n{:?}", days.system));
+            }
+            parsing::fnv1a(text.as_bytes())
+        })
+    }
+
+    /// Returns which of `workers` workers, numbered from `0`, owns the given occurrence, so that
+    /// several consumers of the same schedule can each act on a share of the occurrences without
+    /// coordinating, and no occurrence is acted on twice.
+    ///
+    /// The owner only depends on the schedule's expression and fields, the occurrence's instant
+    /// and the number of workers, so every process agrees on it, across releases too. Occurrences
+    /// are spread evenly over the workers, and when workers are added, occurrences only move to
+    /// the new workers. Returns `None` if `workers` is `0`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 * * * *").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// // The second of three workers only acts on its own occurrences.
+    /// let mine: Vec<_> = schedule
+    ///     .after(&start)
+    ///     .take(12)
+    ///     .filter(|occurrence| schedule.is_owned_by(occurrence, 1, 3))
+    ///     .collect();
+    /// assert!(mine.iter().all(|occurrence| schedule.owner_of(occurrence, 3) == Some(1)));
+    /// assert_eq!(None, schedule.owner_of(&start, 0));
+    /// ```
+    pub fn owner_of<Z>(&self, occurrence: &DateTime<Z>, workers: u32) -> Option<u32>
+    where
+        Z: TimeZone,
+    {
+        if workers == 0 {
+            return None;
+        }
+        let instant = parsing::mix(occurrence.timestamp() as u64)
+            ^ u64::from(occurrence.timestamp_subsec_nanos());
+        let hash = parsing::mix(self.checkpoint_hash() ^ instant);
+        Some(parsing::jump_hash(hash, workers))
+    }
+
+    /// Returns true if the given worker, numbered from `0`, owns the given occurrence among
+    /// `workers` workers, as decided by [Schedule::owner_of], which no worker does if there are
+    /// none.
+    pub fn is_owned_by<Z>(&self, occurrence: &DateTime<Z>, worker: u32, workers: u32) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.owner_of(occurrence, workers) == Some(worker)
+    }
+
+    /// Decodes a [Schedule] previously encoded with [Schedule::to_bytes].
+    ///
+    /// Fails if the bytes are malformed or were produced by an incompatible version of the format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Schedule, Error> {
+        let mut decoder = Decoder::new(bytes)?;
+        let source = decoder.read_str()?;
+        let fields = ScheduleFields::decode(&mut decoder)?;
+        decoder.finish()?;
+        Ok(Schedule::new(source, fields))
+    }
+
+    pub(crate) fn is_excluded(&self, datetime: &NaiveDateTime) -> bool {
+        self.is_excluded_by_calendars(datetime)
+            || self.misses_business_day(datetime)
+            || self
+                .calendar_system
+                .as_ref()
+                .is_some_and(|days| days.excludes_day(datetime.date()))
+    }
+
+    fn is_excluded_by_calendars(&self, datetime: &NaiveDateTime) -> bool {
+        self.calendars
+            .iter()
+            .any(|calendar| calendar.excludes(datetime))
+    }
+
+    // Whether a calendar excludes the whole local day of the instant, in which case searches
+    // carry on from the end of the day, or from its start when searching backwards.
+    fn excluded_day_bound<Z>(&self, date_time: &DateTime<Z>, forward: bool) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let date = date_time.naive_local().date();
+        let excluded = self
+            .calendars
+            .iter()
+            .any(|calendar| calendar.excludes_day(&date))
+            || self
+                .calendar_system
+                .as_ref()
+                .is_some_and(|days| days.excludes_day(date));
+        if !excluded {
+            return None;
+        }
+        let bound = match forward {
+            true => date.and_hms_opt(23, 59, 59)?,
+            false => date.and_hms_opt(0, 0, 0)?,
+        };
+        let bound = date_time.timezone().from_local_datetime(&bound);
+        match forward {
+            true => bound.latest().filter(|bound| bound > date_time),
+            false => bound.earliest().filter(|bound| bound < date_time),
+        }
+    }
+
+    // Whether the day of month field includes the date only as a weekday which a business day
+    // may be pushed back to, while the holidays before it push it to another day.
+    fn misses_business_day(&self, datetime: &NaiveDateTime) -> bool {
+        let fields = &self.fields;
+        let mut business_days = fields.days_of_month.business_days().peekable();
+        if self.calendars.is_empty() || business_days.peek().is_none() {
+            return false;
+        }
+        let date = datetime.date();
+        if fields
+            .days_of_month
+            .includes_besides_business_days(date, fields.nearest_weekday)
+            || (fields.days_match_either && fields.days_of_week.match_day_of(date))
+        {
+            return false;
+        }
+        let is_holiday =
+            |day: NaiveDate| self.is_excluded_by_calendars(&day.and_time(datetime.time()));
+        !business_days.any(|nth| {
+            calendar::nth_business_day(date.year(), date.month(), nth, is_holiday) == Some(date)
+        })
     }
 
     fn next_after<Z>(&self, after: &DateTime<Z>) -> LocalResult<DateTime<Z>>
     where
         Z: TimeZone,
     {
-        let mut query = NextAfterQuery::from(after);
-        for year in self
-            .fields
+        self.next_after_until(after, None)
+    }
+
+    // Like `next_after`, but doesn't search past `until`.
+    fn next_after_until<Z>(
+        &self,
+        after: &DateTime<Z>,
+        until: Option<&DateTime<Z>>,
+    ) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        trace_span!(TRACE, "next_after", schedule = %self.source, after = ?after, until = ?until);
+        let timezone = after.timezone();
+        let not_after = self
+            .not_after
+            .map(|not_after| not_after.with_timezone(&timezone));
+        let until = match (until, not_after.as_ref()) {
+            (Some(until), Some(not_after)) => Some(until.min(not_after)),
+            (until, not_after) => until.or(not_after),
+        };
+        let last_year = until.map_or(Ordinal::MAX, |until| until.year().max(0) as Ordinal);
+        let mut after = match self.not_before {
+            // Searching from a second earlier finds an occurrence right at the start.
+            Some(not_before) if *after < not_before => not_before
+                .with_timezone(&timezone)
+                .checked_sub_signed(Duration::seconds(1))
+                .unwrap_or_else(|| after.clone()),
+            _ => after.clone(),
+        };
+        loop {
+            let candidate = self.next_matching_after(&after, last_year);
+            if let (Some(earliest), Some(until)) = (candidate.clone().earliest(), until) {
+                if earliest > *until {
+                    trace_event!(trace, next = ?earliest, "Reached the horizon");
+                    return LocalResult::None;
+                }
+            }
+            // Both datetimes of an ambiguous candidate share the same local time.
+            let latest = candidate.clone().latest();
+            let blackout = self.blackout_over(&candidate, true);
+            match (latest, blackout) {
+                (Some(latest), _) if self.is_excluded(&latest.naive_local()) => {
+                    trace_event!(trace, excluded = ?latest, "Skipped a time a calendar excludes");
+                    after = self.excluded_day_bound(&latest, true).unwrap_or(latest)
+                }
+                (Some(latest), _) if self.not_before.is_some_and(|start| latest < start) => {
+                    after = latest
+                }
+                (Some(latest), Some(blackout)) => {
+                    trace_event!(trace, blackout = ?blackout, "Skipped a blackout");
+                    // Searching from a second before the end finds an occurrence right at it.
+                    let end = blackout.end.with_timezone(&timezone);
+                    after = match end.checked_sub_signed(Duration::seconds(1)) {
+                        Some(end) if end > latest => end,
+                        _ => latest,
+                    };
+                }
+                _ => {
+                    let candidate = self.clip(candidate);
+                    trace_event!(trace, occurrence = ?candidate, "Found the next occurrence");
+                    return candidate;
+                }
+            }
+        }
+    }
+
+    fn prev_from<Z>(&self, before: &DateTime<Z>) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        trace_span!(TRACE, "prev_from", schedule = %self.source, before = ?before);
+        let mut before = match self.not_after {
+            // Searching from a second later finds an occurrence right at the end.
+            Some(not_after) if *before > not_after => not_after
+                .with_timezone(&before.timezone())
+                .checked_add_signed(Duration::seconds(1))
+                .unwrap_or_else(|| before.clone()),
+            _ => before.clone(),
+        };
+        loop {
+            let candidate = self.prev_matching_from(&before);
+            if let (Some(latest), Some(not_before)) = (candidate.clone().latest(), self.not_before)
+            {
+                if latest < not_before {
+                    trace_event!(trace, previous = ?latest, "Reached the start of the schedule");
+                    return LocalResult::None;
+                }
+            }
+            let earliest = candidate.clone().earliest();
+            let blackout = self.blackout_over(&candidate, false);
+            match (earliest, blackout) {
+                (Some(earliest), _) if self.is_excluded(&earliest.naive_local()) => {
+                    trace_event!(trace, excluded = ?earliest, "Skipped a time a calendar excludes");
+                    before = self
+                        .excluded_day_bound(&earliest, false)
+                        .unwrap_or(earliest)
+                }
+                (Some(earliest), _) if self.not_after.is_some_and(|end| earliest > end) => {
+                    before = earliest
+                }
+                (Some(_), Some(blackout)) => {
+                    trace_event!(trace, blackout = ?blackout, "Skipped a blackout");
+                    before = blackout.start.with_timezone(&before.timezone());
+                }
+                _ => {
+                    let candidate = self.clip(candidate);
+                    trace_event!(trace, occurrence = ?candidate, "Found the previous occurrence");
+                    return candidate;
+                }
+            }
+        }
+    }
+
+    fn next_matching_after<Z>(
+        &self,
+        after: &DateTime<Z>,
+        last_year: Ordinal,
+    ) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        let Some(mut query) = NextAfterQuery::from(after) else {
+            return LocalResult::None;
+        };
+        let fields = self.search_fields();
+        let (hours, minutes, seconds) = fields.time_lookups();
+        if query.year_lower_bound() > last_year {
+            return LocalResult::None;
+        }
+        for year in fields
             .years
             .ordinals()
-            .range((Included(query.year_lower_bound()), Unbounded))
+            .range((Included(query.year_lower_bound()), Included(last_year)))
             .cloned()
         {
             // It's a future year, the current year's range is irrelevant.
-            if year > after.year() as u32 {
+            if year as i32 > after.year() {
                 query.reset_month();
                 query.reset_day_of_month();
             }
             let month_start = query.month_lower_bound();
-            if !self.fields.months.ordinals().contains(&month_start) {
+            let months = fields.months_to_search();
+            if !months.contains(&month_start) {
                 query.reset_month();
             }
             let month_range = (Included(month_start), Included(Months::inclusive_max()));
-            for month in self.fields.months.ordinals().range(month_range).cloned() {
+            for month in months.range(month_range).cloned() {
+                let days_of_month = fields.matching_days(month, year);
                 let day_of_month_start = query.day_of_month_lower_bound();
-                if !self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_start)
-                {
+                if !days_of_month.contains(&day_of_month_start) {
                     query.reset_day_of_month();
                 }
                 let day_of_month_end = days_in_month(month, year);
@@ -70,38 +738,25 @@ impl Schedule {
                     Included(day_of_month_end),
                 );
 
-                'day_loop: for day_of_month in self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
-                    .cloned()
-                {
+                for day_of_month in days_of_month.range(day_of_month_range).cloned() {
                     let hour_start = query.hour_lower_bound();
-                    if !self.fields.hours.ordinals().contains(&hour_start) {
+                    if !hours.contains(hour_start) {
                         query.reset_hour();
                     }
-                    let hour_range = (Included(hour_start), Included(Hours::inclusive_max()));
 
-                    for hour in self.fields.hours.ordinals().range(hour_range).cloned() {
+                    for hour in hours.ascending_from(hour_start) {
                         let minute_start = query.minute_lower_bound();
-                        if !self.fields.minutes.ordinals().contains(&minute_start) {
+                        if !minutes.contains(minute_start) {
                             query.reset_minute();
                         }
-                        let minute_range =
-                            (Included(minute_start), Included(Minutes::inclusive_max()));
 
-                        for minute in self.fields.minutes.ordinals().range(minute_range).cloned() {
+                        for minute in minutes.ascending_from(minute_start) {
                             let second_start = query.second_lower_bound();
-                            if !self.fields.seconds.ordinals().contains(&second_start) {
+                            if !seconds.contains(second_start) {
                                 query.reset_second();
                             }
-                            let second_range =
-                                (Included(second_start), Included(Seconds::inclusive_max()));
 
-                            for second in
-                                self.fields.seconds.ordinals().range(second_range).cloned()
-                            {
+                            for second in seconds.ascending_from(second_start) {
                                 let timezone = after.timezone();
                                 let candidate = match timezone.with_ymd_and_hms(
                                     year as i32,
@@ -111,19 +766,21 @@ impl Schedule {
                                     minute,
                                     second,
                                 ) {
-                                    LocalResult::None => continue,
+                                    LocalResult::None => {
+                                        trace_event!(
+                                            trace,
+                                            year,
+                                            month,
+                                            day_of_month,
+                                            hour,
+                                            minute,
+                                            second,
+                                            "Skipped a time which doesn't exist in the timezone"
+                                        );
+                                        continue;
+                                    }
                                     candidate => candidate,
                                 };
-                                if !self.fields.days_of_week.ordinals().contains(
-                                    &candidate
-                                        .clone()
-                                        .latest()
-                                        .unwrap()
-                                        .weekday()
-                                        .number_from_sunday(),
-                                ) {
-                                    continue 'day_loop;
-                                }
                                 return candidate;
                             }
                             query.reset_minute();
@@ -140,41 +797,39 @@ impl Schedule {
         LocalResult::None
     }
 
-    fn prev_from<Z>(&self, before: &DateTime<Z>) -> LocalResult<DateTime<Z>>
+    fn prev_matching_from<Z>(&self, before: &DateTime<Z>) -> LocalResult<DateTime<Z>>
     where
         Z: TimeZone,
     {
-        let mut query = PrevFromQuery::from(before);
-        for year in self
-            .fields
+        let Some(mut query) = PrevFromQuery::from(before) else {
+            return LocalResult::None;
+        };
+        let fields = self.search_fields();
+        let (hours, minutes, seconds) = fields.time_lookups();
+        for year in fields
             .years
             .ordinals()
             .range((Unbounded, Included(query.year_upper_bound())))
             .rev()
             .cloned()
         {
+            // It's a past year, the current year's range is irrelevant.
+            if (year as i32) < before.year() {
+                query.reset_month();
+                query.reset_day_of_month();
+            }
             let month_start = query.month_upper_bound();
 
-            if !self.fields.months.ordinals().contains(&month_start) {
+            let months = fields.months_to_search();
+            if !months.contains(&month_start) {
                 query.reset_month();
             }
             let month_range = (Included(Months::inclusive_min()), Included(month_start));
 
-            for month in self
-                .fields
-                .months
-                .ordinals()
-                .range(month_range)
-                .rev()
-                .cloned()
-            {
+            for month in months.range(month_range).rev().cloned() {
+                let days_of_month = fields.matching_days(month, year);
                 let day_of_month_end = query.day_of_month_upper_bound();
-                if !self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_end)
-                {
+                if !days_of_month.contains(&day_of_month_end) {
                     query.reset_day_of_month();
                 }
 
@@ -185,58 +840,25 @@ impl Schedule {
                     Included(day_of_month_end),
                 );
 
-                'day_loop: for day_of_month in self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
-                    .rev()
-                    .cloned()
-                {
+                for day_of_month in days_of_month.range(day_of_month_range).rev().cloned() {
                     let hour_start = query.hour_upper_bound();
-                    if !self.fields.hours.ordinals().contains(&hour_start) {
+                    if !hours.contains(hour_start) {
                         query.reset_hour();
                     }
-                    let hour_range = (Included(Hours::inclusive_min()), Included(hour_start));
-
-                    for hour in self
-                        .fields
-                        .hours
-                        .ordinals()
-                        .range(hour_range)
-                        .rev()
-                        .cloned()
-                    {
+
+                    for hour in hours.descending_from(hour_start) {
                         let minute_start = query.minute_upper_bound();
-                        if !self.fields.minutes.ordinals().contains(&minute_start) {
+                        if !minutes.contains(minute_start) {
                             query.reset_minute();
                         }
-                        let minute_range =
-                            (Included(Minutes::inclusive_min()), Included(minute_start));
-
-                        for minute in self
-                            .fields
-                            .minutes
-                            .ordinals()
-                            .range(minute_range)
-                            .rev()
-                            .cloned()
-                        {
+
+                        for minute in minutes.descending_from(minute_start) {
                             let second_start = query.second_upper_bound();
-                            if !self.fields.seconds.ordinals().contains(&second_start) {
+                            if !seconds.contains(second_start) {
                                 query.reset_second();
                             }
-                            let second_range =
-                                (Included(Seconds::inclusive_min()), Included(second_start));
-
-                            for second in self
-                                .fields
-                                .seconds
-                                .ordinals()
-                                .range(second_range)
-                                .rev()
-                                .cloned()
-                            {
+
+                            for second in seconds.descending_from(second_start) {
                                 let timezone = before.timezone();
                                 let candidate = match timezone.with_ymd_and_hms(
                                     year as i32,
@@ -246,19 +868,21 @@ impl Schedule {
                                     minute,
                                     second,
                                 ) {
-                                    LocalResult::None => continue,
+                                    LocalResult::None => {
+                                        trace_event!(
+                                            trace,
+                                            year,
+                                            month,
+                                            day_of_month,
+                                            hour,
+                                            minute,
+                                            second,
+                                            "Skipped a time which doesn't exist in the timezone"
+                                        );
+                                        continue;
+                                    }
                                     some => some,
                                 };
-                                if !self.fields.days_of_week.ordinals().contains(
-                                    &candidate
-                                        .clone()
-                                        .latest()
-                                        .unwrap()
-                                        .weekday()
-                                        .number_from_sunday(),
-                                ) {
-                                    continue 'day_loop;
-                                }
                                 return candidate;
                             }
                             query.reset_minute();
@@ -275,6 +899,16 @@ impl Schedule {
         LocalResult::None
     }
 
+    /// Returns the first time the schedule fires after the given instant along with the jumps the
+    /// search made to get there, e.g. that the hour was rolled to 9 due to the hours field, to
+    /// explain why the schedule fires when it does. See [Trace] for an example.
+    pub fn trace_next_after<Z>(&self, after: &DateTime<Z>) -> Trace<Z>
+    where
+        Z: TimeZone,
+    {
+        trace::trace_next_after(self, after)
+    }
+
     /// Provides an iterator which will return each DateTime that matches the schedule starting with
     /// the current time if applicable.
     pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleIterator<'_, Z>
@@ -289,6 +923,46 @@ impl Schedule {
         self.after_owned(timezone.from_utc_datetime(&Utc::now().naive_utc()))
     }
 
+    /// Waits until the next time the schedule fires at, and returns it. Returns `None` right away
+    /// if the schedule never fires again.
+    ///
+    /// The system clock is checked at least once a minute while waiting, so that the future still
+    /// resolves on time if the clock is adjusted.
+    /// # Example
+    /// ```
+    /// use chrono::Utc;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let schedule = Schedule::from_str("* * * * * *").unwrap();
+    /// let tick = schedule.next_tick(Utc).await.unwrap();
+    /// assert!(tick <= Utc::now());
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn next_tick<Z>(&self, timezone: Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        tick::next_tick(self, &timezone.from_utc_datetime(&Utc::now().naive_utc())).await
+    }
+
+    /// Provides an asynchronous stream of the times the schedule fires at from now on, which
+    /// waits for each of them to come. See [Ticks] for what happens to firings missed while the
+    /// caller was busy.
+    #[cfg(feature = "tokio")]
+    pub fn ticks<Z>(&self, timezone: Z) -> Ticks<Z>
+    where
+        Z: TimeZone,
+    {
+        Ticks::new(
+            self.clone(),
+            timezone.from_utc_datetime(&Utc::now().naive_utc()),
+        )
+    }
+
     /// Like the `upcoming` method, but allows you to specify a start time other than the present.
     pub fn after<Z>(&self, after: &DateTime<Z>) -> ScheduleIterator<'_, Z>
     where
@@ -302,23 +976,592 @@ impl Schedule {
         OwnedScheduleIterator::new(self.clone(), after)
     }
 
-    pub fn includes<Z>(&self, date_time: DateTime<Z>) -> bool
-    where
-        Z: TimeZone,
-    {
-        self.fields.years.includes(date_time.year() as Ordinal)
-            && self.fields.months.includes(date_time.month() as Ordinal)
-            && self
-                .fields
-                .days_of_week
-                .includes(date_time.weekday().number_from_sunday())
-            && self
-                .fields
-                .days_of_month
-                .includes(date_time.day() as Ordinal)
-            && self.fields.hours.includes(date_time.hour() as Ordinal)
-            && self.fields.minutes.includes(date_time.minute() as Ordinal)
-            && self.fields.seconds.includes(date_time.second() as Ordinal)
+    /// Returns the `k`th occurrence of this [Schedule] after `after`, counting from zero, the
+    /// same as `self.after(after).nth(k)`.
+    ///
+    /// Days on which the timezone keeps the same offset are skipped whole, by counting the times
+    /// of day the schedule fires at, rather than by finding each of their occurrences. Schedules
+    /// with calendars, blackouts or bounds are iterated through.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 9-17 * * Mon-Fri").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     Some(Utc.with_ymd_and_hms(2026, 10, 19, 9, 5, 0).unwrap()),
+    ///     schedule.nth_after(109, &start)
+    /// );
+    /// assert_eq!(schedule.after(&start).nth(50_000), schedule.nth_after(50_000, &start));
+    /// ```
+    pub fn nth_after<Z>(&self, k: usize, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        counting::nth_after(self, k, after)
+    }
+
+    /// Returns how many times this [Schedule] fires after `anchor` and before `date_time`, i.e.
+    /// the index of `date_time` among the occurrences after `anchor`, counting from zero, so that
+    /// `nth_after` maps it back to `date_time`. It's counted the same way.
+    ///
+    /// Returns `None` if the schedule doesn't fire at `date_time` or it isn't after `anchor`.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 9-17 * * Mon-Fri").unwrap();
+    /// let anchor = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// let monday = Utc.with_ymd_and_hms(2026, 10, 19, 9, 5, 0).unwrap();
+    /// assert_eq!(Some(109), schedule.occurrence_index(&anchor, &monday));
+    /// assert_eq!(Some(monday), schedule.nth_after(109, &anchor));
+    ///
+    /// let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 9, 5, 0).unwrap();
+    /// assert_eq!(None, schedule.occurrence_index(&anchor, &saturday));
+    /// ```
+    pub fn occurrence_index<Z>(
+        &self,
+        anchor: &DateTime<Z>,
+        date_time: &DateTime<Z>,
+    ) -> Option<usize>
+    where
+        Z: TimeZone,
+    {
+        counting::occurrence_index(self, anchor, date_time)
+    }
+
+    /// Returns the next DateTime after the given instant that matches the schedule, as long as it's
+    /// no later than `horizon` after it.
+    ///
+    /// The search stops at the horizon instead of going on until the last year a schedule can fire
+    /// in, and failing with [ErrorKind::NoUpcomingWithin] tells a schedule which fires rarely apart
+    /// from one which never does.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::error::ErrorKind;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 0 29 2 ?").unwrap();
+    /// let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// let error = schedule.next_within(&now, Duration::days(365)).unwrap_err();
+    /// assert!(matches!(error.kind(), ErrorKind::NoUpcomingWithin(_)));
+    /// assert_eq!(
+    ///     Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap(),
+    ///     schedule.next_within(&now, Duration::days(4 * 365)).unwrap()
+    /// );
+    /// ```
+    pub fn next_within<Z>(
+        &self,
+        after: &DateTime<Z>,
+        horizon: Duration,
+    ) -> Result<DateTime<Z>, Error>
+    where
+        Z: TimeZone,
+    {
+        let deadline = after.clone().checked_add_signed(horizon);
+        match self.next_after_until(after, deadline.as_ref()) {
+            LocalResult::Single(next) | LocalResult::Ambiguous(next, _) => Ok(next),
+            LocalResult::None => Err(ErrorKind::NoUpcomingWithin(horizon).into()),
+        }
+    }
+
+    /// Snaps `date_time` to the latest time this [Schedule] fires at which isn't after it, e.g.
+    /// to bucket metrics by the run of a job they belong to. Returns `date_time` itself if the
+    /// schedule fires at it.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */15 * * * *").unwrap();
+    /// let instant = Utc.with_ymd_and_hms(2026, 10, 16, 9, 20, 30).unwrap();
+    /// let floor = Utc.with_ymd_and_hms(2026, 10, 16, 9, 15, 0).unwrap();
+    /// let ceil = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+    /// assert_eq!(Some(floor), schedule.floor_to_schedule(&instant));
+    /// assert_eq!(Some(ceil), schedule.ceil_to_schedule(&instant));
+    /// assert_eq!(Some(floor), schedule.ceil_to_schedule(&floor));
+    /// ```
+    pub fn floor_to_schedule<Z>(&self, date_time: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.fires_at(date_time) {
+            return Some(date_time.clone());
+        }
+        self.after(date_time).next_back()
+    }
+
+    /// Snaps `date_time` to the earliest time this [Schedule] fires at which isn't before it.
+    /// Returns `date_time` itself if the schedule fires at it.
+    pub fn ceil_to_schedule<Z>(&self, date_time: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if self.fires_at(date_time) {
+            return Some(date_time.clone());
+        }
+        self.after(date_time).next()
+    }
+
+    // Whether the schedule fires at exactly this instant, as it never does within a second.
+    pub(crate) fn fires_at<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        date_time.timestamp_subsec_nanos() == 0 && self.includes(date_time.clone())
+    }
+
+    /// Provides an iterator over the times the schedule fires at after the given instant, each
+    /// with the time elapsed since the occurrence before it, e.g. to report on the cadence of a
+    /// job. The first gap reaches back to the last occurrence at or before `after`, and an
+    /// occurrence with none before it is left out.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9,17 * * Mon-Fri").unwrap();
+    /// // Friday, 2026-10-16
+    /// let after = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    /// let gaps: Vec<_> = schedule.gaps_after(&after).take(2).map(|(_, gap)| gap).collect();
+    /// assert_eq!(vec![Duration::hours(8), Duration::hours(64)], gaps);
+    /// ```
+    pub fn gaps_after<Z>(&self, after: &DateTime<Z>) -> Gaps<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        let previous = if self.fires_at(after) {
+            Some(after.clone())
+        } else {
+            self.after(after).next_back()
+        };
+        Gaps::new(previous, self.after(after))
+    }
+
+    /// Returns the first [Window] of consecutive occurrences starting after `after`, for
+    /// schedules describing when something is active rather than when it runs.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let business_hours = Schedule::from_str("0 * 9-17 * * Mon-Fri").unwrap();
+    /// let friday = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    /// let current = business_hours.window_at(&friday).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 18, 0, 0).unwrap(), current.end);
+    /// let next = business_hours.next_window(&friday).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(), next.start);
+    /// assert!(!next.contains(&friday));
+    /// ```
+    pub fn next_window<Z>(&self, after: &DateTime<Z>) -> Option<Window<Z>>
+    where
+        Z: TimeZone,
+    {
+        window::next_window(self, after)
+    }
+
+    /// Returns the [Window] of consecutive occurrences the given instant falls within, if any.
+    pub fn window_at<Z>(&self, date_time: &DateTime<Z>) -> Option<Window<Z>>
+    where
+        Z: TimeZone,
+    {
+        window::window_at(self, date_time)
+    }
+
+    /// Keeps only every `n`th occurrence of this [Schedule], counted from the first one at or
+    /// after `anchor`. See [EveryNth].
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn every_nth<Z>(&self, n: u32, anchor: &DateTime<Z>) -> EveryNth
+    where
+        Z: TimeZone,
+    {
+        EveryNth::new(self.clone(), n, anchor)
+    }
+
+    /// Counts the times this [Schedule] fires after `after` and no later than `horizon` after
+    /// it, bucketed by hour, day of the week and month. Every occurrence is visited, so schedules
+    /// firing every second should be given a short horizon.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9,17 * * Mon-Fri").unwrap();
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 12, 0, 0, 0).unwrap();
+    /// let histogram = schedule.histogram(&start, Duration::weeks(1));
+    /// assert_eq!(10, histogram.total);
+    /// assert_eq!(5, histogram.hours[9]);
+    /// assert_eq!([2, 2, 2, 2, 2, 0, 0], histogram.days_of_week);
+    /// assert_eq!(10, histogram.months[9]);
+    /// ```
+    pub fn histogram<Z>(&self, after: &DateTime<Z>, horizon: Duration) -> Histogram
+    where
+        Z: TimeZone,
+    {
+        let mut histogram = Histogram::default();
+        let Some(deadline) = after.clone().checked_add_signed(horizon) else {
+            return histogram;
+        };
+        for next in self.after(after).take_while(|next| *next <= deadline) {
+            histogram.record(&next);
+        }
+        histogram
+    }
+
+    /// Shifts every occurrence of this [Schedule] by `offset`, which may be negative, e.g. to
+    /// stagger a job seven minutes after every hour. See [OffsetSchedule].
+    pub fn offset_by(&self, offset: Duration) -> OffsetSchedule {
+        OffsetSchedule::new(self.clone(), offset)
+    }
+
+    /// Returns the next DateTime after the given instant that matches the schedule when it is
+    /// evaluated in each of the given timezones, in the same order as the timezones.
+    /// # Example
+    /// ```
+    /// use chrono::{FixedOffset, TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * ?").unwrap();
+    /// let zones = [FixedOffset::east_opt(9 * 3600).unwrap(), FixedOffset::west_opt(5 * 3600).unwrap()];
+    /// let now = Utc.with_ymd_and_hms(2026, 10, 16, 6, 0, 0).unwrap();
+    /// let next = schedule.next_in_timezones(&now, &zones);
+    /// assert_eq!(Some(zones[0].with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap()), next[0]);
+    /// assert_eq!(Some(zones[1].with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap()), next[1]);
+    /// assert_eq!(next[1], schedule.earliest_in_timezones(&now, &zones));
+    /// ```
+    pub fn next_in_timezones<Z, T>(
+        &self,
+        after: &DateTime<T>,
+        timezones: &[Z],
+    ) -> Vec<Option<DateTime<Z>>>
+    where
+        Z: TimeZone,
+        T: TimeZone,
+    {
+        timezones
+            .iter()
+            .map(|timezone| self.after(&after.with_timezone(timezone)).next())
+            .collect()
+    }
+
+    /// Returns the earliest of the DateTimes returned by [Schedule::next_in_timezones], i.e. the
+    /// next time the schedule fires in any of the given timezones.
+    pub fn earliest_in_timezones<Z, T>(
+        &self,
+        after: &DateTime<T>,
+        timezones: &[Z],
+    ) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+        T: TimeZone,
+    {
+        self.next_in_timezones(after, timezones)
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Returns the first time the schedule fires after the given instant, reading the schedule in
+    /// the local time at the given offset from UTC, e.g. for callers which only know the offset
+    /// of a user rather than their timezone. Offsets don't follow daylight saving time, so the
+    /// result is only right while the offset stays in force.
+    /// # Example
+    /// ```
+    /// use chrono::{FixedOffset, TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 * * ?").unwrap();
+    /// let offset = FixedOffset::from_str("+05:30").unwrap();
+    /// let now = Utc.with_ymd_and_hms(2026, 10, 16, 6, 0, 0).unwrap();
+    /// let next = schedule.next_after_utc_offset(&now, offset).unwrap();
+    /// assert_eq!("2026-10-17T09:00:00+05:30", next.to_rfc3339());
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 17, 3, 30, 0).unwrap(), next);
+    /// ```
+    pub fn next_after_utc_offset<Z>(
+        &self,
+        after: &DateTime<Z>,
+        offset: FixedOffset,
+    ) -> Option<DateTime<FixedOffset>>
+    where
+        Z: TimeZone,
+    {
+        self.after(&after.with_timezone(&offset)).next()
+    }
+
+    /// Returns the last time the schedule fires before the given instant, reading the schedule in
+    /// the local time at the given offset from UTC. See [Schedule::next_after_utc_offset].
+    pub fn prev_before_utc_offset<Z>(
+        &self,
+        before: &DateTime<Z>,
+        offset: FixedOffset,
+    ) -> Option<DateTime<FixedOffset>>
+    where
+        Z: TimeZone,
+    {
+        self.after(&before.with_timezone(&offset)).next_back()
+    }
+
+    /// Returns the first time the schedule ever fires in the given timezone, e.g. to show when a
+    /// job whose years field starts in the future, as in `0 0 9 * * ? 2027-2028`, starts running.
+    ///
+    /// Returns `None` if the schedule may fire in the first year searched, 1970 by default or the
+    /// start of the [ScheduleParser::year_range](crate::ScheduleParser::year_range) it was parsed
+    /// with, as it then has no start of its own, unless it's limited with
+    /// [Schedule::with_not_before]. To start from a given epoch instead, e.g. when a job was
+    /// created, see [Schedule::ceil_to_schedule].
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 ? * MON 2027-2028").unwrap();
+    /// let first = Utc.with_ymd_and_hms(2027, 1, 4, 9, 0, 0).unwrap();
+    /// assert_eq!(Some(first), schedule.first_occurrence(&Utc));
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 ? * MON").unwrap();
+    /// assert_eq!(None, schedule.first_occurrence(&Utc));
+    /// ```
+    pub fn first_occurrence<Z>(&self, timezone: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if let Some(not_before) = self.not_before {
+            return self.ceil_to_schedule(&not_before.with_timezone(timezone));
+        }
+        let first_year = *self.fields.years.ordinals().first()?;
+        if first_year <= *self.fields.year_range.start() {
+            return None;
+        }
+        // A day before the first year in UTC is before its start in any timezone.
+        let previous_year = NaiveDate::from_ymd_opt(first_year as i32 - 1, 12, 31)?;
+        let after = timezone.from_utc_datetime(&previous_year.and_hms_opt(0, 0, 0)?);
+        self.next_after(&after).earliest()
+    }
+
+    /// Returns the last time the schedule will ever fire in the given timezone, e.g. to clean up
+    /// after a job whose expression ends with its years field, as in `0 0 9 * * ? 2026-2027`.
+    ///
+    /// Returns `None` if the schedule may still fire in the last year searched, 2100 by default or
+    /// the end of the [ScheduleParser::year_range](crate::ScheduleParser::year_range) it was
+    /// parsed with, as it's then taken to go on firing rather than to end, unless it's limited
+    /// with [Schedule::with_not_after]. Also returns `None` if it never fires in its last years.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 L * ? 2026-2027").unwrap();
+    /// let last = Utc.with_ymd_and_hms(2027, 12, 31, 9, 0, 0).unwrap();
+    /// assert_eq!(Some(last), schedule.final_occurrence(&Utc));
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 L * ?").unwrap();
+    /// assert_eq!(None, schedule.final_occurrence(&Utc));
+    /// ```
+    pub fn final_occurrence<Z>(&self, timezone: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        if let Some(not_after) = self.not_after {
+            return self.floor_to_schedule(&not_after.with_timezone(timezone));
+        }
+        let last_year = *self.fields.years.ordinals().last()?;
+        if last_year >= *self.fields.year_range.end() {
+            return None;
+        }
+        // A day into the following year in UTC is past the end of the last year in any timezone.
+        let following_year = NaiveDate::from_ymd_opt(last_year as i32 + 1, 1, 2)?;
+        let before = timezone.from_utc_datetime(&following_year.and_hms_opt(0, 0, 0)?);
+        self.prev_from(&before).latest()
+    }
+
+    pub fn includes<Z>(&self, date_time: DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.is_active_at(&date_time) && self.includes_local(&date_time.naive_local())
+    }
+
+    /// Compiles the schedule into bitmasks for testing many timestamps against it at once. See
+    /// [CompiledSchedule].
+    pub fn compile(&self) -> CompiledSchedule {
+        CompiledSchedule::new(self)
+    }
+
+    /// Provides an iterator over the wall clock times the schedule fires at after the given one,
+    /// for schedules which aren't tied to any timezone. As there is no timezone, no time is
+    /// skipped or repeated by daylight saving time transitions.
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+    /// let friday = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    /// let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    /// assert_eq!(Some(monday), schedule.next_after_naive(&friday));
+    /// assert_eq!(Some(monday), schedule.after_naive(&friday).next());
+    /// assert!(schedule.includes_naive(&monday));
+    /// ```
+    pub fn after_naive(
+        &self,
+        after: &NaiveDateTime,
+    ) -> impl DoubleEndedIterator<Item = NaiveDateTime> + '_ {
+        self.after(&Utc.from_utc_datetime(after))
+            .map(|date_time| date_time.naive_utc())
+    }
+
+    /// Returns the first wall clock time the schedule fires at after the given one.
+    pub fn next_after_naive(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.after_naive(after).next()
+    }
+
+    /// Returns the last wall clock time the schedule fires at before the given one.
+    pub fn prev_before_naive(&self, before: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.after_naive(before).next_back()
+    }
+
+    /// Returns the first wall clock time the schedule fires at after the local time of the given
+    /// DateTime, along with every instant it maps to in the timezone, so that callers can apply
+    /// their own policy when daylight saving time repeats it rather than take the earlier one.
+    /// Wall clock times skipped by daylight saving time are passed over, so
+    /// [LocalResult::None] means that the schedule doesn't fire again.
+    /// # Example
+    /// ```
+    /// use chrono::{LocalResult, TimeZone};
+    /// use chrono_tz::Europe::Berlin;
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 2 * * *").unwrap();
+    /// // 2:30 happens twice in Berlin on the 25th of October 2026.
+    /// let after = Berlin.with_ymd_and_hms(2026, 10, 24, 12, 0, 0).unwrap();
+    /// match schedule.next_local_after(&after) {
+    ///     LocalResult::Ambiguous(earliest, latest) => {
+    ///         assert_eq!("2026-10-25T02:30:00+02:00", earliest.to_rfc3339());
+    ///         assert_eq!("2026-10-25T02:30:00+01:00", latest.to_rfc3339());
+    ///     }
+    ///     other => panic!("expected two instants, got {other:?}"),
+    /// }
+    /// ```
+    pub fn next_local_after<Z>(&self, after: &DateTime<Z>) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.next_after(after)
+    }
+
+    /// Returns the last wall clock time the schedule fires at before the local time of the given
+    /// DateTime, along with every instant it maps to. See [Schedule::next_local_after].
+    pub fn prev_local_before<Z>(&self, before: &DateTime<Z>) -> LocalResult<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.prev_from(before)
+    }
+
+    /// Returns true if the schedule fires at the given wall clock time.
+    pub fn includes_naive(&self, date_time: &NaiveDateTime) -> bool {
+        self.includes_local(date_time)
+    }
+
+    /// Provides an iterator over the times the schedule fires at after the given [SystemTime],
+    /// reading the schedule in UTC, so that callers don't need chrono types of their own.
+    /// Iterating it backwards goes over the times before it. The iterator is empty if the time is
+    /// out of the range chrono represents, some 262,000 years around 1970.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+    /// // 2026-10-16 12:30:00 UTC
+    /// let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_792_153_800);
+    /// let next = schedule.next_after_system_time(now).unwrap();
+    /// assert_eq!(Duration::from_secs(1_800), next.duration_since(now).unwrap());
+    /// assert_eq!(Some(next), schedule.after_system_time(now).next());
+    /// assert!(schedule.includes_system_time(next));
+    /// ```
+    pub fn after_system_time(
+        &self,
+        after: SystemTime,
+    ) -> impl DoubleEndedIterator<Item = SystemTime> + '_ {
+        utc_from_system_time(after)
+            .map(|after| self.after(&after))
+            .into_iter()
+            .flatten()
+            .map(SystemTime::from)
+    }
+
+    /// Returns the first time the schedule fires at after the given [SystemTime], in UTC.
+    pub fn next_after_system_time(&self, after: SystemTime) -> Option<SystemTime> {
+        self.after_system_time(after).next()
+    }
+
+    /// Returns the last time the schedule fires at before the given [SystemTime], in UTC.
+    pub fn prev_before_system_time(&self, before: SystemTime) -> Option<SystemTime> {
+        self.after_system_time(before).next_back()
+    }
+
+    /// Returns true if the schedule fires at the given [SystemTime], in UTC.
+    pub fn includes_system_time(&self, time: SystemTime) -> bool {
+        utc_from_system_time(time).is_some_and(|date_time| self.includes(date_time))
+    }
+
+    /// Returns the first time the schedule fires at after the given Unix timestamp, in seconds
+    /// and in UTC, or `None` if it doesn't fire again or the timestamp is out of range.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+    /// // 2026-10-16 12:30:00 UTC
+    /// assert_eq!(Some(1_792_155_600), schedule.next_timestamp_after(1_792_153_800));
+    /// assert_eq!(Some(1_792_152_000), schedule.prev_timestamp_before(1_792_153_800));
+    /// assert!(schedule.includes_timestamp(1_792_152_000));
+    /// ```
+    pub fn next_timestamp_after(&self, after: i64) -> Option<i64> {
+        let after = DateTime::from_timestamp(after, 0)?;
+        self.after(&after).next().map(|next| next.timestamp())
+    }
+
+    /// Returns the last time the schedule fires at before the given Unix timestamp, in UTC.
+    pub fn prev_timestamp_before(&self, before: i64) -> Option<i64> {
+        let before = DateTime::from_timestamp(before, 0)?;
+        self.after(&before).next_back().map(|prev| prev.timestamp())
+    }
+
+    /// Returns true if the schedule fires at the given Unix timestamp, in UTC.
+    pub fn includes_timestamp(&self, timestamp: i64) -> bool {
+        DateTime::from_timestamp(timestamp, 0).is_some_and(|date_time| self.includes(date_time))
+    }
+
+    pub(crate) fn includes_local(&self, date_time: &NaiveDateTime) -> bool {
+        let fields = self.search_fields();
+        !self.is_excluded(date_time)
+            && fields.years.includes(date_time.year() as Ordinal)
+            && fields.matches_date(date_time.date())
+            && fields.hours.includes(date_time.hour() as Ordinal)
+            && fields.minutes.includes(date_time.minute() as Ordinal)
+            && fields.seconds.includes(date_time.second() as Ordinal)
     }
 
     /// Returns a [TimeUnitSpec] describing the years included in this [Schedule].
@@ -341,6 +1584,62 @@ impl Schedule {
         &self.fields.days_of_week
     }
 
+    /// Returns the months of the year included in this [Schedule] as [Month]s, from January.
+    /// # Example
+    /// ```
+    /// use chrono::{Month, Weekday};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 9 ? Jun-Aug Mon,Fri").unwrap();
+    /// assert_eq!(vec![Month::June, Month::July, Month::August], schedule.months_of_year());
+    /// assert_eq!(vec![Weekday::Mon, Weekday::Fri], schedule.weekdays());
+    /// assert!(schedule.includes_month(Month::July));
+    /// assert!(!schedule.includes_weekday(Weekday::Sun));
+    /// ```
+    pub fn months_of_year(&self) -> Vec<Month> {
+        self.fields
+            .months
+            .iter()
+            .filter_map(|month| Month::try_from(u8::try_from(month).ok()?).ok())
+            .collect()
+    }
+
+    /// Returns the days of the week included in this [Schedule] as [Weekday]s, from Sunday.
+    /// Like [Schedule::days_of_week], this leaves out specials such as `6#3`.
+    pub fn weekdays(&self) -> Vec<Weekday> {
+        self.fields
+            .days_of_week
+            .iter()
+            .filter_map(to_weekday)
+            .collect()
+    }
+
+    /// Returns true if the month field includes the given month.
+    pub fn includes_month(&self, month: Month) -> bool {
+        self.fields.months.includes(month.number_from_month())
+    }
+
+    /// Returns true if the day of week field includes every occurrence of the given day of the
+    /// week, leaving out specials such as `6#3` which only include some of them.
+    pub fn includes_weekday(&self, weekday: Weekday) -> bool {
+        self.fields
+            .days_of_week
+            .includes(weekday.number_from_sunday())
+    }
+
+    /// Returns the values of the day of month field which depend on the month, like `L-2` or
+    /// `15W`, in the order `W`, `L` and `LW`. [Schedule::days_of_month] only has the plain days.
+    pub fn day_of_month_specs(&self) -> &[DayOfMonthSpec] {
+        self.fields.days_of_month.specs()
+    }
+
+    /// Returns the values of the day of week field which depend on the month, like `6#3` or
+    /// `FRIL`, in the order `#` then `L`. [Schedule::days_of_week] only has the plain days.
+    pub fn day_of_week_specs(&self) -> &[DayOfWeekSpec] {
+        self.fields.days_of_week.specs()
+    }
+
     /// Returns a [TimeUnitSpec] describing the hours of the day included in this [Schedule].
     pub fn hours(&self) -> &impl TimeUnitSpec {
         &self.fields.hours
@@ -356,19 +1655,512 @@ impl Schedule {
         &self.fields.seconds
     }
 
+    /// Returns true if this [Schedule] fires at times other than the start of a minute, i.e. it
+    /// needs to be checked every second rather than every minute.
+    /// # Example
+    /// ```
+    /// use cron::{Schedule, TimeUnitSpec};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */5 * * * Mon-Fri").unwrap();
+    /// assert!(!schedule.has_second_precision());
+    /// assert!(!schedule.is_dom_restricted());
+    /// assert!(schedule.is_dow_restricted());
+    /// assert!(schedule.months().is_all());
+    /// assert!(Schedule::from_str("30 * * * * *").unwrap().has_second_precision());
+    /// ```
+    pub fn has_second_precision(&self) -> bool {
+        self.fields
+            .seconds
+            .ordinals()
+            .iter()
+            .any(|&second| second != 0)
+    }
+
+    /// Returns true if the day of month field restricts the days this [Schedule] fires on, with
+    /// values or specials like `L`.
+    pub fn is_dom_restricted(&self) -> bool {
+        !self.fields.days_of_month.is_all()
+    }
+
+    /// Returns true if the day of week field restricts the days this [Schedule] fires on, with
+    /// values or specials like `6#3`.
+    pub fn is_dow_restricted(&self) -> bool {
+        !self.fields.days_of_week.is_all()
+    }
+
+    /// Returns true if the year field restricts the years this [Schedule] fires in, beyond the
+    /// [year range](crate::ScheduleParser::year_range) it was parsed with.
+    pub fn is_year_restricted(&self) -> bool {
+        !self.fields.every_year()
+    }
+
+    /// Returns true if this [Schedule] fires exactly when `@yearly` does, at midnight on the
+    /// first of January, however it was written.
+    ///
+    /// Like the other shape predicates, this is false for schedules with calendars, blackouts or
+    /// bounds, which skip some of the nickname's times.
+    pub fn is_yearly(&self) -> bool {
+        self.has_nickname("@yearly")
+    }
+
+    /// Returns true if this [Schedule] fires exactly when `@monthly` does, at midnight on the
+    /// first day of every month.
+    pub fn is_monthly(&self) -> bool {
+        self.has_nickname("@monthly")
+    }
+
+    /// Returns true if this [Schedule] fires exactly when `@weekly` does, at midnight every
+    /// Sunday.
+    pub fn is_weekly(&self) -> bool {
+        self.has_nickname("@weekly")
+    }
+
+    /// Returns true if this [Schedule] fires exactly when `@daily` does, at midnight every day.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(Schedule::from_str("0 0 0 * * ?").unwrap().is_daily());
+    /// assert!(!Schedule::from_str("0 0 0 * * Mon-Fri").unwrap().is_daily());
+    /// assert!(Schedule::from_str("0 0 * * * *").unwrap().is_hourly());
+    /// assert_eq!(Some(15), Schedule::from_str("0 */15 * * * *").unwrap().every_n_minutes());
+    /// ```
+    pub fn is_daily(&self) -> bool {
+        self.has_nickname("@daily")
+    }
+
+    /// Returns true if this [Schedule] fires exactly when `@hourly` does, at the start of every
+    /// hour.
+    pub fn is_hourly(&self) -> bool {
+        self.has_nickname("@hourly")
+    }
+
+    /// Returns `n` if this [Schedule] fires exactly every `n` minutes, at the start of the
+    /// minute, from `1` for every minute to `30`. Only the divisors of 60 qualify, since other
+    /// steps such as `*/7` restart at the top of the hour.
+    pub fn every_n_minutes(&self) -> Option<u32> {
+        let fields = &self.fields;
+        let minutes: Vec<Ordinal> = fields.minutes.iter().collect();
+        let n = *minutes.get(1)?;
+        let every_day = fields.days_of_month.is_all()
+            && fields.months.is_all()
+            && fields.days_of_week.is_all()
+            && fields.every_year();
+        let is_every_n = 60 % n == 0 && minutes.iter().copied().eq((0..60).step_by(n as usize));
+        (self.is_unconstrained()
+            && is_every_n
+            && fields.seconds.iter().eq([0])
+            && fields.hours.is_all()
+            && every_day)
+            .then_some(n)
+    }
+
+    fn has_nickname(&self, nickname: &str) -> bool {
+        self.is_unconstrained() && parsing::nickname_of(&self.fields) == Some(nickname)
+    }
+
+    /// Returns a copy of this [Schedule] with its seconds replaced by the given field, e.g. `0`
+    /// to make it fire at the start of each minute only. The other fields are kept as written,
+    /// and the field is parsed in the dialect, and with the options and limits of the parser, the
+    /// schedule was parsed with. Shorthands are written out in full first.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("*/10 * * * * Mon-Fri").unwrap();
+    /// let schedule = schedule.with_seconds("0").unwrap().with_hours("9-17").unwrap();
+    /// assert_eq!("0 * 9-17 * * Mon-Fri", schedule.source());
+    /// ```
+    pub fn with_seconds(&self, seconds: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 0, seconds)
+    }
+
+    /// Returns a copy of this [Schedule] with its minutes replaced by the given field.
+    pub fn with_minutes(&self, minutes: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 1, minutes)
+    }
+
+    /// Returns a copy of this [Schedule] with its hours replaced by the given field.
+    pub fn with_hours(&self, hours: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 2, hours)
+    }
+
+    /// Returns a copy of this [Schedule] with its days of the month replaced by the given field.
+    pub fn with_days_of_month(&self, days_of_month: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 3, days_of_month)
+    }
+
+    /// Returns a copy of this [Schedule] with its months replaced by the given field.
+    pub fn with_months(&self, months: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 4, months)
+    }
+
+    /// Returns a copy of this [Schedule] with its days of the week replaced by the given field.
+    pub fn with_days_of_week(&self, days_of_week: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 5, days_of_week)
+    }
+
+    /// Returns a copy of this [Schedule] with its days of the week replaced by the given typed
+    /// values, whichever way the dialect numbers them. Like the other setters, this fails if the
+    /// dialect rejects the result, e.g. when both day fields of an AWS expression are restricted,
+    /// or if no values are given.
+    /// # Example
+    /// ```
+    /// use chrono::{Month, Weekday};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * *").unwrap();
+    /// let schedule = schedule.with_weekdays(Weekday::Mon..=Weekday::Fri).unwrap();
+    /// let schedule = schedule.with_months_of_year([Month::January, Month::July]).unwrap();
+    /// assert_eq!("0 30 9 * JAN,JUL MON-FRI", schedule.source());
+    /// ```
+    pub fn with_weekdays(&self, weekdays: impl FieldValues<Weekday>) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 5, &field_values::field_text(weekdays))
+    }
+
+    /// Returns a copy of this [Schedule] with its months replaced by the given typed values.
+    pub fn with_months_of_year(&self, months: impl FieldValues<Month>) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 4, &field_values::field_text(months))
+    }
+
+    /// Returns a copy of this [Schedule] with its years replaced by the given field. Fails for
+    /// dialects without a year field.
+    pub fn with_years(&self, years: &str) -> Result<Schedule, Error> {
+        parsing::replace_field(self, 6, years)
+    }
+
     pub fn timeunitspec_eq(&self, other: &Schedule) -> bool {
         self.fields == other.fields
     }
 
+    /// Describes, field by field, how the times included in `other` differ from the ones
+    /// included in this [Schedule], e.g. for auditing edits to a schedule.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let before = Schedule::from_str("0 0 9,12 * * Mon-Fri").unwrap();
+    /// let after = Schedule::from_str("0 0 9,13 * * Mon-Fri").unwrap();
+    /// let diff = before.diff(&after);
+    /// assert_eq!(vec![13], diff.hours.added);
+    /// assert_eq!(vec![12], diff.hours.removed);
+    /// assert_eq!("Hours: +13 -12", diff.to_string());
+    /// ```
+    pub fn diff(&self, other: &Schedule) -> ScheduleDiff {
+        let (old, new) = (&self.fields, &other.fields);
+        ScheduleDiff {
+            seconds: FieldDiff::between(&old.seconds, &new.seconds),
+            minutes: FieldDiff::between(&old.minutes, &new.minutes),
+            hours: FieldDiff::between(&old.hours, &new.hours),
+            days_of_month: FieldDiff::between(&old.days_of_month, &new.days_of_month),
+            months: FieldDiff::between(&old.months, &new.months),
+            days_of_week: FieldDiff::between(&old.days_of_week, &new.days_of_week),
+            years: FieldDiff::between(&old.years, &new.years),
+        }
+    }
+
+    /// Classifies how often this [Schedule] fires, from the finest field matching several values.
+    ///
+    /// Restrictions of coarser fields are ignored, so `* * 9-17 * * Mon-Fri` fires
+    /// [Frequency::PerSecond] and `0 0 12 * * Mon-Fri` [Frequency::Daily].
+    /// # Example
+    /// ```
+    /// use cron::{Frequency, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * Mon").unwrap();
+    /// assert_eq!(Frequency::Weekly, schedule.frequency());
+    /// ```
+    pub fn frequency(&self) -> Frequency {
+        let fields = &self.fields;
+        if fields.seconds.count() > 1 {
+            return Frequency::PerSecond;
+        }
+        if fields.minutes.count() > 1 {
+            return Frequency::PerMinute;
+        }
+        if fields.hours.count() > 1 {
+            return Frequency::Hourly;
+        }
+        let days_of_week = fields.days_of_week.ordinals();
+        let single_day_of_month = if fields.days_of_week.is_all() {
+            fields.days_of_month.count() as usize + fields.days_of_month.specs().len() == 1
+        } else {
+            // e.g. `FRIL` or `2#1`
+            fields.days_of_month.is_all()
+                && days_of_week.is_empty()
+                && fields.days_of_week.specs().len() == 1
+        };
+        if single_day_of_month {
+            return match fields.months.count() {
+                1 => Frequency::Yearly,
+                _ => Frequency::Monthly,
+            };
+        }
+        if !fields.days_of_month.is_all() || !fields.days_of_week.specs().is_empty() {
+            return Frequency::Irregular;
+        }
+        match days_of_week.len() {
+            1 => Frequency::Weekly,
+            _ => Frequency::Daily,
+        }
+    }
+
+    /// Proposes the tightest [Schedule] which fires at every one of the given times and at no other
+    /// time between the earliest and the latest of them, e.g. to recover the schedule of a job
+    /// from its logs. Returns `None` if there are no times or no schedule fits them exactly.
+    ///
+    /// When both a day of month and a day of week restriction fit, the shorter expression wins.
+    ///
+    /// Times are matched in their own timezone. The year field is left unrestricted, so that the
+    /// schedule keeps firing after the latest time.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    ///
+    /// let runs: Vec<_> = [1, 2, 5, 6, 7]
+    ///     .into_iter()
+    ///     .flat_map(|day| [8, 20].map(|hour| Utc.with_ymd_and_hms(2026, 10, day, hour, 30, 0).unwrap()))
+    ///     .collect();
+    /// let schedule = Schedule::infer(&runs).unwrap();
+    /// assert_eq!("0 30 8,20 * 10 2-6", schedule.source());
+    /// ```
+    pub fn infer<Z>(samples: &[DateTime<Z>]) -> Option<Schedule>
+    where
+        Z: TimeZone,
+    {
+        inference::infer(samples)
+    }
+
+    /// Writes the next `n` times this [Schedule] fires in the given timezone as an iCalendar
+    /// (RFC 5545) `VCALENDAR`, so that the runs of a job can be followed from a calendar app.
+    ///
+    /// Each run is an instantaneous `VEVENT`, with times written in UTC. When the timezone stays
+    /// at UTC over the runs and the fields can be written as a recurrence rule, they are a
+    /// single event repeated by an `RRULE` instead. Years, calendars, blackouts, bounds, nearest
+    /// weekdays (`W`) and days matched by either day field are never written as rules.
+    pub fn to_ics<Z>(&self, n: usize, timezone: Z) -> String
+    where
+        Z: TimeZone,
+    {
+        self.to_ics_after(n, &timezone.from_utc_datetime(&Utc::now().naive_utc()))
+    }
+
+    /// Like the `to_ics` method, but with the runs after the given instant rather than now.
+    /// # Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+    /// let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// // Lines longer than 75 bytes are folded, which is undone here.
+    /// let ics = schedule.to_ics_after(10, &after).replace("\r\n ", "");
+    /// assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    /// assert!(ics.contains("DTSTART:20261016T093000Z\r\n"));
+    /// assert!(ics.contains(
+    ///     "RRULE:FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30;BYSECOND=0;COUNT=10\r\n"
+    /// ));
+    /// ```
+    pub fn to_ics_after<Z>(&self, n: usize, after: &DateTime<Z>) -> String
+    where
+        Z: TimeZone,
+    {
+        ics::to_ics(self, n, after)
+    }
+
+    /// Translates this [Schedule] into a Quartz cron expression, with `?` in whichever of the day
+    /// of month and day of week fields is unrestricted. The year field is only written when it is
+    /// restricted.
+    ///
+    /// Quartz can neither restrict both day fields nor combine `L`, `W` or `#` with other values,
+    /// which is reported as untranslatable.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 12 * * Mon-Fri").unwrap();
+    /// assert_eq!("0 0 12 ? * 2-6", schedule.to_quartz().expression);
+    /// ```
+    pub fn to_quartz(&self) -> Translation {
+        export::to_quartz(&self.fields)
+    }
+
+    /// Translates this [Schedule] into a systemd timer `OnCalendar=` expression.
+    ///
+    /// Nearest weekdays (`W`) and specific occurrences of a day of the week (`#` and `L`) have no
+    /// systemd equivalent and are reported as untranslatable.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 */15 9-17 * * Mon-Fri").unwrap();
+    /// let translation = schedule.to_oncalendar();
+    /// assert!(translation.is_exact());
+    /// assert_eq!("Mon..Fri *-*-* 09..17:00/15:00", translation.expression);
+    /// ```
+    pub fn to_oncalendar(&self) -> Translation {
+        export::to_oncalendar(&self.fields)
+    }
+
+    /// Looks for patterns which parse but likely don't do what was meant, e.g. for code review
+    /// tooling. Steps are only checked for schedules which keep the syntax of their fields.
+    /// # Example
+    /// ```
+    /// use cron::{LintKind, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 20/5 31 4,6 ?").unwrap();
+    /// let kinds: Vec<_> = schedule.lint().into_iter().map(|warning| warning.kind).collect();
+    /// assert_eq!(vec![LintKind::StepCoversField, LintKind::DaysMissingFromMonths], kinds);
+    /// assert!(Schedule::from_str("0 */15 9-17 * * Mon-Fri").unwrap().lint().is_empty());
+    /// ```
+    pub fn lint(&self) -> Vec<LintWarning> {
+        lint::lint(self)
+    }
+
+    /// Suggests simpler ways to write fields of the expression which include the same values,
+    /// e.g. `1-5` for `1,2,3,4,5` or `*/30` for `0,30`. Fields written with names, specials or
+    /// random values are left alone, and shorthands have nothing to simplify.
+    /// # Example
+    /// ```
+    /// use cron::{RewriteReason, Schedule};
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0,30 9,10,11,12 * * Mon-Fri").unwrap();
+    /// let suggestions = schedule.suggestions();
+    /// assert_eq!("*/30", suggestions[0].replacement);
+    /// assert_eq!(RewriteReason::Step, suggestions[0].reason);
+    /// assert_eq!("9-12", suggestions[1].replacement);
+    /// assert_eq!("0 0,30 9-12 * * Mon-Fri", suggestions[1].expression);
+    /// ```
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        rewrite::suggestions(self)
+    }
+
     /// Returns a reference to the source cron expression.
     pub fn source(&self) -> &str {
         &self.source
     }
+
+    /// Lays this [Schedule] out field by field, with the values and specials each field
+    /// includes, for front-ends which render and edit schedules. See [ScheduleDescription].
+    pub fn description(&self) -> ScheduleDescription {
+        ScheduleDescription::new(self)
+    }
+
+    /// Returns the trailing `# comment` of the source expression, if any, without the `#`.
+    /// # Example
+    /// ```
+    /// use cron::Schedule;
+    /// use std::str::FromStr;
+    ///
+    /// let schedule = Schedule::from_str("0 0 3 * * *\t# nightly backup").unwrap();
+    /// assert_eq!(Some("nightly backup"), schedule.comment());
+    /// assert_eq!(None, Schedule::from_str("0 0 3 ? * 6#3").unwrap().comment());
+    /// ```
+    pub fn comment(&self) -> Option<&str> {
+        parsing::split_comment(&self.source).1
+    }
 }
 
+/// Writes the source expression. The alternate form, `{:#}`, follows it with the values each
+/// field resolved to, one field per line.
+/// # Example
+/// ```
+/// use cron::Schedule;
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 */20 9-17 L * ?").unwrap();
+/// assert_eq!("0 */20 9-17 L * ?", format!("{schedule}"));
+/// let verbose = format!("{schedule:#}");
+/// assert!(verbose.contains("Minutes:       0,20,40"));
+/// assert!(verbose.contains("Days of Month: L"));
+/// ```
 impl Display for Schedule {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{}", self.source)
+        write!(f, "{}", self.source)?;
+        if !f.alternate() {
+            return Ok(());
+        }
+        let fields = &self.fields;
+        write_field(f, &fields.seconds)?;
+        write_field(f, &fields.minutes)?;
+        write_field(f, &fields.hours)?;
+        write_field(f, &fields.days_of_month)?;
+        write_field(f, &fields.months)?;
+        write_field(f, &fields.days_of_week)?;
+        write_field(f, &fields.years)?;
+        if fields.days_match_either {
+            write!(
+                f,
+                "\n  Days match either the days of month or the days of week"
+            )?;
+        }
+        if fields.leap_day != LeapDayPolicy::Skip {
+            write!(f, "\n  Leap day policy: {:?}", fields.leap_day)?;
+        }
+        if fields.clamp_to_end_of_month {
+            write!(
+                f,
+                "\n  Days past the end of a month are clamped to its last day"
+            )?;
+        }
+        if fields.nearest_weekday != NearestWeekdayPolicy::WithinMonth {
+            write!(
+                f,
+                "\n  Nearest weekday policy: {:?}",
+                fields.nearest_weekday
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Writes a line of the alternate `Display` form, listing the values and specials of a field.
+fn write_field<T: TimeUnitField>(f: &mut Formatter<'_>, field: &T) -> FmtResult {
+    let plain: Vec<Ordinal> = field.iter().collect();
+    let mut values = Vec::new();
+    if field.is_all() {
+        values.push("*".to_string());
+    } else if !plain.is_empty() {
+        values.push(export::format_list(&plain, "-", |ordinal| {
+            ordinal.to_string()
+        }));
+    }
+    values.extend(field.day_specs().iter().map(DaySpec::to_string));
+    write!(
+        f,
+        "\n  {:<14} {}",
+        format!("{}:", T::name()),
+        values.join(",")
+    )
+}
+
+// Converts a SystemTime to UTC, or `None` if it's out of the range chrono represents, unlike
+// chrono's `From` conversion which panics.
+fn utc_from_system_time(time: SystemTime) -> Option<DateTime<Utc>> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => {
+            DateTime::from_timestamp(i64::try_from(since.as_secs()).ok()?, since.subsec_nanos())
+        }
+        Err(error) => {
+            let before = error.duration();
+            let seconds = i64::try_from(before.as_secs()).ok()?;
+            match before.subsec_nanos() {
+                0 => DateTime::from_timestamp(-seconds, 0),
+                nanos => DateTime::from_timestamp(-seconds - 1, 1_000_000_000 - nanos),
+            }
+        }
     }
 }
 
@@ -378,15 +2170,71 @@ impl PartialEq for Schedule {
     }
 }
 
+impl Eq for Schedule {}
+
+impl<Z> Occurrences<Z> for Schedule
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        self.after(after).next()
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        self.after(before).next_back()
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        Schedule::includes(self, date_time)
+    }
+}
+
+/// What a [Schedule] including the 29th of February does in years without one, as set with
+/// [ScheduleParser::leap_day](crate::ScheduleParser::leap_day).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LeapDayPolicy {
+    /// Only fire in leap years.
+    #[default]
+    Skip,
+    /// Fire on the 28th of February instead.
+    LastDayOfFebruary,
+    /// Fire on the 1st of March instead, even if March isn't included by the schedule.
+    FirstOfMarch,
+}
+
+/// Whether the weekday nearest to a day of the month (`W`) may fall in another month, as set with
+/// [ScheduleParser::nearest_weekday](crate::ScheduleParser::nearest_weekday).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NearestWeekdayPolicy {
+    /// Stay within the month as Quartz does, so `1W` fires on Monday the 3rd when the 1st is a
+    /// Saturday, and `LW` on the Friday two days before a month ending on a Sunday.
+    #[default]
+    WithinMonth,
+    /// Take the truly nearest weekday, so `1W` fires on the last day of the previous month when
+    /// the 1st is a Saturday, and `LW` on the 1st of the next month when the month ends on a
+    /// Sunday. The day still fires in the year it's moved to only if the years include it.
+    AcrossMonths,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ScheduleFields {
-    years: Years,
-    days_of_week: DaysOfWeek,
-    months: Months,
-    days_of_month: DaysOfMonth,
-    hours: Hours,
-    minutes: Minutes,
-    seconds: Seconds,
+    pub(crate) years: Years,
+    pub(crate) days_of_week: DaysOfWeek,
+    pub(crate) months: Months,
+    pub(crate) days_of_month: DaysOfMonth,
+    pub(crate) hours: Hours,
+    pub(crate) minutes: Minutes,
+    pub(crate) seconds: Seconds,
+    // Whether a day matches if it's included by either of the day fields rather than by both,
+    // as in Unix cron when both are restricted.
+    pub(crate) days_match_either: bool,
+    pub(crate) leap_day: LeapDayPolicy,
+    // Whether days past the end of a shorter month move to its last day.
+    pub(crate) clamp_to_end_of_month: bool,
+    pub(crate) nearest_weekday: NearestWeekdayPolicy,
+    // The years searched when the expression doesn't restrict them, as set with
+    // `ScheduleParser::year_range`.
+    pub(crate) year_range: RangeInclusive<Ordinal>,
 }
 
 impl ScheduleFields {
@@ -407,7 +2255,269 @@ impl ScheduleFields {
             hours,
             minutes,
             seconds,
+            days_match_either: false,
+            leap_day: LeapDayPolicy::Skip,
+            clamp_to_end_of_month: false,
+            nearest_weekday: NearestWeekdayPolicy::WithinMonth,
+            year_range: Years::bounds(),
+        }
+    }
+
+    // Whether the years aren't restricted beyond the range searched by default, which may have
+    // been widened or narrowed with `ScheduleParser::year_range`.
+    pub(crate) fn every_year(&self) -> bool {
+        self.years
+            .ordinals()
+            .iter()
+            .copied()
+            .eq(self.year_range.clone())
+    }
+
+    // The fields and the policies resolving them written out as text, which doesn't depend on how
+    // the crate stores or encodes them, e.g. `seconds=0;minutes=0,15,30,45;...;year_range=1970-2100`.
+    fn canonical_form(&self) -> String {
+        fn field<T: TimeUnitField>(name: &str, field: &T) -> String {
+            let ordinals: Vec<Ordinal> = field.iter().collect();
+            let mut items = vec![export::format_list(&ordinals, "-", |ordinal| {
+                ordinal.to_string()
+            })];
+            items.extend(field.day_specs().iter().map(DaySpec::to_string));
+            format!("{name}={}", items.join(","))
+        }
+        let leap_day = match self.leap_day {
+            LeapDayPolicy::Skip => "skip",
+            LeapDayPolicy::LastDayOfFebruary => "last_day_of_february",
+            LeapDayPolicy::FirstOfMarch => "first_of_march",
+        };
+        let nearest_weekday = match self.nearest_weekday {
+            NearestWeekdayPolicy::WithinMonth => "within_month",
+            NearestWeekdayPolicy::AcrossMonths => "across_months",
+        };
+        [
+            field("seconds", &self.seconds),
+            field("minutes", &self.minutes),
+            field("hours", &self.hours),
+            field("days_of_month", &self.days_of_month),
+            field("months", &self.months),
+            field("days_of_week", &self.days_of_week),
+            field("years", &self.years),
+            format!("days_match_either={}", self.days_match_either),
+            format!("leap_day={leap_day}"),
+            format!("clamp_to_end_of_month={}", self.clamp_to_end_of_month),
+            format!("nearest_weekday={nearest_weekday}"),
+            format!(
+                "year_range={}-{}",
+                self.year_range.start(),
+                self.year_range.end()
+            ),
+        ]
+        .join(";")
+    }
+
+    // Switches to Unix cron semantics, where a day matches if it's included by either of the day
+    // fields, as long as both are restricted.
+    pub(crate) fn matching_either_day(mut self) -> ScheduleFields {
+        self.days_match_either = !self.days_of_month.is_all() && !self.days_of_week.is_all();
+        self
+    }
+
+    // Whether the 29th of February is included and moved to another day in common years.
+    fn moves_leap_day(&self, policy: LeapDayPolicy) -> bool {
+        self.leap_day == policy && self.months.includes(2) && self.days_of_month.includes(29)
+    }
+
+    // Whether nearest weekdays may be moved into months around the included ones.
+    fn moves_weekdays(&self) -> bool {
+        self.nearest_weekday == NearestWeekdayPolicy::AcrossMonths
+            && self.days_of_month.has_nearest_weekdays()
+    }
+
+    // The nearest weekdays moved into the given month from the last day of the previous month or
+    // the 1st of the next one.
+    fn weekdays_moved_into(&self, month: Ordinal, year: Ordinal) -> OrdinalSet {
+        let mut days = OrdinalSet::new();
+        let last_day = days_in_month(month, year);
+        let first = NaiveDate::from_ymd_opt(year as i32, month, 1);
+        let previous = first.and_then(|first| first.pred_opt());
+        if let Some(previous) = previous.filter(|previous| previous.weekday() == Weekday::Sun) {
+            if self.months.includes(previous.month())
+                && self
+                    .days_of_month
+                    .has_nearest_weekday(previous.day(), previous.day())
+            {
+                days.insert(1);
+            }
+        }
+        let last = NaiveDate::from_ymd_opt(year as i32, month, last_day);
+        let next = last.and_then(|last| last.succ_opt());
+        if let Some(next) = next.filter(|next| next.weekday() == Weekday::Sat) {
+            let next_last_day = days_in_month(next.month(), next.year() as Ordinal);
+            if self.months.includes(next.month())
+                && self.days_of_month.has_nearest_weekday(1, next_last_day)
+            {
+                days.insert(last_day);
+            }
+        }
+        days
+    }
+
+    // The months to search, including March when it may receive the 29th of February, and the
+    // months around the included ones when they may receive nearest weekdays.
+    pub(crate) fn months_to_search(&self) -> Cow<'_, OrdinalSet> {
+        if self.moves_weekdays() {
+            return Cow::Owned((Months::inclusive_min()..=Months::inclusive_max()).collect());
+        }
+        if self.moves_leap_day(LeapDayPolicy::FirstOfMarch) && !self.months.includes(3) {
+            let mut months = self.months.ordinals().clone();
+            months.insert(3);
+            return Cow::Owned(months);
+        }
+        Cow::Borrowed(self.months.ordinals())
+    }
+
+    // The days the day of month field includes in the given month, once the leap day policy and
+    // end of month clamping have been applied.
+    fn resolved_days_of_month(&self, month: Ordinal, year: Ordinal) -> Cow<'_, OrdinalSet> {
+        let mut days = if self.months.includes(month) {
+            self.days_of_month
+                .days_in_month(month, year, self.nearest_weekday)
+        } else {
+            Cow::Owned(OrdinalSet::new())
+        };
+        if self.moves_weekdays() {
+            let moved = self.weekdays_moved_into(month, year);
+            if !moved.is_empty() {
+                days.to_mut().extend(moved);
+            }
         }
+        if !is_leap_year(year) {
+            if month == 2 && self.moves_leap_day(LeapDayPolicy::LastDayOfFebruary) {
+                days.to_mut().insert(28);
+            }
+            if month == 3 && self.moves_leap_day(LeapDayPolicy::FirstOfMarch) {
+                days.to_mut().insert(1);
+            }
+        }
+        // The 29th of February is left to the leap day policy.
+        let last_day = days_in_month(month, year);
+        if self.clamp_to_end_of_month
+            && self.months.includes(month)
+            && self
+                .days_of_month
+                .iter()
+                .any(|day| day > last_day && !(month == 2 && day == 29))
+        {
+            days.to_mut().insert(last_day);
+        }
+        days
+    }
+
+    // The days of the month to consider before checking the days of the week.
+    pub(crate) fn days_of_month(&self, month: Ordinal, year: Ordinal) -> Cow<'_, OrdinalSet> {
+        if self.days_match_either && self.months.includes(month) {
+            return Cow::Owned((1..=days_in_month(month, year)).collect());
+        }
+        self.resolved_days_of_month(month, year)
+    }
+
+    // The lookup tables of the hours, minutes and seconds the search hops between.
+    fn time_lookups(&self) -> (&LookupTable, &LookupTable, &LookupTable) {
+        (
+            self.hours.lookup(),
+            self.minutes.lookup(),
+            self.seconds.lookup(),
+        )
+    }
+
+    // The days of the given month the schedule fires on, found from the days each day field
+    // includes rather than by testing every day of the month.
+    pub(crate) fn matching_days(&self, month: Ordinal, year: Ordinal) -> Cow<'_, OrdinalSet> {
+        let mut days_of_month = self.resolved_days_of_month(month, year);
+        // The day of month field may include days past the end of shorter months.
+        let last_day = days_in_month(month, year);
+        if days_of_month.range(last_day + 1..).next().is_some() {
+            days_of_month.to_mut().split_off(&(last_day + 1));
+        }
+        if self.days_match_either {
+            // Only a moved leap day or nearest weekday can fall outside of the included months,
+            // and those match whatever the day of the week.
+            if !self.months.includes(month) {
+                return days_of_month;
+            }
+            let mut days = days_of_month.into_owned();
+            days.extend(self.days_of_week.days_in_month(month, year));
+            Cow::Owned(days)
+        } else if days_of_month.is_empty() || self.days_of_week.includes_every_day() {
+            days_of_month
+        } else {
+            let days_of_week = self.days_of_week.days_in_month(month, year);
+            Cow::Owned(days_of_month.intersection(&days_of_week).copied().collect())
+        }
+    }
+
+    pub(crate) fn matches_date(&self, date: NaiveDate) -> bool {
+        let included_day_of_month = self
+            .resolved_days_of_month(date.month(), date.year() as Ordinal)
+            .contains(&date.day());
+        // Only a moved leap day or nearest weekday can fall outside of the included months.
+        if !self.months.includes(date.month()) && !included_day_of_month {
+            return false;
+        }
+        if self.days_match_either {
+            included_day_of_month || self.days_of_week.match_day_of(date)
+        } else {
+            included_day_of_month && self.days_of_week.match_day_of(date)
+        }
+    }
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_field(&self.seconds);
+        encoder.write_field(&self.minutes);
+        encoder.write_field(&self.hours);
+        encoder.write_field(&self.days_of_month);
+        encoder.write_field(&self.months);
+        encoder.write_field(&self.days_of_week);
+        encoder.write_field(&self.years);
+        encoder.write_flag(self.days_match_either);
+        encoder.write_u8(self.leap_day as u8);
+        encoder.write_flag(self.clamp_to_end_of_month);
+        encoder.write_u8(self.nearest_weekday as u8);
+        encoder.write_varint(*self.year_range.start());
+        encoder.write_varint(*self.year_range.end());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<ScheduleFields, Error> {
+        let mut fields = ScheduleFields::new(
+            decoder.read_field(false)?,
+            decoder.read_field(false)?,
+            decoder.read_field(false)?,
+            decoder.read_field(true)?,
+            decoder.read_field(false)?,
+            decoder.read_field(true)?,
+            // Years may have been parsed with a wider year range.
+            decoder.read_field_within(false, &YEAR_LIMITS)?,
+        );
+        fields.days_match_either = decoder.read_flag()?;
+        fields.leap_day = match decoder.read_u8()? {
+            0 => LeapDayPolicy::Skip,
+            1 => LeapDayPolicy::LastDayOfFebruary,
+            2 => LeapDayPolicy::FirstOfMarch,
+            policy => return Err(invalid(&format!("unknown leap day policy {policy}"))),
+        };
+        fields.clamp_to_end_of_month = decoder.read_flag()?;
+        fields.nearest_weekday = match decoder.read_u8()? {
+            0 => NearestWeekdayPolicy::WithinMonth,
+            1 => NearestWeekdayPolicy::AcrossMonths,
+            policy => return Err(invalid(&format!("unknown nearest weekday policy {policy}"))),
+        };
+        fields.year_range = decoder.read_varint()?..=decoder.read_varint()?;
+        if fields.year_range.is_empty()
+            || !YEAR_LIMITS.contains(fields.year_range.start())
+            || !YEAR_LIMITS.contains(fields.year_range.end())
+        {
+            return Err(invalid("year range out of bounds"));
+        }
+        Ok(fields)
     }
 }
 
@@ -419,6 +2529,8 @@ where
     previous_datetime: Option<DateTime<Z>>,
     later_datetime: Option<DateTime<Z>>,
     earlier_datetime: Option<DateTime<Z>>,
+    // Whether the iterator was last moved with `next_back`.
+    backwards: bool,
 }
 //TODO: Cutoff datetime?
 
@@ -432,7 +2544,20 @@ where
             previous_datetime: Some(starting_datetime.clone()),
             later_datetime: None,
             earlier_datetime: None,
+            backwards: false,
+        }
+    }
+
+    /// Saves where the iterator stopped, for [Schedule::resume] to carry on from, or returns
+    /// `None` once it's exhausted. Also returns `None` if it was last moved with
+    /// [next_back](DoubleEndedIterator::next_back), as resuming carries on forwards from the
+    /// checkpoint and would go through the occurrences already yielded again.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        if self.backwards {
+            return None;
         }
+        let instant = self.previous_datetime.as_ref()?.with_timezone(&Utc);
+        Some(Checkpoint::new(instant, self.schedule.checkpoint_hash()))
     }
 }
 
@@ -443,6 +2568,7 @@ where
     type Item = DateTime<Z>;
 
     fn next(&mut self) -> Option<DateTime<Z>> {
+        self.backwards = false;
         let previous = self.previous_datetime.take()?;
 
         if let Some(later) = self.later_datetime.take() {
@@ -470,6 +2596,7 @@ where
     Z: TimeZone,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
+        self.backwards = true;
         let previous = self.previous_datetime.take()?;
 
         if let Some(earlier) = self.earlier_datetime.take() {
@@ -583,20 +2710,11 @@ impl<Z: TimeZone> DoubleEndedIterator for OwnedScheduleIterator<Z> {
 }
 
 fn is_leap_year(year: Ordinal) -> bool {
-    let by_four = year % 4 == 0;
-    let by_hundred = year % 100 == 0;
-    let by_four_hundred = year % 400 == 0;
-    by_four && ((!by_hundred) || by_four_hundred)
+    calendar::is_leap_year(year as i32)
 }
 
-fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
-    let is_leap_year = is_leap_year(year);
-    match month {
-        9 | 4 | 6 | 11 => 30,
-        2 if is_leap_year => 29,
-        2 => 28,
-        _ => 31,
-    }
+pub(crate) fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
+    calendar::days_in_month(year as i32, month).unwrap_or(31)
 }
 
 #[cfg(feature = "serde")]
@@ -695,6 +2813,107 @@ mod test {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_de_cron_expr_tokens() {
+        use crate::CronExpr;
+        use serde_test::assert_de_tokens_error;
+
+        let expression = CronExpr::from_str("0 0 9 * * Mon-Fri").expect("valid format");
+        assert_tokens(&expression, &[Token::String("0 0 9 * * Mon-Fri")]);
+        assert_de_tokens_error::<CronExpr>(
+            &[Token::String("0 0 25 * * *")],
+            "0 0 25 * * *\n    ^\nHours must be less than or equal to 23. ('25' specified.)",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_de_checkpoint_tokens() {
+        let schedule = Schedule::from_str("0 0 * * * *").expect("valid format");
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let checkpoint = schedule.after(&start).checkpoint().unwrap();
+        let text = checkpoint.to_string();
+        assert!(text.starts_with("1792152000:"));
+        assert_tokens(&checkpoint, &[Token::Str(Box::leak(text.into_boxed_str()))]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ser_description_tokens() {
+        use serde_test::assert_ser_tokens;
+
+        let schedule = Schedule::from_str("0 0 12 L * ? 2030 # Payroll").expect("valid format");
+        let description = schedule.description().with_timezone("Europe/Paris");
+        let mut tokens = vec![
+            Token::Struct {
+                name: "ScheduleDescription",
+                len: 5,
+            },
+            Token::Str("expression"),
+            Token::Str("0 0 12 L * ? 2030"),
+            Token::Str("comment"),
+            Token::Some,
+            Token::Str("Payroll"),
+            Token::Str("timezone"),
+            Token::Some,
+            Token::Str("Europe/Paris"),
+            Token::Str("days_match_either"),
+            Token::Bool(false),
+            Token::Str("fields"),
+            Token::Seq { len: Some(7) },
+        ];
+        let fields: [(&str, &str, &[u32]); 7] = [
+            ("seconds", "0", &[0]),
+            ("minutes", "0", &[0]),
+            ("hours", "12", &[12]),
+            ("days_of_month", "L", &[]),
+            ("months", "*", &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]),
+            ("days_of_week", "?", &[1, 2, 3, 4, 5, 6, 7]),
+            ("years", "2030", &[2030]),
+        ];
+        for (kind, text, values) in fields {
+            tokens.extend([
+                Token::Struct {
+                    name: "FieldDescription",
+                    len: 5,
+                },
+                Token::Str("kind"),
+                Token::Str(kind),
+                Token::Str("text"),
+                Token::Some,
+                Token::Str(text),
+                Token::Str("is_all"),
+                Token::Bool(text == "*" || text == "?"),
+                Token::Str("values"),
+                Token::Seq {
+                    len: Some(values.len()),
+                },
+            ]);
+            tokens.extend(values.iter().map(|&value| Token::U32(value)));
+            tokens.extend([Token::SeqEnd, Token::Str("specials")]);
+            if kind == "days_of_month" {
+                tokens.extend([
+                    Token::Seq { len: Some(1) },
+                    Token::Struct {
+                        name: "DaySpec",
+                        len: 2,
+                    },
+                    Token::Str("kind"),
+                    Token::Str("last_day_of_month"),
+                    Token::Str("offset"),
+                    Token::U32(0),
+                    Token::StructEnd,
+                ]);
+            } else {
+                tokens.push(Token::Seq { len: Some(0) });
+            }
+            tokens.extend([Token::SeqEnd, Token::StructEnd]);
+        }
+        tokens.extend([Token::SeqEnd, Token::StructEnd]);
+        assert_ser_tokens(&description, &tokens);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_ser_de_schedule_shorthand() {
@@ -956,6 +3175,118 @@ mod test {
         assert!(schedule_3.timeunitspec_eq(&schedule_4));
     }
 
+    #[test]
+    fn test_clone_shares_fields() {
+        let schedule = Schedule::from_str("0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2").unwrap();
+        let clone = schedule.clone();
+        assert!(Arc::ptr_eq(&schedule.fields, &clone.fields));
+        assert!(Arc::ptr_eq(&schedule.source, &clone.source));
+        assert_eq!(schedule, clone);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        for expression in [
+            "* * * * * * *",
+            "@weekly",
+            "0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2",
+            "0 0 12 L-2,15W,LW,3B * ?",
+            "0 0 12 ? * FRIL-1,2#5",
+            "0 R 3 * * ?",
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let decoded = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+            assert_eq!(schedule, decoded);
+            assert!(schedule.timeunitspec_eq(&decoded));
+        }
+    }
+
+    #[test]
+    fn test_binary_is_compact() {
+        let schedule = Schedule::from_str("0 0 0 * * 1 *").unwrap();
+        // Header, source, three bytes per single-value field, one per unrestricted field, the day
+        // matching flag, the leap day policy, the end of month clamping flag, the nearest weekday
+        // policy and two bytes for each end of the year range.
+        assert_eq!(
+            4 + 14 + 3 * 4 + 3 + 1 + 1 + 1 + 1 + 2 * 2,
+            schedule.to_bytes().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_binary_rejects_invalid_input() {
+        let bytes = Schedule::from_str("0 0 12 * * ?")
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(Schedule::from_bytes(&[]).is_err());
+        assert!(Schedule::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Schedule::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        let mut other_version = bytes.clone();
+        other_version[3] += 1;
+        let error = Schedule::from_bytes(&other_version).unwrap_err();
+        assert!(error.to_string().contains("version"));
+
+        // A blob from before the year range was encoded is rejected for its version rather than
+        // misread.
+        let mut old_version = bytes[..bytes.len() - 4].to_vec();
+        old_version[3] = 5;
+        let error = Schedule::from_bytes(&old_version).unwrap_err();
+        assert!(error.to_string().contains("unsupported format version 5"));
+
+        // An hour of 24 in place of the 12
+        let mut out_of_range = bytes.clone();
+        let hours = out_of_range.len() - 13;
+        assert_eq!(&out_of_range[hours - 2..=hours], &[1, 1, 12]);
+        out_of_range[hours] = 24;
+        assert!(Schedule::from_bytes(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_binary_years_never_alias_specials() {
+        let parser = crate::ScheduleParser::new().year_range(1..=9999);
+        for expression in [
+            "0 0 12 L * ? 9999",
+            "0 0 12 ? * 6#5 1,9999",
+            "0 0 12 LW * ? 1-9999/1000",
+            "0 0 12 ? * FRIL-4 9990-9999",
+        ] {
+            let schedule = parser.parse(expression).unwrap();
+            let decoded = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+            assert_eq!(schedule, decoded);
+            assert!(schedule.timeunitspec_eq(&decoded));
+            assert_eq!(schedule.day_of_month_specs(), decoded.day_of_month_specs());
+            assert_eq!(schedule.day_of_week_specs(), decoded.day_of_week_specs());
+        }
+        let schedule = parser.parse("0 0 12 L * ? 9999").unwrap();
+        let last = Utc.with_ymd_and_hms(9999, 12, 30, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(9999, 12, 31, 12, 0, 0).unwrap()),
+            schedule.after(&last).next()
+        );
+        assert!(parser.parse("0 0 12 L * ? 10000").is_err());
+
+        // A year with the bits of a special set, e.g. written by a corrupted cache, is rejected
+        // rather than read as `L`.
+        let bytes = Schedule::from_str("0 0 12 * * ? 2030")
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        // The years field is followed by the four bytes of the flags and policies, and the four
+        // of the year range.
+        let years = bytes.len() - 4 - 4 - 2;
+        assert_eq!(&bytes[years - 2..years + 2], &[1, 1, 0xEE, 0x0F]);
+        let flagged = [
+            &bytes[..years],
+            // 2030 with the highest bit set, as LEB128.
+            &[0xEE, 0x8F, 0x80, 0x80, 0x08],
+            &bytes[years + 2..],
+        ]
+        .concat();
+        assert!(Schedule::from_bytes(&flagged).is_err());
+    }
+
     #[test]
     fn test_dst_ambiguous_time_after() {
         use chrono_tz::Tz;
@@ -1004,4 +3335,116 @@ mod test {
 
         assert_eq!(times.as_slice(), expected_times.as_slice());
     }
+
+    #[test]
+    fn test_matching_days_agree_with_matches_date() {
+        use crate::parsing::{Dialect, ScheduleParser};
+
+        let schedules = [
+            ScheduleParser::new().parse("0 0 0 * * Mon,Wed").unwrap(),
+            ScheduleParser::new().parse("0 0 0 ? * 6#5,2#1").unwrap(),
+            ScheduleParser::new()
+                .parse("0 0 0 ? 2,4 FRIL-1,1L")
+                .unwrap(),
+            ScheduleParser::new().parse("0 0 0 13 * Fri").unwrap(),
+            ScheduleParser::new().parse("0 0 0 29,31 * ?").unwrap(),
+            ScheduleParser::new()
+                .parse("0 0 0 L-3,15W * Tue-Thu")
+                .unwrap(),
+            ScheduleParser::new()
+                .dialect(Dialect::Unix)
+                .parse("0 0 1,15 3 Sun")
+                .unwrap(),
+            ScheduleParser::new()
+                .leap_day(LeapDayPolicy::FirstOfMarch)
+                .parse("0 0 0 29 2 Sat")
+                .unwrap(),
+            ScheduleParser::new()
+                .nearest_weekday(NearestWeekdayPolicy::AcrossMonths)
+                .parse("0 0 0 1W,31W 1,5 ?")
+                .unwrap(),
+        ];
+        for schedule in &schedules {
+            let fields = schedule.fields();
+            for year in 2023..=2030 {
+                for month in 1..=12 {
+                    let expected: OrdinalSet = (1..=days_in_month(month, year))
+                        .filter(|&day| {
+                            let date = NaiveDate::from_ymd_opt(year as i32, month, day).unwrap();
+                            fields.matches_date(date)
+                        })
+                        .collect();
+                    assert_eq!(
+                        expected,
+                        *fields.matching_days(month, year),
+                        "{} in {year}-{month}",
+                        schedule.source()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_tables_agree_with_ordinals() {
+        for expression in ["* * * * * ?", "0 0 0 * * ?", "5-10,58 */7 3,23 * * ?"] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let (hours, minutes, seconds) = schedule.fields.time_lookups();
+            let units = [
+                (hours, schedule.fields.hours.ordinals()),
+                (minutes, schedule.fields.minutes.ordinals()),
+                (seconds, schedule.fields.seconds.ordinals()),
+            ];
+            for (lookup, ordinals) in units {
+                for value in 0..=70 {
+                    assert_eq!(ordinals.contains(&value), lookup.contains(value));
+                    assert_eq!(
+                        ordinals.range(value..).next().copied(),
+                        lookup.at_or_after(value)
+                    );
+                    assert_eq!(
+                        ordinals.range(..=value).next_back().copied(),
+                        lookup.at_or_before(value)
+                    );
+                    assert!(lookup
+                        .ascending_from(value)
+                        .eq(ordinals.range(value..).copied()));
+                    assert!(lookup
+                        .descending_from(value)
+                        .eq(ordinals.range(..=value).rev().copied()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordinal_sets_agree_with_btree_sets() {
+        use std::collections::BTreeSet;
+
+        let mut set = OrdinalSet::new();
+        let mut expected = BTreeSet::new();
+        // Enough values to spill onto the heap, inserted out of order and with repeats.
+        for value in [7, 3, 59, 3, 0, 12, 45, 7, 30, 1, 2, 58, 31, 15] {
+            assert_eq!(expected.insert(value), set.insert(value));
+        }
+        let check = |set: &OrdinalSet, expected: &BTreeSet<Ordinal>| {
+            assert!(set.iter().eq(expected.iter()));
+            assert_eq!(expected.len(), set.len());
+            for value in 0..=60 {
+                assert_eq!(expected.contains(&value), set.contains(&value));
+                assert!(set.range(value..).eq(expected.range(value..)));
+                assert!(set.range(..=value).rev().eq(expected.range(..=value).rev()));
+                assert!(set.range(10..value).eq(expected.range(10..value.max(10))));
+            }
+        };
+        check(&set, &expected);
+
+        assert_eq!(expected.remove(&12), set.remove(&12));
+        assert_eq!(expected.remove(&13), set.remove(&13));
+        let upper = set.split_off(&30);
+        let expected_upper = expected.split_off(&30);
+        check(&set, &expected);
+        check(&upper, &expected_upper);
+        assert_eq!(set, expected.iter().rev().copied().collect::<OrdinalSet>());
+    }
 }
@@ -0,0 +1,155 @@
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use std::str::FromStr;
+
+use crate::error::{Error, ErrorCode, ErrorKind};
+use crate::interval::IntervalSchedule;
+use crate::occurrences::{OccurrenceIterator, Occurrences};
+use crate::parsing;
+use crate::schedule::Schedule;
+
+/// Any of the kinds of schedules a job may run on, so that applications don't need to maintain
+/// their own wrapper around them.
+///
+/// Besides cron expressions, `@once <datetime>` parses to [ScheduleExpr::Once] and `@never` to
+/// [ScheduleExpr::Never]. The datetime is in RFC 3339 format, where the seconds may be left out.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{Occurrences, ScheduleExpr};
+/// use std::str::FromStr;
+///
+/// let now = Utc.with_ymd_and_hms(2024, 12, 31, 12, 0, 0).unwrap();
+/// let once = ScheduleExpr::from_str("@once 2025-01-01T00:00Z").unwrap();
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()), once.next_after(&now));
+///
+/// let cron = ScheduleExpr::from_str("0 0 18 * * *").unwrap();
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2024, 12, 31, 18, 0, 0).unwrap()), cron.next_after(&now));
+///
+/// let never = ScheduleExpr::from_str("@never").unwrap();
+/// assert_eq!(None, never.next_after(&now));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleExpr {
+    /// Fires when the cron expression matches.
+    Cron(Schedule),
+    /// Fires at a fixed interval.
+    Interval(IntervalSchedule),
+    /// Fires a single time.
+    Once(DateTime<Utc>),
+    /// Never fires, e.g. for a job which is disabled.
+    Never,
+}
+
+impl ScheduleExpr {
+    /// Provides an iterator over the times the schedule fires at after the given instant.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> OccurrenceIterator<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        OccurrenceIterator::new(self, after)
+    }
+
+    /// Returns the first time the schedule ever fires in the given timezone, if it has a start.
+    /// See [Schedule::first_occurrence]. Intervals start at their first occurrence.
+    pub fn first_occurrence<Z>(&self, timezone: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match self {
+            ScheduleExpr::Cron(schedule) => schedule.first_occurrence(timezone),
+            ScheduleExpr::Interval(interval) => Some(interval.start().with_timezone(timezone)),
+            ScheduleExpr::Once(once) => Some(once.with_timezone(timezone)),
+            ScheduleExpr::Never => None,
+        }
+    }
+
+    /// Returns the last time the schedule will ever fire in the given timezone, if it stops
+    /// firing. See [Schedule::final_occurrence]. Intervals go on firing forever.
+    pub fn final_occurrence<Z>(&self, timezone: &Z) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match self {
+            ScheduleExpr::Cron(schedule) => schedule.final_occurrence(timezone),
+            ScheduleExpr::Once(once) => Some(once.with_timezone(timezone)),
+            ScheduleExpr::Interval(_) | ScheduleExpr::Never => None,
+        }
+    }
+}
+
+impl<Z> Occurrences<Z> for ScheduleExpr
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        match self {
+            ScheduleExpr::Cron(schedule) => schedule.next_after(after),
+            ScheduleExpr::Interval(interval) => interval.next_after(after),
+            ScheduleExpr::Once(once) => {
+                (once > after).then(|| once.with_timezone(&after.timezone()))
+            }
+            ScheduleExpr::Never => None,
+        }
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        match self {
+            ScheduleExpr::Cron(schedule) => schedule.prev_before(before),
+            ScheduleExpr::Interval(interval) => interval.prev_before(before),
+            ScheduleExpr::Once(once) => {
+                (once < before).then(|| once.with_timezone(&before.timezone()))
+            }
+            ScheduleExpr::Never => None,
+        }
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        match self {
+            ScheduleExpr::Cron(schedule) => schedule.includes(date_time),
+            ScheduleExpr::Interval(interval) => Occurrences::includes(interval, date_time),
+            ScheduleExpr::Once(once) => *once == date_time,
+            ScheduleExpr::Never => false,
+        }
+    }
+}
+
+impl From<Schedule> for ScheduleExpr {
+    fn from(schedule: Schedule) -> ScheduleExpr {
+        ScheduleExpr::Cron(schedule)
+    }
+}
+
+impl From<IntervalSchedule> for ScheduleExpr {
+    fn from(interval: IntervalSchedule) -> ScheduleExpr {
+        ScheduleExpr::Interval(interval)
+    }
+}
+
+impl FromStr for ScheduleExpr {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let trimmed = parsing::split_comment(expression).0.trim();
+        if trimmed == "@never" {
+            return Ok(ScheduleExpr::Never);
+        }
+        match trimmed.strip_prefix("@once") {
+            Some(datetime) if datetime.is_empty() || datetime.starts_with(char::is_whitespace) => {
+                parse_datetime(datetime.trim()).map(ScheduleExpr::Once)
+            }
+            _ => Schedule::from_str(expression).map(ScheduleExpr::Cron),
+        }
+    }
+}
+
+fn parse_datetime(datetime: &str) -> Result<DateTime<Utc>, Error> {
+    DateTime::parse_from_rfc3339(datetime)
+        .or_else(|_| DateTime::<FixedOffset>::parse_from_str(datetime, "%Y-%m-%dT%H:%M%#z"))
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|_| {
+            ErrorKind::Expression(format!(
+                "Invalid datetime for @once: '{datetime}'. Expected e.g. 2025-01-01T00:00Z."
+            ))
+            .with_code(ErrorCode::InvalidDateTime)
+        })
+}
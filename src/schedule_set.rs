@@ -0,0 +1,418 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::iter;
+use std::ops::Range;
+
+use crate::histogram::Histogram;
+use crate::schedule::{Schedule, ScheduleIterator};
+
+/// A collection of keyed [Schedule]s whose upcoming times can be iterated over as a single,
+/// chronologically ordered stream of `(key, datetime)` pairs.
+///
+/// Only the next time of each schedule is computed ahead, so iterating over a set holding many
+/// schedules doesn't require polling each of them.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{Schedule, ScheduleSet};
+/// use std::str::FromStr;
+///
+/// let mut jobs = ScheduleSet::new();
+/// jobs.insert("backup", Schedule::from_str("0 0 2 * * *").unwrap());
+/// jobs.insert("report", Schedule::from_str("0 0 */12 * * *").unwrap());
+///
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap();
+/// let mut upcoming = jobs.after(&start);
+/// assert_eq!(Some((&"backup", Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap())), upcoming.next());
+/// assert_eq!(Some((&"report", Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap())), upcoming.next());
+/// assert_eq!(Some((&"report", Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap())), upcoming.next());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScheduleSet<K> {
+    entries: Vec<(K, Schedule)>,
+}
+
+impl<K> Default for ScheduleSet<K> {
+    fn default() -> Self {
+        ScheduleSet {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<K> ScheduleSet<K> {
+    /// Creates an empty set.
+    pub fn new() -> ScheduleSet<K> {
+        ScheduleSet::default()
+    }
+
+    /// Adds a schedule to the set. Times at which several schedules fire are yielded in the order
+    /// the schedules were inserted.
+    pub fn insert(&mut self, key: K, schedule: Schedule) {
+        self.entries.push((key, schedule));
+    }
+
+    /// Removes the first schedule inserted with the given key and returns it.
+    pub fn remove(&mut self, key: &K) -> Option<Schedule>
+    where
+        K: PartialEq,
+    {
+        let position = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(position).1)
+    }
+
+    /// Returns the schedule inserted with the given key, if any.
+    pub fn get(&self, key: &K) -> Option<&Schedule>
+    where
+        K: PartialEq,
+    {
+        self.iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, schedule)| schedule)
+    }
+
+    /// Iterates over the keys and schedules in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &Schedule)> {
+        self.entries.iter().map(|(key, schedule)| (key, schedule))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Provides an iterator over the times at which any schedule of the set fires, starting with
+    /// the current time if applicable.
+    pub fn upcoming<Z>(&self, timezone: Z) -> ScheduleSetIterator<'_, K, Z>
+    where
+        Z: TimeZone,
+    {
+        self.after(&timezone.from_utc_datetime(&Utc::now().naive_utc()))
+    }
+
+    /// Like the `upcoming` method, but allows you to specify a start time other than the present.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> ScheduleSetIterator<'_, K, Z>
+    where
+        Z: TimeZone,
+    {
+        ScheduleSetIterator::new(self, after)
+    }
+
+    /// Finds the longest stretch of time after `after`, and no later than `horizon` after it,
+    /// during which no schedule of the set fires, e.g. for on-call tooling to check that
+    /// monitoring jobs run at least every hour. The stretch starts at `after` or at a firing and
+    /// ends at the next firing or at the horizon; the earliest one is returned when several are
+    /// as long.
+    ///
+    /// Every firing within the horizon is visited, so sets with schedules firing every second
+    /// should be given a short horizon. Returns `None` if the horizon is out of range.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::{Schedule, ScheduleSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut monitoring = ScheduleSet::new();
+    /// monitoring.insert("probe", Schedule::from_str("0 0 0-11 * * *").unwrap());
+    /// monitoring.insert("audit", Schedule::from_str("0 30 15 * * *").unwrap());
+    ///
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// let gap = monitoring.longest_gap(&start, Duration::days(1)).unwrap();
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 15, 30, 0).unwrap(), gap.start);
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap(), gap.end);
+    /// ```
+    pub fn longest_gap<Z>(
+        &self,
+        after: &DateTime<Z>,
+        horizon: Duration,
+    ) -> Option<Range<DateTime<Z>>>
+    where
+        Z: TimeZone,
+    {
+        let deadline = after.clone().checked_add_signed(horizon)?;
+        let firings = self
+            .after(after)
+            .map(|(_, firing)| firing)
+            .take_while(|firing| *firing <= deadline);
+        let mut longest = after.clone()..after.clone();
+        let mut start = after.clone();
+        for end in firings.chain(iter::once(deadline.clone())) {
+            if end.clone() - start.clone() > longest.end.clone() - longest.start.clone() {
+                longest = start.clone()..end.clone();
+            }
+            start = end;
+        }
+        Some(longest)
+    }
+
+    /// Finds the times at which runs of the schedules of the set would overlap, given how long a
+    /// run of each of them is expected to take, e.g. to plan the capacity of shared workers.
+    ///
+    /// Runs starting after `after` and no later than `horizon` after it are considered, and go on
+    /// until their expected end even past the horizon. Every change in the runs in progress
+    /// starts a new [Overlap], so three runs in progress at once and then two are reported as two
+    /// overlaps. A run ending when another starts doesn't overlap it, and runs whose expected
+    /// duration isn't positive never do.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::{Schedule, ScheduleSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut jobs = ScheduleSet::new();
+    /// jobs.insert("backup", Schedule::from_str("0 0 2 * * *").unwrap());
+    /// jobs.insert("vacuum", Schedule::from_str("0 30 2 * * *").unwrap());
+    ///
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    /// let overlaps = jobs.overlaps(&start, Duration::days(1), |job| match *job {
+    ///     "backup" => Duration::hours(1),
+    ///     _ => Duration::minutes(15),
+    /// });
+    /// assert_eq!(1, overlaps.len());
+    /// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 16, 2, 30, 0).unwrap(), overlaps[0].start);
+    /// assert_eq!(Duration::minutes(15), overlaps[0].duration());
+    /// assert_eq!(vec![&"backup", &"vacuum"], overlaps[0].keys().collect::<Vec<_>>());
+    /// ```
+    pub fn overlaps<Z, F>(
+        &self,
+        after: &DateTime<Z>,
+        horizon: Duration,
+        expected_duration: F,
+    ) -> Vec<Overlap<'_, K, Z>>
+    where
+        Z: TimeZone,
+        F: Fn(&K) -> Duration,
+    {
+        let Some(deadline) = after.clone().checked_add_signed(horizon) else {
+            return Vec::new();
+        };
+        // The start and end of every run, with ends first among events at the same time.
+        let mut runs = Vec::new();
+        let mut events = Vec::new();
+        for (key, start) in self
+            .after(after)
+            .take_while(|(_, start)| *start <= deadline)
+        {
+            let duration = expected_duration(key);
+            let Some(end) = start.clone().checked_add_signed(duration) else {
+                continue;
+            };
+            if duration > Duration::zero() {
+                events.push((start.clone(), true, runs.len()));
+                events.push((end, false, runs.len()));
+                runs.push((key, start));
+            }
+        }
+        events.sort_by(|(time, starts, _), (other_time, other_starts, _)| {
+            (time, starts).cmp(&(other_time, other_starts))
+        });
+
+        let mut overlaps = Vec::new();
+        let mut in_progress: Vec<usize> = Vec::new();
+        let mut events = events.into_iter().peekable();
+        while let Some((time, starts, run)) = events.next() {
+            if starts {
+                in_progress.push(run);
+            } else {
+                in_progress.retain(|&other| other != run);
+            }
+            let Some((next, _, _)) = events.peek() else {
+                break;
+            };
+            if in_progress.len() > 1 && *next > time {
+                let mut ordered = in_progress.clone();
+                ordered.sort_unstable();
+                overlaps.push(Overlap {
+                    start: time,
+                    end: next.clone(),
+                    runs: ordered.into_iter().map(|run| runs[run].clone()).collect(),
+                });
+            }
+        }
+        overlaps
+    }
+
+    /// Counts the times each schedule of the set fires after `after` and no later than
+    /// `horizon` after it, and keeps the first `sample_size` of them in chronological order, so
+    /// that operators can preview the load a change to the schedules would put on their workers
+    /// before deploying it.
+    ///
+    /// Every firing within the horizon is visited, so sets with schedules firing every second
+    /// should be given a short horizon. Nothing is counted if the horizon is out of range.
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use cron::{Schedule, ScheduleSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut jobs = ScheduleSet::new();
+    /// jobs.insert("sync", Schedule::from_str("0 */15 * * * *").unwrap());
+    /// jobs.insert("report", Schedule::from_str("0 0 9 * * Mon-Fri").unwrap());
+    ///
+    /// // Friday the 16th of October 2026.
+    /// let start = Utc.with_ymd_and_hms(2026, 10, 16, 8, 50, 0).unwrap();
+    /// let simulation = jobs.simulate(&start, Duration::days(7), 3);
+    /// assert_eq!(vec![(&"sync", 672), (&"report", 5)], simulation.counts);
+    /// assert_eq!(677, simulation.histogram.total);
+    /// let nine = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     vec![(&"sync", nine), (&"report", nine), (&"sync", nine + Duration::minutes(15))],
+    ///     simulation.sample
+    /// );
+    /// ```
+    pub fn simulate<Z>(
+        &self,
+        after: &DateTime<Z>,
+        horizon: Duration,
+        sample_size: usize,
+    ) -> Simulation<'_, K, Z>
+    where
+        Z: TimeZone,
+    {
+        let mut counts = vec![0; self.entries.len()];
+        let mut sample = Vec::new();
+        let mut histogram = Histogram::default();
+        if let Some(deadline) = after.clone().checked_add_signed(horizon) {
+            let mut firings = self.after(after);
+            while let Some((index, firing)) = firings.next_indexed() {
+                if firing > deadline {
+                    break;
+                }
+                counts[index] += 1;
+                histogram.record(&firing);
+                if sample.len() < sample_size {
+                    sample.push((&self.entries[index].0, firing));
+                }
+            }
+        }
+        Simulation {
+            counts: self
+                .entries
+                .iter()
+                .map(|(key, _)| key)
+                .zip(counts)
+                .collect(),
+            sample,
+            histogram,
+        }
+    }
+}
+
+/// The load the schedules of a [ScheduleSet] put on their workers within a span of time, as
+/// returned by [ScheduleSet::simulate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Simulation<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    /// How many times each schedule fires, in the order the schedules were inserted.
+    pub counts: Vec<(&'a K, u64)>,
+    /// The first firings of every schedule, merged in chronological order.
+    pub sample: Vec<(&'a K, DateTime<Z>)>,
+    /// The firings of every schedule, bucketed by hour of the day, day of the week and month.
+    pub histogram: Histogram,
+}
+
+/// A span of time during which two or more runs of the schedules of a [ScheduleSet] are in
+/// progress at once, as returned by [ScheduleSet::overlaps].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overlap<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    /// The instant the runs start overlapping.
+    pub start: DateTime<Z>,
+    /// The instant one of the runs ends or another one starts, which is excluded from the overlap.
+    pub end: DateTime<Z>,
+    /// The key of the schedule of each run in progress along with the time the run started, in
+    /// the order the runs started.
+    pub runs: Vec<(&'a K, DateTime<Z>)>,
+}
+
+impl<'a, K, Z> Overlap<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    /// The length of the overlap.
+    pub fn duration(&self) -> Duration {
+        self.end.clone() - self.start.clone()
+    }
+
+    /// The keys of the schedules whose runs overlap.
+    pub fn keys(&self) -> impl Iterator<Item = &'a K> + '_ {
+        self.runs.iter().map(|(key, _)| *key)
+    }
+}
+
+impl<K> FromIterator<(K, Schedule)> for ScheduleSet<K> {
+    fn from_iter<I: IntoIterator<Item = (K, Schedule)>>(iter: I) -> Self {
+        ScheduleSet {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K> Extend<(K, Schedule)> for ScheduleSet<K> {
+    fn extend<I: IntoIterator<Item = (K, Schedule)>>(&mut self, iter: I) {
+        self.entries.extend(iter)
+    }
+}
+
+/// Merges the upcoming times of every schedule of a [ScheduleSet].
+pub struct ScheduleSetIterator<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    set: &'a ScheduleSet<K>,
+    iterators: Vec<ScheduleIterator<'a, Z>>,
+    // The next time of each schedule along with its index, earliest first.
+    heap: BinaryHeap<Reverse<(DateTime<Z>, usize)>>,
+}
+
+impl<'a, K, Z> ScheduleSetIterator<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    fn new(set: &'a ScheduleSet<K>, after: &DateTime<Z>) -> Self {
+        let mut iterators: Vec<_> = set
+            .entries
+            .iter()
+            .map(|(_, schedule)| schedule.after(after))
+            .collect();
+        let heap = iterators
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, iterator)| Some(Reverse((iterator.next()?, index))))
+            .collect();
+        ScheduleSetIterator {
+            set,
+            iterators,
+            heap,
+        }
+    }
+
+    // The next time along with the index of the schedule firing at it.
+    fn next_indexed(&mut self) -> Option<(usize, DateTime<Z>)> {
+        let Reverse((datetime, index)) = self.heap.pop()?;
+        if let Some(next) = self.iterators[index].next() {
+            self.heap.push(Reverse((next, index)));
+        }
+        Some((index, datetime))
+    }
+}
+
+impl<'a, K, Z> Iterator for ScheduleSetIterator<'a, K, Z>
+where
+    Z: TimeZone,
+{
+    type Item = (&'a K, DateTime<Z>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, datetime) = self.next_indexed()?;
+        Some((&self.set.entries[index].0, datetime))
+    }
+}
@@ -0,0 +1,262 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use std::f64::consts::PI;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::iter;
+use std::sync::Arc;
+
+use crate::error::{Error, ErrorCode, ErrorKind};
+use crate::occurrences::{OccurrenceIterator, Occurrences};
+use crate::parsing;
+
+// The number of days searched for an occurrence, covering the longest polar day or night.
+const MAX_DAYS: usize = 367;
+
+/// The moments of the day a [SolarSchedule] fires at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SolarEvent {
+    /// When the upper edge of the sun appears over the horizon, `@sunrise`.
+    Sunrise,
+    /// When the upper edge of the sun disappears below the horizon, `@sunset`.
+    Sunset,
+}
+
+/// Computes when the sun rises and sets, resolving the days of a [SolarSchedule].
+///
+/// [SunriseEquation] implements it without any data, and applications needing more precision can
+/// plug in their own implementation, e.g. backed by an ephemeris.
+pub trait SolarProvider: Debug + Send + Sync {
+    /// The instant of the event on the given date, at a latitude and longitude in degrees north
+    /// and east, or `None` if the sun doesn't rise or set that day, as in polar days and nights.
+    fn event_on(
+        &self,
+        event: SolarEvent,
+        date: NaiveDate,
+        latitude: f64,
+        longitude: f64,
+    ) -> Option<DateTime<Utc>>;
+}
+
+/// A [SolarProvider] computing sunrises and sunsets with the sunrise equation used by NOAA's
+/// solar calculator, within a minute or so away from the polar circles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SunriseEquation;
+
+impl SolarProvider for SunriseEquation {
+    fn event_on(
+        &self,
+        event: SolarEvent,
+        date: NaiveDate,
+        latitude: f64,
+        longitude: f64,
+    ) -> Option<DateTime<Utc>> {
+        let radians = |degrees: f64| degrees * PI / 180.0;
+        // Days since noon on the 1st of January 2000, at the mean solar noon of the longitude.
+        let j2000 = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+        let day = date.signed_duration_since(j2000).num_days() as f64 + 0.0008;
+        let mean_noon = day - longitude / 360.0;
+        let anomaly = (357.5291 + 0.98560028 * mean_noon).rem_euclid(360.0);
+        let center = 1.9148 * radians(anomaly).sin()
+            + 0.0200 * radians(2.0 * anomaly).sin()
+            + 0.0003 * radians(3.0 * anomaly).sin();
+        let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+        let transit = mean_noon + 0.0053 * radians(anomaly).sin()
+            - 0.0069 * radians(2.0 * ecliptic_longitude).sin();
+        let declination = (radians(ecliptic_longitude).sin() * radians(23.4397).sin()).asin();
+        let latitude = radians(latitude);
+        let cos_hour_angle = (radians(-0.833).sin() - latitude.sin() * declination.sin())
+            / (latitude.cos() * declination.cos());
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+        let half_day = cos_hour_angle.acos() * 180.0 / PI / 360.0;
+        let days = match event {
+            SolarEvent::Sunrise => transit - half_day,
+            SolarEvent::Sunset => transit + half_day,
+        };
+        // Noon on the 1st of January 2000 is 946728000 seconds after the Unix epoch.
+        let seconds = 946_728_000.0 + days * 86_400.0;
+        DateTime::from_timestamp_millis((seconds * 1000.0).round() as i64)
+    }
+}
+
+/// A schedule firing every day at sunrise or sunset at a given location, optionally shifted by an
+/// offset, e.g. to turn lights on a quarter of an hour before sunset.
+///
+/// Expressions are `@sunrise` or `@sunset`, followed by an offset such as `+30m` or `-1h15m`
+/// made of days (`d`), hours (`h`), minutes (`m`) and seconds (`s`). Occurrences are rounded
+/// down to the second, and days on which the sun doesn't rise or set are skipped.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Timelike, Utc};
+/// use cron::{SolarSchedule, SunriseEquation};
+///
+/// // Fifteen minutes before sunset in Paris.
+/// let lights = SolarSchedule::parse("@sunset-15m", 48.8566, 2.3522, SunriseEquation).unwrap();
+/// let start = Utc.with_ymd_and_hms(2026, 6, 21, 0, 0, 0).unwrap();
+/// let tonight = lights.after(&start).next().unwrap();
+/// assert_eq!((19, 43), (tonight.hour(), tonight.minute()));
+/// assert_eq!("@sunset-15m", lights.to_string());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SolarSchedule {
+    event: SolarEvent,
+    offset: Duration,
+    latitude: f64,
+    longitude: f64,
+    provider: Arc<dyn SolarProvider>,
+}
+
+impl SolarSchedule {
+    /// Parses a `@sunrise` or `@sunset` expression, resolved by the provider at a latitude and
+    /// longitude in degrees north and east.
+    ///
+    /// Fails with [ErrorCode::FieldOutOfRange] if the latitude isn't within -90 to 90 or the
+    /// longitude within -180 to 180.
+    pub fn parse<P>(
+        expression: &str,
+        latitude: f64,
+        longitude: f64,
+        provider: P,
+    ) -> Result<SolarSchedule, Error>
+    where
+        P: SolarProvider + 'static,
+    {
+        let (event, offset) = parse_expression(expression)?;
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(ErrorKind::Expression(format!(
+                "The location {latitude}, {longitude} must have a latitude within -90 to 90 and \
+                 a longitude within -180 to 180."
+            ))
+            .with_code(ErrorCode::FieldOutOfRange));
+        }
+        Ok(SolarSchedule {
+            event,
+            offset,
+            latitude,
+            longitude,
+            provider: Arc::new(provider),
+        })
+    }
+
+    /// The event the schedule fires at.
+    pub fn event(&self) -> SolarEvent {
+        self.event
+    }
+
+    /// The duration every occurrence is shifted by from the event, which may be negative.
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+
+    /// The latitude of the location, in degrees north.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// The longitude of the location, in degrees east.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Provides an iterator over the times the schedule fires at after the given instant.
+    pub fn after<Z>(&self, after: &DateTime<Z>) -> OccurrenceIterator<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        OccurrenceIterator::new(self, after)
+    }
+
+    fn occurrence_on(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        self.provider
+            .event_on(self.event, date, self.latitude, self.longitude)?
+            .with_nanosecond(0)?
+            .checked_add_signed(self.offset)
+    }
+
+    // The date of the event which an occurrence at the given instant would come from, in mean
+    // solar time at the longitude.
+    fn event_date(&self, datetime: DateTime<Utc>) -> Option<NaiveDate> {
+        let solar_time = Duration::seconds((self.longitude * 240.0) as i64);
+        let event = datetime.checked_sub_signed(self.offset)?;
+        Some(event.checked_add_signed(solar_time)?.date_naive())
+    }
+}
+
+fn parse_expression(expression: &str) -> Result<(SolarEvent, Duration), Error> {
+    let invalid = || {
+        ErrorKind::Expression(format!(
+            "Invalid solar expression: '{expression}'. Expected e.g. @sunrise or @sunset-15m."
+        ))
+        .with_code(ErrorCode::Syntax)
+    };
+    let trimmed = expression.trim();
+    let (event, offset) = if let Some(offset) = trimmed.strip_prefix("@sunrise") {
+        (SolarEvent::Sunrise, offset)
+    } else if let Some(offset) = trimmed.strip_prefix("@sunset") {
+        (SolarEvent::Sunset, offset)
+    } else {
+        return Err(invalid());
+    };
+    let offset = match offset.chars().next() {
+        None => Duration::zero(),
+        Some('+') => parsing::parse_duration(&offset[1..]).ok_or_else(invalid)?,
+        Some('-') => -parsing::parse_duration(&offset[1..]).ok_or_else(invalid)?,
+        Some(_) => return Err(invalid()),
+    };
+    Ok((event, offset))
+}
+
+impl<Z> Occurrences<Z> for SolarSchedule
+where
+    Z: TimeZone,
+{
+    fn next_after(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let after_utc = after.with_timezone(&Utc);
+        // The event of the day before may still come after the instant.
+        let start = self.event_date(after_utc)?.pred_opt();
+        iter::successors(start, NaiveDate::succ_opt)
+            .take(MAX_DAYS)
+            .filter_map(|date| self.occurrence_on(date))
+            .find(|occurrence| *occurrence > after_utc)
+            .map(|occurrence| occurrence.with_timezone(&after.timezone()))
+    }
+
+    fn prev_before(&self, before: &DateTime<Z>) -> Option<DateTime<Z>> {
+        let before_utc = before.with_timezone(&Utc);
+        // The event of the day after may still come before the instant.
+        let start = self.event_date(before_utc)?.succ_opt();
+        iter::successors(start, NaiveDate::pred_opt)
+            .take(MAX_DAYS)
+            .filter_map(|date| self.occurrence_on(date))
+            .find(|occurrence| *occurrence < before_utc)
+            .map(|occurrence| occurrence.with_timezone(&before.timezone()))
+    }
+
+    fn includes(&self, date_time: DateTime<Z>) -> bool {
+        let date_time = date_time.with_timezone(&Utc);
+        self.event_date(date_time).is_some_and(|date| {
+            [date.pred_opt(), Some(date), date.succ_opt()]
+                .into_iter()
+                .flatten()
+                .any(|date| self.occurrence_on(date) == Some(date_time))
+        })
+    }
+}
+
+impl Display for SolarSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.event {
+            SolarEvent::Sunrise => write!(f, "@sunrise")?,
+            SolarEvent::Sunset => write!(f, "@sunset")?,
+        }
+        if self.offset.is_zero() {
+            return Ok(());
+        }
+        let sign = if self.offset < Duration::zero() {
+            '-'
+        } else {
+            '+'
+        };
+        write!(f, "{sign}{}", parsing::format_duration(self.offset))
+    }
+}
@@ -8,6 +8,13 @@ pub enum Specifier {
     NamedRange(String, String),
 }
 
+// A single value given either as a number or as a name, e.g. '6' or 'FRI'.
+#[derive(Debug, PartialEq)]
+pub enum PointValue {
+    Ordinal(Ordinal),
+    Named(String),
+}
+
 // Separating out a root specifier allows for a higher tiered specifier, allowing us to achieve
 // periods with base values that are more advanced than an ordinal:
 // - all: '*/2'
@@ -15,11 +22,35 @@ pub enum Specifier {
 // - named range: 'Mon-Thurs/2'
 //
 // Without this separation we would end up with invalid combinations such as 'Mon/2'
+//
+// Random values are also kept at the root, along with the value drawn to pick them: '0~30', '~'
+// Hashed values likewise, with their optional range and step: 'H', 'H(0-29)', 'H/15'
+//
+// Specials which depend on the month being searched also live here, as they can't be combined
+// with periods either:
+// - last occurrence of a weekday in the month, optionally counting back: '6L', 'FRIL-1'
+// - nth occurrence of a weekday in the month: '2#1', 'MON#3'
+// - last day of the month, optionally counting back: 'L', 'L-2'
+// - weekday nearest to a day of the month: '15W'
+// - last weekday of the month: 'LW'
+// - nth business day of the month: '3B'
+//
+// Quarters expand to the three months they span, and are only parsed in the months field: 'Q3'.
+// The month the fiscal year starts on is filled in from the parser, as random draws are.
 #[derive(Debug, PartialEq)]
 pub enum RootSpecifier {
     Specifier(Specifier),
     Period(Specifier, u32),
     NamedPoint(String),
+    Random(Option<Ordinal>, Option<Ordinal>, u64),
+    Hashed(Option<(Ordinal, Ordinal)>, Option<u32>, u64),
+    LastOfMonth(PointValue, Ordinal),
+    NthOfMonth(PointValue, Ordinal),
+    LastDayOfMonth(Ordinal),
+    NearestWeekday(Ordinal),
+    LastWeekdayOfMonth,
+    Quarter(Ordinal, Ordinal),
+    BusinessDay(Ordinal),
 }
 
 impl From<Specifier> for RootSpecifier {
@@ -0,0 +1,55 @@
+use sqlx::database::Database;
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+
+use crate::cron_expr::CronExpr;
+use crate::schedule::Schedule;
+
+// Schedules and expressions are stored as the text of their expression, and parsed again when
+// read, so that a row holding an invalid expression fails to decode.
+macro_rules! impl_text {
+    ($type:ty, $expression:ident) => {
+        impl<DB> Type<DB> for $type
+        where
+            DB: Database,
+            String: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <String as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <String as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB> Encode<'q, DB> for $type
+        where
+            DB: Database,
+            String: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                self.$expression().to_string().encode(buf)
+            }
+        }
+
+        impl<'r, DB> Decode<'r, DB> for $type
+        where
+            DB: Database,
+            String: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let expression = <String as Decode<'r, DB>>::decode(value)?;
+                Ok(<$type>::try_from(expression)?)
+            }
+        }
+    };
+}
+
+impl_text!(Schedule, source);
+impl_text!(CronExpr, as_str);
@@ -0,0 +1,191 @@
+#[cfg(feature = "locales")]
+use crate::locale::Locale;
+use crate::ordinal::Ordinal;
+use crate::parsing::Dialect;
+use crate::specifier::{RootSpecifier, Specifier};
+
+/// The form of a single item of a field, as written in the expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SpecifierForm {
+    /// `*`, or `?` in the day fields.
+    Wildcard,
+    /// A single value, e.g. `5` or `MON`.
+    Value,
+    /// A range of values, e.g. `1-5` or `MON-FRI`.
+    Range,
+    /// Values repeating from a start, e.g. `*/15` or `10-40/10`.
+    Step,
+    /// A value drawn at random, e.g. `~` or `0~30`.
+    Random,
+    /// A value derived from the parser's hash key, e.g. `H`, `H(0-29)` or `H/15`.
+    Hashed,
+    /// The last day of the month or the last occurrence of a weekday, e.g. `L`, `L-2` or `5L`.
+    Last,
+    /// The weekday nearest to a day of the month, e.g. `15W`.
+    Weekday,
+    /// The last weekday of the month, `LW`.
+    LastWeekday,
+    /// The nth occurrence of a weekday in the month, e.g. `6#3`.
+    Nth,
+    /// The months of a quarter of the year, e.g. `Q3`.
+    Quarter,
+    /// The nth business day of the month, e.g. `3B`.
+    BusinessDay,
+}
+
+impl From<&RootSpecifier> for SpecifierForm {
+    fn from(specifier: &RootSpecifier) -> SpecifierForm {
+        match specifier {
+            RootSpecifier::Specifier(Specifier::All) => SpecifierForm::Wildcard,
+            RootSpecifier::Specifier(Specifier::Point(_)) | RootSpecifier::NamedPoint(_) => {
+                SpecifierForm::Value
+            }
+            RootSpecifier::Specifier(Specifier::Range(..) | Specifier::NamedRange(..)) => {
+                SpecifierForm::Range
+            }
+            RootSpecifier::Period(..) => SpecifierForm::Step,
+            RootSpecifier::Random(..) => SpecifierForm::Random,
+            RootSpecifier::Hashed(..) => SpecifierForm::Hashed,
+            RootSpecifier::LastOfMonth(..) | RootSpecifier::LastDayOfMonth(_) => {
+                SpecifierForm::Last
+            }
+            RootSpecifier::NearestWeekday(_) => SpecifierForm::Weekday,
+            RootSpecifier::LastWeekdayOfMonth => SpecifierForm::LastWeekday,
+            RootSpecifier::NthOfMonth(..) => SpecifierForm::Nth,
+            RootSpecifier::Quarter(..) => SpecifierForm::Quarter,
+            RootSpecifier::BusinessDay(_) => SpecifierForm::BusinessDay,
+        }
+    }
+}
+
+/// How a field of the expression was written, as opposed to the values it resolved to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldSyntax {
+    text: String,
+    forms: Vec<SpecifierForm>,
+}
+
+impl FieldSyntax {
+    pub(crate) fn new(text: &str, specifiers: &[RootSpecifier]) -> FieldSyntax {
+        FieldSyntax {
+            text: text.trim().to_string(),
+            forms: specifiers.iter().map(SpecifierForm::from).collect(),
+        }
+    }
+
+    /// The field as written, e.g. `MON-FRI`. Empty if the field was left out.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The form of each comma separated item of the field.
+    pub fn forms(&self) -> &[SpecifierForm] {
+        &self.forms
+    }
+
+    /// Returns true if the field is a comma separated list of several items.
+    pub fn is_list(&self) -> bool {
+        self.forms.len() > 1
+    }
+
+    /// Returns true if the field was left out of the expression, as the seconds are in Unix
+    /// cron or the years may be in Quartz.
+    pub fn is_omitted(&self) -> bool {
+        self.forms.is_empty()
+    }
+
+    /// Returns true if any item of the field was written in the given form.
+    pub fn uses(&self, form: SpecifierForm) -> bool {
+        self.forms.contains(&form)
+    }
+}
+
+/// How each field of an expression was written, as returned by
+/// [Schedule::syntax](crate::Schedule::syntax), so that e.g. an editor can show `*/15` as a step
+/// rather than as the four minutes it resolves to.
+/// # Example
+/// ```
+/// use cron::{Schedule, SpecifierForm};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 */15 9-17 L * ?").unwrap();
+/// let syntax = schedule.syntax().unwrap();
+/// assert_eq!(&[SpecifierForm::Step], syntax.minutes.forms());
+/// assert_eq!(&[SpecifierForm::Range], syntax.hours.forms());
+/// assert!(syntax.days_of_month.uses(SpecifierForm::Last));
+/// assert_eq!("?", syntax.days_of_week.text());
+/// assert!(syntax.years.is_omitted());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScheduleSyntax {
+    pub seconds: FieldSyntax,
+    pub minutes: FieldSyntax,
+    pub hours: FieldSyntax,
+    pub days_of_month: FieldSyntax,
+    pub months: FieldSyntax,
+    pub days_of_week: FieldSyntax,
+    pub years: FieldSyntax,
+    pub(crate) dialect: Dialect,
+    // The month the fiscal year starts on, which quarters were resolved against.
+    pub(crate) fiscal_year_start: Option<Ordinal>,
+    // The seed and hash key random and hashed values were drawn with.
+    pub(crate) seed: Option<u64>,
+    pub(crate) hash_key: Option<u64>,
+    // The locale names were translated from.
+    #[cfg(feature = "locales")]
+    pub(crate) locale: Option<Locale>,
+    // The limits of the parser, which replaced fields must stay within too.
+    pub(crate) max_length: Option<usize>,
+    pub(crate) max_list_items: Option<usize>,
+    // Whether the expression was a shorthand such as `@daily`, which only keeps the options of
+    // the parser and not the syntax of its fields.
+    pub(crate) shorthand: bool,
+}
+
+impl ScheduleSyntax {
+    pub(crate) fn new(dialect: Dialect) -> ScheduleSyntax {
+        ScheduleSyntax {
+            dialect,
+            ..ScheduleSyntax::default()
+        }
+    }
+
+    // Writes the fields back out as an expression, leaving out the omitted ones.
+    pub(crate) fn expression(&self) -> String {
+        [
+            &self.seconds,
+            &self.minutes,
+            &self.hours,
+            &self.days_of_month,
+            &self.months,
+            &self.days_of_week,
+            &self.years,
+        ]
+        .iter()
+        .filter(|field| !field.is_omitted())
+        .map(|field| field.text())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    // The syntax with the field at `position` written as `text` instead, keeping its forms.
+    pub(crate) fn replacing_text(&self, position: u64, text: &str) -> ScheduleSyntax {
+        let mut syntax = self.clone();
+        syntax.field_mut(position).text = text.to_string();
+        syntax
+    }
+
+    // The syntax of the field at the given position, in the order of a Quartz expression.
+    pub(crate) fn field_mut(&mut self, position: u64) -> &mut FieldSyntax {
+        match position {
+            0 => &mut self.seconds,
+            1 => &mut self.minutes,
+            2 => &mut self.hours,
+            3 => &mut self.days_of_month,
+            4 => &mut self.months,
+            5 => &mut self.days_of_week,
+            _ => &mut self.years,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, TimeZone, Utc};
+use tokio::time::{sleep_until, Duration, Instant};
+
+use crate::occurrences::Occurrences;
+use crate::schedule::Schedule;
+
+// The longest to sleep before looking at the system clock again. Tokio's timers run on a
+// monotonic clock, so checking the wall clock regularly catches it being set forward or back, and
+// also keeps far off firings within the range of the timers.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+// Sleeps until the first time the schedule fires at after the given instant, and returns it.
+pub(crate) async fn next_tick<O, Z>(occurrences: &O, after: &DateTime<Z>) -> Option<DateTime<Z>>
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    let next = occurrences.next_after(after)?;
+    sleep_until_occurrence(occurrences, next, None).await
+}
+
+// Sleeps until the system clock reaches the given occurrence, and returns it. If the clock is set
+// back meanwhile, the first occurrence after the new time is waited for instead, as long as it's
+// after `floor`, so that occurrences already yielded aren't yielded again.
+async fn sleep_until_occurrence<O, Z>(
+    occurrences: &O,
+    mut next: DateTime<Z>,
+    floor: Option<&DateTime<Z>>,
+) -> Option<DateTime<Z>>
+where
+    O: Occurrences<Z> + ?Sized,
+    Z: TimeZone,
+{
+    loop {
+        match (next.with_timezone(&Utc) - Utc::now()).to_std() {
+            Ok(remaining) if !remaining.is_zero() => {
+                sleep_until(Instant::now() + remaining.min(MAX_SLEEP)).await
+            }
+            // The firing is now or has just passed.
+            _ => return Some(next),
+        }
+        // If the clock was set back, a firing may now come before the one waited for.
+        let now = Utc::now().with_timezone(&next.timezone());
+        if now < next {
+            let after = match floor {
+                Some(floor) if *floor > now => floor.clone(),
+                _ => now,
+            };
+            next = occurrences.next_after(&after)?;
+        }
+    }
+}
+
+/// What a [Ticks] stream does with the firings it missed because [Ticks::tick] was called late,
+/// e.g. when a job ran for longer than the time between two firings. Analogous to tokio's
+/// `MissedTickBehavior`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MissedTickBehavior {
+    /// Yields each missed firing right away, until the stream has caught up.
+    #[default]
+    Burst,
+    /// Drops the missed firings and waits for the next one to come.
+    Skip,
+    /// Yields the last missed firing right away and drops the ones before it, then continues from
+    /// the current time.
+    Delay,
+}
+
+/// An asynchronous stream of the times a [Schedule] fires at, waiting for each of them to come,
+/// as returned by [Schedule::ticks].
+/// # Example
+/// ```
+/// use chrono::Utc;
+/// use cron::{MissedTickBehavior, Schedule};
+/// use std::str::FromStr;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let schedule = Schedule::from_str("* * * * * *").unwrap();
+/// let mut ticks = schedule.ticks(Utc);
+/// ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+/// let first = ticks.tick().await.unwrap();
+/// let second = ticks.tick().await.unwrap();
+/// assert!(first < second);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ticks<Z>
+where
+    Z: TimeZone,
+{
+    schedule: Schedule,
+    previous: DateTime<Z>,
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+impl<Z> Ticks<Z>
+where
+    Z: TimeZone,
+{
+    pub(crate) fn new(schedule: Schedule, after: DateTime<Z>) -> Ticks<Z> {
+        Ticks {
+            schedule,
+            previous: after,
+            missed_tick_behavior: MissedTickBehavior::default(),
+        }
+    }
+
+    /// What the stream does with missed firings.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Sets what the stream does with missed firings.
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    /// Waits until the next firing and returns it, or returns a missed firing right away
+    /// depending on the [MissedTickBehavior]. Returns `None` once the schedule doesn't fire
+    /// anymore.
+    pub async fn tick(&mut self) -> Option<DateTime<Z>> {
+        let now = Utc::now().with_timezone(&self.previous.timezone());
+        let mut next = self.schedule.next_after(&self.previous)?;
+        if next < now {
+            match self.missed_tick_behavior {
+                MissedTickBehavior::Burst => {}
+                MissedTickBehavior::Skip => next = self.schedule.next_after(&now)?,
+                MissedTickBehavior::Delay => {
+                    next = self.schedule.prev_before(&now).unwrap_or(next);
+                    self.previous = now;
+                    return Some(next);
+                }
+            }
+        }
+        let next = sleep_until_occurrence(&self.schedule, next, Some(&self.previous)).await?;
+        self.previous = next.clone();
+        Some(next)
+    }
+}
@@ -1,19 +1,33 @@
-use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::calendar;
+use crate::error::Error;
+use crate::ordinal::*;
+use crate::schedule::NearestWeekdayPolicy;
+use crate::time_unit::{
+    fmt_ordinals, unsupported_day_spec, DayOfMonthSpec, DaySpec, TimeUnitField,
+};
+use chrono::{Datelike, NaiveDate};
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::fmt;
 
 static ALL: Lazy<OrdinalSet> = Lazy::new(DaysOfMonth::supported_ordinals);
 
-#[derive(Clone, Debug, Eq)]
+#[derive(Clone, Eq)]
 pub struct DaysOfMonth {
     ordinals: Option<OrdinalSet>,
+    // Sorted and without duplicates.
+    specs: Vec<DayOfMonthSpec>,
+    // Whether business days may fall after the nth weekday of the month, as holidays from the
+    // calendars attached to the schedule aren't counted. Left out of comparisons, like calendars.
+    holidays: bool,
 }
 
 impl TimeUnitField for DaysOfMonth {
     fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
         DaysOfMonth {
             ordinals: ordinal_set,
+            specs: Vec::new(),
+            holidays: false,
         }
     }
     fn name() -> Cow<'static, str> {
@@ -31,10 +45,122 @@ impl TimeUnitField for DaysOfMonth {
             None => &ALL,
         }
     }
+    fn day_specs(&self) -> Vec<DaySpec> {
+        self.specs.iter().copied().map(DaySpec::OfMonth).collect()
+    }
+    fn with_day_specs(mut self, specs: Vec<DaySpec>) -> Result<Self, Error> {
+        for spec in specs {
+            match spec {
+                DaySpec::OfMonth(spec) => self.specs.push(spec),
+                DaySpec::OfWeek(_) => return Err(unsupported_day_spec::<Self>(&spec)),
+            }
+        }
+        self.specs.sort_unstable();
+        self.specs.dedup();
+        Ok(self)
+    }
+}
+
+impl DaysOfMonth {
+    // The field of a single special, e.g. `L`.
+    pub(crate) fn from_spec(spec: DayOfMonthSpec) -> DaysOfMonth {
+        DaysOfMonth {
+            ordinals: Some(OrdinalSet::new()),
+            specs: vec![spec],
+            holidays: false,
+        }
+    }
+
+    /// The values of the field which depend on the month, like `L-2` or `15W`.
+    pub fn specs(&self) -> &[DayOfMonthSpec] {
+        &self.specs
+    }
+
+    /// Returns the included days of the given month, with specials such as `L-2` or `15W`
+    /// resolved to the day they fall on that month. Nearest weekdays which the policy moves into
+    /// another month are left out. When holidays are skipped, business days resolve to every
+    /// weekday they may be pushed back to, leaving the schedule to pick the one they fall on.
+    pub fn days_in_month(
+        &self,
+        month: Ordinal,
+        year: Ordinal,
+        policy: NearestWeekdayPolicy,
+    ) -> Cow<'_, OrdinalSet> {
+        let ordinals = self.ordinals();
+        if self.specs.is_empty() {
+            return Cow::Borrowed(ordinals);
+        }
+        let mut days = ordinals.clone();
+        for spec in &self.specs {
+            let Some(day) = spec.day_in_month(year as i32, month, policy) else {
+                continue;
+            };
+            days.insert(day);
+            if self.holidays && matches!(spec, DayOfMonthSpec::BusinessDay { .. }) {
+                let weekdays = (day + 1..=calendar::days_in_month(year as i32, month).unwrap_or(0))
+                    .filter(|&later| is_weekday(year, month, later));
+                days.extend(weekdays);
+            }
+        }
+        Cow::Owned(days)
+    }
+
+    // Whether the given day is included by anything other than a business day, which holidays
+    // may push back.
+    pub(crate) fn includes_besides_business_days(
+        &self,
+        date: NaiveDate,
+        policy: NearestWeekdayPolicy,
+    ) -> bool {
+        self.ordinals().contains(&date.day())
+            || self.specs.iter().any(|spec| {
+                !matches!(spec, DayOfMonthSpec::BusinessDay { .. })
+                    && spec.day_in_month(date.year(), date.month(), policy) == Some(date.day())
+            })
+    }
+
+    // The business days of the month the field includes, e.g. 3 for `3B`.
+    pub(crate) fn business_days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.specs.iter().filter_map(|spec| match *spec {
+            DayOfMonthSpec::BusinessDay { nth } => Some(nth),
+            _ => None,
+        })
+    }
+
+    // Resolves business days to every weekday holidays may push them back to, for schedules
+    // with calendars attached.
+    pub(crate) fn skip_holidays(&mut self) {
+        self.holidays = true;
+    }
+
+    // Whether a `W` special resolves from the given day of a month ending on `last_day`.
+    pub(crate) fn has_nearest_weekday(&self, day: Ordinal, last_day: Ordinal) -> bool {
+        self.specs.iter().any(|spec| match *spec {
+            DayOfMonthSpec::NearestWeekday { day: from } => from == day,
+            DayOfMonthSpec::LastWeekday => day == last_day,
+            DayOfMonthSpec::LastDay { .. } | DayOfMonthSpec::BusinessDay { .. } => false,
+        })
+    }
+
+    // Whether the field has any `W` special.
+    pub(crate) fn has_nearest_weekdays(&self) -> bool {
+        self.specs.iter().any(DayOfMonthSpec::is_nearest_weekday)
+    }
+}
+
+fn is_weekday(year: Ordinal, month: Ordinal, day: Ordinal) -> bool {
+    NaiveDate::from_ymd_opt(year as i32, month, day)
+        .is_some_and(|date| date.weekday().number_from_monday() <= 5)
 }
 
 impl PartialEq for DaysOfMonth {
     fn eq(&self, other: &DaysOfMonth) -> bool {
-        self.ordinals() == other.ordinals()
+        self.ordinals() == other.ordinals() && self.specs == other.specs
+    }
+}
+
+impl fmt::Debug for DaysOfMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_ordinals(self, "DaysOfMonth", f)
     }
 }
@@ -1,20 +1,37 @@
 use crate::error::*;
-use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::ordinal::*;
+use crate::schedule::days_in_month;
+use crate::time_unit::{fmt_ordinals, unsupported_day_spec, DayOfWeekSpec, DaySpec, TimeUnitField};
+use chrono::{Datelike, NaiveDate};
 use once_cell::sync::Lazy;
+use phf::phf_map;
 use std::borrow::Cow;
+use std::fmt;
 
 static ALL: Lazy<OrdinalSet> = Lazy::new(DaysOfWeek::supported_ordinals);
 
-#[derive(Clone, Debug, Eq)]
+static NAMES: phf::Map<&'static str, Ordinal> = phf_map! {
+    "sun" => 1, "sunday" => 1,
+    "mon" => 2, "monday" => 2,
+    "tue" => 3, "tues" => 3, "tuesday" => 3,
+    "wed" => 4, "weds" => 4, "wednesday" => 4,
+    "thu" => 5, "thur" => 5, "thurs" => 5, "thursday" => 5,
+    "fri" => 6, "friday" => 6,
+    "sat" => 7, "saturday" => 7,
+};
+
+#[derive(Clone, Eq)]
 pub struct DaysOfWeek {
     ordinals: Option<OrdinalSet>,
+    // Sorted and without duplicates.
+    specs: Vec<DayOfWeekSpec>,
 }
 
 impl TimeUnitField for DaysOfWeek {
     fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
         DaysOfWeek {
             ordinals: ordinal_set,
+            specs: Vec::new(),
         }
     }
     fn name() -> Cow<'static, str> {
@@ -27,24 +44,13 @@ impl TimeUnitField for DaysOfWeek {
         7
     }
     fn ordinal_from_name(name: &str) -> Result<Ordinal, Error> {
-        //TODO: Use phf crate
-        let ordinal = match name.to_lowercase().as_ref() {
-            "sun" | "sunday" => 1,
-            "mon" | "monday" => 2,
-            "tue" | "tues" | "tuesday" => 3,
-            "wed" | "wednesday" => 4,
-            "thu" | "thurs" | "thursday" => 5,
-            "fri" | "friday" => 6,
-            "sat" | "saturday" => 7,
-            _ => {
-                return Err(ErrorKind::Expression(format!(
-                    "'{}' is not a valid day of the week.",
-                    name
-                ))
-                .into())
-            }
-        };
-        Ok(ordinal)
+        NAMES
+            .get(name.to_lowercase().as_str())
+            .copied()
+            .ok_or_else(|| {
+                ErrorKind::Expression(format!("'{}' is not a valid day of the week.", name))
+                    .with_code(ErrorCode::UnknownName)
+            })
     }
     fn ordinals(&self) -> &OrdinalSet {
         match &self.ordinals {
@@ -52,10 +58,73 @@ impl TimeUnitField for DaysOfWeek {
             None => &ALL,
         }
     }
+    fn day_specs(&self) -> Vec<DaySpec> {
+        self.specs.iter().copied().map(DaySpec::OfWeek).collect()
+    }
+    fn with_day_specs(mut self, specs: Vec<DaySpec>) -> Result<Self, Error> {
+        for spec in specs {
+            match spec {
+                DaySpec::OfWeek(spec) => self.specs.push(spec),
+                DaySpec::OfMonth(_) => return Err(unsupported_day_spec::<Self>(&spec)),
+            }
+        }
+        self.specs.sort_unstable();
+        self.specs.dedup();
+        Ok(self)
+    }
+}
+
+impl DaysOfWeek {
+    /// Returns true if every day of the week is included, so that the field matches any date.
+    pub fn includes_every_day(&self) -> bool {
+        self.ordinals
+            .as_ref()
+            .is_none_or(|ordinals| ordinals.len() == 7)
+    }
+
+    /// The values of the field which depend on the month, like `6#3` or `FRIL`.
+    pub fn specs(&self) -> &[DayOfWeekSpec] {
+        &self.specs
+    }
+
+    /// Returns the days of the given month falling on one of the included days of the week,
+    /// stepping a week at a time from the first occurrence of each rather than testing every day.
+    pub fn days_in_month(&self, month: Ordinal, year: Ordinal) -> OrdinalSet {
+        let Some(first) = NaiveDate::from_ymd_opt(year as i32, month, 1) else {
+            return OrdinalSet::new();
+        };
+        let last_day = days_in_month(month, year);
+        let first_day_of_week = first.weekday().number_from_sunday();
+        let mut days = OrdinalSet::new();
+        for &day_of_week in self.ordinals() {
+            // The first day of the month falling on this day of the week.
+            let first_day = 1 + (day_of_week + 7 - first_day_of_week) % 7;
+            days.extend((first_day..=last_day).step_by(7));
+        }
+        days.extend(
+            self.specs
+                .iter()
+                .filter_map(|spec| spec.day_in_month(year as i32, month)),
+        );
+        days
+    }
+
+    /// Returns true if the given date falls on one of the included days of the week, either
+    /// directly or through a special such as `FRIL-1` or `MON#3`.
+    pub fn match_day_of(&self, date: NaiveDate) -> bool {
+        let day_of_week = date.weekday().number_from_sunday();
+        self.ordinals().contains(&day_of_week) || self.specs.iter().any(|spec| spec.matches(date))
+    }
 }
 
 impl PartialEq for DaysOfWeek {
     fn eq(&self, other: &DaysOfWeek) -> bool {
-        self.ordinals() == other.ordinals()
+        self.ordinals() == other.ordinals() && self.specs == other.specs
+    }
+}
+
+impl fmt::Debug for DaysOfWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_ordinals(self, "DaysOfWeek", f)
     }
 }
@@ -4,6 +4,8 @@ mod hours;
 mod minutes;
 mod months;
 mod seconds;
+mod specs;
+mod unix_days_of_week;
 mod years;
 
 pub use self::days_of_month::DaysOfMonth;
@@ -12,18 +14,23 @@ pub use self::hours::Hours;
 pub use self::minutes::Minutes;
 pub use self::months::Months;
 pub use self::seconds::Seconds;
+pub(crate) use self::specs::to_weekday;
+pub use self::specs::{DayOfMonthSpec, DayOfWeekSpec, DaySpec};
+pub(crate) use self::unix_days_of_week::UnixDaysOfWeek;
 pub use self::years::Years;
+pub(crate) use self::years::YEAR_LIMITS;
 
 use crate::error::*;
-use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::specifier::{RootSpecifier, Specifier};
+use crate::ordinal::*;
+use crate::specifier::{PointValue, RootSpecifier, Specifier};
 use std::borrow::Cow;
-use std::collections::btree_set;
+use std::fmt;
 use std::iter;
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
+use std::slice;
 
 pub struct OrdinalIter<'a> {
-    set_iter: btree_set::Iter<'a, Ordinal>,
+    set_iter: slice::Iter<'a, Ordinal>,
 }
 
 impl Iterator for OrdinalIter<'_> {
@@ -40,7 +47,7 @@ impl DoubleEndedIterator for OrdinalIter<'_> {
 }
 
 pub struct OrdinalRangeIter<'a> {
-    range_iter: btree_set::Range<'a, Ordinal>,
+    range_iter: slice::Iter<'a, Ordinal>,
 }
 
 impl Iterator for OrdinalRangeIter<'_> {
@@ -193,15 +200,42 @@ where
         }
     }
     fn count(&self) -> u32 {
-        self.ordinals().len() as u32
+        TimeUnitSpec::iter(self).count() as u32
     }
 
     fn is_all(&self) -> bool {
         let max_supported_ordinals = Self::inclusive_max() - Self::inclusive_min() + 1;
-        self.ordinals().len() == max_supported_ordinals as usize
+        TimeUnitSpec::count(self) == max_supported_ordinals
     }
 }
 
+// Writes a field for `Debug`, with runs of plain ordinals as ranges and specials spelled out, e.g. `DaysOfWeek([2..=6, 6 NTH#3])`.
+pub(crate) fn fmt_ordinals<T: TimeUnitField>(
+    field: &T,
+    name: &str,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let plain: Vec<Ordinal> = field.iter().collect();
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while index < plain.len() {
+        let mut end = index;
+        while end + 1 < plain.len() && plain[end + 1] == plain[end] + 1 {
+            end += 1;
+        }
+        if end == index {
+            entries.push(plain[index].to_string());
+        } else {
+            entries.push(format!("{}..={}", plain[index], plain[end]));
+        }
+        index = end + 1;
+    }
+    entries.extend(field.day_specs().iter().map(DaySpec::describe));
+    f.debug_tuple(name)
+        .field(&format_args!("[{}]", entries.join(", ")))
+        .finish()
+}
+
 pub trait TimeUnitField
 where
     Self: Sized,
@@ -212,10 +246,27 @@ where
     fn inclusive_max() -> Ordinal;
     fn ordinals(&self) -> &OrdinalSet;
 
+    // The values which depend on the month, like `L` or `6#3`. Only the day fields have any.
+    fn day_specs(&self) -> Vec<DaySpec> {
+        Vec::new()
+    }
+
+    // Adds values which depend on the month to the field, failing for those it can't hold.
+    fn with_day_specs(self, specs: Vec<DaySpec>) -> Result<Self, Error> {
+        match specs.first() {
+            None => Ok(self),
+            Some(spec) => Err(unsupported_day_spec::<Self>(spec)),
+        }
+    }
+
     fn from_ordinal(ordinal: Ordinal) -> Self {
         Self::from_ordinal_set(iter::once(ordinal).collect())
     }
 
+    fn bounds() -> RangeInclusive<Ordinal> {
+        Self::inclusive_min()..=Self::inclusive_max()
+    }
+
     fn supported_ordinals() -> OrdinalSet {
         (Self::inclusive_min()..Self::inclusive_max() + 1).collect()
     }
@@ -235,38 +286,57 @@ where
             Self::name(),
             name
         ))
-        .into())
+        .with_code(ErrorCode::UnknownName))
     }
     fn validate_ordinal(ordinal: Ordinal) -> Result<Ordinal, Error> {
+        Self::validate_ordinal_within(ordinal, &Self::bounds())
+    }
+
+    // Validates an ordinal against narrower or wider bounds than the field's own, as configured
+    // for years with `ScheduleParser::year_range`.
+    fn validate_ordinal_within(
+        ordinal: Ordinal,
+        bounds: &RangeInclusive<Ordinal>,
+    ) -> Result<Ordinal, Error> {
         //println!("validate_ordinal for {} => {}", Self::name(), ordinal);
         match ordinal {
-            i if i < Self::inclusive_min() => Err(ErrorKind::Expression(format!(
+            i if i < *bounds.start() => Err(ErrorKind::Expression(format!(
                 "{} must be greater than or equal to {}. ('{}' \
                  specified.)",
                 Self::name(),
-                Self::inclusive_min(),
+                *bounds.start(),
                 i
             ))
-            .into()),
-            i if i > Self::inclusive_max() => Err(ErrorKind::Expression(format!(
-                "{} must be less than {}. ('{}' specified.)",
+            .with_code(ErrorCode::FieldOutOfRange)),
+            i if i > *bounds.end() => Err(ErrorKind::Expression(format!(
+                "{} must be less than or equal to {}. ('{}' specified.)",
                 Self::name(),
-                Self::inclusive_max(),
+                *bounds.end(),
                 i
             ))
-            .into()),
+            .with_code(ErrorCode::FieldOutOfRange)),
             i => Ok(i),
         }
     }
 
     fn ordinals_from_specifier(specifier: &Specifier) -> Result<OrdinalSet, Error> {
+        Self::ordinals_from_specifier_within(specifier, &Self::bounds())
+    }
+
+    fn ordinals_from_specifier_within(
+        specifier: &Specifier,
+        bounds: &RangeInclusive<Ordinal>,
+    ) -> Result<OrdinalSet, Error> {
         use self::Specifier::*;
         //println!("ordinals_from_specifier for {} => {:?}", Self::name(), specifier);
         match *specifier {
-            All => Ok(Self::supported_ordinals()),
+            All => Ok(bounds.clone().collect()),
             Point(ordinal) => Ok(([ordinal]).iter().cloned().collect()),
             Range(start, end) => {
-                match (Self::validate_ordinal(start), Self::validate_ordinal(end)) {
+                match (
+                    Self::validate_ordinal_within(start, bounds),
+                    Self::validate_ordinal_within(end, bounds),
+                ) {
                     (Ok(start), Ok(end)) if start <= end => Ok((start..end + 1).collect()),
                     _ => Err(ErrorKind::Expression(format!(
                         "Invalid range for {}: {}-{}",
@@ -274,13 +344,16 @@ where
                         start,
                         end
                     ))
-                    .into()),
+                    .with_code(ErrorCode::InvalidRange)),
                 }
             }
             NamedRange(ref start_name, ref end_name) => {
                 let start = Self::ordinal_from_name(start_name)?;
                 let end = Self::ordinal_from_name(end_name)?;
-                match (Self::validate_ordinal(start), Self::validate_ordinal(end)) {
+                match (
+                    Self::validate_ordinal_within(start, bounds),
+                    Self::validate_ordinal_within(end, bounds),
+                ) {
                     (Ok(start), Ok(end)) if start <= end => Ok((start..end + 1).collect()),
                     _ => Err(ErrorKind::Expression(format!(
                         "Invalid named range for {}: {}-{}",
@@ -288,37 +361,58 @@ where
                         start_name,
                         end_name
                     ))
-                    .into()),
+                    .with_code(ErrorCode::InvalidRange)),
                 }
             }
         }
     }
 
-    fn ordinals_from_root_specifier(root_specifier: &RootSpecifier) -> Result<OrdinalSet, Error> {
+    fn ordinal_from_point_value(
+        value: &PointValue,
+        bounds: &RangeInclusive<Ordinal>,
+    ) -> Result<Ordinal, Error> {
+        let ordinal = match value {
+            PointValue::Ordinal(ordinal) => *ordinal,
+            PointValue::Named(name) => Self::ordinal_from_name(name)?,
+        };
+        Self::validate_ordinal_within(ordinal, bounds)
+    }
+
+    fn resolve_root_specifier(root_specifier: &RootSpecifier) -> Result<Resolved, Error> {
+        Self::resolve_root_specifier_within(root_specifier, &Self::bounds())
+    }
+
+    fn resolve_root_specifier_within(
+        root_specifier: &RootSpecifier,
+        bounds: &RangeInclusive<Ordinal>,
+    ) -> Result<Resolved, Error> {
         let ordinals = match root_specifier {
-            RootSpecifier::Specifier(specifier) => Self::ordinals_from_specifier(specifier)?,
+            RootSpecifier::Specifier(specifier) => {
+                Self::ordinals_from_specifier_within(specifier, bounds)?
+            }
             RootSpecifier::Period(_, 0) => Err(ErrorKind::Expression(
                 "range step cannot be zero".to_string(),
-            ))?,
+            )
+            .with_code(ErrorCode::InvalidStep))?,
             RootSpecifier::Period(start, step) => {
-                if *step < 1 || *step > Self::inclusive_max() {
+                if *step < 1 || *step > *bounds.end() {
                     return Err(ErrorKind::Expression(format!(
                         "{} must be between 1 and {}. ('{}' specified.)",
                         Self::name(),
-                        Self::inclusive_max(),
+                        *bounds.end(),
                         step,
                     ))
-                    .into());
+                    .with_code(ErrorCode::InvalidStep));
                 }
 
                 let base_set = match start {
                     // A point prior to a period implies a range whose start is the specified
                     // point and terminating inclusively with the inclusive max
                     Specifier::Point(start) => {
-                        let start = Self::validate_ordinal(*start)?;
-                        (start..=Self::inclusive_max()).collect()
+                        let start = Self::validate_ordinal_within(*start, bounds)?;
+                        (start..=*bounds.end()).collect()
                     }
-                    specifier => Self::ordinals_from_specifier(specifier)?,
+                    specifier => Self::ordinals_from_specifier_within(specifier, bounds)?,
                 };
                 base_set.into_iter().step_by(*step as usize).collect()
             }
@@ -326,7 +420,156 @@ where
                 .iter()
                 .cloned()
                 .collect::<OrdinalSet>(),
+            RootSpecifier::Random(start, end, draw) => {
+                let start =
+                    Self::validate_ordinal_within(start.unwrap_or(*bounds.start()), bounds)?;
+                let end = Self::validate_ordinal_within(end.unwrap_or(*bounds.end()), bounds)?;
+                if start > end {
+                    return Err(ErrorKind::Expression(format!(
+                        "Invalid random range for {}: {}~{}",
+                        Self::name(),
+                        start,
+                        end
+                    ))
+                    .with_code(ErrorCode::InvalidRange));
+                }
+                let ordinal = start + (draw % u64::from(end - start + 1)) as Ordinal;
+                ([ordinal]).iter().cloned().collect::<OrdinalSet>()
+            }
+            RootSpecifier::Hashed(range, step, draw) => {
+                let (start, end) = range.unwrap_or((*bounds.start(), *bounds.end()));
+                let start = Self::validate_ordinal_within(start, bounds)?;
+                let end = Self::validate_ordinal_within(end, bounds)?;
+                if start > end {
+                    return Err(ErrorKind::Expression(format!(
+                        "Invalid hash range for {}: H({}-{})",
+                        Self::name(),
+                        start,
+                        end
+                    ))
+                    .with_code(ErrorCode::InvalidRange));
+                }
+                match step {
+                    // The hash picks the offset of the first value, as in Jenkins.
+                    Some(0) => {
+                        return Err(ErrorKind::Expression(format!(
+                            "The step of a hashed {} value must be at least 1.",
+                            Self::name()
+                        ))
+                        .with_code(ErrorCode::InvalidStep))
+                    }
+                    // Steps wider than the range leave room for a single value, which the
+                    // offset must stay within.
+                    Some(step) => {
+                        let span = (*step).min(end - start + 1);
+                        let offset = (draw % u64::from(span)) as Ordinal;
+                        (start + offset..=end).step_by(*step as usize).collect()
+                    }
+                    None => {
+                        let ordinal = start + (draw % u64::from(end - start + 1)) as Ordinal;
+                        ([ordinal]).iter().cloned().collect::<OrdinalSet>()
+                    }
+                }
+            }
+            RootSpecifier::LastOfMonth(value, offset) => {
+                let weekday = Self::ordinal_from_point_value(value, bounds)?;
+                // A day of the week occurs at most five times in a month.
+                if *offset > 4 {
+                    return Err(ErrorKind::Expression(format!(
+                        "The offset of a last occurrence must be between 0 and 4. ('{}' \
+                         specified.)",
+                        offset
+                    ))
+                    .with_code(ErrorCode::InvalidSpecial));
+                }
+                return Ok(Resolved::Day(DaySpec::OfWeek(DayOfWeekSpec::Last {
+                    weekday,
+                    offset: *offset,
+                })));
+            }
+            RootSpecifier::NthOfMonth(value, nth) => {
+                let weekday = Self::ordinal_from_point_value(value, bounds)?;
+                if !(1..=5).contains(nth) {
+                    return Err(ErrorKind::Expression(format!(
+                        "The occurrence of a day within the month must be between 1 and 5. \
+                         ('{}' specified.)",
+                        nth
+                    ))
+                    .with_code(ErrorCode::InvalidSpecial));
+                }
+                return Ok(Resolved::Day(DaySpec::OfWeek(DayOfWeekSpec::Nth {
+                    weekday,
+                    nth: *nth,
+                })));
+            }
+            RootSpecifier::LastDayOfMonth(offset) => {
+                // No month has a day before its 31st-to-last day.
+                if *offset > 30 {
+                    return Err(ErrorKind::Expression(format!(
+                        "The offset from the last day of the month must be between 0 and 30. \
+                         ('{}' specified.)",
+                        offset
+                    ))
+                    .with_code(ErrorCode::InvalidSpecial));
+                }
+                return Ok(Resolved::Day(DaySpec::OfMonth(DayOfMonthSpec::LastDay {
+                    offset: *offset,
+                })));
+            }
+            RootSpecifier::NearestWeekday(day) => {
+                let day = Self::validate_ordinal_within(*day, bounds)?;
+                return Ok(Resolved::Day(DaySpec::OfMonth(
+                    DayOfMonthSpec::NearestWeekday { day },
+                )));
+            }
+            RootSpecifier::LastWeekdayOfMonth => {
+                return Ok(Resolved::Day(DaySpec::OfMonth(DayOfMonthSpec::LastWeekday)))
+            }
+            RootSpecifier::BusinessDay(nth) => {
+                // No month has more than 23 days from Monday to Friday.
+                if !(1..=23).contains(nth) {
+                    return Err(ErrorKind::Expression(format!(
+                        "The business day of the month must be between 1 and 23. \
+                         ('{}' specified.)",
+                        nth
+                    ))
+                    .with_code(ErrorCode::InvalidSpecial));
+                }
+                return Ok(Resolved::Day(DaySpec::OfMonth(
+                    DayOfMonthSpec::BusinessDay { nth: *nth },
+                )));
+            }
+            // Only the months field parses quarters, counting from the month the year starts on.
+            RootSpecifier::Quarter(quarter, year_start) => {
+                if !(1..=4).contains(quarter) {
+                    return Err(ErrorKind::Expression(format!(
+                        "A quarter must be between Q1 and Q4. ('Q{}' specified.)",
+                        quarter
+                    ))
+                    .with_code(ErrorCode::FieldOutOfRange));
+                }
+                let first = year_start - 1 + (quarter - 1) * 3;
+                (first..first + 3)
+                    .map(|month| Self::validate_ordinal_within(month % 12 + 1, bounds))
+                    .collect::<Result<_, _>>()?
+            }
         };
-        Ok(ordinals)
+        Ok(Resolved::Ordinals(ordinals))
     }
 }
+
+// What a root specifier resolves to in a field: plain ordinals, or a day which depends on the
+// month.
+pub enum Resolved {
+    Ordinals(OrdinalSet),
+    Day(DaySpec),
+}
+
+pub(crate) fn unsupported_day_spec<T: TimeUnitField>(spec: &DaySpec) -> Error {
+    ErrorKind::Expression(format!(
+        "The '{}' field does not support '{}'.",
+        T::name(),
+        spec
+    ))
+    .with_code(ErrorCode::InvalidSpecial)
+}
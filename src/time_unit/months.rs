@@ -1,12 +1,29 @@
 use crate::error::*;
 use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::time_unit::{fmt_ordinals, TimeUnitField};
 use once_cell::sync::Lazy;
+use phf::phf_map;
 use std::borrow::Cow;
+use std::fmt;
 
 static ALL: Lazy<OrdinalSet> = Lazy::new(Months::supported_ordinals);
 
-#[derive(Clone, Debug, Eq)]
+static NAMES: phf::Map<&'static str, Ordinal> = phf_map! {
+    "jan" => 1, "january" => 1,
+    "feb" => 2, "february" => 2,
+    "mar" => 3, "march" => 3,
+    "apr" => 4, "april" => 4,
+    "may" => 5,
+    "jun" => 6, "june" => 6,
+    "jul" => 7, "july" => 7,
+    "aug" => 8, "august" => 8,
+    "sep" => 9, "sept" => 9, "september" => 9,
+    "oct" => 10, "october" => 10,
+    "nov" => 11, "november" => 11,
+    "dec" => 12, "december" => 12,
+};
+
+#[derive(Clone, Eq)]
 pub struct Months {
     ordinals: Option<OrdinalSet>,
 }
@@ -27,27 +44,13 @@ impl TimeUnitField for Months {
         12
     }
     fn ordinal_from_name(name: &str) -> Result<Ordinal, Error> {
-        //TODO: Use phf crate
-        let ordinal = match name.to_lowercase().as_ref() {
-            "jan" | "january" => 1,
-            "feb" | "february" => 2,
-            "mar" | "march" => 3,
-            "apr" | "april" => 4,
-            "may" => 5,
-            "jun" | "june" => 6,
-            "jul" | "july" => 7,
-            "aug" | "august" => 8,
-            "sep" | "september" => 9,
-            "oct" | "october" => 10,
-            "nov" | "november" => 11,
-            "dec" | "december" => 12,
-            _ => {
-                return Err(
-                    ErrorKind::Expression(format!("'{}' is not a valid month name.", name)).into(),
-                )
-            }
-        };
-        Ok(ordinal)
+        NAMES
+            .get(name.to_lowercase().as_str())
+            .copied()
+            .ok_or_else(|| {
+                ErrorKind::Expression(format!("'{}' is not a valid month name.", name))
+                    .with_code(ErrorCode::UnknownName)
+            })
     }
     fn ordinals(&self) -> &OrdinalSet {
         match &self.ordinals {
@@ -62,3 +65,9 @@ impl PartialEq for Months {
         self.ordinals() == other.ordinals()
     }
 }
+
+impl fmt::Debug for Months {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_ordinals(self, "Months", f)
+    }
+}
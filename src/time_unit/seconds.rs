@@ -1,19 +1,23 @@
-use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::ordinal::{LookupTable, Ordinal, OrdinalSet};
+use crate::time_unit::{fmt_ordinals, TimeUnitField};
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::fmt;
 
 static ALL: Lazy<OrdinalSet> = Lazy::new(Seconds::supported_ordinals);
 
-#[derive(Clone, Debug, Eq)]
+#[derive(Clone, Eq)]
 pub struct Seconds {
     ordinals: Option<OrdinalSet>,
+    lookup: LookupTable,
 }
 
 impl TimeUnitField for Seconds {
     fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+        let lookup = LookupTable::new(ordinal_set.as_ref().unwrap_or(&ALL));
         Seconds {
             ordinals: ordinal_set,
+            lookup,
         }
     }
     fn name() -> Cow<'static, str> {
@@ -33,8 +37,21 @@ impl TimeUnitField for Seconds {
     }
 }
 
+impl Seconds {
+    // The included seconds, for the search to hop between without walking the ordinal set.
+    pub fn lookup(&self) -> &LookupTable {
+        &self.lookup
+    }
+}
+
 impl PartialEq for Seconds {
     fn eq(&self, other: &Seconds) -> bool {
         self.ordinals() == other.ordinals()
     }
 }
+
+impl fmt::Debug for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_ordinals(self, "Seconds", f)
+    }
+}
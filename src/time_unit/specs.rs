@@ -0,0 +1,221 @@
+use crate::calendar;
+use crate::schedule::NearestWeekdayPolicy;
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::fmt;
+
+/// A value of the days of month field which depends on the month it falls in, as returned by
+/// [Schedule::day_of_month_specs](crate::Schedule::day_of_month_specs).
+///
+/// Its [Display](fmt::Display) implementation writes it in Quartz syntax, e.g. `L-2` or `15W`.
+/// # Example
+/// ```
+/// use cron::{DayOfMonthSpec, NearestWeekdayPolicy, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 12 15W,L-2 * ?").unwrap();
+/// let specs = schedule.day_of_month_specs();
+/// assert_eq!(
+///     &[
+///         DayOfMonthSpec::NearestWeekday { day: 15 },
+///         DayOfMonthSpec::LastDay { offset: 2 },
+///     ],
+///     specs
+/// );
+/// // The 15th of August 2026 is a Saturday.
+/// let policy = NearestWeekdayPolicy::WithinMonth;
+/// assert_eq!(Some(14), specs[0].day_in_month(2026, 8, policy));
+/// assert_eq!(Some(29), specs[1].day_in_month(2026, 8, policy));
+/// assert_eq!("L-2", specs[1].to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DayOfMonthSpec {
+    /// The weekday nearest to a day of the month, e.g. `15W`.
+    NearestWeekday { day: u32 },
+    /// The last day of the month, counting back by an offset, e.g. `L` or `L-2`.
+    LastDay { offset: u32 },
+    /// The last weekday of the month, `LW`.
+    LastWeekday,
+    /// The nth business day of the month, counting the days from Monday to Friday, e.g. `3B`.
+    /// Schedules with [calendars](crate::Schedule::with_calendar) attached don't count the days
+    /// these exclude, so that holidays push it back.
+    BusinessDay { nth: u32 },
+}
+
+/// A value of the days of week field which depends on the month it falls in, as returned by
+/// [Schedule::day_of_week_specs](crate::Schedule::day_of_week_specs). The day of the week goes
+/// from 1 for Sunday to 7 for Saturday.
+///
+/// Its [Display](fmt::Display) implementation writes it in Quartz syntax, e.g. `6#3` or `6L-1`.
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use cron::{DayOfWeekSpec, Schedule};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 12 ? * FRI#3").unwrap();
+/// let spec = schedule.day_of_week_specs()[0];
+/// assert_eq!(DayOfWeekSpec::Nth { weekday: 6, nth: 3 }, spec);
+/// assert_eq!(Some(16), spec.day_in_month(2026, 10));
+/// assert!(spec.matches(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()));
+/// assert_eq!("6#3", spec.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DayOfWeekSpec {
+    /// The nth occurrence of a day of the week in the month, from 1 to 5, e.g. `6#3`.
+    Nth { weekday: u32, nth: u32 },
+    /// The last occurrence of a day of the week in the month, counting back by an offset in
+    /// weeks, e.g. `6L` or `FRIL-1`.
+    Last { weekday: u32, offset: u32 },
+}
+
+/// A value of either day field which depends on the month it falls in, as listed by
+/// [FieldDescription::specials](crate::FieldDescription::specials).
+///
+/// Its [Display](fmt::Display) implementation writes it in Quartz syntax, e.g. `L` or `6#3`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DaySpec {
+    /// A value of the days of month field, e.g. `15W`.
+    OfMonth(DayOfMonthSpec),
+    /// A value of the days of week field, e.g. `6#3`.
+    OfWeek(DayOfWeekSpec),
+}
+
+impl DayOfMonthSpec {
+    /// The day of the given month this resolves to, or `None` if there's none, as for `L-30` in
+    /// a month of 30 days, or if the policy moves the nearest weekday into another month.
+    /// Business days are counted without holidays.
+    pub fn day_in_month(&self, year: i32, month: u32, policy: NearestWeekdayPolicy) -> Option<u32> {
+        let last_day = calendar::days_in_month(year, month)?;
+        let nearest_weekday = |day| {
+            let date = NaiveDate::from_ymd_opt(year, month, day)?;
+            calendar::nearest_weekday(date, policy)
+                .filter(|nearest| nearest.month() == month)
+                .map(|nearest| nearest.day())
+        };
+        match *self {
+            DayOfMonthSpec::NearestWeekday { day } => nearest_weekday(day),
+            DayOfMonthSpec::LastDay { offset } => {
+                last_day.checked_sub(offset).filter(|&day| day >= 1)
+            }
+            DayOfMonthSpec::LastWeekday => nearest_weekday(last_day),
+            DayOfMonthSpec::BusinessDay { nth } => {
+                calendar::nth_business_day(year, month, nth, |_| false).map(|date| date.day())
+            }
+        }
+    }
+
+    /// Returns true if this is `W` or `LW`, which move to a weekday.
+    pub fn is_nearest_weekday(&self) -> bool {
+        matches!(
+            self,
+            DayOfMonthSpec::NearestWeekday { .. } | DayOfMonthSpec::LastWeekday
+        )
+    }
+}
+
+impl DayOfWeekSpec {
+    /// The day of the week, from 1 for Sunday to 7 for Saturday.
+    pub fn weekday(&self) -> u32 {
+        match *self {
+            DayOfWeekSpec::Nth { weekday, .. } | DayOfWeekSpec::Last { weekday, .. } => weekday,
+        }
+    }
+
+    /// The day of the week as a [Weekday], or `None` if it's out of range.
+    pub fn chrono_weekday(&self) -> Option<Weekday> {
+        to_weekday(self.weekday())
+    }
+
+    /// The day of the given month this resolves to, or `None` if the month doesn't have it, as
+    /// for the fifth Friday of most months.
+    pub fn day_in_month(&self, year: i32, month: u32) -> Option<u32> {
+        let weekday = self.chrono_weekday()?;
+        match *self {
+            DayOfWeekSpec::Nth { nth, .. } => {
+                calendar::nth_weekday_of_month(year, month, weekday, nth).map(|date| date.day())
+            }
+            DayOfWeekSpec::Last { offset, .. } => {
+                let last = calendar::last_weekday_of_month(year, month, weekday)?.day();
+                last.checked_sub(offset.checked_mul(7)?)
+                    .filter(|&day| day >= 1)
+            }
+        }
+    }
+
+    /// Returns true if the date is the day this resolves to in its month.
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        if date.weekday().number_from_sunday() != self.weekday() {
+            return false;
+        }
+        match *self {
+            DayOfWeekSpec::Nth { nth, .. } => (date.day() - 1) / 7 + 1 == nth,
+            DayOfWeekSpec::Last { offset, .. } => {
+                // The number of later occurrences of this weekday in the month.
+                calendar::days_in_month(date.year(), date.month())
+                    .is_some_and(|last_day| (last_day - date.day()) / 7 == offset)
+            }
+        }
+    }
+}
+
+// Days of the week are numbered from 1 for Sunday.
+pub(crate) fn to_weekday(weekday: u32) -> Option<Weekday> {
+    let days_from_monday = u8::try_from((weekday.checked_sub(1)? + 6) % 7).ok()?;
+    Weekday::try_from(days_from_monday)
+        .ok()
+        .filter(|_| weekday <= 7)
+}
+
+impl fmt::Display for DayOfMonthSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DayOfMonthSpec::NearestWeekday { day } => write!(f, "{day}W"),
+            DayOfMonthSpec::LastDay { offset: 0 } => write!(f, "L"),
+            DayOfMonthSpec::LastDay { offset } => write!(f, "L-{offset}"),
+            DayOfMonthSpec::LastWeekday => write!(f, "LW"),
+            DayOfMonthSpec::BusinessDay { nth } => write!(f, "{nth}B"),
+        }
+    }
+}
+
+impl fmt::Display for DayOfWeekSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DayOfWeekSpec::Nth { weekday, nth } => write!(f, "{weekday}#{nth}"),
+            DayOfWeekSpec::Last { weekday, offset: 0 } => write!(f, "{weekday}L"),
+            DayOfWeekSpec::Last { weekday, offset } => write!(f, "{weekday}L-{offset}"),
+        }
+    }
+}
+
+impl fmt::Display for DaySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DaySpec::OfMonth(spec) => spec.fmt(f),
+            DaySpec::OfWeek(spec) => spec.fmt(f),
+        }
+    }
+}
+
+impl DaySpec {
+    // Spells the value out for `Debug` output, e.g. `6 NTH#3` or `LAST-2`.
+    pub(crate) fn describe(&self) -> String {
+        let last = |offset| match offset {
+            0 => "LAST".to_string(),
+            offset => format!("LAST-{offset}"),
+        };
+        match *self {
+            DaySpec::OfMonth(DayOfMonthSpec::NearestWeekday { day }) => format!("{day} WEEKDAY"),
+            DaySpec::OfMonth(DayOfMonthSpec::LastDay { offset }) => last(offset),
+            DaySpec::OfMonth(DayOfMonthSpec::LastWeekday) => "LAST WEEKDAY".to_string(),
+            DaySpec::OfMonth(DayOfMonthSpec::BusinessDay { nth }) => format!("{nth} BUSINESS DAY"),
+            DaySpec::OfWeek(DayOfWeekSpec::Nth { weekday, nth }) => format!("{weekday} NTH#{nth}"),
+            DaySpec::OfWeek(DayOfWeekSpec::Last { weekday, offset }) => {
+                format!("{weekday} {}", last(offset))
+            }
+        }
+    }
+}
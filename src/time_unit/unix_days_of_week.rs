@@ -0,0 +1,54 @@
+use crate::error::*;
+use crate::ordinal::*;
+use crate::time_unit::{DaysOfWeek, TimeUnitField};
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+
+static ALL: Lazy<OrdinalSet> = Lazy::new(UnixDaysOfWeek::supported_ordinals);
+
+// Days of the week numbered from Sunday = 0 as in Unix cron, where 7 is Sunday too. Only used
+// while parsing, before converting to `DaysOfWeek`.
+#[derive(Clone, Debug)]
+pub struct UnixDaysOfWeek {
+    ordinals: Option<OrdinalSet>,
+}
+
+impl TimeUnitField for UnixDaysOfWeek {
+    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+        UnixDaysOfWeek {
+            ordinals: ordinal_set,
+        }
+    }
+    fn name() -> Cow<'static, str> {
+        DaysOfWeek::name()
+    }
+    fn inclusive_min() -> Ordinal {
+        0
+    }
+    fn inclusive_max() -> Ordinal {
+        7
+    }
+    fn ordinal_from_name(name: &str) -> Result<Ordinal, Error> {
+        DaysOfWeek::ordinal_from_name(name).map(|ordinal| ordinal - 1)
+    }
+    fn ordinals(&self) -> &OrdinalSet {
+        match &self.ordinals {
+            Some(ordinal_set) => ordinal_set,
+            None => &ALL,
+        }
+    }
+}
+
+impl From<UnixDaysOfWeek> for DaysOfWeek {
+    fn from(days_of_week: UnixDaysOfWeek) -> DaysOfWeek {
+        match days_of_week.ordinals {
+            None => DaysOfWeek::all(),
+            Some(ordinals) => DaysOfWeek::from_ordinal_set(
+                ordinals
+                    .into_iter()
+                    .map(|ordinal| ordinal % 7 + 1)
+                    .collect(),
+            ),
+        }
+    }
+}
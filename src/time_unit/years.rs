@@ -1,11 +1,16 @@
 use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::time_unit::{fmt_ordinals, TimeUnitField};
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// The widest range of years a parser can be configured to search, keeping years to four digits.
+pub(crate) const YEAR_LIMITS: RangeInclusive<Ordinal> = 1..=9999;
 
 static ALL: Lazy<OrdinalSet> = Lazy::new(Years::supported_ordinals);
 
-#[derive(Clone, Debug, Eq)]
+#[derive(Clone, Eq)]
 pub struct Years {
     ordinals: Option<OrdinalSet>,
 }
@@ -41,3 +46,9 @@ impl PartialEq for Years {
         self.ordinals() == other.ordinals()
     }
 }
+
+impl fmt::Debug for Years {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_ordinals(self, "Years", f)
+    }
+}
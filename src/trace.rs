@@ -0,0 +1,346 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use std::fmt;
+
+use crate::ast::FieldKind;
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::schedule::Schedule;
+use crate::time_unit::TimeUnitField;
+
+/// The next occurrence of a [Schedule] along with the jumps the search made to get to it, as
+/// returned by [Schedule::trace_next_after], to answer "why will it run then?".
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::{FieldKind, Schedule, TraceStep};
+/// use std::str::FromStr;
+///
+/// let schedule = Schedule::from_str("0 0 9 15 * ?").unwrap();
+/// let after = Utc.with_ymd_and_hms(2026, 10, 16, 13, 0, 0).unwrap();
+/// let trace = schedule.trace_next_after(&after);
+/// assert_eq!(Some(Utc.with_ymd_and_hms(2026, 11, 15, 9, 0, 0).unwrap()), trace.occurrence);
+/// assert_eq!(
+///     TraceStep::Rolled { unit: FieldKind::Hours, to: 9, field: FieldKind::Hours },
+///     trace.steps[2],
+/// );
+/// assert_eq!(
+///     "carried the month over to 11 as no later day matched, \
+///      rolled the day to 15 due to the days of the month field, \
+///      rolled the hour to 9 due to the hours field",
+///     trace.to_string()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<Z>
+where
+    Z: TimeZone,
+{
+    /// The first time the schedule fires after the given instant, if it fires again.
+    pub occurrence: Option<DateTime<Z>>,
+    /// The jumps made from the instant to the occurrence, in order.
+    pub steps: Vec<TraceStep>,
+}
+
+impl<Z> fmt::Display for Trace<Z>
+where
+    Z: TimeZone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.steps.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{step}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A jump made by the search for the next occurrence of a [Schedule], in the local time of the
+/// timezone it's searched in. Days are [FieldKind::DaysOfMonth] units.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TraceStep {
+    /// The `unit` was rolled forward to `to`, as its value wasn't included by the `field`.
+    Rolled {
+        unit: FieldKind,
+        to: u32,
+        field: FieldKind,
+    },
+    /// The `unit` was carried over to `to`, as no later value of the `exhausted` unit matched
+    /// before it, e.g. the next day as no later hour matched today.
+    Carried {
+        unit: FieldKind,
+        to: u32,
+        exhausted: FieldKind,
+    },
+    /// A matching local time was passed over as it doesn't exist in the timezone, e.g. when
+    /// clocks are put forward for daylight saving time.
+    Skipped(NaiveDateTime),
+    /// A matching local time was passed over as a calendar attached to the schedule excludes it.
+    Excluded(NaiveDateTime),
+    /// A matching local time was passed over as it falls within a blackout attached to the
+    /// schedule, and the search jumped to the end of the blackout.
+    BlackedOut(NaiveDateTime),
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceStep::Rolled { unit, to, field } => write!(
+                f,
+                "rolled the {} to {to} due to the {} field",
+                unit_name(*unit),
+                field_name(*field)
+            ),
+            TraceStep::Carried {
+                unit,
+                to,
+                exhausted,
+            } => write!(
+                f,
+                "carried the {} over to {to} as no later {} matched",
+                unit_name(*unit),
+                unit_name(*exhausted)
+            ),
+            TraceStep::Skipped(date_time) => {
+                write!(
+                    f,
+                    "skipped {date_time}, which doesn't exist in the timezone"
+                )
+            }
+            TraceStep::Excluded(date_time) => {
+                write!(f, "skipped {date_time}, which a calendar excludes")
+            }
+            TraceStep::BlackedOut(date_time) => {
+                write!(f, "skipped {date_time}, which falls within a blackout")
+            }
+        }
+    }
+}
+
+fn unit_name(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::Seconds => "second",
+        FieldKind::Minutes => "minute",
+        FieldKind::Hours => "hour",
+        FieldKind::DaysOfMonth | FieldKind::DaysOfWeek => "day",
+        FieldKind::Months => "month",
+        FieldKind::Years => "year",
+    }
+}
+
+fn field_name(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::Seconds => "seconds",
+        FieldKind::Minutes => "minutes",
+        FieldKind::Hours => "hours",
+        FieldKind::DaysOfMonth => "days of the month",
+        FieldKind::Months => "months",
+        FieldKind::DaysOfWeek => "days of the week",
+        FieldKind::Years => "years",
+    }
+}
+
+pub(crate) fn trace_next_after<Z>(schedule: &Schedule, after: &DateTime<Z>) -> Trace<Z>
+where
+    Z: TimeZone,
+{
+    let mut trace = Trace {
+        occurrence: None,
+        steps: Vec::new(),
+    };
+    let timezone = after.timezone();
+    let after = match schedule.not_before() {
+        Some(not_before) if *after < not_before => not_before
+            .with_timezone(&timezone)
+            .checked_sub_signed(Duration::seconds(1))
+            .unwrap_or_else(|| after.clone()),
+        _ => after.clone(),
+    };
+    // Like the search itself, start at the next whole second.
+    let Some(start) = after.checked_add_signed(Duration::seconds(1)) else {
+        return trace;
+    };
+    let mut cursor = start.naive_local().with_nanosecond(0).unwrap_or_default();
+    while let Some(matching) = next_matching(schedule, cursor, &mut trace.steps) {
+        // Like the search itself, look at the latest instant of an ambiguous local time.
+        let candidate = timezone.from_local_datetime(&matching);
+        let blackout = schedule.blackout_over(&candidate, true);
+        match (candidate.clone().latest(), blackout) {
+            (None, _) => trace.steps.push(TraceStep::Skipped(matching)),
+            (Some(_), _) if schedule.is_excluded(&matching) => {
+                trace.steps.push(TraceStep::Excluded(matching))
+            }
+            (Some(latest), _) if schedule.not_before().is_some_and(|start| latest < start) => {}
+            (Some(_), Some(blackout)) => {
+                trace.steps.push(TraceStep::BlackedOut(matching));
+                // Carry on from the first whole second at or after the end of the blackout.
+                let end = blackout.end.with_timezone(&timezone).naive_local();
+                let end = match end.with_nanosecond(0) {
+                    Some(second) if second < end => second + Duration::seconds(1),
+                    _ => end,
+                };
+                if end > matching {
+                    cursor = end;
+                    continue;
+                }
+            }
+            (Some(_), None) => {
+                trace.occurrence = schedule.clip(candidate).earliest();
+                break;
+            }
+        }
+        let Some(next) = matching.checked_add_signed(Duration::seconds(1)) else {
+            break;
+        };
+        cursor = next;
+    }
+    trace
+}
+
+// The first local time at or after `cursor` which the fields match, recording the jumps made to
+// get to it.
+fn next_matching(
+    schedule: &Schedule,
+    mut cursor: NaiveDateTime,
+    steps: &mut Vec<TraceStep>,
+) -> Option<NaiveDateTime> {
+    let fields = schedule.search_fields();
+    loop {
+        let year = cursor.year().max(0) as Ordinal;
+        // Years never wrap around, so running out of them ends the search.
+        let next_year = at_or_after(fields.years.ordinals(), year)?;
+        if next_year != year {
+            steps.push(rolled(FieldKind::Years, next_year, FieldKind::Years));
+            cursor = start_of(next_year, 1, 1)?;
+            continue;
+        }
+
+        let month = cursor.month();
+        match at_or_after(&fields.months_to_search(), month) {
+            Some(next) if next == month => {}
+            Some(next) => {
+                steps.push(rolled(FieldKind::Months, next, FieldKind::Months));
+                cursor = start_of(year, next, 1)?;
+                continue;
+            }
+            None => {
+                cursor = carry(cursor, FieldKind::Months, steps)?;
+                continue;
+            }
+        }
+
+        let days = fields.matching_days(month, year);
+        let day = cursor.day();
+        if !days.contains(&day) {
+            let field = if fields.days_of_month(month, year).contains(&day) {
+                FieldKind::DaysOfWeek
+            } else {
+                FieldKind::DaysOfMonth
+            };
+            match at_or_after(&days, day) {
+                Some(next) => {
+                    steps.push(rolled(FieldKind::DaysOfMonth, next, field));
+                    cursor = start_of(year, month, next)?;
+                }
+                None => cursor = carry(cursor, FieldKind::DaysOfMonth, steps)?,
+            }
+            continue;
+        }
+
+        let units = [
+            (FieldKind::Hours, fields.hours.ordinals(), cursor.hour()),
+            (
+                FieldKind::Minutes,
+                fields.minutes.ordinals(),
+                cursor.minute(),
+            ),
+            (
+                FieldKind::Seconds,
+                fields.seconds.ordinals(),
+                cursor.second(),
+            ),
+        ];
+        let mut jumped = false;
+        for (unit, ordinals, value) in units {
+            match at_or_after(ordinals, value) {
+                Some(next) if next == value => continue,
+                Some(next) => {
+                    steps.push(rolled(unit, next, unit));
+                    cursor = with_time_unit(cursor, unit, next)?;
+                }
+                None => cursor = carry(cursor, unit, steps)?,
+            }
+            jumped = true;
+            break;
+        }
+        if !jumped {
+            return Some(cursor);
+        }
+    }
+}
+
+fn at_or_after(ordinals: &OrdinalSet, value: Ordinal) -> Option<Ordinal> {
+    ordinals.range(value..).next().copied()
+}
+
+fn rolled(unit: FieldKind, to: Ordinal, field: FieldKind) -> TraceStep {
+    TraceStep::Rolled { unit, to, field }
+}
+
+fn start_of(year: Ordinal, month: Ordinal, day: Ordinal) -> Option<NaiveDateTime> {
+    NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(0, 0, 0)
+}
+
+// The cursor with the given unit of its time of day set to `value` and the smaller units reset.
+fn with_time_unit(cursor: NaiveDateTime, unit: FieldKind, value: Ordinal) -> Option<NaiveDateTime> {
+    let (hour, minute, second) = match unit {
+        FieldKind::Hours => (value, 0, 0),
+        FieldKind::Minutes => (cursor.hour(), value, 0),
+        _ => (cursor.hour(), cursor.minute(), value),
+    };
+    cursor.date().and_hms_opt(hour, minute, second)
+}
+
+// Moves the cursor to the start of the next value of the unit above the `exhausted` one,
+// recording the largest unit which changed.
+fn carry(
+    cursor: NaiveDateTime,
+    exhausted: FieldKind,
+    steps: &mut Vec<TraceStep>,
+) -> Option<NaiveDateTime> {
+    let date = cursor.date();
+    let next = match exhausted {
+        FieldKind::Seconds => cursor
+            .date()
+            .and_hms_opt(cursor.hour(), cursor.minute(), 0)?
+            .checked_add_signed(Duration::minutes(1))?,
+        FieldKind::Minutes => cursor
+            .date()
+            .and_hms_opt(cursor.hour(), 0, 0)?
+            .checked_add_signed(Duration::hours(1))?,
+        FieldKind::Hours => date.succ_opt()?.and_hms_opt(0, 0, 0)?,
+        FieldKind::DaysOfMonth | FieldKind::DaysOfWeek => {
+            start_of(date.year() as Ordinal, date.month(), 1)?
+                .checked_add_months(chrono::Months::new(1))?
+        }
+        FieldKind::Months | FieldKind::Years => start_of(date.year() as Ordinal + 1, 1, 1)?,
+    };
+    let (unit, to) = if next.year() != cursor.year() {
+        (FieldKind::Years, next.year() as Ordinal)
+    } else if next.month() != cursor.month() {
+        (FieldKind::Months, next.month())
+    } else if next.day() != cursor.day() {
+        (FieldKind::DaysOfMonth, next.day())
+    } else if next.hour() != cursor.hour() {
+        (FieldKind::Hours, next.hour())
+    } else {
+        (FieldKind::Minutes, next.minute())
+    };
+    steps.push(TraceStep::Carried {
+        unit,
+        to,
+        exhausted,
+    });
+    Some(next)
+}
@@ -0,0 +1,177 @@
+use crate::ast::{Ast, AstField, FieldKind, Item, Value};
+
+/// Walks an [Ast] without changing it, e.g. to check expressions against a policy.
+///
+/// Each method walks into the parts of the node by default. An implementation overriding one of
+/// them calls the matching `walk_` function to keep walking into the node, or returns to skip it.
+/// # Example
+/// ```
+/// use cron::{parse_to_ast, FieldKind, Item, Visitor};
+///
+/// // Collects the steps used in the minutes.
+/// struct Steps(Vec<u32>);
+///
+/// impl Visitor for Steps {
+///     fn visit_item(&mut self, kind: FieldKind, item: &Item) {
+///         if let (FieldKind::Minutes, Item::Step(_, step)) = (kind, item) {
+///             self.0.push(*step);
+///         }
+///     }
+/// }
+///
+/// let mut steps = Steps(Vec::new());
+/// parse_to_ast("0 */5,30/15 * * * *").unwrap().accept(&mut steps);
+/// assert_eq!(vec![5, 15], steps.0);
+/// ```
+pub trait Visitor {
+    /// Visits a field present in the expression.
+    fn visit_field(&mut self, kind: FieldKind, field: &AstField) {
+        walk_field(self, kind, field);
+    }
+
+    /// Visits an item of a field.
+    fn visit_item(&mut self, kind: FieldKind, item: &Item) {
+        walk_item(self, kind, item);
+    }
+
+    /// Visits a value of an item.
+    fn visit_value(&mut self, _kind: FieldKind, _value: &Value) {}
+}
+
+/// Visits each field of the tree, in the order of a Quartz expression.
+pub fn walk_ast<V>(visitor: &mut V, ast: &Ast)
+where
+    V: Visitor + ?Sized,
+{
+    for kind in FieldKind::ALL {
+        if let Some(field) = ast.field(kind) {
+            visitor.visit_field(kind, field);
+        }
+    }
+}
+
+/// Visits each item of the field.
+pub fn walk_field<V>(visitor: &mut V, kind: FieldKind, field: &AstField)
+where
+    V: Visitor + ?Sized,
+{
+    for item in &field.items {
+        visitor.visit_item(kind, item);
+    }
+}
+
+/// Visits the values of the item, and the start of a step.
+pub fn walk_item<V>(visitor: &mut V, kind: FieldKind, item: &Item)
+where
+    V: Visitor + ?Sized,
+{
+    match item {
+        Item::Value(value) | Item::LastOfMonth(value, _) | Item::NthOfMonth(value, _) => {
+            visitor.visit_value(kind, value)
+        }
+        Item::Range(start, end) => {
+            visitor.visit_value(kind, start);
+            visitor.visit_value(kind, end);
+        }
+        Item::Step(start, _) => visitor.visit_item(kind, start),
+        _ => {}
+    }
+}
+
+/// Rewrites an [Ast] node by node, e.g. to enforce a policy on expressions before compiling
+/// them.
+///
+/// Each method rewrites the parts of the node by default and otherwise keeps it as it is. An
+/// implementation overriding one of them calls the matching `transform_` function to keep
+/// rewriting the parts of the node.
+/// # Example
+/// ```
+/// use cron::{parse_to_ast, FieldKind, Transform, Value};
+///
+/// // Keeps jobs out of the night by moving them to 6 o'clock at the earliest.
+/// struct NotBefore6;
+///
+/// impl Transform for NotBefore6 {
+///     fn transform_value(&mut self, kind: FieldKind, value: Value) -> Value {
+///         match (kind, value) {
+///             (FieldKind::Hours, Value::Number(hour)) => Value::Number(hour.max(6)),
+///             (_, value) => value,
+///         }
+///     }
+/// }
+///
+/// let ast = parse_to_ast("0 0 2-8 * * *").unwrap().transform(&mut NotBefore6);
+/// assert_eq!("0 0 6-8 * * *", ast.compile().unwrap().source());
+/// ```
+pub trait Transform {
+    /// Rewrites the whole tree, e.g. to add a field the expression leaves out.
+    fn transform_ast(&mut self, ast: Ast) -> Ast {
+        transform_ast(self, ast)
+    }
+
+    /// Rewrites a field present in the expression.
+    fn transform_field(&mut self, kind: FieldKind, field: AstField) -> AstField {
+        transform_field(self, kind, field)
+    }
+
+    /// Rewrites an item of a field.
+    fn transform_item(&mut self, kind: FieldKind, item: Item) -> Item {
+        transform_item(self, kind, item)
+    }
+
+    /// Rewrites a value of an item.
+    fn transform_value(&mut self, _kind: FieldKind, value: Value) -> Value {
+        value
+    }
+}
+
+/// Rewrites each field of the tree, in the order of a Quartz expression.
+pub fn transform_ast<T>(transform: &mut T, mut ast: Ast) -> Ast
+where
+    T: Transform + ?Sized,
+{
+    for kind in FieldKind::ALL {
+        if let Some(field) = ast.field_mut(kind) {
+            *field = transform.transform_field(kind, std::mem::take(field));
+        }
+    }
+    ast
+}
+
+/// Rewrites each item of the field.
+pub fn transform_field<T>(transform: &mut T, kind: FieldKind, field: AstField) -> AstField
+where
+    T: Transform + ?Sized,
+{
+    AstField {
+        items: field
+            .items
+            .into_iter()
+            .map(|item| transform.transform_item(kind, item))
+            .collect(),
+    }
+}
+
+/// Rewrites the values of the item, and the start of a step.
+pub fn transform_item<T>(transform: &mut T, kind: FieldKind, item: Item) -> Item
+where
+    T: Transform + ?Sized,
+{
+    match item {
+        Item::Value(value) => Item::Value(transform.transform_value(kind, value)),
+        Item::Range(start, end) => Item::Range(
+            transform.transform_value(kind, start),
+            transform.transform_value(kind, end),
+        ),
+        Item::Step(start, step) => {
+            Item::Step(Box::new(transform.transform_item(kind, *start)), step)
+        }
+        Item::LastOfMonth(value, offset) => {
+            Item::LastOfMonth(transform.transform_value(kind, value), offset)
+        }
+        Item::NthOfMonth(value, nth) => {
+            Item::NthOfMonth(transform.transform_value(kind, value), nth)
+        }
+        item => item,
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::schedule::Schedule;
+use crate::time_unit::TimeUnitSpec;
+
+/// A span of time during which a [Schedule] fires continuously, e.g. every minute from 9:00 to
+/// 17:59 for `0 * 9-17 * * Mon-Fri`, as returned by [Schedule::next_window] and
+/// [Schedule::window_at].
+///
+/// The end is exclusive: it's the last occurrence of the run plus the schedule's resolution,
+/// which is a minute if the schedule fires at a single second of the minute and a second
+/// otherwise. Windows are cut after a week, so that schedules which never stop firing, like
+/// `* * * * * *`, aren't followed to their last year.
+///
+/// A [WindowedSchedule](crate::WindowedSchedule) instead opens a window of a fixed duration at
+/// every occurrence.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Window<Z>
+where
+    Z: TimeZone,
+{
+    /// The first occurrence of the window.
+    pub start: DateTime<Z>,
+    /// The instant the window ends at, which is excluded from it.
+    pub end: DateTime<Z>,
+}
+
+impl<Z> Window<Z>
+where
+    Z: TimeZone,
+{
+    /// Returns true if the given instant falls within this window.
+    pub fn contains(&self, date_time: &DateTime<Z>) -> bool {
+        self.start <= *date_time && *date_time < self.end
+    }
+
+    /// The length of this window.
+    pub fn duration(&self) -> Duration {
+        self.end.clone() - self.start.clone()
+    }
+}
+
+fn max_length() -> Duration {
+    Duration::weeks(1)
+}
+
+// The time between two consecutive occurrences of the same window.
+fn resolution(schedule: &Schedule) -> Duration {
+    if schedule.seconds().count() == 1 {
+        Duration::minutes(1)
+    } else {
+        Duration::seconds(1)
+    }
+}
+
+// Whether the occurrence continues a window which started before it.
+fn continues_window<Z>(schedule: &Schedule, occurrence: &DateTime<Z>) -> bool
+where
+    Z: TimeZone,
+{
+    occurrence
+        .clone()
+        .checked_sub_signed(resolution(schedule))
+        .is_some_and(|previous| schedule.includes(previous))
+}
+
+// The window starting at the given occurrence, following the run of occurrences from `from` to
+// its end.
+fn window_from<Z>(schedule: &Schedule, start: DateTime<Z>, from: DateTime<Z>) -> Window<Z>
+where
+    Z: TimeZone,
+{
+    let resolution = resolution(schedule);
+    let limit = from.clone() + max_length();
+    let mut last = from.clone();
+    for occurrence in schedule.after(&from) {
+        if occurrence != last.clone() + resolution || occurrence >= limit {
+            break;
+        }
+        last = occurrence;
+    }
+    Window {
+        start,
+        end: last + resolution,
+    }
+}
+
+pub(crate) fn window_at<Z>(schedule: &Schedule, date_time: &DateTime<Z>) -> Option<Window<Z>>
+where
+    Z: TimeZone,
+{
+    let resolution = resolution(schedule);
+    let floor = schedule.floor_to_schedule(date_time)?;
+    if *date_time >= floor.clone() + resolution {
+        return None;
+    }
+    let limit = floor.clone() - max_length();
+    let mut start = floor.clone();
+    for occurrence in schedule.after(&floor).rev() {
+        if occurrence != start.clone() - resolution || occurrence < limit {
+            break;
+        }
+        start = occurrence;
+    }
+    Some(window_from(schedule, start, floor))
+}
+
+pub(crate) fn next_window<Z>(schedule: &Schedule, after: &DateTime<Z>) -> Option<Window<Z>>
+where
+    Z: TimeZone,
+{
+    let mut start = schedule.after(after).next()?;
+    if continues_window(schedule, &start) {
+        // Skip the rest of the window the instant falls within.
+        let current = window_from(schedule, start.clone(), start);
+        start = schedule.ceil_to_schedule(&current.end)?;
+    }
+    Some(window_from(schedule, start.clone(), start))
+}
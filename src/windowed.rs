@@ -0,0 +1,155 @@
+use chrono::{DateTime, Duration, TimeZone};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::parsing::{self, ScheduleParser};
+use crate::schedule::{Schedule, ScheduleIterator};
+use crate::window::Window;
+
+/// A [Schedule] whose occurrences each open a [Window] of a fixed duration, written as a cron
+/// expression followed by a `for` suffix, e.g. `0 0 9 * * Mon-Fri for 8h` for working hours.
+///
+/// The duration is made of days (`d`), hours (`h`), minutes (`m`) and seconds (`s`), in that
+/// order, such as `90m` or `1h30m`, and is elapsed time. When windows overlap, the instants
+/// they share belong to the one which started last.
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron::WindowedSchedule;
+/// use std::str::FromStr;
+///
+/// let hours = WindowedSchedule::from_str("0 0 9 * * Mon-Fri for 8h").unwrap();
+/// // The 16th of October 2026 is a Friday.
+/// assert!(hours.contains(&Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()));
+/// assert!(!hours.contains(&Utc.with_ymd_and_hms(2026, 10, 16, 17, 0, 0).unwrap()));
+///
+/// let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+/// let next = hours.windows_after(&start).next().unwrap();
+/// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(), next.start);
+/// assert_eq!(Utc.with_ymd_and_hms(2026, 10, 19, 17, 0, 0).unwrap(), next.end);
+/// assert_eq!("0 0 9 * * Mon-Fri for 8h", hours.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowedSchedule {
+    schedule: Schedule,
+    duration: Duration,
+}
+
+impl WindowedSchedule {
+    /// Creates a schedule opening a window of `duration` at every occurrence of `schedule`.
+    ///
+    /// # Panics
+    /// Panics if `duration` isn't positive.
+    pub fn new(schedule: Schedule, duration: Duration) -> WindowedSchedule {
+        assert!(
+            duration > Duration::zero(),
+            "The duration must be positive."
+        );
+        WindowedSchedule { schedule, duration }
+    }
+
+    /// The schedule the windows start at.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// The length of every window.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns true if the given instant falls within one of the windows.
+    pub fn contains<Z>(&self, date_time: &DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        self.window_at(date_time).is_some()
+    }
+
+    /// The window the given instant falls within, if any.
+    pub fn window_at<Z>(&self, date_time: &DateTime<Z>) -> Option<Window<Z>>
+    where
+        Z: TimeZone,
+    {
+        let start = if self.schedule.fires_at(date_time) {
+            date_time.clone()
+        } else {
+            self.schedule.after(date_time).next_back()?
+        };
+        let window = window_of(self.duration, start)?;
+        window.contains(date_time).then_some(window)
+    }
+
+    /// Provides an iterator over the windows starting after the given instant. Iterating it
+    /// backwards goes over the windows which started before it.
+    pub fn windows_after<Z>(&self, after: &DateTime<Z>) -> Windows<'_, Z>
+    where
+        Z: TimeZone,
+    {
+        Windows {
+            duration: self.duration,
+            occurrences: self.schedule.after(after),
+        }
+    }
+}
+
+fn window_of<Z>(duration: Duration, start: DateTime<Z>) -> Option<Window<Z>>
+where
+    Z: TimeZone,
+{
+    let end = start.clone().checked_add_signed(duration)?;
+    Some(Window { start, end })
+}
+
+impl FromStr for WindowedSchedule {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        ScheduleParser::new().parse_windowed(expression)
+    }
+}
+
+/// Writes the expression of the schedule followed by the `for` suffix, and then its comment if it
+/// has one.
+impl Display for WindowedSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (expression, comment) = parsing::split_comment(self.schedule.source());
+        let duration = parsing::format_duration(self.duration);
+        write!(f, "{} for {duration}", expression.trim_end())?;
+        match comment {
+            Some(comment) => write!(f, " # {comment}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An iterator over the windows of a [WindowedSchedule], as returned by
+/// [WindowedSchedule::windows_after].
+pub struct Windows<'a, Z>
+where
+    Z: TimeZone,
+{
+    duration: Duration,
+    occurrences: ScheduleIterator<'a, Z>,
+}
+
+impl<Z> Iterator for Windows<'_, Z>
+where
+    Z: TimeZone,
+{
+    type Item = Window<Z>;
+
+    fn next(&mut self) -> Option<Window<Z>> {
+        window_of(self.duration, self.occurrences.next()?)
+    }
+}
+
+impl<Z> DoubleEndedIterator for Windows<'_, Z>
+where
+    Z: TimeZone,
+{
+    fn next_back(&mut self) -> Option<Window<Z>> {
+        window_of(self.duration, self.occurrences.next_back()?)
+    }
+}
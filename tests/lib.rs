@@ -2,7 +2,21 @@
 mod tests {
     use chrono::*;
     use chrono_tz::Tz;
-    use cron::{Schedule, TimeUnitSpec};
+    use cron::error::{ErrorCode, ErrorKind};
+    #[cfg(feature = "rayon")]
+    use cron::next_after_batch;
+    #[cfg(feature = "tokio")]
+    use cron::MissedTickBehavior;
+    #[cfg(feature = "intern")]
+    use cron::ScheduleInterner;
+    use cron::{
+        sanitize, AnnualCalendar, Ast, AstField, Checkpoint, Correction, CorrectionKind,
+        CronCalendar, CronExpr, Dialect, FieldCount, FieldKind, Histogram, IntervalSchedule, Item,
+        LeapDayPolicy, LintKind, MisfirePolicy, NearestWeekdayPolicy, OccurrenceIterator,
+        Occurrences, RewriteReason, Sanitized, Schedule, ScheduleExpr, ScheduleFormatter,
+        ScheduleParser, ScheduleSet, SpecifierForm, TimeUnitSpec, TraceStep, Transform, Value,
+        Visitor, WeeklyCalendar,
+    };
     use std::ops::Bound::{Excluded, Included};
     use std::str::FromStr;
 
@@ -395,7 +409,7 @@ mod tests {
         let schedule_tz: Tz = "Europe/London".parse().unwrap();
         let dt = schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
         let mut schedule_iter = schedule.after(&dt);
-        let expected_values = vec![
+        let expected_values = [
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 17).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 34).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 51).unwrap(),
@@ -448,7 +462,7 @@ mod tests {
         let schedule_tz: Tz = "Europe/London".parse().unwrap();
         let dt = schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
         let mut schedule_iter = schedule.after(&dt);
-        let expected_values = vec![
+        let expected_values = [
             schedule_tz.with_ymd_and_hms(2020, 1, 11, 0, 0, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 21, 0, 0, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 31, 0, 0, 0).unwrap(),
@@ -468,7 +482,7 @@ mod tests {
         let schedule_tz: Tz = "Europe/London".parse().unwrap();
         let dt = schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
         let mut schedule_iter = schedule.after(&dt);
-        let expected_values = vec![
+        let expected_values = [
             schedule_tz.with_ymd_and_hms(2020, 2, 1, 0, 0, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 3, 1, 0, 0, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 4, 1, 0, 0, 0).unwrap(),
@@ -502,7 +516,7 @@ mod tests {
         let schedule_tz: Tz = "Europe/London".parse().unwrap();
         let dt = schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
         let mut schedule_iter = schedule.after(&dt);
-        let expected_values = vec![
+        let expected_values = [
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 21, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 0, 42, 0).unwrap(),
             schedule_tz.with_ymd_and_hms(2020, 1, 1, 1, 0, 0).unwrap(),
@@ -555,4 +569,4367 @@ mod tests {
         assert!(schedule.includes(included));
         assert!(!schedule.includes(not_included));
     }
+
+    #[test]
+    fn test_last_weekday_of_month() {
+        let schedule = Schedule::from_str("0 0 0 ? * FRIL").unwrap();
+        let starting_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 1, 26, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 23, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 29, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(3)));
+    }
+
+    #[test]
+    fn test_nth_from_last_weekday_of_month() {
+        let schedule = Schedule::from_str("0 0 0 ? * FRIL-1").unwrap();
+        let starting_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 1, 19, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 16, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 22, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(3)));
+
+        let mut previous = schedule.after(&starting_date).rev();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 12, 22, 0, 0, 0).unwrap(),
+            previous.next().unwrap()
+        );
+
+        assert_eq!(
+            Schedule::from_str("0 0 0 ? * 6L-1")
+                .unwrap()
+                .after(&starting_date)
+                .next(),
+            schedule.after(&starting_date).next()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2024, 1, 19, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 1, 26, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_last_weekday_of_month_is_not_an_ordinal() {
+        let schedule = Schedule::from_str("* * * ? * MON,FRIL").unwrap();
+        assert_eq!(1, schedule.days_of_week().count());
+        assert!(schedule.days_of_week().includes(2));
+        assert!(!schedule.days_of_week().includes(6));
+        assert!(!schedule.days_of_week().is_all());
+    }
+
+    #[test]
+    fn test_nth_weekdays_of_month_list() {
+        let schedule = Schedule::from_str("0 0 0 ? * MON#1,MON#3,FRI#2").unwrap();
+        let starting_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 1, 12, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 5, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 9, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 19, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(5)));
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_fifth_weekday_of_month_skips_short_months() {
+        let schedule = Schedule::from_str("0 0 0 ? * 2#5").unwrap();
+        let starting_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 1, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 4, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 7, 29, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(3)));
+    }
+
+    #[test]
+    fn test_days_of_month_mixed_with_last_day() {
+        let schedule = Schedule::from_str("0 0 0 1,15,L * ?").unwrap();
+        let starting_date = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 2, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(5)));
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2023, 2, 28, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 2, 28, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_days_of_month_nearest_weekday_and_offset_from_last() {
+        let schedule = Schedule::from_str("0 0 0 1W,L-2 * ?").unwrap();
+        // 2024-06-01 is a Saturday and 2024-09-01 a Sunday.
+        let starting_date = Utc.with_ymd_and_hms(2024, 5, 31, 0, 0, 0).unwrap();
+        let expected_values = [
+            Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 6, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 7, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 8, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 9, 2, 0, 0, 0).unwrap(),
+        ];
+        assert!(expected_values
+            .into_iter()
+            .eq(schedule.after(&starting_date).take(7)));
+
+        let mut previous = schedule.after(&starting_date).rev();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 5, 29, 0, 0, 0).unwrap(),
+            previous.next().unwrap()
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            previous.next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_days_of_month_last_weekday() {
+        let schedule = Schedule::from_str("0 0 0 LW * ?").unwrap();
+        // 2024-08-31 is a Saturday and 2024-03-31 a Sunday.
+        let starting_date = Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 8, 30, 0, 0, 0).unwrap(),
+            schedule.after(&starting_date).next().unwrap()
+        );
+        let starting_date = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 3, 29, 0, 0, 0).unwrap(),
+            schedule.after(&starting_date).next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_random_values_are_stable_with_a_seed() {
+        let expression = "0 0~29 ~ * * ?";
+        let schedule = ScheduleParser::new().seed(7).parse(expression).unwrap();
+        for _ in 0..10 {
+            let again = ScheduleParser::new().seed(7).parse(expression).unwrap();
+            assert!(schedule.timeunitspec_eq(&again));
+        }
+        assert_eq!(1, schedule.minutes().count());
+        assert!(schedule.minutes().iter().all(|minute| minute <= 29));
+        assert_eq!(1, schedule.hours().count());
+        assert_eq!(expression, schedule.source());
+    }
+
+    #[test]
+    fn test_random_values_spread_across_seeds() {
+        let minutes: std::collections::BTreeSet<u32> = (0..50)
+            .map(|seed| {
+                let schedule = ScheduleParser::new()
+                    .seed(seed)
+                    .parse("0 R * * * ?")
+                    .unwrap();
+                schedule.minutes().iter().next().unwrap()
+            })
+            .collect();
+        assert!(minutes.len() > 10);
+    }
+
+    #[test]
+    fn test_random_values_without_seed() {
+        let first = Schedule::from_str("0 ~ 10~12 * * ?").unwrap();
+        for _ in 0..20 {
+            let schedule = Schedule::from_str("0 ~ 10~12 * * ?").unwrap();
+            assert_eq!(1, schedule.hours().count());
+            assert!(schedule.hours().range(10..=12).next().is_some());
+            // Equal schedules fire at the same times.
+            assert_eq!(first, schedule);
+            assert!(first.timeunitspec_eq(&schedule));
+        }
+
+        let minutes: std::collections::BTreeSet<u32> = (0..24)
+            .map(|hour| {
+                let schedule = Schedule::from_str(&format!("0 ~ {hour} * * ?")).unwrap();
+                schedule.minutes().iter().next().unwrap()
+            })
+            .collect();
+        assert!(minutes.len() > 10);
+    }
+
+    #[test]
+    fn test_hashed_values_keyed_by_job_name() {
+        let expression = "0 H(0-29) H * * ?";
+        let parse = |key: &str| {
+            ScheduleParser::new()
+                .hash_key(key)
+                .parse(expression)
+                .unwrap()
+        };
+        let backup = parse("backup");
+        for _ in 0..10 {
+            assert!(backup.timeunitspec_eq(&parse("backup")));
+        }
+        assert_eq!(1, backup.minutes().count());
+        assert!(backup.minutes().iter().all(|minute| minute <= 29));
+        assert_eq!(1, backup.hours().count());
+
+        let minutes: std::collections::BTreeSet<u32> = (0..50)
+            .map(|job| {
+                parse(&format!("job-{job}"))
+                    .minutes()
+                    .iter()
+                    .next()
+                    .unwrap()
+            })
+            .collect();
+        assert!(minutes.len() > 10);
+
+        // Steps keep their spacing, starting from a hashed offset.
+        let every_quarter = ScheduleParser::new()
+            .hash_key("report")
+            .parse("0 H/15 * * * ?")
+            .unwrap();
+        let quarters: Vec<u32> = every_quarter.minutes().iter().collect();
+        assert_eq!(4, quarters.len());
+        assert!(quarters[0] < 15);
+        assert!(quarters.windows(2).all(|pair| pair[1] - pair[0] == 15));
+
+        // Steps wider than the range still resolve to a value within it, whatever the key.
+        for job in 0..200 {
+            let parser = ScheduleParser::new().hash_key(&format!("job-{job}"));
+            let narrow = parser.parse("H(0-5)/10 * * * * ?").unwrap();
+            assert_eq!(1, narrow.seconds().count());
+            assert!(narrow.seconds().iter().all(|second| second <= 5));
+            let wide = parser.parse("0 H/90 * * * ?").unwrap();
+            assert_eq!(1, wide.minutes().count());
+        }
+
+        let jenkins = ScheduleParser::new()
+            .dialect(Dialect::Jenkins)
+            .hash_key("nightly")
+            .parse("H H(0-5) * * *")
+            .unwrap();
+        assert!(jenkins.hours().iter().all(|hour| hour <= 5));
+        assert!(ScheduleParser::new().parse("0 H(30-10) * * * ?").is_err());
+        assert!(ScheduleParser::new().parse("0 H/0 * * * ?").is_err());
+        assert!(ScheduleParser::new().parse("0 H(0-60) * * * ?").is_err());
+    }
+
+    #[test]
+    fn test_weekly_calendar_skips_weekends() {
+        let schedule = Schedule::from_str("0 30 9 * * ?").unwrap().with_calendar(
+            WeeklyCalendar::new()
+                .excluding(Weekday::Sat)
+                .excluding(Weekday::Sun),
+        );
+        // Friday
+        let starting_date = Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap();
+        let mut events = schedule.after(&starting_date);
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap(),
+            events.next().unwrap()
+        );
+        let mut events = schedule.after(&starting_date).rev();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap(),
+            events.next().unwrap()
+        );
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap()));
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_annual_calendar_excludes_every_year() {
+        let schedule = Schedule::from_str("0 0 0 25 12 ?")
+            .unwrap()
+            .with_calendar(AnnualCalendar::new().excluding(12, 25));
+        let starting_date = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        assert!(schedule.after(&starting_date).take(3).next().is_none());
+    }
+
+    #[test]
+    fn test_calendars_excluding_every_day() {
+        let every_day = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .into_iter()
+        .fold(WeeklyCalendar::new(), WeeklyCalendar::excluding);
+        let every_date = (1..=12)
+            .flat_map(|month| (1..=31).map(move |day| (month, day)))
+            .fold(AnnualCalendar::new(), |calendar, (month, day)| {
+                calendar.excluding(month, day)
+            });
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        // Whole days are skipped at once, so that searches through a century end quickly.
+        let began = std::time::Instant::now();
+        for schedule in [
+            Schedule::from_str("* * * * * ?")
+                .unwrap()
+                .with_calendar(every_day),
+            Schedule::from_str("0 * * * * ?")
+                .unwrap()
+                .with_calendar(every_date),
+        ] {
+            assert_eq!(None, schedule.after(&start).next());
+            assert_eq!(None, schedule.after(&start).next_back());
+            assert!(!schedule.includes(start));
+        }
+        assert!(began.elapsed() < std::time::Duration::from_secs(5));
+
+        // Times on the days left are still found, in timezones with daylight saving time too.
+        let weekends = WeeklyCalendar::new()
+            .excluding(Weekday::Sat)
+            .excluding(Weekday::Sun);
+        let schedule = Schedule::from_str("0 */30 * * * ?")
+            .unwrap()
+            .with_calendar(weekends);
+        let berlin = Tz::Europe__Berlin;
+        // Friday, 2026-10-23 before the clocks go back on Sunday.
+        let friday = berlin.with_ymd_and_hms(2026, 10, 23, 23, 45, 0).unwrap();
+        assert_eq!(
+            Some(berlin.with_ymd_and_hms(2026, 10, 26, 0, 0, 0).unwrap()),
+            schedule.after(&friday).next()
+        );
+        let monday = berlin.with_ymd_and_hms(2026, 10, 26, 0, 10, 0).unwrap();
+        let before: Vec<_> = schedule.after(&monday).rev().take(2).collect();
+        assert_eq!(
+            vec![
+                berlin.with_ymd_and_hms(2026, 10, 26, 0, 0, 0).unwrap(),
+                berlin.with_ymd_and_hms(2026, 10, 23, 23, 30, 0).unwrap(),
+            ],
+            before
+        );
+    }
+
+    #[test]
+    fn test_cron_calendar_excludes_matching_times() {
+        let schedule = Schedule::from_str("0 0 * * * ?")
+            .unwrap()
+            .with_calendar(CronCalendar::new(
+                Schedule::from_str("* * 0-5,22-23 * * ?").unwrap(),
+            ));
+        let starting_date = Utc.with_ymd_and_hms(2026, 10, 16, 21, 0, 0).unwrap();
+        let events: Vec<_> = schedule.after(&starting_date).take(2).collect();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 17, 6, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 17, 7, 0, 0).unwrap(),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn test_calendars_are_evaluated_in_local_time() {
+        let schedule = Schedule::from_str("0 0 23 * * ?")
+            .unwrap()
+            .with_calendar(WeeklyCalendar::new().excluding(Weekday::Sat));
+        // Friday 23:00 in Tokyo is still Friday locally
+        let starting_date = Tz::Asia__Tokyo
+            .with_ymd_and_hms(2026, 10, 16, 22, 0, 0)
+            .unwrap();
+        let mut events = schedule.after(&starting_date);
+        assert_eq!(
+            Tz::Asia__Tokyo
+                .with_ymd_and_hms(2026, 10, 16, 23, 0, 0)
+                .unwrap(),
+            events.next().unwrap()
+        );
+        assert_eq!(
+            Tz::Asia__Tokyo
+                .with_ymd_and_hms(2026, 10, 18, 23, 0, 0)
+                .unwrap(),
+            events.next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parser_max_length() {
+        let parser = ScheduleParser::new().max_length(16);
+        assert!(parser.parse("0 0 12 * * ?").is_ok());
+        let error = parser.parse("0 0 12 1,15 * Mon-Fri").unwrap_err();
+        assert!(error.to_string().contains("limit of 16"));
+    }
+
+    #[test]
+    fn test_parser_max_list_items() {
+        let parser = ScheduleParser::new().max_list_items(3);
+        assert!(parser.parse("0 0,15,30 9-17 * * ?").is_ok());
+        let error = parser.parse("0 0,15,30,45 9-17 * * ?").unwrap_err();
+        assert!(error.to_string().contains("limit of 3"));
+        assert!(parser.parse("0 0 12 ? * MON,TUE,WED,THU").is_err());
+        assert!(parser.parse("0 0 12 ? * * 2020,2021,2022,2023").is_err());
+        assert!(Schedule::from_str("0 0,15,30,45 9-17 * * ?").is_ok());
+    }
+
+    #[test]
+    fn test_diff_identical_schedules() {
+        let schedule = Schedule::from_str("@weekly").unwrap();
+        let diff = schedule.diff(&Schedule::from_str("0 0 0 * * 1 *").unwrap());
+        assert!(diff.is_empty());
+        assert_eq!("", diff.to_string());
+    }
+
+    #[test]
+    fn test_diff_per_field() {
+        let before = Schedule::from_str("0 0 9 * * Mon-Fri").unwrap();
+        let after = Schedule::from_str("0 0 9 * Jan-Jun Mon-Thu,6L").unwrap();
+        let diff = before.diff(&after);
+        assert!(!diff.is_empty());
+        assert!(diff.hours.is_empty());
+        assert!(diff.months.added.is_empty());
+        assert_eq!((7..=12).collect::<Vec<_>>(), diff.months.removed);
+        assert_eq!(vec![6], diff.days_of_week.removed);
+        assert!(diff.days_of_week.specials_changed);
+        assert_eq!(
+            "Months: -7 -8 -9 -10 -11 -12; Days of Week: -6 special constraints changed",
+            diff.to_string()
+        );
+    }
+
+    #[test]
+    fn test_frequency() {
+        use cron::Frequency::*;
+        for (expression, frequency) in [
+            ("* * * * * *", PerSecond),
+            ("*/5 * 9-17 * * Mon-Fri", PerSecond),
+            ("0 * * * * *", PerMinute),
+            ("0 0 * * * *", Hourly),
+            ("@hourly", Hourly),
+            ("@daily", Daily),
+            ("0 0 12 * * Mon-Fri", Daily),
+            ("0 0 12 * Jan ?", Daily),
+            ("@weekly", Weekly),
+            ("0 30 9 ? * Fri", Weekly),
+            ("@monthly", Monthly),
+            ("0 0 12 L * ?", Monthly),
+            ("0 0 12 ? * FRIL", Monthly),
+            ("0 0 12 ? 1/3 2#1", Monthly),
+            ("@yearly", Yearly),
+            ("0 0 0 25 Dec ? 2030/2", Yearly),
+            ("0 0 12 1,15 * ?", Irregular),
+            ("0 0 12 13 * Fri", Irregular),
+            ("0 0 12 ? * Mon,FRIL", Irregular),
+        ] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(frequency, schedule.frequency(), "{expression}");
+        }
+        assert!(PerMinute < Daily);
+    }
+
+    #[test]
+    fn test_next_in_timezones() {
+        let schedule = Schedule::from_str("0 0 9 * * Mon-Fri").unwrap();
+        let zones = [Tz::Asia__Tokyo, Tz::Europe__London, Tz::America__New_York];
+        // Friday 2026-10-16 06:00 UTC: 15:00 in Tokyo, 07:00 in London, 02:00 in New York
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 6, 0, 0).unwrap();
+        let next = schedule.next_in_timezones(&now, &zones);
+        assert_eq!(
+            vec![
+                Some(
+                    Tz::Asia__Tokyo
+                        .with_ymd_and_hms(2026, 10, 19, 9, 0, 0)
+                        .unwrap()
+                ),
+                Some(
+                    Tz::Europe__London
+                        .with_ymd_and_hms(2026, 10, 16, 9, 0, 0)
+                        .unwrap()
+                ),
+                Some(
+                    Tz::America__New_York
+                        .with_ymd_and_hms(2026, 10, 16, 9, 0, 0)
+                        .unwrap()
+                ),
+            ],
+            next
+        );
+        assert_eq!(next[1], schedule.earliest_in_timezones(&now, &zones));
+        assert_eq!(None, schedule.earliest_in_timezones(&now, &[] as &[Tz]));
+    }
+
+    #[test]
+    fn test_schedule_set_merges_in_order() {
+        let jobs: ScheduleSet<u32> = [
+            (1, Schedule::from_str("0 0,30 * * * *").unwrap()),
+            (2, Schedule::from_str("0 15 * * * *").unwrap()),
+            (3, Schedule::from_str("0 0 * * * *").unwrap()),
+        ]
+        .into_iter()
+        .collect();
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 8, 59, 0).unwrap();
+        let events: Vec<_> = jobs
+            .after(&start)
+            .take(5)
+            .map(|(key, datetime)| (*key, datetime.minute()))
+            .collect();
+        // Ties are broken by insertion order.
+        assert_eq!(vec![(1, 0), (3, 0), (2, 15), (1, 30), (1, 0)], events);
+    }
+
+    #[test]
+    fn test_schedule_set_drops_exhausted_schedules() {
+        let mut jobs = ScheduleSet::new();
+        jobs.insert("once", Schedule::from_str("0 0 0 1 1 ? 2027").unwrap());
+        jobs.insert("yearly", Schedule::from_str("@yearly").unwrap());
+        assert_eq!(2, jobs.len());
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let keys: Vec<_> = jobs.after(&start).take(3).map(|(key, _)| *key).collect();
+        assert_eq!(vec!["once", "yearly", "yearly"], keys);
+
+        assert!(jobs.remove(&"once").is_some());
+        assert!(jobs.get(&"once").is_none());
+        assert!(jobs.get(&"yearly").is_some());
+        jobs.remove(&"yearly");
+        assert!(jobs.is_empty());
+        assert!(jobs.after(&start).next().is_none());
+    }
+
+    #[test]
+    fn test_infer_steps_and_days_of_month() {
+        let runs: Vec<_> = (1..=3)
+            .flat_map(|month| {
+                (0..4).map(move |quarter| {
+                    Utc.with_ymd_and_hms(2026, month, 15, 6, quarter * 15, 0)
+                        .unwrap()
+                })
+            })
+            .collect();
+        let schedule = Schedule::infer(&runs).unwrap();
+        assert_eq!("0 */15 6 15 1-3 *", schedule.source());
+    }
+
+    #[test]
+    fn test_infer_in_sample_timezone() {
+        let runs = [
+            Tz::Europe__Paris
+                .with_ymd_and_hms(2026, 3, 28, 9, 0, 0)
+                .unwrap(),
+            Tz::Europe__Paris
+                .with_ymd_and_hms(2026, 3, 29, 9, 0, 0)
+                .unwrap(),
+        ];
+        let schedule = Schedule::infer(&runs).unwrap();
+        assert_eq!("0 0 9 * 3 1,7", schedule.source());
+    }
+
+    #[test]
+    fn test_infer_without_fit() {
+        assert!(Schedule::infer::<Utc>(&[]).is_none());
+        // Every day but the 2nd of the month at 12:00, yet 13:00 on the 2nd.
+        let runs = [
+            Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 2, 13, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 3, 12, 0, 0).unwrap(),
+        ];
+        assert!(Schedule::infer(&runs).is_none());
+    }
+
+    #[test]
+    fn test_to_oncalendar() {
+        for (expression, oncalendar) in [
+            ("* * * * * *", "*-*-* *:*:*"),
+            ("@daily", "*-*-* 00:00:00"),
+            (
+                "0 30 9,12,15 1,15 May-Aug Mon,Wed,Fri 2018/2",
+                "Mon,Wed,Fri 2018/2-05..08-01,15 09,12,15:30:00",
+            ),
+            ("0 0 12 L * ?", "*-*~01 12:00:00"),
+            ("0 0 12 L-2 Feb ?", "*-02~03 12:00:00"),
+            ("0 0 0 ? * Sat,Sun", "Sun,Sat *-*-* 00:00:00"),
+        ] {
+            let translation = Schedule::from_str(expression).unwrap().to_oncalendar();
+            assert!(translation.is_exact(), "{expression}");
+            assert_eq!(oncalendar, translation.expression, "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_to_oncalendar_untranslatable() {
+        let translation = Schedule::from_str("0 0 12 1,15W,L * Mon,FRIL")
+            .unwrap()
+            .to_oncalendar();
+        assert_eq!("*-*-* 12:00:00", translation.expression);
+        let fields: Vec<_> = translation
+            .untranslatable
+            .iter()
+            .map(|part| part.field.as_ref())
+            .collect();
+        assert_eq!(
+            vec!["Days of Week", "Days of Month", "Days of Month"],
+            fields
+        );
+        assert!(translation.untranslatable[0].reason.contains("Fri"));
+    }
+
+    #[test]
+    fn test_to_quartz() {
+        for (expression, quartz) in [
+            ("* * * * * *", "* * * * * ?"),
+            ("@weekly", "0 0 0 ? * 1"),
+            (
+                "0 0,15,30,45 9-17 ? Jan,Mar-May * 2030",
+                "0 */15 9-17 * 1,3-5 ? 2030",
+            ),
+            (
+                "0 30 9,12,15 1,15 May-Aug ? 2018/2",
+                "0 30 9,12,15 1,15 5-8 ? 2018/2",
+            ),
+            ("0 0 12 L-3 * ?", "0 0 12 L-3 * ?"),
+            ("0 0 12 15W * ?", "0 0 12 15W * ?"),
+            ("0 0 12 LW * ?", "0 0 12 LW * ?"),
+            ("0 0 12 ? * FRIL", "0 0 12 ? * 6L"),
+            ("0 0 12 ? * Mon#2", "0 0 12 ? * 2#2"),
+        ] {
+            let translation = Schedule::from_str(expression).unwrap().to_quartz();
+            assert!(translation.is_exact(), "{expression}");
+            assert_eq!(quartz, translation.expression, "{expression}");
+            let round_trip = Schedule::from_str(&translation.expression).unwrap();
+            assert!(round_trip.timeunitspec_eq(&Schedule::from_str(expression).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_to_quartz_untranslatable() {
+        let translation = Schedule::from_str("0 0 12 1,L * FRIL-1")
+            .unwrap()
+            .to_quartz();
+        assert_eq!("0 0 12 * * ?", translation.expression);
+        assert_eq!(2, translation.untranslatable.len());
+
+        let translation = Schedule::from_str("0 0 12 13 * Fri").unwrap().to_quartz();
+        assert_eq!("0 0 12 13 * ?", translation.expression);
+        assert_eq!("Days of Week", translation.untranslatable[0].field);
+    }
+
+    #[test]
+    fn test_dialect_unix() {
+        let parser = ScheduleParser::new().dialect(Dialect::Unix);
+        let schedule = parser.parse("30 9 * * 5-7").unwrap();
+        // Friday, Saturday and Sunday
+        assert_eq!(
+            vec![1, 6, 7],
+            schedule.days_of_week().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0], schedule.seconds().iter().collect::<Vec<_>>());
+        assert!(parser.parse("0 30 9 * * 5").is_err());
+        assert!(parser.parse("30 9 ? * 5").is_err());
+        assert!(parser.parse("30 9 L * *").is_err());
+        assert!(parser.parse("@daily").is_ok());
+
+        // Both day fields are restricted, so either of them matches.
+        let schedule = parser.parse("0 0 13 * Fri").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let days: Vec<_> = schedule.after(&start).take(4).map(|dt| dt.day()).collect();
+        assert_eq!(vec![2, 9, 13, 16], days);
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2026, 10, 13, 0, 0, 0).unwrap()));
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2026, 10, 9, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2026, 10, 10, 0, 0, 0).unwrap()));
+        let round_trip = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 2, 0, 0, 0).unwrap()),
+            round_trip.after(&start).next()
+        );
+        assert!(!schedule.to_quartz().is_exact());
+        assert!(!schedule.to_oncalendar().is_exact());
+
+        // With only one of them restricted, both must match as usual.
+        let schedule = parser.parse("0 0 * * Fri").unwrap();
+        let days: Vec<_> = schedule.after(&start).take(2).map(|dt| dt.day()).collect();
+        assert_eq!(vec![2, 9], days);
+    }
+
+    #[test]
+    fn test_dialect_jenkins() {
+        let parser = ScheduleParser::new().dialect(Dialect::Jenkins);
+        let schedule = parser.parse("0 0 13 * Fri").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 2, 13, 0, 0, 0).unwrap()),
+            schedule.after(&start).next()
+        );
+        assert_eq!(
+            vec![1],
+            parser
+                .parse("0 0 * * 0")
+                .unwrap()
+                .days_of_week()
+                .iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_dialect_aws() {
+        let parser = ScheduleParser::new().dialect(Dialect::Aws);
+        let schedule = parser.parse("15 10 ? * 6L 2026-2030").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 30, 10, 15, 0).unwrap()),
+            schedule.after(&start).next()
+        );
+        assert!(parser.parse("0 18 L * ? *").is_ok());
+        assert!(parser.parse("15 10 ? * 6L").is_err());
+        assert!(parser.parse("@daily").is_err());
+        let error = parser.parse("0 18 1 * MON *").unwrap_err();
+        assert!(error.to_string().contains("'?'"));
+    }
+
+    #[test]
+    fn test_dialect_robfig() {
+        let parser = ScheduleParser::new().dialect(Dialect::Robfig);
+        let schedule = parser.parse("*/30 0 12 ? * 0").unwrap();
+        assert_eq!(vec![0, 30], schedule.seconds().iter().collect::<Vec<_>>());
+        assert_eq!(vec![1], schedule.days_of_week().iter().collect::<Vec<_>>());
+        assert!(parser.parse("0 0 12 L * ?").is_err());
+        assert!(parser.parse("0 12 * * 0").is_err());
+    }
+
+    #[test]
+    fn test_kubernetes_most_recent_schedule_time() {
+        use cron::kubernetes::CronJob;
+
+        let schedule = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 * * * *")
+            .unwrap();
+        let job = CronJob::new(schedule, Tz::Europe__Berlin);
+        let created = Utc.with_ymd_and_hms(2026, 10, 16, 8, 30, 0).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 8, 45, 0).unwrap();
+        assert!(job
+            .most_recent_schedule_time(&created, None, &now)
+            .is_none());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap()),
+            job.next_schedule_time(&now)
+                .map(|time| time.with_timezone(&Utc))
+        );
+
+        // Four days of missed hourly runs
+        let now = Utc.with_ymd_and_hms(2026, 10, 20, 8, 40, 0).unwrap();
+        let most_recent = job.most_recent_schedule_time(&created, None, &now).unwrap();
+        assert_eq!(
+            Tz::Europe__Berlin
+                .with_ymd_and_hms(2026, 10, 20, 10, 0, 0)
+                .unwrap(),
+            most_recent.time
+        );
+        assert_eq!(96, most_recent.missed);
+        assert!(!most_recent.too_many_missed());
+        assert_eq!(96, job.missed_runs(&created, None, &now).count());
+
+        let last = Utc.with_ymd_and_hms(2026, 10, 20, 6, 0, 0).unwrap();
+        let most_recent = job
+            .most_recent_schedule_time(&created, Some(&last), &now)
+            .unwrap();
+        assert_eq!(2, most_recent.missed);
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 21, 8, 40, 0).unwrap();
+        let most_recent = job.most_recent_schedule_time(&created, None, &now).unwrap();
+        assert!(most_recent.too_many_missed());
+    }
+
+    #[test]
+    fn test_kubernetes_starting_deadline() {
+        use cron::kubernetes::CronJob;
+
+        let schedule = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("30 6-16/4 * * 1-5")
+            .unwrap();
+        let job = CronJob::new(schedule, Utc).starting_deadline_seconds(3 * 3600);
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        // Monday 2026-10-19, 15:00: runs at 06:30 and 10:30, and 14:30 within the deadline.
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 15, 0, 0).unwrap();
+        let most_recent = job.most_recent_schedule_time(&created, None, &now).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 19, 14, 30, 0).unwrap(),
+            most_recent.time
+        );
+        assert_eq!(
+            vec![Utc.with_ymd_and_hms(2026, 10, 19, 14, 30, 0).unwrap()],
+            job.missed_runs(&created, None, &now).collect::<Vec<_>>()
+        );
+        let scheduled = Utc.with_ymd_and_hms(2026, 10, 19, 10, 30, 0).unwrap();
+        assert!(job.is_too_late(&scheduled, &now));
+        assert!(!job.is_too_late(&most_recent.time, &now));
+    }
+
+    #[test]
+    fn test_leap_day_policies() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let dates = |policy| {
+            let schedule = ScheduleParser::new()
+                .leap_day(policy)
+                .parse("0 0 0 29 2 ?")
+                .unwrap();
+            let forward: Vec<_> = schedule.after(&start).take(3).collect();
+            let end = forward[2] + Duration::seconds(1);
+            let mut backward: Vec<_> = schedule.after(&end).rev().take(3).collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            assert_eq!(
+                schedule,
+                Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap()
+            );
+            forward
+                .iter()
+                .map(|dt| (dt.year(), dt.month(), dt.day()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![(2028, 2, 29), (2032, 2, 29), (2036, 2, 29)],
+            dates(LeapDayPolicy::Skip)
+        );
+        assert_eq!(
+            vec![(2026, 2, 28), (2027, 2, 28), (2028, 2, 29)],
+            dates(LeapDayPolicy::LastDayOfFebruary)
+        );
+        assert_eq!(
+            vec![(2026, 3, 1), (2027, 3, 1), (2028, 2, 29)],
+            dates(LeapDayPolicy::FirstOfMarch)
+        );
+
+        let schedule = ScheduleParser::new()
+            .leap_day(LeapDayPolicy::FirstOfMarch)
+            .parse("0 0 0 29 2 ?")
+            .unwrap();
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2027, 3, 1, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2028, 3, 1, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2027, 3, 2, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_clamp_to_end_of_month() {
+        let schedule = ScheduleParser::new()
+            .clamp_to_end_of_month(true)
+            .parse("0 0 0 31 * ?")
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        let days: Vec<_> = schedule
+            .after(&start)
+            .take(5)
+            .map(|dt| (dt.month(), dt.day()))
+            .collect();
+        assert_eq!(vec![(1, 31), (2, 28), (3, 31), (4, 30), (5, 31)], days);
+        let mut backward: Vec<_> = schedule
+            .after(&Utc.with_ymd_and_hms(2027, 6, 1, 0, 0, 0).unwrap())
+            .rev()
+            .take(5)
+            .map(|dt| (dt.month(), dt.day()))
+            .collect();
+        backward.reverse();
+        assert_eq!(days, backward);
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2028, 2, 28, 0, 0, 0).unwrap()));
+
+        // Days which exist in every month aren't affected.
+        let schedule = ScheduleParser::new()
+            .clamp_to_end_of_month(true)
+            .parse("0 0 0 15,30 * ?")
+            .unwrap();
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2027, 2, 28, 0, 0, 0).unwrap()));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2027, 4, 29, 0, 0, 0).unwrap()));
+        assert_eq!(
+            schedule,
+            Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap()
+        );
+        assert!(Schedule::from_bytes(&schedule.to_bytes().unwrap())
+            .unwrap()
+            .includes(Utc.with_ymd_and_hms(2027, 2, 28, 0, 0, 0).unwrap()));
+
+        // Without the option, shorter months are skipped.
+        let schedule = Schedule::from_str("0 0 0 31 * ?").unwrap();
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2027, 4, 30, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_year_range() {
+        let error = Schedule::from_str("0 0 0 1 1 ? 2500").unwrap_err();
+        assert!(error.to_string().contains("less than or equal to 2100"));
+
+        let schedule = ScheduleParser::new()
+            .year_range(1970..=9999)
+            .parse("0 0 0 1 1 ? 2500")
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2500, 1, 1, 0, 0, 0).unwrap()),
+            schedule.after(&start).next()
+        );
+        assert_eq!(
+            schedule,
+            Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap()
+        );
+
+        let parser = ScheduleParser::new().year_range(2020..=2030);
+        let schedule = parser.parse("@yearly").unwrap();
+        assert_eq!(4, schedule.after(&start).count());
+        let schedule = parser.parse("0 0 0 1 1 ? */5").unwrap();
+        assert_eq!(
+            vec![2020, 2025, 2030],
+            schedule.years().iter().collect::<Vec<_>>()
+        );
+        let error = parser.parse("0 0 0 1 1 ? 2040").unwrap_err();
+        assert!(error.to_string().contains("less than or equal to 2030"));
+        let error = parser.parse("0 0 0 1 1 ? 2019-2025").unwrap_err();
+        assert!(error.to_string().contains("2019"));
+
+        assert!(ScheduleParser::new()
+            .year_range(0..=2020)
+            .parse("@daily")
+            .is_err());
+        assert!(ScheduleParser::new()
+            .year_range(1970..=10000)
+            .parse("@daily")
+            .is_err());
+    }
+
+    #[test]
+    fn test_interval_schedule() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        let schedule = IntervalSchedule::new(&start, Duration::seconds(45));
+        assert_eq!(start, schedule.start());
+        assert_eq!(Duration::seconds(45), schedule.every());
+
+        let before = start - Duration::hours(1);
+        assert_eq!(Some(start), schedule.next_after(&before));
+        assert_eq!(None, schedule.prev_before(&start));
+        let later = start + Duration::seconds(100);
+        assert_eq!(
+            Some(start + Duration::seconds(135)),
+            schedule.next_after(&later)
+        );
+        assert_eq!(
+            Some(start + Duration::seconds(90)),
+            schedule.prev_before(&later)
+        );
+        assert_eq!(
+            Some(start + Duration::seconds(45)),
+            schedule.prev_before(&(start + Duration::seconds(90)))
+        );
+        assert!(Occurrences::includes(
+            &schedule,
+            start + Duration::seconds(90)
+        ));
+        assert!(!Occurrences::includes(
+            &schedule,
+            start + Duration::seconds(91)
+        ));
+        assert!(!Occurrences::includes(
+            &schedule,
+            start - Duration::seconds(45)
+        ));
+
+        let mut times = schedule.after(&start).skip(1);
+        assert_eq!(Some(start + Duration::seconds(90)), times.next());
+        let times: Vec<_> = schedule.after(&later).rev().collect();
+        assert_eq!(
+            vec![
+                start + Duration::seconds(90),
+                start + Duration::seconds(45),
+                start
+            ],
+            times
+        );
+
+        // Intervals are elapsed time, whatever the timezone the times are queried in.
+        let paris = Tz::Europe__Paris;
+        let schedule = IntervalSchedule::new(
+            &paris.with_ymd_and_hms(2026, 10, 25, 1, 0, 0).unwrap(),
+            Duration::hours(1),
+        );
+        let times: Vec<_> = schedule
+            .after(&paris.with_ymd_and_hms(2026, 10, 25, 1, 0, 0).unwrap())
+            .take(3)
+            .map(|dt| dt.naive_local().hour())
+            .collect();
+        assert_eq!(vec![2, 2, 3], times);
+    }
+
+    #[test]
+    fn test_occurrences_are_shared_by_schedules() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        let schedules: Vec<Box<dyn Occurrences<Utc>>> = vec![
+            Box::new(Schedule::from_str("0 0 * * * *").unwrap()),
+            Box::new(IntervalSchedule::new(&start, Duration::minutes(40))),
+        ];
+        let next: Vec<_> = schedules
+            .iter()
+            .map(|schedule| schedule.next_after(&start).unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 16, 9, 40, 0).unwrap()
+            ],
+            next
+        );
+        let times: Vec<_> = OccurrenceIterator::new(schedules[1].as_ref(), &start)
+            .take(2)
+            .collect();
+        assert_eq!(next[1] + Duration::minutes(40), times[1]);
+
+        let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 8, 0, 0).unwrap()),
+            schedule.prev_before(&start)
+        );
+        assert_eq!(Some(next[0]), schedule.next_after(&start));
+    }
+
+    #[test]
+    fn test_schedule_expr() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 31, 12, 0, 0).unwrap();
+        let once = ScheduleExpr::from_str("@once 2025-01-01T00:00:30+01:00").unwrap();
+        let time = Utc.with_ymd_and_hms(2024, 12, 31, 23, 0, 30).unwrap();
+        assert_eq!(ScheduleExpr::Once(time), once);
+        assert_eq!(vec![time], once.after(&now).collect::<Vec<_>>());
+        assert_eq!(None, once.next_after(&time));
+        assert_eq!(Some(time), once.prev_before(&(time + Duration::seconds(1))));
+        assert!(once.includes(time));
+
+        let never = ScheduleExpr::from_str(" @never ").unwrap();
+        assert_eq!(ScheduleExpr::Never, never);
+        assert_eq!(None, never.next_after(&now));
+        assert_eq!(None, never.prev_before(&now));
+
+        let cron = ScheduleExpr::from_str("@daily").unwrap();
+        assert_eq!(
+            ScheduleExpr::from(Schedule::from_str("@daily").unwrap()),
+            cron
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            cron.next_after(&now)
+        );
+
+        let interval = ScheduleExpr::from(IntervalSchedule::new(&now, Duration::hours(5)));
+        assert_eq!(Some(now + Duration::hours(5)), interval.after(&now).next());
+
+        assert!(ScheduleExpr::from_str("@once").is_err());
+        assert!(ScheduleExpr::from_str("@once tomorrow").is_err());
+        assert!(ScheduleExpr::from_str("@sometimes").is_err());
+    }
+
+    #[test]
+    fn test_alternate_display() {
+        let schedule = Schedule::from_str("0 0,30 9-17 L-2,15W * 6#3,2L 2026").unwrap();
+        assert_eq!("0 0,30 9-17 L-2,15W * 6#3,2L 2026", format!("{schedule}"));
+        assert_eq!(
+            "0 0,30 9-17 L-2,15W * 6#3,2L 2026
+  Seconds:       0
+  Minutes:       0,30
+  Hours:         9-17
+  Days of Month: 15W,L-2
+  Months:        *
+  Days of Week:  6#3,2L
+  Years:         2026",
+            format!("{schedule:#}")
+        );
+
+        let schedule = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .leap_day(LeapDayPolicy::FirstOfMarch)
+            .parse("0 12 1,2,3 * MON")
+            .unwrap();
+        let verbose = format!("{schedule:#}");
+        assert!(verbose.contains("Days of Month: 1-3"));
+        assert!(verbose.contains("Days of Week:  2"));
+        assert!(verbose.contains("Days match either"));
+        assert!(verbose.contains("Leap day policy: FirstOfMarch"));
+    }
+
+    #[test]
+    fn test_debug_shows_resolved_ordinals() {
+        let schedule = Schedule::from_str("0 0,30 9-17 L-2,15W * 6#3,2L").unwrap();
+        let debug = format!("{schedule:?}");
+        assert!(debug.contains("Seconds([0])"));
+        assert!(debug.contains("Minutes([0, 30])"));
+        assert!(debug.contains("Hours([9..=17])"));
+        assert!(debug.contains("DaysOfMonth([15 WEEKDAY, LAST-2])"));
+        assert!(debug.contains("Months([1..=12])"));
+        assert!(debug.contains("DaysOfWeek([6 NTH#3, 2 LAST])"));
+        assert!(debug.contains("Years([1970..=2100])"));
+
+        let schedule = Schedule::from_str("0 0 0 LW * 5L-1").unwrap();
+        let debug = format!("{schedule:?}");
+        assert!(debug.contains("DaysOfMonth([LAST WEEKDAY])"));
+        assert!(debug.contains("DaysOfWeek([5 LAST-1])"));
+    }
+
+    #[test]
+    fn test_wildcard_detection() {
+        let schedule = Schedule::from_str("0 0 9 ? * 6#3 2026-2030").unwrap();
+        assert!(!schedule.has_second_precision());
+        assert!(!schedule.is_dom_restricted());
+        assert!(schedule.is_dow_restricted());
+        assert!(schedule.is_year_restricted());
+        assert!(schedule.months().is_all());
+        assert!(!schedule.hours().is_all());
+
+        let schedule = Schedule::from_str("*/10 * * L * ?").unwrap();
+        assert!(schedule.has_second_precision());
+        assert!(schedule.is_dom_restricted());
+        assert!(!schedule.is_dow_restricted());
+        assert!(!schedule.is_year_restricted());
+        assert!(schedule.minutes().is_all());
+    }
+
+    #[test]
+    fn test_field_syntax() {
+        let schedule = Schedule::from_str("0,30 */15 9-17/2 LW JAN-MAR,DEC 6#3,FRIL 2026").unwrap();
+        let syntax = schedule.syntax().unwrap();
+        assert_eq!("0,30", syntax.seconds.text());
+        assert!(syntax.seconds.is_list());
+        assert_eq!(
+            &[SpecifierForm::Value, SpecifierForm::Value],
+            syntax.seconds.forms()
+        );
+        assert_eq!(&[SpecifierForm::Step], syntax.minutes.forms());
+        assert_eq!(&[SpecifierForm::Step], syntax.hours.forms());
+        assert_eq!(&[SpecifierForm::LastWeekday], syntax.days_of_month.forms());
+        assert_eq!(
+            &[SpecifierForm::Range, SpecifierForm::Value],
+            syntax.months.forms()
+        );
+        assert_eq!(
+            &[SpecifierForm::Nth, SpecifierForm::Last],
+            syntax.days_of_week.forms()
+        );
+        assert_eq!("2026", syntax.years.text());
+        assert!(!syntax.years.is_omitted());
+
+        let schedule = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("~ 9 1-15 * *")
+            .unwrap();
+        let syntax = schedule.syntax().unwrap();
+        assert!(syntax.seconds.is_omitted());
+        assert!(syntax.minutes.uses(SpecifierForm::Random));
+        assert!(syntax.days_of_month.uses(SpecifierForm::Range));
+        assert_eq!(&[SpecifierForm::Wildcard], syntax.days_of_week.forms());
+        assert!(syntax.years.is_omitted());
+
+        // Shorthands and decoded schedules don't keep the syntax of their fields.
+        assert!(Schedule::from_str("@daily").unwrap().syntax().is_none());
+        assert!(Schedule::from_bytes(&schedule.to_bytes().unwrap())
+            .unwrap()
+            .syntax()
+            .is_none());
+    }
+
+    #[test]
+    fn test_field_mutation() {
+        let schedule = Schedule::from_str("*/30 0 8 * * Mon-Fri").unwrap();
+        let business_hours = schedule
+            .with_seconds("0")
+            .unwrap()
+            .with_hours("9-17")
+            .unwrap();
+        assert_eq!("0 0 9-17 * * Mon-Fri", business_hours.source());
+        assert_eq!(
+            business_hours,
+            Schedule::from_str("0 0 9-17 * * Mon-Fri").unwrap()
+        );
+        assert!(business_hours.timeunitspec_eq(&Schedule::from_str("0 0 9-17 * * 2-6").unwrap()));
+        assert_eq!("*/30 0 8 * * Mon-Fri", schedule.source());
+
+        let with_years = schedule.with_years("2030").unwrap();
+        assert_eq!("*/30 0 8 * * Mon-Fri 2030", with_years.source());
+        assert!(schedule.with_hours("24").is_err());
+        assert!(schedule.with_hours("9 10").is_err());
+
+        // Fields are parsed in the dialect of the schedule.
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 9 * * *")
+            .unwrap();
+        let weekdays = unix.with_days_of_week("1-5").unwrap();
+        assert_eq!("0 9 * * 1-5", weekdays.source());
+        assert!(weekdays.timeunitspec_eq(&Schedule::from_str("0 0 9 * * Mon-Fri").unwrap()));
+        let either = weekdays.with_days_of_month("1").unwrap();
+        let first = Utc.with_ymd_and_hms(2026, 11, 1, 9, 0, 0).unwrap();
+        assert!(either.includes(first));
+        assert!(unix.with_seconds("0").is_err());
+        assert!(unix.with_years("2030").is_err());
+
+        // Fields are resolved with the options the schedule was parsed with.
+        let parser = ScheduleParser::new().seed(7).hash_key("backup");
+        let seeded = parser.parse("0 0 0 * * *").unwrap();
+        let expected = parser.parse("0 ~ H * * *").unwrap();
+        let replaced = seeded.with_minutes("~").unwrap().with_hours("H").unwrap();
+        assert!(replaced.timeunitspec_eq(&expected));
+        let wide = ScheduleParser::new().year_range(1900..=2200);
+        let schedule = wide.parse("0 0 0 1 1 ? 2030").unwrap();
+        assert!(schedule.with_years("2150").is_ok());
+        let every_year = schedule.with_years("*").unwrap();
+        assert!(every_year.timeunitspec_eq(&wide.parse("0 0 0 1 1 ?").unwrap()));
+        assert!(every_year.years().includes(1900));
+
+        // Shorthands are written out in full.
+        let daily = Schedule::from_str("@daily")
+            .unwrap()
+            .with_hours("6")
+            .unwrap();
+        assert_eq!("0 0 6 * * *", daily.source());
+        assert!(daily.syntax().is_some());
+        assert!(Schedule::from_str("@daily").unwrap().syntax().is_none());
+
+        // Shorthands keep their dialect where it can express them.
+        let unix = ScheduleParser::new().dialect(Dialect::Unix);
+        let weekly = unix.parse("@weekly").unwrap().with_hours("6").unwrap();
+        assert_eq!("0 6 * * 0", weekly.source());
+        let sunday = Utc.with_ymd_and_hms(2026, 10, 18, 6, 0, 0).unwrap();
+        assert!(weekly.includes(sunday));
+        assert!(weekly.with_seconds("0").is_err());
+        let year_end = unix.parse("@yearend").unwrap().with_hours("6").unwrap();
+        assert_eq!("0 0 6 L 12 *", year_end.source());
+        let robfig = ScheduleParser::new().dialect(Dialect::Robfig);
+        let daily = robfig.parse("@daily").unwrap().with_minutes("30").unwrap();
+        assert_eq!("0 30 0 * * *", daily.source());
+
+        // Replaced fields stay within the limits of the parser.
+        let limited = ScheduleParser::new().max_list_items(2).max_length(14);
+        let schedule = limited.parse("0 0 9 * * *").unwrap();
+        assert!(schedule.with_hours("9,17").is_ok());
+        let error = schedule.with_hours("9,12,17").unwrap_err();
+        assert_eq!(ErrorCode::LimitExceeded, error.code());
+        let error = schedule.with_hours("10-12/2").unwrap_err();
+        assert_eq!(ErrorCode::LimitExceeded, error.code());
+        let daily = limited.parse("@daily").unwrap();
+        assert!(daily.with_minutes("1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_offset_by() {
+        let daily = Schedule::from_str("0 0 0 * * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+
+        let before_midnight = daily.offset_by(Duration::minutes(-5));
+        assert_eq!(Duration::minutes(-5), before_midnight.offset());
+        let upcoming: Vec<_> = before_midnight.after(&start).take(2).collect();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 16, 23, 55, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 17, 23, 55, 0).unwrap(),
+            ],
+            upcoming
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 15, 23, 55, 0).unwrap()),
+            before_midnight.after(&start).next_back()
+        );
+        assert!(Occurrences::includes(
+            &before_midnight,
+            Utc.with_ymd_and_hms(2026, 10, 16, 23, 55, 0).unwrap()
+        ));
+        assert!(!Occurrences::includes(
+            &before_midnight,
+            Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap()
+        ));
+
+        // The offset is applied to the exact occurrence, so it's never skipped at the boundary.
+        let after_midnight = daily.offset_by(Duration::hours(1));
+        let midnight = Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 1, 0, 0).unwrap()),
+            after_midnight.next_after(&midnight)
+        );
+    }
+
+    #[test]
+    fn test_every_nth() {
+        let fridays = Schedule::from_str("0 0 9 * * Fri").unwrap();
+        let anchor = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let every_third = fridays.every_nth(3, &anchor);
+        let friday = |month, day| Utc.with_ymd_and_hms(2026, month, day, 9, 0, 0).unwrap();
+
+        let upcoming: Vec<_> = every_third.after(&anchor).take(3).collect();
+        assert_eq!(
+            vec![friday(10, 16), friday(11, 6), friday(11, 27)],
+            upcoming
+        );
+        assert_eq!(Some(friday(11, 27)), every_third.next_after(&friday(11, 6)));
+        assert_eq!(Some(friday(11, 6)), every_third.prev_before(&friday(11, 7)));
+
+        // Occurrences before the anchor are counted backwards from it.
+        let september = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        assert_eq!(Some(friday(9, 4)), every_third.next_after(&september));
+        assert_eq!(Some(friday(9, 25)), every_third.next_after(&friday(9, 4)));
+        assert_eq!(
+            Some(friday(9, 25)),
+            every_third.prev_before(&friday(10, 16))
+        );
+        let previous: Vec<_> = every_third.after(&anchor).rev().take(2).collect();
+        assert_eq!(vec![friday(9, 25), friday(9, 4)], previous);
+
+        assert!(every_third.includes(friday(11, 6)));
+        assert!(every_third.includes(friday(9, 4)));
+        assert!(!every_third.includes(friday(10, 30)));
+        assert!(!every_third.includes(friday(9, 11)));
+
+        let every = fridays.every_nth(1, &anchor);
+        assert!(every
+            .after(&anchor)
+            .take(5)
+            .eq(fridays.after(&anchor).take(5)));
+    }
+
+    #[test]
+    fn test_floor_and_ceil_to_schedule() {
+        let schedule = Schedule::from_str("0 0 9,17 * * Mon-Fri").unwrap();
+        let at = |day, hour, minute| {
+            Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+                .unwrap()
+        };
+
+        // Friday evening snaps back to the evening run, forwards to Monday morning.
+        let friday_night = at(16, 22, 0);
+        assert_eq!(
+            Some(at(16, 17, 0)),
+            schedule.floor_to_schedule(&friday_night)
+        );
+        assert_eq!(Some(at(19, 9, 0)), schedule.ceil_to_schedule(&friday_night));
+
+        // Exact matches snap to themselves.
+        assert_eq!(
+            Some(at(16, 9, 0)),
+            schedule.floor_to_schedule(&at(16, 9, 0))
+        );
+        assert_eq!(Some(at(16, 9, 0)), schedule.ceil_to_schedule(&at(16, 9, 0)));
+
+        // Within a matching second, the floor is the start of the second.
+        let just_after = at(16, 9, 0) + Duration::milliseconds(500);
+        assert_eq!(Some(at(16, 9, 0)), schedule.floor_to_schedule(&just_after));
+        assert_eq!(Some(at(16, 17, 0)), schedule.ceil_to_schedule(&just_after));
+        let just_before = at(16, 9, 0) - Duration::milliseconds(500);
+        assert_eq!(
+            Some(at(15, 17, 0)),
+            schedule.floor_to_schedule(&just_before)
+        );
+        assert_eq!(Some(at(16, 9, 0)), schedule.ceil_to_schedule(&just_before));
+
+        let past = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(None, past.ceil_to_schedule(&friday_night));
+    }
+
+    #[test]
+    fn test_active_windows() {
+        let business_hours = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("* 9-17 * * MON-FRI")
+            .unwrap();
+        let at = |day, hour, minute| {
+            Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+                .unwrap()
+        };
+
+        let current = business_hours.window_at(&at(16, 12, 30)).unwrap();
+        assert_eq!(at(16, 9, 0), current.start);
+        assert_eq!(at(16, 18, 0), current.end);
+        assert_eq!(Duration::hours(9), current.duration());
+        assert!(current.contains(&at(16, 17, 59)));
+        assert!(!current.contains(&at(16, 18, 0)));
+        assert_eq!(None, business_hours.window_at(&at(16, 18, 0)));
+        assert_eq!(None, business_hours.window_at(&at(17, 12, 0)));
+
+        let next = business_hours.next_window(&at(16, 12, 30)).unwrap();
+        assert_eq!(at(19, 9, 0), next.start);
+        assert_eq!(at(19, 18, 0), next.end);
+        assert_eq!(Some(next), business_hours.next_window(&at(17, 0, 0)));
+
+        // Windows at the resolution of a second.
+        let seconds = Schedule::from_str("0-29 0 12 * * *").unwrap();
+        let window = seconds.next_window(&at(16, 0, 0)).unwrap();
+        assert_eq!(at(16, 12, 0), window.start);
+        assert_eq!(Duration::seconds(30), window.duration());
+
+        // Schedules which never stop firing are cut after a week.
+        let always = Schedule::from_str("0 * * * * *").unwrap();
+        let window = always.window_at(&at(16, 12, 0)).unwrap();
+        assert_eq!(Duration::weeks(2), window.duration());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let schedule = Schedule::from_str("0 30 */6 * * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let histogram = schedule.histogram(&start, Duration::days(365));
+        assert_eq!(4 * 365, histogram.total);
+        assert_eq!(365, histogram.hours[0]);
+        assert_eq!(0, histogram.hours[1]);
+        assert_eq!(365, histogram.hours[18]);
+        assert_eq!(4 * 31, histogram.months[0]);
+        assert_eq!(4 * 28, histogram.months[1]);
+        // 2026 starts and ends on a Thursday.
+        assert_eq!(4 * 53, histogram.days_of_week[3]);
+        assert_eq!(4 * 52, histogram.days_of_week[4]);
+
+        // Buckets follow the local time of the timezone given.
+        let start = Tz::America__New_York
+            .with_ymd_and_hms(2026, 10, 16, 0, 0, 0)
+            .unwrap();
+        let histogram = schedule.histogram(&start, Duration::days(1));
+        assert_eq!(4, histogram.total);
+        assert_eq!(1, histogram.hours[0]);
+        assert_eq!(4, histogram.days_of_week[4]);
+
+        let never = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(
+            Histogram::default(),
+            never.histogram(&start, Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn test_whitespace_and_comments() {
+        let schedule = Schedule::from_str("0\t0  12 * *\t\t*   # lunch reminder  ").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 12 * * *").unwrap()));
+        assert_eq!(Some("lunch reminder"), schedule.comment());
+        assert_eq!("0\t0  12 * *\t\t*   # lunch reminder  ", schedule.source());
+
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("30 2 * * 0 #weekly cleanup")
+            .unwrap();
+        assert_eq!(Some("weekly cleanup"), unix.comment());
+        let daily = Schedule::from_str("@daily # midnight").unwrap();
+        assert_eq!(Some("midnight"), daily.comment());
+
+        // A `#` within a field is the nth occurrence of a day of the week.
+        let nth = Schedule::from_str("0 0 12 ? * 6#3 # third friday").unwrap();
+        assert_eq!(Some("third friday"), nth.comment());
+        assert!(nth.includes(Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()));
+        assert!(Schedule::from_str("# only a comment").is_err());
+
+        let edited = schedule.with_hours("13").unwrap();
+        assert_eq!("0 0 13 * * * # lunch reminder", edited.source());
+        assert_eq!(Some("lunch reminder"), edited.comment());
+
+        let expr = ScheduleExpr::from_str("@once 2027-01-01T00:00Z # new year").unwrap();
+        assert!(matches!(expr, ScheduleExpr::Once(_)));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "0 0 2 * * *\n  \n# Reports\n\t0 0 */12 * * * # twice a day\r\n@weekly";
+        let schedules = ScheduleParser::new().parse_lines(input).unwrap();
+        let lines: Vec<_> = schedules.iter().map(|(line, _)| *line).collect();
+        assert_eq!(vec![1, 4, 5], lines);
+        assert_eq!(Some("twice a day"), schedules.get(&4).unwrap().comment());
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap();
+        assert_eq!(
+            Some((&1, Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap())),
+            schedules.after(&start).next()
+        );
+        assert!(ScheduleParser::new().parse_lines("").unwrap().is_empty());
+
+        let error = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse_lines("0 25 * * *\n0 9 * * 1-5\n61 * * * *")
+            .unwrap_err();
+        match error.kind() {
+            ErrorKind::Lines(lines) => {
+                let numbers: Vec<_> = lines.iter().map(|(line, _)| *line).collect();
+                assert_eq!(vec![1, 3], numbers);
+            }
+            kind => panic!("Unexpected error: {kind:?}"),
+        }
+        assert!(error.to_string().contains("\nLine 3: "));
+    }
+
+    #[test]
+    fn test_extended_name_aliases() {
+        let aliases = Schedule::from_str("0 0 9 * Sept,SEP THUR,Tues,weds").unwrap();
+        let names = Schedule::from_str("0 0 9 * September Thursday,Tuesday,Wednesday").unwrap();
+        assert!(aliases.timeunitspec_eq(&names));
+        let range = Schedule::from_str("0 0 9 * Jan-Sept Tues-Thur").unwrap();
+        assert_eq!(9, range.months().count());
+        assert_eq!(3, range.days_of_week().count());
+        assert!(Schedule::from_str("0 0 9 * * Thursd").is_err());
+        assert!(Schedule::from_str("0 0 9 * Septe *").is_err());
+    }
+
+    #[cfg(feature = "locales")]
+    #[test]
+    fn test_localized_names() {
+        use cron::Locale;
+
+        let english = Schedule::from_str("0 0 9 ? Jan,Aug,Dec Tue-Thu").unwrap();
+        for (locale, expression) in [
+            (Locale::French, "0 0 9 ? janv,AOUT,decembre mar-jeudi"),
+            (Locale::German, "0 0 9 ? Januar,aug,Dez Di-Do"),
+            (Locale::Spanish, "0 0 9 ? ene,agosto,dic martes-jue"),
+        ] {
+            let schedule = ScheduleParser::new()
+                .locale(locale)
+                .parse(expression)
+                .unwrap();
+            assert!(schedule.timeunitspec_eq(&english), "{locale:?}");
+        }
+
+        // In the day of week field, `mar` is a Tuesday in French, while it's March in months.
+        let french = ScheduleParser::new().locale(Locale::French);
+        let schedule = french.parse("0 0 9 ? mar mar").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 9 ? Mar Tue").unwrap()));
+        let last_friday = french.parse("0 0 9 ? * venL").unwrap();
+        assert!(last_friday.timeunitspec_eq(&Schedule::from_str("0 0 9 ? * FRIL").unwrap()));
+        assert!(french.parse("0 0 9 * Jan Mon").is_ok());
+        assert!(Schedule::from_str("0 0 9 * janv *").is_err());
+        let edited = schedule.with_days_of_week("lun-ven").unwrap();
+        assert!(edited.timeunitspec_eq(&Schedule::from_str("0 0 9 ? Mar Mon-Fri").unwrap()));
+    }
+
+    #[test]
+    fn test_lint() {
+        let kinds = |expression: &str| -> Vec<LintKind> {
+            Schedule::from_str(expression)
+                .unwrap()
+                .lint()
+                .into_iter()
+                .map(|warning| warning.kind)
+                .collect()
+        };
+        assert!(kinds("0 0 9 * * Mon-Fri").is_empty());
+        assert!(kinds("@daily").is_empty());
+        assert!(kinds("0 0 0 29 2 ?").is_empty());
+        assert!(kinds("0 */30 10-12/2 * * *").is_empty());
+        assert_eq!(vec![LintKind::StepCoversField], kinds("0 0 0 1 */12 ?"));
+        assert_eq!(vec![LintKind::StepCoversField], kinds("0 0 10-12/3 * * *"));
+        assert_eq!(vec![LintKind::StepCoversField], kinds("0 0 20/4 * * *"));
+        assert_eq!(vec![LintKind::EverySecond], kinds("* 0 9 * * *"));
+        assert_eq!(vec![LintKind::BothDaysRestricted], kinds("0 0 9 1 * Mon"));
+        assert_eq!(
+            vec![LintKind::DaysMissingFromMonths],
+            kinds("0 0 9 30,31 2 ?")
+        );
+
+        let warnings = Schedule::from_str("0 0 9 15,31 Apr,Jun ?").unwrap().lint();
+        assert_eq!(1, warnings.len());
+        assert_eq!(Some("Days of Month"), warnings[0].field.as_deref());
+        assert_eq!(
+            "None of the included months have day 31.",
+            warnings[0].message
+        );
+
+        // The message follows how the dialect combines the day fields.
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("30/30 9 1 * 1")
+            .unwrap();
+        let warnings = unix.lint();
+        assert_eq!(2, warnings.len());
+        assert_eq!(LintKind::StepCoversField, warnings[0].kind);
+        assert!(warnings[1].message.contains("either"));
+
+        // Clamped days fire on the last day of shorter months.
+        let clamped = ScheduleParser::new()
+            .clamp_to_end_of_month(true)
+            .parse("0 0 9 31 4 ?")
+            .unwrap();
+        assert!(clamped.lint().is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_suggestions() {
+        let suggest = |expression: &str| -> Vec<(String, RewriteReason)> {
+            Schedule::from_str(expression)
+                .unwrap()
+                .suggestions()
+                .into_iter()
+                .map(|suggestion| (suggestion.replacement, suggestion.reason))
+                .collect()
+        };
+        assert_eq!(
+            vec![("1-5".to_string(), RewriteReason::Range)],
+            suggest("0 1,2,3,4,5 * * * *")
+        );
+        assert_eq!(
+            vec![("*/30".to_string(), RewriteReason::Step)],
+            suggest("0 0,30 * * * *")
+        );
+        assert_eq!(
+            vec![("5/15".to_string(), RewriteReason::Step)],
+            suggest("0 5,20,35,50 * * * *")
+        );
+        assert_eq!(
+            vec![("*".to_string(), RewriteReason::Wildcard)],
+            suggest("0 0 0-23 * * *")
+        );
+        assert_eq!(
+            vec![("1,3-5".to_string(), RewriteReason::Range)],
+            suggest("0 0 0 1,3,4,5 * ?")
+        );
+
+        // Fields which are already simple, or written with names or specials, are left alone.
+        for expression in [
+            "0 */15 9-17 * * Mon-Fri",
+            "0 0 12 1,15 * ?",
+            "0 0 12 L * ?",
+            "0 0 12 ? JAN,FEB,MAR *",
+            "0 ~ 12 * * *",
+            "@daily",
+        ] {
+            assert!(suggest(expression).is_empty(), "{expression}");
+        }
+
+        let suggestion = &Schedule::from_str("0 0 9 * * 2,3,4,5,6 # weekdays")
+            .unwrap()
+            .suggestions()[0];
+        assert_eq!("Days of Week", suggestion.field);
+        assert_eq!("2,3,4,5,6", suggestion.original);
+        assert_eq!("0 0 9 * * 2-6 # weekdays", suggestion.expression);
+
+        // Days of the week are written in the numbering of the dialect.
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 9 * * 0,1,2,3,4,5,6")
+            .unwrap();
+        let suggestions = unix.suggestions();
+        assert_eq!("*", suggestions[0].replacement);
+        assert_eq!("0 9 * * *", suggestions[0].expression);
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 9 * * 1,2,3,4,5")
+            .unwrap();
+        assert_eq!("0 9 * * 1-5", unix.suggestions()[0].expression);
+    }
+
+    #[test]
+    fn test_parse_to_ast() {
+        let ast = cron::parse_to_ast("0 */15 9-17 ? JAN-MAR MON#2,FRIL # standup").unwrap();
+        assert_eq!(Dialect::Quartz, ast.dialect());
+        assert_eq!(Some("standup"), ast.comment.as_deref());
+        assert_eq!(vec![Item::Step(Box::new(Item::All), 15)], ast.minutes.items);
+        assert_eq!(
+            vec![Item::Range(Value::Number(9), Value::Number(17))],
+            ast.hours.items
+        );
+        assert_eq!(vec![Item::Any], ast.days_of_month.items);
+        assert_eq!(
+            vec![Item::Range(
+                Value::Name("JAN".to_string()),
+                Value::Name("MAR".to_string())
+            )],
+            ast.months.items
+        );
+        assert_eq!(
+            vec![
+                Item::NthOfMonth(Value::Name("MON".to_string()), 2),
+                Item::LastOfMonth(Value::Name("FRI".to_string()), 0),
+            ],
+            ast.days_of_week.items
+        );
+        assert!(ast.years.is_none());
+        assert_eq!(
+            "0 */15 9-17 ? JAN-MAR MON#2,FRIL # standup",
+            ast.to_string()
+        );
+
+        // The tree can be rewritten before compiling it.
+        let mut ast = ast;
+        ast.hours.items = vec![Item::Value(Value::Number(10))];
+        ast.years = Some(AstField {
+            items: vec![Item::Value(Value::Number(2030))],
+        });
+        let schedule = ast.compile().unwrap();
+        assert_eq!(
+            "0 */15 10 ? JAN-MAR MON#2,FRIL 2030 # standup",
+            schedule.source()
+        );
+        let next = schedule
+            .after(&Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+            .next();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2030, 1, 14, 10, 0, 0).unwrap()),
+            next
+        );
+
+        // Invalid trees fail to compile.
+        ast.hours.items = vec![Item::Value(Value::Number(24))];
+        assert!(ast.compile().is_err());
+
+        // Other dialects leave out the fields they don't have.
+        let parser = ScheduleParser::new().dialect(Dialect::Unix);
+        let ast = parser.parse_to_ast("30 9 * * 1-5").unwrap();
+        assert!(ast.seconds.is_none());
+        assert_eq!(Dialect::Unix, ast.dialect());
+        assert_eq!("30 9 * * 1-5", parser.compile(&ast).unwrap().source());
+
+        // Shorthands are expanded.
+        let ast = cron::parse_to_ast("@daily").unwrap();
+        assert_eq!(
+            Schedule::from_str("@daily")
+                .unwrap()
+                .next_after(&Utc::now()),
+            ast.compile().unwrap().next_after(&Utc::now())
+        );
+
+        assert!(cron::parse_to_ast("0 0 25 * * *").is_err());
+    }
+
+    #[test]
+    fn test_ast_visitor_and_transform() {
+        // Renames the days of the week to their numbers.
+        struct NumberedDays;
+        impl Transform for NumberedDays {
+            fn transform_value(&mut self, kind: FieldKind, value: Value) -> Value {
+                match (kind, value) {
+                    (FieldKind::DaysOfWeek, Value::Name(name)) => {
+                        let days = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+                        let day = days.iter().position(|day| name.eq_ignore_ascii_case(day));
+                        Value::Number(day.unwrap() as u32 + 1)
+                    }
+                    (_, value) => value,
+                }
+            }
+        }
+        let ast = cron::parse_to_ast("0 0 9 ? * Mon-Fri,SUN#1").unwrap();
+        let ast = ast.transform(&mut NumberedDays);
+        assert_eq!("0 0 9 ? * 2-6,1#1", ast.compile().unwrap().source());
+
+        // Clamps the hours to office hours, including the starts of steps.
+        struct OfficeHours;
+        impl Transform for OfficeHours {
+            fn transform_value(&mut self, kind: FieldKind, value: Value) -> Value {
+                match (kind, value) {
+                    (FieldKind::Hours, Value::Number(hour)) => Value::Number(hour.clamp(8, 18)),
+                    (_, value) => value,
+                }
+            }
+        }
+        let ast = cron::parse_to_ast("0 0 3,6-20,22/2 * * ?").unwrap();
+        let ast = ast.transform(&mut OfficeHours);
+        assert_eq!("0 0 8,8-18,18/2 * * ?", ast.to_string());
+        assert!(ast.compile().is_ok());
+
+        // Adds seconds to Unix expressions, which then compile as robfig/cron expressions.
+        struct InjectSeconds;
+        impl Transform for InjectSeconds {
+            fn transform_ast(&mut self, mut ast: Ast) -> Ast {
+                if ast.seconds.is_none() {
+                    ast.seconds = Some(AstField {
+                        items: vec![Item::Value(Value::Number(30))],
+                    });
+                }
+                cron::transform_ast(self, ast)
+            }
+        }
+        let parser = ScheduleParser::new().dialect(Dialect::Unix);
+        let mut ast = parser
+            .parse_to_ast("*/15 9 * * 1-5")
+            .unwrap()
+            .transform(&mut InjectSeconds);
+        ast.set_dialect(Dialect::Robfig);
+        let schedule = ast.compile().unwrap();
+        assert_eq!("30 */15 9 * * 1-5", schedule.source());
+        let friday = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 30).unwrap()),
+            schedule.next_after(&friday)
+        );
+
+        // Counts the names used in the expression, without walking into the days of the week.
+        struct Names(usize);
+        impl Visitor for Names {
+            fn visit_field(&mut self, kind: FieldKind, field: &AstField) {
+                if kind != FieldKind::DaysOfWeek {
+                    cron::walk_field(self, kind, field);
+                }
+            }
+            fn visit_value(&mut self, _kind: FieldKind, value: &Value) {
+                if let Value::Name(_) = value {
+                    self.0 += 1;
+                }
+            }
+        }
+        let mut names = Names(0);
+        cron::parse_to_ast("0 0 9 ? JAN-MAR/2,DEC MON-FRI")
+            .unwrap()
+            .accept(&mut names);
+        assert_eq!(3, names.0);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_next_tick() {
+        let schedule = Schedule::from_str("* * * * * *").unwrap();
+        let before = Utc::now();
+        let tick = schedule.next_tick(Utc).await.unwrap();
+        assert!(tick > before);
+        assert!(Utc::now() >= tick);
+        assert!(Utc::now() - tick < Duration::seconds(1));
+
+        // The future can be spawned.
+        let handle = tokio::spawn(async move { schedule.next_tick(Utc).await });
+        assert!(handle.await.unwrap().is_some());
+
+        // Schedules which never fire again resolve right away.
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020").unwrap();
+        assert_eq!(None, schedule.next_tick(Utc).await);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_missed_tick_behavior() {
+        let schedule = Schedule::from_str("* * * * * *").unwrap();
+        let mut burst = schedule.ticks(Utc);
+        assert_eq!(MissedTickBehavior::Burst, burst.missed_tick_behavior());
+        let mut skip = schedule.ticks(Utc);
+        skip.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut delay = schedule.ticks(Utc);
+        delay.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+        let late = Utc::now();
+
+        // Only the last missed firing is yielded, then the stream continues from now.
+        let missed = delay.tick().await.unwrap();
+        assert!(missed < late);
+        assert!(late - missed < Duration::seconds(1));
+        assert!(delay.tick().await.unwrap() > late);
+
+        // Each missed firing is yielded right away.
+        let first = burst.tick().await.unwrap();
+        let second = burst.tick().await.unwrap();
+        assert!(second < late);
+        assert_eq!(Duration::seconds(1), second - first);
+
+        // The missed firings are dropped.
+        assert!(skip.tick().await.unwrap() > late);
+    }
+
+    #[test]
+    fn test_queries_at_the_limits_of_dates() {
+        let min = DateTime::<Utc>::MIN_UTC;
+        let max = DateTime::<Utc>::MAX_UTC;
+        let schedule = Schedule::from_str("0 0 0 L * ?").unwrap();
+        assert_eq!(None, schedule.next_after(&max));
+        assert_eq!(None, schedule.prev_before(&min));
+        assert_eq!(None, schedule.window_at(&max));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(1970, 1, 31, 0, 0, 0).unwrap()),
+            schedule.next_after(&min)
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2100, 12, 31, 0, 0, 0).unwrap()),
+            schedule.prev_before(&max)
+        );
+        // Years before the common era come before every supported year.
+        let before_common_era = Utc.with_ymd_and_hms(-5, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(None, schedule.prev_before(&before_common_era));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(1970, 1, 31, 0, 0, 0).unwrap()),
+            schedule.next_after(&before_common_era)
+        );
+        let every_other = schedule.every_nth(2, &min);
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(1970, 1, 31, 0, 0, 0).unwrap()),
+            every_other.next_after(&min)
+        );
+
+        // Starting deadlines reaching past the range of dates never expire.
+        use cron::kubernetes::CronJob;
+        let job = CronJob::new(schedule, Utc).starting_deadline_seconds(i64::MAX);
+        let scheduled = Utc.with_ymd_and_hms(2026, 10, 31, 0, 0, 0).unwrap();
+        assert!(!job.is_too_late(&scheduled, &max));
+        assert!(job
+            .most_recent_schedule_time(&scheduled, None, &max)
+            .is_some());
+    }
+
+    #[test]
+    fn test_nearest_weekday_in_every_kind_of_month() {
+        // Quartz moves a Saturday to the Friday and a Sunday to the Monday, unless that leaves
+        // the month.
+        let expected = |date: NaiveDate, last_day: u32| {
+            let day = date.day();
+            match date.weekday() {
+                Weekday::Sat if day == 1 => 3,
+                Weekday::Sat => day - 1,
+                Weekday::Sun if day == last_day => day - 2,
+                Weekday::Sun => day + 1,
+                _ => day,
+            }
+        };
+        let schedules: Vec<_> = (1..=31)
+            .map(|day| Schedule::from_str(&format!("0 0 0 {day}W * ?")).unwrap())
+            .collect();
+        let last_weekday = Schedule::from_str("0 0 0 LW * ?").unwrap();
+        let matching = |schedule: &Schedule, year: i32, month: u32, last_day: u32| {
+            (1..=last_day)
+                .filter(|&day| {
+                    schedule.includes(Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap())
+                })
+                .collect::<Vec<_>>()
+        };
+        // 28 years cover every length of month starting on every day of the week.
+        let mut kinds = std::collections::HashSet::new();
+        for year in 2000..2028 {
+            for month in 1..=12 {
+                let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+                let last_day = (28..=31)
+                    .rev()
+                    .find(|&day| NaiveDate::from_ymd_opt(year, month, day).is_some())
+                    .unwrap();
+                kinds.insert((first.weekday(), last_day));
+                for (day, schedule) in (1..=31).zip(&schedules) {
+                    let expected = NaiveDate::from_ymd_opt(year, month, day)
+                        .map(|date| expected(date, last_day));
+                    assert_eq!(
+                        expected.into_iter().collect::<Vec<_>>(),
+                        matching(schedule, year, month, last_day),
+                        "{day}W in {year}-{month}"
+                    );
+                    if let Some(expected) = expected {
+                        let date = NaiveDate::from_ymd_opt(year, month, expected).unwrap();
+                        assert!(!matches!(date.weekday(), Weekday::Sat | Weekday::Sun));
+                    }
+                }
+                let last = NaiveDate::from_ymd_opt(year, month, last_day).unwrap();
+                assert_eq!(
+                    vec![expected(last, last_day)],
+                    matching(&last_weekday, year, month, last_day),
+                    "LW in {year}-{month}"
+                );
+            }
+        }
+        assert_eq!(7 * 4, kinds.len());
+    }
+
+    #[test]
+    fn test_nearest_weekday_across_months() {
+        let parse = |policy, expression| {
+            ScheduleParser::new()
+                .nearest_weekday(policy)
+                .parse(expression)
+                .unwrap()
+        };
+        let dates = |schedule: &Schedule, year, month, count| {
+            let start = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap();
+            let forward: Vec<_> = schedule
+                .after(&(start - Duration::seconds(1)))
+                .take(count)
+                .map(|date| date.date_naive())
+                .collect();
+            let end = Utc.from_utc_datetime(&forward[count - 1].and_hms_opt(0, 0, 1).unwrap());
+            let mut backward: Vec<_> = schedule
+                .after(&end)
+                .rev()
+                .take(count)
+                .map(|date| date.date_naive())
+                .collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            forward
+        };
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        // The 1st of August 2026 is a Saturday, and the 31st of May a Sunday.
+        let within = parse(NearestWeekdayPolicy::WithinMonth, "0 0 0 1W * ?");
+        assert_eq!(
+            vec![date(2026, 7, 1), date(2026, 8, 3)],
+            dates(&within, 2026, 7, 2)
+        );
+        let across = parse(NearestWeekdayPolicy::AcrossMonths, "0 0 0 1W * ?");
+        assert_eq!(
+            vec![date(2026, 7, 1), date(2026, 7, 31)],
+            dates(&across, 2026, 7, 2)
+        );
+        let within = parse(NearestWeekdayPolicy::WithinMonth, "0 0 0 LW * ?");
+        assert_eq!(
+            vec![date(2026, 5, 29), date(2026, 6, 30)],
+            dates(&within, 2026, 5, 2)
+        );
+        let across = parse(NearestWeekdayPolicy::AcrossMonths, "0 0 0 LW * ?");
+        assert_eq!(
+            vec![date(2026, 6, 1), date(2026, 6, 30)],
+            dates(&across, 2026, 5, 2)
+        );
+
+        // Weekdays move into months the schedule doesn't include, and into the next year.
+        let august = parse(NearestWeekdayPolicy::AcrossMonths, "0 0 0 1W 8 ?");
+        assert_eq!(vec![date(2026, 7, 31)], dates(&august, 2026, 1, 1));
+        let may = parse(NearestWeekdayPolicy::AcrossMonths, "0 0 0 31W 5 ?");
+        assert_eq!(vec![date(2026, 6, 1)], dates(&may, 2026, 1, 1));
+        // The 31st of December 2023 is a Sunday.
+        let december = parse(NearestWeekdayPolicy::AcrossMonths, "0 0 0 LW 12 ?");
+        assert_eq!(vec![date(2024, 1, 1)], dates(&december, 2023, 6, 1));
+
+        assert!(format!("{across:#}").contains("Nearest weekday policy: AcrossMonths"));
+        let decoded = Schedule::from_bytes(&across.to_bytes().unwrap()).unwrap();
+        assert_eq!(dates(&across, 2026, 5, 2), dates(&decoded, 2026, 5, 2));
+    }
+
+    #[test]
+    fn test_naive_queries() {
+        let naive = |day, hour, minute| {
+            NaiveDate::from_ymd_opt(2026, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        let schedule = Schedule::from_str("0 30 2 * * *").unwrap();
+        // 2:30 doesn't exist in Berlin on the 29th of March 2026, but wall clock times have no
+        // gaps.
+        let start = naive(28, 12, 0);
+        let upcoming: Vec<_> = schedule.after_naive(&start).take(2).collect();
+        assert_eq!(vec![naive(29, 2, 30), naive(30, 2, 30)], upcoming);
+        let berlin = Tz::Europe__Berlin.from_local_datetime(&start).unwrap();
+        assert_eq!(
+            naive(30, 2, 30),
+            schedule.next_after(&berlin).unwrap().naive_local()
+        );
+
+        assert_eq!(Some(naive(29, 2, 30)), schedule.next_after_naive(&start));
+        assert_eq!(Some(naive(28, 2, 30)), schedule.prev_before_naive(&start));
+        assert_eq!(
+            Some(naive(28, 2, 30)),
+            schedule.prev_before_naive(&naive(29, 2, 30))
+        );
+        assert!(schedule.includes_naive(&naive(29, 2, 30)));
+        assert!(!schedule.includes_naive(&naive(29, 2, 31)));
+        let backward: Vec<_> = schedule.after_naive(&start).rev().take(2).collect();
+        assert_eq!(vec![naive(28, 2, 30), naive(27, 2, 30)], backward);
+    }
+
+    #[test]
+    fn test_utc_offset_queries() {
+        let schedule = Schedule::from_str("0 0 9 * * Mon-Fri").unwrap();
+        let offset = FixedOffset::west_opt(3 * 3600).unwrap();
+        // Friday the 16th of October 2026, 13:00 in UTC and 10:00 at the offset.
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 13, 0, 0).unwrap();
+        let next = schedule.next_after_utc_offset(&now, offset).unwrap();
+        assert_eq!(offset, *next.offset());
+        assert_eq!(
+            offset.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(),
+            next
+        );
+        let prev = schedule.prev_before_utc_offset(&now, offset).unwrap();
+        assert_eq!(
+            offset.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap(),
+            prev
+        );
+        // Read in UTC, the schedule would already have fired today at 9:00.
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap(),
+            schedule.prev_before(&now).unwrap()
+        );
+
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let next = schedule
+            .next_after_utc_offset(&now.with_timezone(&Tz::Europe__Berlin), tokyo)
+            .unwrap();
+        assert_eq!(tokyo.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(), next);
+    }
+
+    #[test]
+    fn test_local_results() {
+        let schedule = Schedule::from_str("0 30 2 * * *").unwrap();
+        let berlin = |month, day, hour| {
+            Tz::Europe__Berlin
+                .with_ymd_and_hms(2026, month, day, hour, 0, 0)
+                .unwrap()
+        };
+        // 2:30 is repeated on the 25th of October 2026.
+        let LocalResult::Ambiguous(earliest, latest) =
+            schedule.next_local_after(&berlin(10, 24, 12))
+        else {
+            panic!("2:30 should be ambiguous");
+        };
+        assert_eq!(earliest + Duration::hours(1), latest);
+        assert_eq!(
+            LocalResult::Ambiguous(earliest, latest),
+            schedule.prev_local_before(&berlin(10, 25, 12))
+        );
+        assert_eq!(
+            Tz::Europe__Berlin.with_ymd_and_hms(2026, 10, 24, 2, 30, 0),
+            schedule.prev_local_before(&berlin(10, 24, 12))
+        );
+
+        // 2:30 is skipped on the 29th of March 2026.
+        assert_eq!(
+            Tz::Europe__Berlin.with_ymd_and_hms(2026, 3, 30, 2, 30, 0),
+            schedule.next_local_after(&berlin(3, 28, 12))
+        );
+
+        let schedule = Schedule::from_str("0 30 2 * * * 2026").unwrap();
+        assert_eq!(
+            LocalResult::None,
+            schedule.next_local_after(&berlin(12, 31, 12))
+        );
+    }
+
+    #[test]
+    fn test_trace_next_after() {
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 13, 0, 0).unwrap();
+        // The 16th is a Friday.
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let trace = schedule.trace_next_after(&after);
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap()),
+            trace.occurrence
+        );
+        assert_eq!(
+            vec![
+                TraceStep::Carried {
+                    unit: FieldKind::DaysOfMonth,
+                    to: 17,
+                    exhausted: FieldKind::Hours
+                },
+                TraceStep::Rolled {
+                    unit: FieldKind::DaysOfMonth,
+                    to: 19,
+                    field: FieldKind::DaysOfWeek
+                },
+                TraceStep::Rolled {
+                    unit: FieldKind::Hours,
+                    to: 9,
+                    field: FieldKind::Hours
+                },
+                TraceStep::Rolled {
+                    unit: FieldKind::Minutes,
+                    to: 30,
+                    field: FieldKind::Minutes
+                },
+            ],
+            trace.steps
+        );
+
+        let schedule = Schedule::from_str("0 0 0 29 2 ? 2027-2030").unwrap();
+        let trace = schedule.trace_next_after(&after);
+        assert_eq!(
+            "rolled the year to 2027 due to the years field, \
+             rolled the month to 2 due to the months field, \
+             carried the month over to 3 as no later day matched, \
+             carried the year over to 2028 as no later month matched, \
+             rolled the month to 2 due to the months field, \
+             rolled the day to 29 due to the days of the month field",
+            trace.to_string()
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap()),
+            trace.occurrence
+        );
+        assert_eq!(
+            None,
+            schedule
+                .trace_next_after(&Utc.with_ymd_and_hms(2028, 3, 1, 0, 0, 0).unwrap())
+                .occurrence
+        );
+
+        // 2:30 doesn't exist in Berlin on the 29th of March 2026.
+        let schedule = Schedule::from_str("0 30 2 * * *")
+            .unwrap()
+            .with_calendar(WeeklyCalendar::new().excluding(Weekday::Mon));
+        let berlin = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 3, 28, 12, 0, 0)
+            .unwrap();
+        let trace = schedule.trace_next_after(&berlin);
+        let naive = |day| {
+            NaiveDate::from_ymd_opt(2026, 3, day)
+                .unwrap()
+                .and_hms_opt(2, 30, 0)
+                .unwrap()
+        };
+        assert!(trace.steps.contains(&TraceStep::Skipped(naive(29))));
+        assert!(trace.steps.contains(&TraceStep::Excluded(naive(30))));
+        assert_eq!(schedule.next_after(&berlin), trace.occurrence);
+
+        // The trace always ends at the occurrence the schedule's queries find.
+        let expressions = [
+            "0 0 12 L * ?",
+            "0 15 10 ? * 6#3",
+            "0 0 0 15W * ?",
+            "*/20 */7 1-5 * * ?",
+            "0 0 9 LW 2,8 ? 2026-2029",
+            "0 30 1 * * Sun",
+        ];
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            for date_time in schedule.after(&berlin).take(20) {
+                for before in [Duration::seconds(1), Duration::hours(5), Duration::days(9)] {
+                    let after = date_time - before;
+                    assert_eq!(
+                        schedule.next_after(&after),
+                        schedule.trace_next_after(&after).occurrence,
+                        "{expression} after {after}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_schedule_interner() {
+        use std::sync::Arc;
+
+        let interner = ScheduleInterner::new();
+        let first = interner.intern("0 0 9 * * Mon-Fri").unwrap();
+        let second = interner.intern("0 0 9 * * Mon-Fri").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        let other = interner.intern("0 0 9 * * Mon-Sat").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(2, interner.len());
+        assert!(interner.intern("0 0 25 * * *").is_err());
+        assert_eq!(2, interner.len());
+
+        // Schedules are freed along with the last Arc handed out for them.
+        drop(other);
+        assert_eq!(1, interner.len());
+        let weak = Arc::downgrade(&first);
+        drop((first, second));
+        assert!(weak.upgrade().is_none());
+        assert!(interner.is_empty());
+
+        // Dropped schedules are cleared out as new expressions are interned.
+        let kept: Vec<_> = (0..60)
+            .map(|minute| interner.intern(&format!("0 {minute} * * * *")).unwrap())
+            .collect();
+        for hour in 0..24 {
+            drop(interner.intern(&format!("0 0 {hour} * * *")).unwrap());
+        }
+        assert_eq!(60, interner.len());
+        assert!(Arc::ptr_eq(
+            &kept[5],
+            &interner.intern("0 5 * * * *").unwrap()
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_next_after_batch() {
+        use std::sync::Arc;
+
+        let schedules: Vec<_> = [
+            "0 */5 * * * *",
+            "0 0 9 * * Mon-Fri",
+            "@once 2030-01-01T00:00Z",
+        ]
+        .iter()
+        .map(|expression| Arc::new(ScheduleExpr::from_str(expression).unwrap()))
+        .collect();
+        let start = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 10, 16, 9, 0, 0)
+            .unwrap();
+        let jobs: Vec<_> = (0..3000)
+            .map(|index| {
+                let after = start + Duration::minutes(17 * index);
+                (Arc::clone(&schedules[index as usize % 3]), after)
+            })
+            .collect();
+        let expected: Vec<_> = jobs
+            .iter()
+            .map(|(schedule, after)| schedule.next_after(after))
+            .collect();
+        assert_eq!(expected, next_after_batch(&jobs));
+        assert_eq!(
+            start.with_minute(5),
+            next_after_batch(&[(schedules[0].as_ref(), start)])[0]
+        );
+    }
+
+    #[test]
+    fn test_compiled_schedule_matches() {
+        let schedules = [
+            Schedule::from_str("0 */15 9-17 * * Mon-Fri").unwrap(),
+            Schedule::from_str("*/10 * * L,15W * ? 2026-2027").unwrap(),
+            Schedule::from_str("0 0 * ? * 6#3,FRIL").unwrap(),
+            Schedule::from_str("* * * * * *")
+                .unwrap()
+                .with_calendar(AnnualCalendar::new().excluding(12, 25)),
+        ];
+        // Every 17 minutes and 13 seconds over two years, crossing months, years and DST changes.
+        let start = Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap();
+        let date_times: Vec<_> = (0..60_000)
+            .map(|step| start + Duration::seconds(1033 * step))
+            .collect();
+        let timestamps: Vec<_> = date_times.iter().map(DateTime::timestamp).collect();
+        let berlin: Vec<_> = date_times
+            .iter()
+            .map(|date_time| date_time.with_timezone(&Tz::Europe__Berlin))
+            .collect();
+        for schedule in schedules {
+            let compiled = schedule.compile();
+            let expected: Vec<_> = date_times.iter().map(|t| schedule.includes(*t)).collect();
+            assert!(expected.contains(&true), "{}", schedule);
+            assert_eq!(
+                expected,
+                compiled.matches_batch(&date_times),
+                "{}",
+                schedule
+            );
+            assert_eq!(
+                expected,
+                compiled.matches_timestamps(&timestamps),
+                "{}",
+                schedule
+            );
+            let expected: Vec<_> = berlin.iter().map(|t| schedule.includes(*t)).collect();
+            assert_eq!(expected, compiled.matches_batch(&berlin), "{}", schedule);
+            assert_eq!(expected[17], compiled.matches(&berlin[17]));
+        }
+
+        let compiled = Schedule::from_str("0 0 0 1 1 ? 1970").unwrap().compile();
+        assert_eq!(
+            vec![true, false, false, false],
+            compiled.matches_timestamps(&[0, -86_400, i64::MIN, i64::MAX])
+        );
+
+        // Years outside the mask, which a widened year range includes.
+        let schedule = ScheduleParser::new()
+            .year_range(1900..=2200)
+            .parse("0 0 0 1 1 ?")
+            .unwrap();
+        let compiled = schedule.compile();
+        for year in [1950, 2180] {
+            let date_time = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+            assert!(schedule.includes(date_time), "{}", year);
+            assert!(compiled.matches(&date_time), "{}", year);
+            assert_eq!(
+                vec![true, false],
+                compiled.matches_timestamps(&[date_time.timestamp(), date_time.timestamp() + 1])
+            );
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_events() {
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        // Records the message of every event.
+        #[derive(Clone, Default)]
+        struct Messages(Arc<Mutex<Vec<String>>>);
+
+        impl Visit for Messages {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                if field.name() == "message" {
+                    self.0.lock().unwrap().push(format!("{:?}", value));
+                }
+            }
+        }
+
+        impl Subscriber for Messages {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                event.record(&mut self.clone());
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let messages = Messages::default();
+        tracing::subscriber::with_default(messages.clone(), || {
+            assert!(Schedule::from_str("0 30 2 * *").is_err());
+            let schedule = Schedule::from_str("0 30 2 * * *")
+                .unwrap()
+                .with_calendar(AnnualCalendar::new().excluding(3, 30));
+            let after = Tz::Europe__Berlin
+                .with_ymd_and_hms(2026, 3, 28, 3, 0, 0)
+                .unwrap();
+            assert_eq!(
+                Tz::Europe__Berlin
+                    .with_ymd_and_hms(2026, 3, 31, 2, 30, 0)
+                    .unwrap(),
+                schedule.next_within(&after, Duration::days(5)).unwrap()
+            );
+            assert!(schedule.next_within(&after, Duration::days(1)).is_err());
+        });
+        assert_eq!(
+            vec![
+                "Failed to parse the expression",
+                "Skipped a time which doesn't exist in the timezone",
+                "Skipped a time a calendar excludes",
+                "Found the next occurrence",
+                "Skipped a time which doesn't exist in the timezone",
+                "Reached the horizon",
+            ],
+            *messages.0.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let code = |expression: &str| Schedule::from_str(expression).unwrap_err().code();
+        assert_eq!(ErrorCode::FieldOutOfRange, code("0 0 25 * * *"));
+        assert_eq!(ErrorCode::FieldOutOfRange, code("0 0 0 1 1 ? 1969"));
+        assert_eq!(ErrorCode::InvalidRange, code("0 0 20-10 * * *"));
+        assert_eq!(ErrorCode::InvalidStep, code("0 */0 * * * *"));
+        assert_eq!(ErrorCode::InvalidStep, code("0 0/99 * * * *"));
+        assert_eq!(ErrorCode::UnknownName, code("0 0 0 * Foo *"));
+        assert_eq!(ErrorCode::UnknownName, code("0 0 Mon * * *"));
+        assert_eq!(ErrorCode::InvalidSpecial, code("0 0 0 ? * 6#6"));
+        assert_eq!(ErrorCode::Syntax, code("0 0 0 *"));
+        let error = ScheduleExpr::from_str("@once tomorrow").unwrap_err();
+        assert_eq!(ErrorCode::InvalidDateTime, error.code());
+
+        let parse =
+            |parser: ScheduleParser, expression| parser.parse(expression).unwrap_err().code();
+        assert_eq!(
+            ErrorCode::DayFieldConflict,
+            parse(ScheduleParser::new().dialect(Dialect::Aws), "0 0 1 * MON *")
+        );
+        assert_eq!(
+            ErrorCode::LimitExceeded,
+            parse(ScheduleParser::new().max_length(8), "0 0 0 * * *")
+        );
+        let schedule = Schedule::from_str("0 0 0 * * *").unwrap();
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 0 * * *");
+        assert_eq!(
+            ErrorCode::UnsupportedField,
+            unix.unwrap().with_seconds("0").unwrap_err().code()
+        );
+        assert_eq!(
+            ErrorCode::FieldOutOfRange,
+            schedule.with_hours("24").unwrap_err().code()
+        );
+        assert_eq!(
+            ErrorCode::Decode,
+            Schedule::from_bytes(&[1]).unwrap_err().code()
+        );
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap();
+        let error = schedule
+            .next_within(&after, Duration::hours(1))
+            .unwrap_err();
+        assert_eq!(ErrorCode::NoUpcomingWithin, error.code());
+        let error = ScheduleParser::new()
+            .parse_lines("0 0 25 * * *")
+            .unwrap_err();
+        assert_eq!(ErrorCode::Lines, error.code());
+        assert_eq!("E001", ErrorCode::FieldOutOfRange.as_str());
+        assert_eq!("E013", ErrorCode::Lines.to_string());
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let plain = "0 30 9 * * Mon-Fri";
+        assert_eq!(
+            Sanitized {
+                expression: plain.to_string(),
+                corrections: vec![],
+            },
+            sanitize(plain)
+        );
+
+        // Pasted from a document, with an ideographic space, a zero width space and full-width
+        // characters.
+        let sanitized = sanitize(" '0\u{3000}*\u{ff0f}15 *\u{200b} * * ?' ");
+        assert_eq!(" 0 */15 * * * ? ", sanitized.expression);
+        assert_eq!(
+            vec![
+                Correction {
+                    kind: CorrectionKind::EnclosingQuote,
+                    offset: 1,
+                    found: '\'',
+                },
+                Correction {
+                    kind: CorrectionKind::Whitespace,
+                    offset: 3,
+                    found: '\u{3000}',
+                },
+                Correction {
+                    kind: CorrectionKind::FullWidth,
+                    offset: 7,
+                    found: '\u{ff0f}',
+                },
+                Correction {
+                    kind: CorrectionKind::Invisible,
+                    offset: 14,
+                    found: '\u{200b}',
+                },
+                Correction {
+                    kind: CorrectionKind::EnclosingQuote,
+                    offset: 23,
+                    found: '\'',
+                },
+            ],
+            sanitized.corrections
+        );
+        assert!(Schedule::from_str(&sanitized.expression).is_ok());
+
+        // Quotes within the expression are straightened but left in place.
+        let sanitized = sanitize("0 0 \u{2018}9\u{2019} * * *");
+        assert_eq!("0 0 '9' * * *", sanitized.expression);
+        assert!(sanitized
+            .corrections
+            .iter()
+            .all(|correction| correction.kind == CorrectionKind::SmartQuote));
+        assert!(Schedule::from_str(&sanitized.expression).is_err());
+        assert_eq!("\"", sanitize("\u{201d}").expression);
+    }
+
+    #[test]
+    fn test_blackouts() {
+        let freeze_start = Utc.with_ymd_and_hms(2026, 12, 20, 0, 0, 0).unwrap();
+        let freeze_end = Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 0).unwrap();
+        let outage_start = Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 0).unwrap();
+        let outage_end = Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 30).unwrap();
+        // Every second, which would take over a million steps to walk through the freeze.
+        let schedule = Schedule::from_str("* * * * * *")
+            .unwrap()
+            .with_blackout(freeze_start..freeze_end)
+            .with_blackout(
+                outage_start.with_timezone(&Tz::Europe__Berlin)
+                    ..outage_end.with_timezone(&Tz::Europe__Berlin),
+            );
+        assert_eq!(
+            &[freeze_start..freeze_end, outage_start..outage_end],
+            schedule.blackouts()
+        );
+
+        // Blackouts end where the next one starts, so both are skipped.
+        let before = freeze_start - Duration::seconds(2);
+        let next: Vec<_> = schedule.after(&before).take(2).collect();
+        assert_eq!(vec![before + Duration::seconds(1), outage_end], next);
+        let after = outage_end + Duration::seconds(1);
+        let previous: Vec<_> = schedule.after(&after).rev().take(3).collect();
+        assert_eq!(
+            vec![
+                outage_end,
+                freeze_start - Duration::seconds(1),
+                freeze_start - Duration::seconds(2),
+            ],
+            previous
+        );
+
+        assert!(!schedule.includes(freeze_start));
+        assert!(schedule.includes(outage_end));
+        assert_eq!(
+            vec![false, true],
+            schedule
+                .compile()
+                .matches_timestamps(&[freeze_end.timestamp(), outage_end.timestamp()])
+        );
+
+        let trace = schedule.trace_next_after(&before);
+        assert_eq!(Some(before + Duration::seconds(1)), trace.occurrence);
+        let trace = schedule.trace_next_after(&(freeze_start - Duration::seconds(1)));
+        assert_eq!(Some(outage_end), trace.occurrence);
+        assert_eq!(
+            vec![
+                TraceStep::BlackedOut(freeze_start.naive_utc()),
+                TraceStep::BlackedOut(outage_start.naive_utc()),
+            ],
+            trace.steps
+        );
+
+        // 02:30 happens twice in Berlin on the 25th of October 2026, at 00:30 and 01:30 UTC, and
+        // the search and its trace keep whichever instants aren't blacked out.
+        let after = Utc.with_ymd_and_hms(2026, 10, 24, 12, 0, 0).unwrap();
+        let after = after.with_timezone(&Tz::Europe__Berlin);
+        let at = |hour, minute| Utc.with_ymd_and_hms(2026, 10, 25, hour, minute, 0).unwrap();
+        let next_day = Utc.with_ymd_and_hms(2026, 10, 26, 1, 30, 0).unwrap();
+        for (blackout, expected) in [
+            (at(0, 0)..at(1, 0), at(1, 30)),
+            (at(1, 0)..at(2, 0), at(0, 30)),
+            (at(0, 0)..at(2, 0), next_day),
+        ] {
+            let schedule = Schedule::from_str("0 30 2 * * *")
+                .unwrap()
+                .with_blackout(blackout);
+            let next = schedule.next_after(&after).unwrap();
+            let trace = schedule.trace_next_after(&after);
+            assert_eq!(expected, next);
+            assert_eq!(Some(next), trace.occurrence);
+        }
+    }
+
+    #[test]
+    fn test_not_before_and_not_after() {
+        let start = Utc.with_ymd_and_hms(2026, 11, 2, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 11, 4, 9, 0, 0).unwrap();
+        let schedule = Schedule::from_str("0 0 9,17 * * *")
+            .unwrap()
+            .with_not_before(&start)
+            .with_not_after(&end.with_timezone(&Tz::Europe__Berlin));
+        assert_eq!(Some(start), schedule.not_before());
+        assert_eq!(Some(end), schedule.not_after());
+
+        // The bounds are included.
+        let expected = vec![
+            start,
+            Utc.with_ymd_and_hms(2026, 11, 2, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 11, 3, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 11, 3, 17, 0, 0).unwrap(),
+            end,
+        ];
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        assert_eq!(expected, schedule.after(&now).collect::<Vec<_>>());
+        let later = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        let backwards: Vec<_> = schedule.after(&later).rev().collect();
+        assert_eq!(
+            expected.iter().rev().cloned().collect::<Vec<_>>(),
+            backwards
+        );
+        assert_eq!(None, schedule.after(&end).next());
+        assert_eq!(None, schedule.after(&start).next_back());
+
+        assert!(schedule.includes(start));
+        assert!(!schedule.includes(start - Duration::days(1)));
+        assert!(!schedule.includes(end + Duration::hours(8)));
+        let compiled = schedule.compile();
+        let instants = [
+            start - Duration::days(1),
+            start,
+            end,
+            end + Duration::hours(8),
+        ];
+        assert_eq!(
+            vec![false, true, true, false],
+            compiled.matches_batch(&instants)
+        );
+        let timestamps: Vec<_> = instants.iter().map(DateTime::timestamp).collect();
+        assert_eq!(
+            vec![false, true, true, false],
+            compiled.matches_timestamps(&timestamps)
+        );
+
+        assert_eq!(Some(start), schedule.first_occurrence(&Utc));
+        assert_eq!(Some(end), schedule.final_occurrence(&Utc));
+        assert_eq!(Some(start), schedule.trace_next_after(&now).occurrence);
+        assert_eq!(None, schedule.trace_next_after(&end).occurrence);
+        assert!(schedule.next_within(&now, Duration::weeks(2)).is_err());
+
+        // Bounds within a second only keep the occurrences after them.
+        let schedule = Schedule::from_str("* * * * * *")
+            .unwrap()
+            .with_not_before(&(start + Duration::milliseconds(500)));
+        assert_eq!(
+            Some(start + Duration::seconds(1)),
+            schedule.after(&now).next()
+        );
+    }
+
+    #[test]
+    fn test_first_occurrence() {
+        let schedule = Schedule::from_str("0 30 2 L * ? 2027,2029").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 1, 31, 2, 30, 0).unwrap()),
+            schedule.first_occurrence(&Utc)
+        );
+        // Midnight of the first year in Auckland is still in the previous year in UTC.
+        let schedule = Schedule::from_str("0 0 0 * * ? 2027").unwrap();
+        let auckland = Tz::Pacific__Auckland;
+        assert_eq!(
+            Some(auckland.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()),
+            schedule.first_occurrence(&auckland)
+        );
+        let schedule = schedule.with_calendar(AnnualCalendar::new().excluding(1, 1));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 1, 2, 0, 0, 0).unwrap()),
+            schedule.first_occurrence(&Utc)
+        );
+        assert_eq!(
+            None,
+            Schedule::from_str("0 0 0 1 1 ? 1970-1980")
+                .unwrap()
+                .first_occurrence(&Utc)
+        );
+        // Schedules have no start of their own unless they're restricted to years after the
+        // start of the configured year range.
+        let narrow = ScheduleParser::new().year_range(2000..=2050);
+        let schedule = narrow.parse("0 0 0 1 1 ?").unwrap();
+        assert_eq!(None, schedule.first_occurrence(&Utc));
+        let decoded = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+        assert_eq!(None, decoded.first_occurrence(&Utc));
+        let schedule = narrow.parse("0 0 0 1 1 ? 2010-2020").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2010, 1, 1, 0, 0, 0).unwrap()),
+            schedule.first_occurrence(&Utc)
+        );
+        let wide = ScheduleParser::new().year_range(1..=9999);
+        let schedule = wide.parse("0 0 0 1 1 ? 1900").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap()),
+            schedule.first_occurrence(&Utc)
+        );
+
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let interval = ScheduleExpr::from(IntervalSchedule::new(&start, Duration::hours(1)));
+        assert_eq!(Some(start), interval.first_occurrence(&Utc));
+        let never = ScheduleExpr::from_str("@never").unwrap();
+        assert_eq!(None, never.first_occurrence(&Utc));
+    }
+
+    #[test]
+    fn test_final_occurrence() {
+        let schedule = Schedule::from_str("0 30 1 ? 10 SUNL 2020-2026").unwrap();
+        // The last Sunday of October 2026 has 1:30 twice in London, the latest being in GMT.
+        assert_eq!(
+            Some(
+                Tz::Europe__London
+                    .with_ymd_and_hms(2026, 10, 25, 1, 30, 0)
+                    .latest()
+                    .unwrap()
+            ),
+            schedule.final_occurrence(&Tz::Europe__London)
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 25, 1, 30, 0).unwrap()),
+            schedule.final_occurrence(&Utc)
+        );
+        // Calendars may exclude what would have been the final occurrence.
+        let schedule = schedule.with_calendar(AnnualCalendar::new().excluding(10, 25));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2025, 10, 26, 1, 30, 0).unwrap()),
+            schedule.final_occurrence(&Utc)
+        );
+        // Years which never have a 30th of February don't end the schedule early.
+        let schedule = Schedule::from_str("0 0 0 30 2 ? 2024-2030").unwrap();
+        assert_eq!(None, schedule.final_occurrence(&Utc));
+        assert_eq!(
+            None,
+            Schedule::from_str("0 0 0 1 1 ? 2030/10")
+                .unwrap()
+                .final_occurrence(&Utc)
+        );
+        // Schedules go on past the end of the configured year range unless they're restricted
+        // to earlier years.
+        let wide = ScheduleParser::new().year_range(1970..=3000);
+        let schedule = wide.parse("0 0 0 1 1 ? 2500").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2500, 1, 1, 0, 0, 0).unwrap()),
+            schedule.final_occurrence(&Utc)
+        );
+        let schedule = wide.parse("0 0 0 1 1 ?").unwrap();
+        assert_eq!(None, schedule.final_occurrence(&Utc));
+        let schedule = wide.parse("0 0 0 1 1 ? 2099").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap()),
+            schedule.final_occurrence(&Utc)
+        );
+        let narrow = ScheduleParser::new().year_range(2000..=2050);
+        let schedule = narrow.parse("0 0 0 1 1 ?").unwrap();
+        assert_eq!(None, schedule.final_occurrence(&Utc));
+        let decoded = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+        assert_eq!(None, decoded.final_occurrence(&Utc));
+        let schedule = narrow.parse("0 0 0 1 1 ? 2040").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2040, 1, 1, 0, 0, 0).unwrap()),
+            schedule.final_occurrence(&Utc)
+        );
+
+        let once = ScheduleExpr::from_str("@once 2027-01-01T00:00Z").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()),
+            once.final_occurrence(&Utc)
+        );
+        let never = ScheduleExpr::from_str("@never").unwrap();
+        assert_eq!(None, never.final_occurrence(&Utc));
+
+        // Searching back from a year the schedule doesn't fire in starts from the end of the year
+        // before it.
+        let schedule = Schedule::from_str("0 0 9 1 * ? 2026").unwrap();
+        let after = Utc.with_ymd_and_hms(2027, 1, 2, 0, 0, 0).unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 12, 1, 9, 0, 0).unwrap()),
+            schedule.after(&after).next_back()
+        );
+    }
+
+    #[test]
+    fn test_misfire_policies() {
+        let every_ten_minutes = Schedule::from_str("0 */10 * * * *").unwrap();
+        let at = |hour, minute, second| {
+            Utc.with_ymd_and_hms(2026, 10, 16, hour, minute, second)
+                .unwrap()
+        };
+        let threshold = Duration::minutes(15);
+        let resolve = |policy: MisfirePolicy, last_fire, now| {
+            policy.resolve(&every_ten_minutes, &last_fire, &now, threshold)
+        };
+
+        // Running late by less than the threshold isn't a misfire.
+        for policy in [
+            MisfirePolicy::FireOnceNow,
+            MisfirePolicy::RescheduleNext,
+            MisfirePolicy::Ignore,
+        ] {
+            let misfire = resolve(policy, at(9, 0, 0), at(9, 12, 0));
+            assert!(!misfire.misfired);
+            assert_eq!(vec![at(9, 10, 0)], misfire.runs);
+            assert_eq!(Some(at(9, 20, 0)), misfire.next);
+            assert_eq!(
+                vec![at(9, 10, 0)],
+                resolve(policy, at(9, 0, 0), at(9, 10, 0)).runs
+            );
+            assert!(resolve(policy, at(9, 0, 0), at(9, 5, 0)).runs.is_empty());
+        }
+
+        // Down from 9:05 to 10:05, only the run at 10:00 is late rather than missed.
+        let misfire = resolve(MisfirePolicy::FireOnceNow, at(9, 0, 0), at(10, 5, 0));
+        assert!(misfire.misfired);
+        assert_eq!(vec![at(10, 0, 0)], misfire.runs);
+        assert_eq!(Some(at(10, 10, 0)), misfire.next);
+        let misfire = resolve(MisfirePolicy::RescheduleNext, at(9, 0, 0), at(10, 5, 0));
+        assert!(misfire.misfired);
+        assert_eq!(vec![at(10, 0, 0)], misfire.runs);
+        let misfire = resolve(MisfirePolicy::Ignore, at(9, 0, 0), at(10, 5, 0));
+        assert_eq!(
+            (1..=6)
+                .map(|step| at(9, 0, 0) + Duration::minutes(10 * step))
+                .collect::<Vec<_>>(),
+            misfire.runs
+        );
+        // Due exactly the threshold ago counts as missed.
+        let misfire = resolve(MisfirePolicy::RescheduleNext, at(9, 0, 0), at(9, 25, 0));
+        assert!(misfire.misfired);
+        assert_eq!(vec![at(9, 20, 0)], misfire.runs);
+
+        // Any kind of schedule can be resolved.
+        let once = ScheduleExpr::from_str("@once 2026-10-16T09:30Z").unwrap();
+        let misfire =
+            MisfirePolicy::FireOnceNow.resolve(&once, &at(9, 0, 0), &at(12, 0, 0), threshold);
+        assert_eq!(vec![at(9, 30, 0)], misfire.runs);
+        assert_eq!(None, misfire.next);
+        assert_eq!(MisfirePolicy::FireOnceNow, MisfirePolicy::default());
+    }
+
+    #[test]
+    fn test_nth_after() {
+        let utc = Utc.with_ymd_and_hms(2026, 10, 16, 9, 7, 30).unwrap();
+        let berlin = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 3, 28, 23, 0, 0)
+            .unwrap();
+        let expressions = [
+            "0 */5 * * * *",
+            "*/20 30 1-3 * * *",
+            "0 0 9-17 * * Mon-Fri",
+            "0 0 0 29 2 ? *",
+            "0 30 2 * * * 2026,2028",
+            "0 0 12 L * ?",
+        ];
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            for k in [0, 1, 2, 7, 100, 1_000, 5_000] {
+                assert_eq!(
+                    schedule.after(&utc).nth(k),
+                    schedule.nth_after(k, &utc),
+                    "{expression} {k}"
+                );
+                assert_eq!(
+                    schedule.after(&berlin).nth(k),
+                    schedule.nth_after(k, &berlin),
+                    "{expression} {k}"
+                );
+            }
+        }
+
+        // The repeated hour in October counts twice.
+        let autumn = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 10, 24, 0, 0, 0)
+            .unwrap();
+        let schedule = Schedule::from_str("0 30 * * * *").unwrap();
+        for k in 0..60 {
+            assert_eq!(
+                schedule.after(&autumn).nth(k),
+                schedule.nth_after(k, &autumn)
+            );
+        }
+
+        // Past the last year there's nothing left.
+        let schedule = Schedule::from_str("0 0 0 * * * 2026").unwrap();
+        assert_eq!(None, schedule.nth_after(100, &utc));
+
+        // Calendars are iterated through.
+        let weekends = WeeklyCalendar::new()
+            .excluding(Weekday::Sat)
+            .excluding(Weekday::Sun);
+        let schedule = Schedule::from_str("0 0 9 * * *")
+            .unwrap()
+            .with_calendar(weekends);
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 20, 9, 0, 0).unwrap()),
+            schedule.nth_after(1, &utc)
+        );
+    }
+
+    #[test]
+    fn test_occurrence_index() {
+        let utc = Utc.with_ymd_and_hms(2026, 10, 16, 9, 7, 30).unwrap();
+        let berlin = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 10, 24, 12, 0, 0)
+            .unwrap();
+        for expression in ["0 */20 * * * *", "*/30 0 1-3 * * *", "0 0 12 L * ?"] {
+            let schedule = Schedule::from_str(expression).unwrap();
+            for (index, occurrence) in schedule.after(&utc).take(200).enumerate() {
+                assert_eq!(Some(index), schedule.occurrence_index(&utc, &occurrence));
+                assert_eq!(Some(occurrence), schedule.nth_after(index, &utc));
+            }
+            // Both instants of a repeated time are counted.
+            for (index, occurrence) in schedule.after(&berlin).take(200).enumerate() {
+                assert_eq!(Some(index), schedule.occurrence_index(&berlin, &occurrence));
+            }
+        }
+
+        let schedule = Schedule::from_str("0 0 9 * * *").unwrap();
+        let at = |day, hour| Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        assert_eq!(Some(3), schedule.occurrence_index(&utc, &at(20, 9)));
+        // Times the schedule doesn't fire at, and those before the anchor, have no index.
+        assert_eq!(None, schedule.occurrence_index(&utc, &at(20, 10)));
+        assert_eq!(None, schedule.occurrence_index(&utc, &at(16, 9)));
+        assert_eq!(None, schedule.occurrence_index(&at(20, 9), &at(20, 9)));
+
+        // Calendars are iterated through.
+        let schedule = schedule.with_calendar(WeeklyCalendar::new().excluding(Weekday::Sat));
+        assert_eq!(Some(2), schedule.occurrence_index(&utc, &at(20, 9)));
+        assert_eq!(None, schedule.occurrence_index(&utc, &at(17, 9)));
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let events = |ics: &str| ics.matches("BEGIN:VEVENT").count();
+        let rule = |expression: &str| {
+            let ics = Schedule::from_str(expression)
+                .unwrap()
+                .to_ics_after(5, &after);
+            assert_eq!(1, events(&ics), "{expression}");
+            // Unfolds the lines.
+            let ics = ics.replace("\r\n ", "");
+            let line = ics.lines().find(|line| line.starts_with("RRULE:")).unwrap();
+            line.trim_start_matches("RRULE:").to_string()
+        };
+        assert_eq!(
+            "FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=12;BYMINUTE=0;BYSECOND=0;COUNT=5",
+            rule("0 0 12 L * ?")
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;BYMONTH=1,7;BYDAY=3FR,-1SU;BYHOUR=10;BYMINUTE=0;BYSECOND=0;COUNT=5",
+            rule("0 0 10 ? Jan,Jul 6#3,1L")
+        );
+
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let ics = schedule.to_ics_after(5, &after);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("SUMMARY:0 30 9 * * Mon-Fri\r\n"));
+
+        // Daylight saving time can't be followed by a rule in UTC.
+        let berlin = after.with_timezone(&Tz::Europe__Berlin);
+        let ics = schedule.to_ics_after(5, &berlin);
+        assert_eq!(5, events(&ics));
+        assert!(ics.contains("DTSTART:20261016T073000Z\r\n"));
+        assert!(ics.contains("DTSTART:20261022T073000Z\r\n"));
+        // Nor can nearest weekdays.
+        let ics = Schedule::from_str("0 0 12 15W * ?")
+            .unwrap()
+            .to_ics_after(3, &after);
+        assert_eq!(3, events(&ics));
+        assert_eq!(
+            0,
+            schedule.to_ics_after(0, &after).matches("VEVENT").count()
+        );
+
+        // Long lines are folded.
+        let ics = Schedule::from_str("* * * * * *")
+            .unwrap()
+            .to_ics_after(5, &after);
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        assert!(ics
+            .replace("\r\n ", "")
+            .contains(";BYSECOND=0,1,2,3,4,5,6,7,8,9,10,"));
+    }
+
+    #[test]
+    fn test_description() {
+        use cron::{DayOfMonthSpec, DayOfWeekSpec, DaySpec};
+
+        let schedule = Schedule::from_str("0 0 10 LW,15W * 6#3,1L,Mon").unwrap();
+        let description = schedule.description();
+        assert_eq!("0 0 10 LW,15W * 6#3,1L,Mon", description.expression);
+        assert_eq!(None, description.comment);
+        assert_eq!(None, description.timezone);
+        assert_eq!(
+            FieldKind::ALL.to_vec(),
+            description
+                .fields
+                .iter()
+                .map(|field| field.kind)
+                .collect::<Vec<_>>()
+        );
+        let days = description.field(FieldKind::DaysOfMonth).unwrap();
+        assert!(!days.is_all);
+        assert!(days.values.is_empty());
+        assert_eq!(
+            vec![
+                DaySpec::OfMonth(DayOfMonthSpec::NearestWeekday { day: 15 }),
+                DaySpec::OfMonth(DayOfMonthSpec::LastWeekday)
+            ],
+            days.specials
+        );
+        let weekdays = description.field(FieldKind::DaysOfWeek).unwrap();
+        assert_eq!(vec![2], weekdays.values);
+        assert_eq!(
+            vec![
+                DaySpec::OfWeek(DayOfWeekSpec::Nth { weekday: 6, nth: 3 }),
+                DaySpec::OfWeek(DayOfWeekSpec::Last {
+                    weekday: 1,
+                    offset: 0
+                })
+            ],
+            weekdays.specials
+        );
+        let years = description.field(FieldKind::Years).unwrap();
+        assert!(years.is_all);
+        assert_eq!(None, years.text);
+
+        // Shorthands don't keep how their fields were written.
+        let description = Schedule::from_str("@daily").unwrap().description();
+        let hours = description.field(FieldKind::Hours).unwrap();
+        assert_eq!((None, vec![0]), (hours.text.clone(), hours.values.clone()));
+        assert_eq!(
+            Some("UTC"),
+            description.with_timezone("UTC").timezone.as_deref()
+        );
+    }
+
+    #[test]
+    fn test_cron_expr() {
+        let expression = CronExpr::from_str("0 0 9 * * Mon-Fri # standup").unwrap();
+        assert_eq!("0 0 9 * * Mon-Fri # standup", expression.as_str());
+        assert_eq!(18, expression.find("# standup").unwrap());
+        assert_eq!(Some("standup"), expression.schedule().comment());
+        // The schedule is compiled once.
+        assert!(std::ptr::eq(expression.schedule(), expression.schedule()));
+        assert_eq!(
+            Schedule::from_str("0 0 9 * * Mon-Fri # standup").unwrap(),
+            expression.clone().into_schedule()
+        );
+        assert_eq!(
+            Ok(expression.clone()),
+            CronExpr::try_from(String::from(expression.clone())).map_err(|e| e.code())
+        );
+        assert_eq!("0 0 9 * * Mon-Fri # standup", expression.to_string());
+
+        let error = CronExpr::try_from("0 0 25 * * *").unwrap_err();
+        assert_eq!(ErrorCode::FieldOutOfRange, error.code());
+        assert!(CronExpr::new("").is_err());
+    }
+
+    #[test]
+    fn test_calendar() {
+        use cron::calendar;
+
+        assert!(calendar::is_leap_year(2000));
+        assert!(!calendar::is_leap_year(2100));
+        assert_eq!(Some(29), calendar::days_in_month(2028, 2));
+        assert_eq!(Some(28), calendar::days_in_month(2100, 2));
+        assert_eq!(Some(30), calendar::days_in_month(2026, 11));
+        assert_eq!(None, calendar::days_in_month(2026, 13));
+
+        let date = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+        let within = NearestWeekdayPolicy::WithinMonth;
+        let across = NearestWeekdayPolicy::AcrossMonths;
+        // Saturday the 1st of August 2026.
+        assert_eq!(
+            Some(date(8, 3)),
+            calendar::nearest_weekday(date(8, 1), within)
+        );
+        assert_eq!(
+            Some(date(7, 31)),
+            calendar::nearest_weekday(date(8, 1), across)
+        );
+        assert_eq!(
+            Some(date(8, 14)),
+            calendar::nearest_weekday(date(8, 15), within)
+        );
+        // Sunday the 31st of May 2026.
+        assert_eq!(
+            Some(date(5, 29)),
+            calendar::nearest_weekday(date(5, 31), within)
+        );
+        assert_eq!(
+            Some(date(6, 1)),
+            calendar::nearest_weekday(date(5, 31), across)
+        );
+        assert_eq!(
+            Some(date(10, 16)),
+            calendar::nearest_weekday(date(10, 16), within)
+        );
+
+        assert_eq!(
+            Some(date(10, 16)),
+            calendar::nth_weekday_of_month(2026, 10, Weekday::Fri, 3)
+        );
+        assert_eq!(
+            Some(date(10, 30)),
+            calendar::nth_weekday_of_month(2026, 10, Weekday::Fri, 5)
+        );
+        assert_eq!(
+            None,
+            calendar::nth_weekday_of_month(2026, 11, Weekday::Fri, 5)
+        );
+        assert_eq!(
+            None,
+            calendar::nth_weekday_of_month(2026, 10, Weekday::Fri, 0)
+        );
+        assert_eq!(
+            Some(date(10, 30)),
+            calendar::last_weekday_of_month(2026, 10, Weekday::Fri)
+        );
+        assert_eq!(
+            Some(date(11, 30)),
+            calendar::last_weekday_of_month(2026, 11, Weekday::Mon)
+        );
+
+        // Schedules resolve their specials the same way.
+        let after = Utc.with_ymd_and_hms(2026, 7, 31, 12, 0, 0).unwrap();
+        let first = |expression| {
+            let schedule = Schedule::from_str(expression).unwrap();
+            schedule.after(&after).next().unwrap().date_naive()
+        };
+        assert_eq!(
+            calendar::nearest_weekday(date(8, 1), within).unwrap(),
+            first("0 0 0 1W * ?")
+        );
+        assert_eq!(
+            calendar::nth_weekday_of_month(2026, 8, Weekday::Fri, 3).unwrap(),
+            first("0 0 0 ? * FRI#3")
+        );
+        assert_eq!(
+            calendar::last_weekday_of_month(2026, 8, Weekday::Fri).unwrap(),
+            first("0 0 0 ? * 6L")
+        );
+    }
+
+    #[test]
+    fn test_day_specs() {
+        use cron::{DayOfMonthSpec, DayOfWeekSpec};
+
+        let schedule = Schedule::from_str("0 0 12 3,LW,L-2,15W,L * ?").unwrap();
+        assert_eq!(
+            &[
+                DayOfMonthSpec::NearestWeekday { day: 15 },
+                DayOfMonthSpec::LastDay { offset: 0 },
+                DayOfMonthSpec::LastDay { offset: 2 },
+                DayOfMonthSpec::LastWeekday,
+            ],
+            schedule.day_of_month_specs()
+        );
+        // The plain days don't include the specials.
+        assert_eq!(vec![3], schedule.days_of_month().iter().collect::<Vec<_>>());
+        assert_eq!(1, schedule.days_of_month().count());
+        assert!(schedule.day_of_week_specs().is_empty());
+
+        let schedule = Schedule::from_str("0 0 12 ? * MON,FRIL-1,6#3").unwrap();
+        assert_eq!(
+            &[
+                DayOfWeekSpec::Nth { weekday: 6, nth: 3 },
+                DayOfWeekSpec::Last {
+                    weekday: 6,
+                    offset: 1
+                },
+            ],
+            schedule.day_of_week_specs()
+        );
+        assert_eq!(vec![2], schedule.days_of_week().iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec!["6#3", "6L-1"],
+            schedule
+                .day_of_week_specs()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        // Specs resolve in the month, and may not fall in it.
+        let policy = NearestWeekdayPolicy::AcrossMonths;
+        // Saturday the 1st of August 2026 moves to Friday the 31st of July.
+        let first = DayOfMonthSpec::NearestWeekday { day: 1 };
+        assert_eq!(None, first.day_in_month(2026, 8, policy));
+        assert_eq!(
+            Some(3),
+            first.day_in_month(2026, 8, NearestWeekdayPolicy::WithinMonth)
+        );
+        assert_eq!(
+            None,
+            DayOfMonthSpec::LastDay { offset: 30 }.day_in_month(2026, 9, policy)
+        );
+        let fifth_friday = DayOfWeekSpec::Nth { weekday: 6, nth: 5 };
+        assert_eq!(Some(30), fifth_friday.day_in_month(2026, 10));
+        assert_eq!(None, fifth_friday.day_in_month(2026, 11));
+        let friday_before_last = DayOfWeekSpec::Last {
+            weekday: 6,
+            offset: 1,
+        };
+        assert_eq!(Some(23), friday_before_last.day_in_month(2026, 10));
+        assert!(friday_before_last.matches(NaiveDate::from_ymd_opt(2026, 10, 23).unwrap()));
+        assert!(!friday_before_last.matches(NaiveDate::from_ymd_opt(2026, 10, 30).unwrap()));
+
+        // Specials round trip through the binary encoding, and compare equal.
+        let decoded = Schedule::from_bytes(&schedule.to_bytes().unwrap()).unwrap();
+        assert_eq!(schedule.day_of_week_specs(), decoded.day_of_week_specs());
+        assert_eq!(
+            Schedule::from_str("0 0 12 ? * 6#3")
+                .unwrap()
+                .day_of_week_specs(),
+            Schedule::from_str("0 0 12 ? * FRI#3,6#3")
+                .unwrap()
+                .day_of_week_specs()
+        );
+    }
+
+    #[test]
+    fn test_year_steps() {
+        let years = |schedule: &Schedule| schedule.years().iter().collect::<Vec<_>>();
+
+        // Leap years only, from a starting year to the end of the field.
+        let leap_days = Schedule::from_str("0 0 0 29 2 ? 2024/4").unwrap();
+        assert_eq!(
+            (2024..=2100).step_by(4).collect::<Vec<_>>(),
+            years(&leap_days)
+        );
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2032, 2, 29, 0, 0, 0).unwrap(),
+            ],
+            leap_days.after(&after).take(2).collect::<Vec<_>>()
+        );
+        // 2100 isn't a leap year.
+        let last = Utc.with_ymd_and_hms(2096, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(None, leap_days.after(&last).next());
+
+        // Every fifth year of a range, and steps combined with other years.
+        let quinquennial = Schedule::from_str("0 0 0 1 1 ? 2025-2100/5").unwrap();
+        assert_eq!(16, quinquennial.years().count());
+        assert_eq!(Some(2025), quinquennial.years().iter().next());
+        assert_eq!(Some(2100), quinquennial.years().iter().next_back());
+        let combined = Schedule::from_str("0 0 0 1 1 ? 2030-2040/5,2026").unwrap();
+        assert_eq!(vec![2026, 2030, 2035, 2040], years(&combined));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()),
+            combined.after(&after).next()
+        );
+
+        // Steps run to the end of the configured year range.
+        let parser = ScheduleParser::new().year_range(1970..=9999);
+        let far = parser.parse("0 0 0 29 2 ? 2024/4").unwrap();
+        assert_eq!(Some(9996), far.years().iter().next_back());
+        assert_eq!("0 0 0 29 2 ? 2024/4", far.to_quartz().expression);
+
+        assert!(Schedule::from_str("0 0 0 1 1 ? 2024/0").is_err());
+        assert!(Schedule::from_str("0 0 0 1 1 ? 2100-2025/5").is_err());
+    }
+
+    #[test]
+    fn test_quarters() {
+        use cron::calendar;
+
+        let months = |schedule: &Schedule| schedule.months().iter().collect::<Vec<_>>();
+        let schedule = Schedule::from_str("0 0 9 1 Q1,q3 ?").unwrap();
+        assert_eq!(vec![1, 2, 3, 7, 8, 9], months(&schedule));
+        let syntax = schedule.syntax().unwrap();
+        assert_eq!(
+            &[SpecifierForm::Quarter, SpecifierForm::Quarter],
+            syntax.months.forms()
+        );
+        let ast = cron::parse_to_ast("0 0 9 1 Q2,DEC ?").unwrap();
+        assert_eq!(
+            vec![
+                Item::Quarter(2),
+                Item::Value(Value::Name("DEC".to_string()))
+            ],
+            ast.months.items
+        );
+        assert_eq!("0 0 9 1 Q2,DEC ?", ast.to_string());
+        let unix = ScheduleParser::new().dialect(Dialect::Unix);
+        assert_eq!(
+            vec![10, 11, 12],
+            months(&unix.parse("0 9 * Q4 1-5").unwrap())
+        );
+
+        let code = |expression: &str| Schedule::from_str(expression).unwrap_err().code();
+        assert_eq!(ErrorCode::FieldOutOfRange, code("0 0 9 1 Q5 ?"));
+        assert_eq!(ErrorCode::FieldOutOfRange, code("0 0 9 1 Q0 ?"));
+        assert!(Schedule::from_str("0 0 Q1 1 * ?").is_err());
+        assert!(Schedule::from_str("0 0 9 1 Q1-Q2 ?").is_err());
+
+        // The first and last days of each quarter.
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let quarterly = Schedule::from_str("@quarterly").unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2027, 4, 1, 0, 0, 0).unwrap(),
+            ],
+            quarterly.after(&after).take(2).collect::<Vec<_>>()
+        );
+        let quarter_end = Schedule::from_str("@quarterend").unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2027, 3, 31, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2027, 6, 30, 0, 0, 0).unwrap(),
+            ],
+            quarter_end.after(&after).take(3).collect::<Vec<_>>()
+        );
+        assert_eq!("0 0 0 L 3/3 ?", quarter_end.to_quartz().expression);
+
+        assert_eq!(Some(4), calendar::quarter_of_month(10, 1));
+        assert_eq!(None, calendar::quarter_of_month(13, 1));
+        assert_eq!(None, calendar::quarter_of_month(10, 0));
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2026, 10, 1),
+            calendar::first_day_of_quarter(2026, 4, 1)
+        );
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2028, 3, 31),
+            calendar::last_day_of_quarter(2028, 1, 1)
+        );
+        assert_eq!(None, calendar::last_day_of_quarter(2028, 5, 1));
+        assert_eq!(None, calendar::first_day_of_quarter(2028, 1, 13));
+
+        // Fiscal quarters agree with the schedules of a parser with the same fiscal year start.
+        let parser = ScheduleParser::new().fiscal_year_start(10);
+        let quarterly = parser.parse("@quarterly").unwrap();
+        let quarter_end = parser.parse("@quarterend").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 9, 30, 0, 0, 0).unwrap();
+        let first_days: Vec<_> = quarterly.after(&start).take(4).collect();
+        let last_days: Vec<_> = quarter_end.after(&start).take(4).collect();
+        for (quarter, (first, last)) in (1..=4).zip(first_days.iter().zip(&last_days)) {
+            let first = first.date_naive();
+            assert_eq!(
+                Some(first),
+                calendar::first_day_of_quarter(2026, quarter, 10)
+            );
+            assert_eq!(
+                Some(last.date_naive()),
+                calendar::last_day_of_quarter(2026, quarter, 10)
+            );
+            assert_eq!(Some(quarter), calendar::quarter_of_month(first.month(), 10));
+        }
+    }
+
+    #[test]
+    fn test_fiscal_year_start() {
+        let months = |schedule: &Schedule| schedule.months().iter().collect::<Vec<_>>();
+        let parser = ScheduleParser::new().fiscal_year_start(10);
+        assert_eq!(
+            vec![10, 11, 12],
+            months(&parser.parse("0 0 9 1 Q1 ?").unwrap())
+        );
+        assert_eq!(
+            vec![1, 2, 3, 7, 8, 9],
+            months(&parser.parse("0 0 9 1 Q2,Q4 ?").unwrap())
+        );
+        // A year starting in November has quarters spanning two calendar years.
+        let november = ScheduleParser::new().fiscal_year_start(11);
+        assert_eq!(
+            vec![1, 11, 12],
+            months(&november.parse("0 0 9 1 Q1 ?").unwrap())
+        );
+
+        // The boundaries of the year and its quarters.
+        let after = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let next = |expression: &str| parser.parse(expression).unwrap().after(&after).next();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 10, 1, 0, 0, 0).unwrap()),
+            next("@yearly")
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 9, 30, 0, 0, 0).unwrap()),
+            next("@yearend")
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()),
+            next("@quarterly")
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2027, 2, 28, 0, 0, 0).unwrap()),
+            ScheduleParser::new()
+                .fiscal_year_start(3)
+                .parse("@yearend")
+                .unwrap()
+                .after(&after)
+                .next()
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap()),
+            Schedule::from_str("@yearend").unwrap().after(&after).next()
+        );
+        // Other shorthands don't depend on the fiscal year.
+        assert!(parser.parse("@monthly").unwrap().months().is_all());
+
+        // Replacing a field keeps resolving quarters against the fiscal year.
+        let schedule = parser.parse("0 0 9 1 * ?").unwrap();
+        let replaced = schedule.with_months("Q3").unwrap();
+        assert_eq!(vec![4, 5, 6], months(&replaced));
+
+        let error = ScheduleParser::new()
+            .fiscal_year_start(13)
+            .parse("0 0 9 1 Q1 ?")
+            .unwrap_err();
+        assert_eq!(ErrorCode::FieldOutOfRange, error.code());
+    }
+
+    #[test]
+    fn test_calendar_systems() {
+        use cron::calendar::CalendarSystem;
+
+        // Months of 28 days starting on the 1st of January, and a last month of the remaining
+        // days.
+        #[derive(Debug)]
+        struct FourWeekMonths;
+
+        impl CalendarSystem for FourWeekMonths {
+            fn month_and_day(&self, date: NaiveDate) -> Option<(u32, u32)> {
+                let day_of_year = date.ordinal0();
+                Some(((day_of_year / 28 + 1).min(13), day_of_year % 28 + 1))
+            }
+
+            fn days_in_month(&self, date: NaiveDate) -> Option<u32> {
+                let (month, _) = self.month_and_day(date)?;
+                let days_in_year = if date.leap_year() { 366 } else { 365 };
+                Some(if month == 13 { days_in_year - 336 } else { 28 })
+            }
+        }
+
+        let after = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let upcoming = |schedule: Schedule| schedule.after(&after).take(3).collect::<Vec<_>>();
+        let schedule = Schedule::from_str("0 0 9 1,L 3 ?")
+            .unwrap()
+            .in_calendar_system(FourWeekMonths)
+            .unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 2, 26, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 25, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2027, 2, 26, 9, 0, 0).unwrap(),
+            ],
+            upcoming(schedule)
+        );
+        // The day of the week still has to match.
+        let monday = Schedule::from_str("0 0 9 1 2 MON")
+            .unwrap()
+            .in_calendar_system(FourWeekMonths)
+            .unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2029, 1, 29, 9, 0, 0).unwrap()),
+            monday.after(&after).next()
+        );
+        // In Unix cron, either of the day fields matches.
+        let either = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 9 1 2 MON")
+            .unwrap()
+            .in_calendar_system(FourWeekMonths)
+            .unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 1, 29, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 2, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 2, 9, 9, 0, 0).unwrap(),
+            ],
+            upcoming(either)
+        );
+
+        // The fields keep their values, while encoding fails rather than lose the calendar.
+        let schedule = Schedule::from_str("0 0 9 1,L 3 ?")
+            .unwrap()
+            .in_calendar_system(FourWeekMonths)
+            .unwrap();
+        assert_eq!(vec![3], schedule.months().iter().collect::<Vec<_>>());
+        assert_eq!(vec![1], schedule.days_of_month().iter().collect::<Vec<_>>());
+        assert!(!schedule.is_daily());
+        let error = schedule.to_bytes().unwrap_err();
+        assert_eq!(ErrorCode::Encode, error.code());
+        assert_eq!("E015", error.code().as_str());
+        // Checkpoints aren't resumed by the Gregorian schedule.
+        let mut iterator = schedule.after(&after);
+        iterator.next();
+        let checkpoint = iterator.checkpoint().unwrap();
+        assert!(schedule.resume(&checkpoint, &Utc).is_ok());
+        let gregorian = Schedule::from_str("0 0 9 1,L 3 ?").unwrap();
+        assert!(gregorian.resume(&checkpoint, &Utc).is_err());
+        // Changing a field keeps the calendar system.
+        let hours = schedule.with_hours("10").unwrap();
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 2, 26, 10, 0, 0).unwrap()),
+            hours.after(&after).next()
+        );
+        let error = schedule.with_days_of_month("15W").unwrap_err();
+        assert_eq!(ErrorCode::InvalidSpecial, error.code());
+
+        for expression in [
+            "0 0 9 15W * ?",
+            "0 0 9 LW * ?",
+            "0 0 9 ? * FRI#3",
+            "0 0 9 ? * 6L",
+        ] {
+            let error = Schedule::from_str(expression)
+                .unwrap()
+                .in_calendar_system(FourWeekMonths)
+                .unwrap_err();
+            assert_eq!(ErrorCode::InvalidSpecial, error.code());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "icu")]
+    fn test_icu_calendars() {
+        use cron::calendar::IcuCalendar;
+
+        let after = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        // Rosh Hashanah, the first day of Tishrei.
+        let new_year = Schedule::from_str("0 0 0 1 1 ?")
+            .unwrap()
+            .in_calendar_system(IcuCalendar::hebrew())
+            .unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 9, 12, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2027, 10, 2, 0, 0, 0).unwrap(),
+            ],
+            new_year.after(&after).take(2).collect::<Vec<_>>()
+        );
+        // The first and last days of Ramadan.
+        let ramadan = Schedule::from_str("0 0 0 1,L 9 ?")
+            .unwrap()
+            .in_calendar_system(IcuCalendar::hijri())
+            .unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 2, 18, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 19, 0, 0, 0).unwrap(),
+            ],
+            ramadan.after(&after).take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_solar_schedules() {
+        use cron::{SolarEvent, SolarProvider, SolarSchedule, SunriseEquation};
+
+        // The sun rises at 6:00 and sets at 18:00 UTC, except on Sundays when it doesn't rise.
+        #[derive(Debug)]
+        struct Equinox;
+
+        impl SolarProvider for Equinox {
+            fn event_on(
+                &self,
+                event: SolarEvent,
+                date: NaiveDate,
+                _latitude: f64,
+                _longitude: f64,
+            ) -> Option<DateTime<Utc>> {
+                let hour = match event {
+                    SolarEvent::Sunrise if date.weekday() == Weekday::Sun => return None,
+                    SolarEvent::Sunrise => 6,
+                    SolarEvent::Sunset => 18,
+                };
+                Some(date.and_hms_milli_opt(hour, 0, 0, 500)?.and_utc())
+            }
+        }
+
+        let sunrise = SolarSchedule::parse("@sunrise+1h30m", 0.0, 0.0, Equinox).unwrap();
+        assert_eq!(SolarEvent::Sunrise, sunrise.event());
+        assert_eq!(Duration::minutes(90), sunrise.offset());
+        // Saturday the 17th of October 2026.
+        let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 7, 30, 0).unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 19, 7, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 20, 7, 30, 0).unwrap(),
+            ],
+            sunrise.after(&saturday).take(2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 7, 30, 0).unwrap()),
+            sunrise.after(&saturday).next_back()
+        );
+        assert!(sunrise.includes(saturday));
+        assert!(!sunrise.includes(saturday + Duration::seconds(1)));
+
+        // Offsets may move occurrences to another day.
+        let sunset = SolarSchedule::parse("@sunset-1d", 0.0, 0.0, Equinox).unwrap();
+        let paris = Tz::Europe__Paris
+            .with_ymd_and_hms(2026, 10, 17, 12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            Some(
+                Tz::Europe__Paris
+                    .with_ymd_and_hms(2026, 10, 17, 20, 0, 0)
+                    .unwrap()
+            ),
+            sunset.after(&paris).next()
+        );
+        assert_eq!("@sunset-1d", sunset.to_string());
+
+        // The equation at the location of Paris and in the polar night.
+        let paris = SolarSchedule::parse("@sunrise", 48.8566, 2.3522, SunriseEquation).unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 12, 21, 0, 0, 0).unwrap();
+        let next = paris.after(&start).next().unwrap();
+        assert_eq!((7, 42), (next.hour(), next.minute()));
+        let svalbard = SolarSchedule::parse("@sunrise", 78.22, 15.65, SunriseEquation).unwrap();
+        let polar_night = Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+        let next = svalbard.after(&polar_night).next().unwrap();
+        assert_eq!((2027, 2), (next.year(), next.month()));
+
+        for expression in ["@sunrise+", "@sunrise30m", "@noon", "@sunset-1x"] {
+            let error = SolarSchedule::parse(expression, 0.0, 0.0, Equinox).unwrap_err();
+            assert_eq!(ErrorCode::Syntax, error.code());
+        }
+        let error = SolarSchedule::parse("@sunset", 91.0, 0.0, Equinox).unwrap_err();
+        assert_eq!(ErrorCode::FieldOutOfRange, error.code());
+    }
+
+    #[test]
+    fn test_windowed_schedules() {
+        use cron::{Window, WindowedSchedule};
+
+        let hours = WindowedSchedule::from_str("0 0 9 * * MON-FRI for 8h30m # office").unwrap();
+        assert_eq!(Duration::minutes(510), hours.duration());
+        assert_eq!(Some("office"), hours.schedule().comment());
+        assert_eq!("0 0 9 * * MON-FRI for 8h30m # office", hours.to_string());
+        // Friday the 16th of October 2026.
+        let friday = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let today = Window {
+            start: Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2026, 10, 16, 17, 30, 0).unwrap(),
+        };
+        assert_eq!(Some(today.clone()), hours.window_at(&friday));
+        assert_eq!(Some(today.clone()), hours.window_at(&today.start));
+        assert_eq!(None, hours.window_at(&today.end));
+        assert!(hours.contains(&friday));
+        assert!(!hours.contains(&Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap()));
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 20, 9, 0, 0).unwrap(),
+            ],
+            hours
+                .windows_after(&friday)
+                .take(2)
+                .map(|window| window.start)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(today), hours.windows_after(&friday).next_back());
+
+        // Overlapping windows, where instants belong to the latest one.
+        let overlapping = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse_windowed("0 * * * * FOR 90m")
+            .unwrap();
+        let window = overlapping.window_at(&friday).unwrap();
+        assert_eq!(friday, window.start);
+        assert_eq!(Duration::minutes(90), window.duration());
+
+        for expression in [
+            "0 0 9 * * *",
+            "0 0 9 * * * for",
+            "0 0 9 * * * during 8h",
+            "for 8h",
+        ] {
+            let error = WindowedSchedule::from_str(expression).unwrap_err();
+            assert_eq!(ErrorCode::Syntax, error.code());
+        }
+        let error = WindowedSchedule::from_str("0 0 9 * * * for 0s").unwrap_err();
+        assert_eq!(ErrorCode::InvalidRange, error.code());
+    }
+
+    #[test]
+    fn test_business_days() {
+        use cron::{DayOfMonthSpec, WeeklyCalendar};
+
+        let schedule = Schedule::from_str("0 0 9 3B * ?").unwrap();
+        assert_eq!(
+            &[DayOfMonthSpec::BusinessDay { nth: 3 }],
+            schedule.day_of_month_specs()
+        );
+        // The 1st of October 2026 is a Thursday.
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 5, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 11, 4, 9, 0, 0).unwrap(),
+            ],
+            schedule.after(&start).take(2).collect::<Vec<_>>()
+        );
+
+        // Holidays push the business day back, and the days they push it from don't fire.
+        let holidays = AnnualCalendar::new().excluding(10, 2).excluding(11, 2);
+        let schedule = schedule.with_calendar(holidays);
+        let days = |schedule: &Schedule| {
+            schedule
+                .after(&start)
+                .take(3)
+                .map(|date_time| (date_time.month(), date_time.day()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![(10, 6), (11, 5), (12, 3)], days(&schedule));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2026, 10, 5, 9, 0, 0).unwrap()));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 10, 6, 9, 0, 0).unwrap()),
+            schedule
+                .after(&Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap())
+                .next_back()
+        );
+        // Replacing a field keeps resolving business days against the calendars.
+        let schedule = schedule.with_hours("12").unwrap();
+        assert_eq!(vec![(10, 6), (11, 5), (12, 3)], days(&schedule));
+
+        // Other days of the field still fire, and calendars excluding weekdays skip them too.
+        let schedule = Schedule::from_str("0 0 9 1,1b * ?")
+            .unwrap()
+            .with_calendar(WeeklyCalendar::new().excluding(Weekday::Mon));
+        assert_eq!(vec![(10, 1), (11, 1), (11, 3)], days(&schedule));
+
+        // Quartz has no business days, so the field is widened.
+        let quartz = Schedule::from_str("0 0 9 3B * ?").unwrap().to_quartz();
+        assert_eq!("0 0 9 * * ?", quartz.expression);
+        assert!(!quartz.untranslatable.is_empty());
+        for expression in ["0 0 9 0B * ?", "0 0 9 24B * ?"] {
+            let error = Schedule::from_str(expression).unwrap_err();
+            assert_eq!(ErrorCode::InvalidSpecial, error.code());
+        }
+        assert!(Schedule::from_str("0 0 9 ? * 3B").is_err());
+    }
+
+    #[test]
+    fn test_coverage_gaps() {
+        let mut checks = ScheduleSet::new();
+        checks.insert("hourly", Schedule::from_str("0 0 * * * *").unwrap());
+        checks.insert("nightly", Schedule::from_str("0 0 2 * * *").unwrap());
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 20, 0).unwrap();
+        // The earliest of the hour long gaps.
+        let gap = checks.longest_gap(&start, Duration::hours(6)).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap()
+                ..Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap(),
+            gap
+        );
+        // The stretch from the start to the first firing counts as a gap.
+        let gap = checks.longest_gap(&start, Duration::minutes(50)).unwrap();
+        assert_eq!(
+            start..Utc.with_ymd_and_hms(2026, 10, 16, 1, 0, 0).unwrap(),
+            gap
+        );
+
+        // An hourly job which stops for the night leaves a gap until it resumes.
+        checks.remove(&"hourly");
+        checks.insert("daytime", Schedule::from_str("0 0 8-20 * * *").unwrap());
+        let gap = checks.longest_gap(&start, Duration::days(2)).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap()
+                ..Utc.with_ymd_and_hms(2026, 10, 16, 8, 0, 0).unwrap(),
+            gap
+        );
+
+        // The stretch after the last firing ends at the horizon, and an empty set never fires.
+        let gap = checks.longest_gap(&start, Duration::minutes(30)).unwrap();
+        assert_eq!(Duration::minutes(30), gap.end - gap.start);
+        let empty = ScheduleSet::<&str>::new();
+        assert_eq!(
+            Some(start..start + Duration::days(1)),
+            empty.longest_gap(&start, Duration::days(1))
+        );
+        assert_eq!(None, empty.longest_gap(&start, Duration::MAX));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let mut jobs = ScheduleSet::new();
+        jobs.insert("etl", Schedule::from_str("0 0 1 * * *").unwrap());
+        jobs.insert("report", Schedule::from_str("0 30 1 * * *").unwrap());
+        jobs.insert("cleanup", Schedule::from_str("0 45 1 * * *").unwrap());
+        jobs.insert("heartbeat", Schedule::from_str("0 0 * * * *").unwrap());
+        let durations = |job: &&str| match *job {
+            "etl" => Duration::hours(1),
+            "report" => Duration::minutes(30),
+            "cleanup" => Duration::minutes(5),
+            _ => Duration::zero(),
+        };
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let at = |hour, minute| Utc.with_ymd_and_hms(2026, 10, 16, hour, minute, 0).unwrap();
+        let overlaps = jobs.overlaps(&start, Duration::hours(12), durations);
+        let summary: Vec<_> = overlaps
+            .iter()
+            .map(|overlap| {
+                let keys: Vec<_> = overlap.keys().copied().collect();
+                (overlap.start, overlap.duration(), keys)
+            })
+            .collect();
+        // The heartbeat takes no time, so it never overlaps the other jobs.
+        assert_eq!(
+            vec![
+                (at(1, 30), Duration::minutes(15), vec!["etl", "report"]),
+                (
+                    at(1, 45),
+                    Duration::minutes(5),
+                    vec!["etl", "report", "cleanup"]
+                ),
+                (at(1, 50), Duration::minutes(10), vec!["etl", "report"]),
+            ],
+            summary
+        );
+        assert_eq!(
+            vec![(&"etl", at(1, 0)), (&"report", at(1, 30))],
+            overlaps[0].runs
+        );
+
+        // Runs started within the horizon go on past it.
+        let overlaps = jobs.overlaps(&start, Duration::minutes(90), durations);
+        assert_eq!(at(2, 0), overlaps[0].end);
+        assert!(jobs
+            .overlaps(&start, Duration::minutes(60), durations)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_simulate() {
+        let mut jobs = ScheduleSet::new();
+        jobs.insert("hourly", Schedule::from_str("0 0 * * * *").unwrap());
+        jobs.insert("nightly", Schedule::from_str("0 0 2 * * *").unwrap());
+        jobs.insert("never", Schedule::from_str("0 0 0 30 2 *").unwrap());
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let simulation = jobs.simulate(&start, Duration::days(2), 4);
+        assert_eq!(
+            vec![(&"hourly", 48), (&"nightly", 2), (&"never", 0)],
+            simulation.counts
+        );
+        let at = |hour| Utc.with_ymd_and_hms(2026, 10, 16, hour, 0, 0).unwrap();
+        assert_eq!(
+            vec![
+                (&"hourly", at(1)),
+                (&"hourly", at(2)),
+                (&"nightly", at(2)),
+                (&"hourly", at(3)),
+            ],
+            simulation.sample
+        );
+        assert_eq!(50, simulation.histogram.total);
+        assert_eq!(4, simulation.histogram.hours[2]);
+
+        let empty = jobs.simulate(&start, Duration::MAX, 4);
+        assert_eq!(0, empty.histogram.total);
+        assert!(empty.sample.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_dialect() {
+        let parser = ScheduleParser::new().dialect(Dialect::Legacy);
+        let schedule = parser.parse("0 30 9 ? * 2-6 2026").unwrap();
+        assert_eq!(
+            vec![2, 3, 4, 5, 6],
+            schedule.days_of_week().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![2026], schedule.years().iter().collect::<Vec<_>>());
+        assert!(parser.parse("0 0 12 1 * Sun").is_ok());
+        assert!(parser.parse("@yearly").is_ok());
+        assert!(parser.parse("@daily").is_ok());
+
+        assert!(parser.parse("@quarterly").is_err());
+        assert!(parser.parse("@yearend").is_err());
+        assert!(parser.parse("0 0 12 L * ?").is_err());
+        assert!(parser.parse("0 0 12 ? * 6#3").is_err());
+        assert!(parser.parse("0 0 12 1 Q1 ?").is_err());
+        assert!(parser.parse("0 0 12 * * 0").is_err());
+        assert!(parser.parse("0 0 12 * * * # noon").is_err());
+        let error = parser.parse("0 ~ 12 * * *").unwrap_err();
+        assert_eq!(ErrorCode::Syntax, error.code());
+        assert!(parser.parse("0 H 12 * * *").is_err());
+        assert!(ScheduleParser::new().parse("0 ~ 12 * * *").is_ok());
+    }
+
+    #[test]
+    fn test_schedule_formatter() {
+        let schedule = Schedule::from_str("0 0/15 9-17 ? Jan,Jul 2-6").unwrap();
+        let formatter = ScheduleFormatter::new();
+        assert_eq!(
+            "0 */15 9-17 * 1,7 2-6",
+            formatter.format(&schedule).expression
+        );
+        let named = formatter.clone().names(true).format(&schedule);
+        assert_eq!("0 */15 9-17 * JAN,JUL MON-FRI", named.expression);
+        let five = formatter.clone().field_count(FieldCount::Five);
+        let translation = five.format(&schedule);
+        assert!(translation.is_exact());
+        assert_eq!("*/15 9-17 * 1,7 1-5", translation.expression);
+        let seven = formatter.clone().field_count(FieldCount::Seven);
+        assert_eq!(
+            "0 */15 9-17 * 1,7 2-6 *",
+            seven.format(&schedule).expression
+        );
+
+        // Shifted steps are written over a range where their start would be ambiguous.
+        let schedule = Schedule::from_str("0 0 12 * 2/3 2/2").unwrap();
+        assert_eq!("0 0 12 * 2/3 2/2", formatter.format(&schedule).expression);
+        assert_eq!("0 12 * 2/3 1-5/2", five.format(&schedule).expression);
+        let named = formatter.clone().names(true).format(&schedule);
+        assert_eq!("0 0 12 * FEB-NOV/3 MON-FRI/2", named.expression);
+        let reparsed = Schedule::from_str(&named.expression).unwrap();
+        assert!(schedule.timeunitspec_eq(&reparsed));
+
+        let schedule = Schedule::from_str("30 0 12 L * 6#3 2027").unwrap();
+        let named = formatter.clone().names(true).format(&schedule);
+        assert_eq!("30 0 12 L * FRI#3", named.expression);
+        assert_eq!(1, named.untranslatable.len());
+        assert_eq!("30 0 12 L * 6#3 2027", seven.format(&schedule).expression);
+        let translation = five.format(&schedule);
+        assert_eq!("0 12 * * *", translation.expression);
+        assert_eq!(4, translation.untranslatable.len());
+
+        let unix = ScheduleParser::new().dialect(Dialect::Unix);
+        let either = unix.parse("0 12 1 * 1").unwrap();
+        assert!(five.format(&either).is_exact());
+        assert_eq!("0 12 1 * 1", five.format(&either).expression);
+        let translation = formatter.format(&either);
+        assert_eq!("0 0 12 * * *", translation.expression);
+        assert!(!translation.is_exact());
+        let both = Schedule::from_str("0 0 12 1 * 2").unwrap();
+        assert_eq!("0 12 1 * *", five.format(&both).expression);
+
+        let nicknames = formatter.clone().nicknames(true);
+        for nickname in ["@yearly", "@monthly", "@weekly", "@daily", "@hourly"] {
+            let schedule = Schedule::from_str(nickname).unwrap();
+            assert_eq!(nickname, nicknames.format(&schedule).expression);
+        }
+        let weekly = unix.parse("0 0 * * 0").unwrap();
+        assert_eq!("@weekly", nicknames.format(&weekly).expression);
+        let quarterly = Schedule::from_str("@quarterly").unwrap();
+        assert_eq!("0 0 0 1 */3 *", nicknames.format(&quarterly).expression);
+        assert_eq!(
+            "0 0 0 * * *",
+            formatter
+                .format(&Schedule::from_str("@daily").unwrap())
+                .expression
+        );
+
+        // Every year of a widened range fits six fields, while seven fields write out the years
+        // of a range other than the default one.
+        let widened = ScheduleParser::new().year_range(1900..=2200);
+        let translation = formatter.format(&widened.parse("@daily").unwrap());
+        assert!(translation.is_exact());
+        assert_eq!("0 0 0 * * *", translation.expression);
+        let narrowed = ScheduleParser::new().year_range(2000..=2130);
+        let daily = narrowed.parse("@daily").unwrap();
+        assert_eq!("0 0 0 * * * 2000-2130", seven.format(&daily).expression);
+        let daily = Schedule::from_str("@daily").unwrap();
+        assert_eq!("0 0 0 * * * *", seven.format(&daily).expression);
+    }
+
+    #[test]
+    fn test_shape_predicates() {
+        let shape = |expression: &str| {
+            let schedule = Schedule::from_str(expression).unwrap();
+            [
+                schedule.is_yearly(),
+                schedule.is_monthly(),
+                schedule.is_weekly(),
+                schedule.is_daily(),
+                schedule.is_hourly(),
+            ]
+        };
+        assert_eq!([true, false, false, false, false], shape("0 0 0 1 Jan ?"));
+        assert_eq!([false, true, false, false, false], shape("@monthly"));
+        assert_eq!([false, false, true, false, false], shape("0 0 0 ? * Sun"));
+        assert_eq!([false, false, false, true, false], shape("0 0 0 * * *"));
+        assert_eq!([false, false, false, false, true], shape("0 0 0-23 * * ?"));
+        assert_eq!([false; 5], shape("@quarterly"));
+        assert_eq!([false; 5], shape("0 0 0 * * * 2027"));
+        assert_eq!([false; 5], shape("30 0 0 * * *"));
+
+        let weekly = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 0 * * 0")
+            .unwrap();
+        assert!(weekly.is_weekly());
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let bounded = Schedule::from_str("@daily")
+            .unwrap()
+            .with_not_before(&start);
+        assert!(!bounded.is_daily());
+
+        let every = |expression: &str| Schedule::from_str(expression).unwrap().every_n_minutes();
+        assert_eq!(Some(1), every("0 * * * * *"));
+        assert_eq!(Some(5), every("0 */5 * * * ?"));
+        assert_eq!(Some(20), every("0 0,20,40 * * * *"));
+        assert_eq!(None, every("0 */7 * * * *"));
+        assert_eq!(None, every("0 5/15 * * * *"));
+        assert_eq!(None, every("0 */5 9-17 * * *"));
+        assert_eq!(None, every("*/30 */5 * * * *"));
+        assert_eq!(None, every("@hourly"));
+
+        // Every year of a widened year range, though not of one narrowed by the years field.
+        let parser = ScheduleParser::new().year_range(1900..=2200);
+        let schedule = |expression| parser.parse(expression).unwrap();
+        assert!(schedule("@daily").is_daily());
+        assert!(!schedule("@daily").is_year_restricted());
+        assert_eq!(Some(1), schedule("0 * * * * *").every_n_minutes());
+        assert!(!schedule("0 0 0 * * * 2000-2200").is_daily());
+        assert!(schedule("0 0 0 * * * 2000-2200").is_year_restricted());
+    }
+
+    #[test]
+    fn test_typed_field_values() {
+        let schedule = Schedule::from_str("0 0 12 * * ?").unwrap();
+        let weekdays = |schedule: &Schedule| schedule.days_of_week().iter().collect::<Vec<_>>();
+        let single = schedule.with_weekdays(Weekday::Wed).unwrap();
+        assert_eq!("0 0 12 * * WED", single.source());
+        assert_eq!(vec![4], weekdays(&single));
+        let weekend = schedule.with_weekdays(Weekday::Fri..=Weekday::Mon).unwrap();
+        assert_eq!("0 0 12 * * SUN,MON,FRI,SAT", weekend.source());
+        let slice: &[Weekday] = &[Weekday::Tue, Weekday::Mon, Weekday::Tue];
+        assert_eq!(
+            vec![2, 3],
+            weekdays(&schedule.with_weekdays(slice).unwrap())
+        );
+        assert!(schedule.with_weekdays(Vec::new()).is_err());
+
+        let summer = schedule
+            .with_months_of_year(Month::June..=Month::August)
+            .unwrap();
+        assert_eq!("0 0 12 * JUN-AUG ?", summer.source());
+        let winter = schedule
+            .with_months_of_year(vec![Month::December, Month::January])
+            .unwrap();
+        assert_eq!(vec![1, 12], winter.months().iter().collect::<Vec<_>>());
+
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 12 * * *")
+            .unwrap();
+        let sundays = unix.with_weekdays([Weekday::Sun]).unwrap();
+        assert_eq!("0 12 * * SUN", sundays.source());
+        assert_eq!(vec![1], weekdays(&sundays));
+
+        let aws = ScheduleParser::new()
+            .dialect(Dialect::Aws)
+            .parse("0 12 1 * ? *")
+            .unwrap();
+        let error = aws.with_weekdays(Weekday::Mon).unwrap_err();
+        assert_eq!(ErrorCode::DayFieldConflict, error.code());
+    }
+
+    #[test]
+    fn test_chrono_weekdays_and_months() {
+        let schedule = Schedule::from_str("0 0 12 ? Jan,Feb,Nov,Dec Sat,Sun").unwrap();
+        assert_eq!(
+            vec![
+                Month::January,
+                Month::February,
+                Month::November,
+                Month::December
+            ],
+            schedule.months_of_year()
+        );
+        assert_eq!(vec![Weekday::Sun, Weekday::Sat], schedule.weekdays());
+        assert!(schedule.includes_month(Month::December));
+        assert!(!schedule.includes_month(Month::March));
+        assert!(schedule.includes_weekday(Weekday::Sat));
+        assert!(!schedule.includes_weekday(Weekday::Mon));
+
+        let unix = ScheduleParser::new()
+            .dialect(Dialect::Unix)
+            .parse("0 12 * * 0,5")
+            .unwrap();
+        assert_eq!(vec![Weekday::Sun, Weekday::Fri], unix.weekdays());
+        assert_eq!(12, unix.months_of_year().len());
+
+        let third_friday = Schedule::from_str("0 0 12 ? * 6#3").unwrap();
+        assert!(third_friday.weekdays().is_empty());
+        assert!(!third_friday.includes_weekday(Weekday::Fri));
+        let spec = third_friday.day_of_week_specs()[0];
+        assert_eq!(Some(Weekday::Fri), spec.chrono_weekday());
+    }
+
+    #[test]
+    fn test_system_time_queries() {
+        use std::time::{Duration as StdDuration, SystemTime};
+
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let at = |date_time: DateTime<Utc>| SystemTime::from(date_time);
+        // Friday, 2026-10-16
+        let friday = at(Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap());
+        let monday = at(Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap());
+        assert_eq!(Some(monday), schedule.next_after_system_time(friday));
+        assert_eq!(
+            Some(at(Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap())),
+            schedule.prev_before_system_time(friday)
+        );
+        let upcoming: Vec<_> = schedule.after_system_time(friday).take(2).collect();
+        assert_eq!(monday + StdDuration::from_secs(86_400), upcoming[1]);
+        assert!(schedule.includes_system_time(monday));
+        assert!(!schedule.includes_system_time(friday));
+
+        // Sub-second times are read as the second they fall in.
+        assert!(schedule.includes_system_time(monday + StdDuration::from_millis(1)));
+        let just_before = monday - StdDuration::from_millis(500);
+        assert_eq!(Some(monday), schedule.next_after_system_time(just_before));
+        assert_eq!(
+            Some(monday + StdDuration::from_secs(86_400)),
+            schedule.next_after_system_time(monday + StdDuration::from_millis(500))
+        );
+
+        // Times before 1970 are read in the second they fall in too.
+        let wide = ScheduleParser::new().year_range(1900..=2100);
+        let new_year_eve = wide.parse("59 59 23 31 12 ?").unwrap();
+        let before_epoch = SystemTime::UNIX_EPOCH - StdDuration::from_millis(500);
+        assert!(new_year_eve.includes_system_time(before_epoch));
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH - StdDuration::from_secs(1)),
+            new_year_eve.prev_before_system_time(before_epoch)
+        );
+
+        // Times chrono can't represent are never included, rather than panicking.
+        let far = StdDuration::from_secs(1 << 50);
+        for time in [
+            SystemTime::UNIX_EPOCH.checked_add(far),
+            SystemTime::UNIX_EPOCH.checked_sub(far),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            assert_eq!(None, schedule.next_after_system_time(time));
+            assert_eq!(None, schedule.prev_before_system_time(time));
+            assert_eq!(0, schedule.after_system_time(time).count());
+            assert!(!schedule.includes_system_time(time));
+        }
+    }
+
+    #[test]
+    fn test_timestamp_queries() {
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let at = |date_time: DateTime<Utc>| date_time.timestamp();
+        // Friday, 2026-10-16
+        let friday = at(Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap());
+        let monday = at(Utc.with_ymd_and_hms(2026, 10, 19, 9, 30, 0).unwrap());
+        assert_eq!(Some(monday), schedule.next_timestamp_after(friday));
+        assert_eq!(
+            Some(at(Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap())),
+            schedule.prev_timestamp_before(friday)
+        );
+        assert_eq!(Some(monday + 86_400), schedule.next_timestamp_after(monday));
+        assert!(schedule.includes_timestamp(monday));
+        assert!(!schedule.includes_timestamp(monday + 1));
+
+        assert_eq!(None, schedule.next_timestamp_after(i64::MAX));
+        assert_eq!(None, schedule.prev_timestamp_before(i64::MIN));
+        assert!(!schedule.includes_timestamp(i64::MAX));
+        let once = Schedule::from_str("0 0 0 1 1 ? 2020").unwrap();
+        assert_eq!(None, once.next_timestamp_after(friday));
+    }
+
+    #[test]
+    fn test_gaps_after() {
+        let schedule = Schedule::from_str("0 0 9,17 * * Mon-Fri").unwrap();
+        // Friday, 2026-10-16
+        let friday = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        let gaps: Vec<_> = schedule.gaps_after(&friday).take(3).collect();
+        assert_eq!(
+            vec![
+                (
+                    Utc.with_ymd_and_hms(2026, 10, 16, 17, 0, 0).unwrap(),
+                    Duration::hours(8)
+                ),
+                (
+                    Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap(),
+                    Duration::hours(64)
+                ),
+                (
+                    Utc.with_ymd_and_hms(2026, 10, 19, 17, 0, 0).unwrap(),
+                    Duration::hours(8)
+                ),
+            ],
+            gaps
+        );
+
+        // Gaps are elapsed time, so they shrink when the clocks go forward.
+        let daily = Schedule::from_str("0 0 12 * * *").unwrap();
+        let before = Tz::Europe__Berlin
+            .with_ymd_and_hms(2026, 3, 28, 13, 0, 0)
+            .unwrap();
+        let (next, gap) = daily.gaps_after(&before).next().unwrap();
+        assert_eq!(29, next.day());
+        assert_eq!(Duration::hours(23), gap);
+
+        // The first occurrence of a schedule has nothing to measure from.
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let bounded = daily.clone().with_not_before(&start);
+        let (first, gap) = bounded.gaps_after(&start).next().unwrap();
+        assert_eq!(17, first.day());
+        assert_eq!(Duration::days(1), gap);
+        let once = Schedule::from_str("0 0 12 16 10 ? 2026").unwrap();
+        assert!(once.gaps_after(&start).next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_checkpoints() {
+        let schedule = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        // Friday, 2026-10-16
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let mut upcoming = schedule.after(&start);
+        let first = upcoming.next().unwrap();
+        let checkpoint = upcoming.checkpoint().unwrap();
+        assert_eq!(first, checkpoint.instant());
+
+        let saved = checkpoint.to_string();
+        let restored = Checkpoint::from_str(&saved).unwrap();
+        assert_eq!(checkpoint, restored);
+        let again = Schedule::from_str("0 30 9 * * Mon-Fri").unwrap();
+        let resumed: Vec<_> = again.resume(&restored, &Utc).unwrap().take(3).collect();
+        let expected: Vec<_> = upcoming.take(3).collect();
+        assert_eq!(expected, resumed);
+
+        let mut backwards = schedule.resume(&restored, &Utc).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap(),
+            backwards.next_back().unwrap()
+        );
+        // Resuming goes forwards, so iterators going backwards don't take checkpoints.
+        assert!(backwards.checkpoint().is_none());
+        backwards.next();
+        assert!(backwards.checkpoint().is_some());
+
+        // The hash of the schedule doesn't change between releases.
+        let at_noon = schedule.after(&start).checkpoint().unwrap();
+        assert_eq!("1792152000:a92d1564fa219747", at_noon.to_string());
+
+        // The instant keeps its fraction of a second.
+        let instant =
+            Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap() + Duration::milliseconds(250);
+        let fractional = schedule.after(&instant).checkpoint().unwrap();
+        assert!(fractional.to_string().starts_with("1792152000.25:"));
+        assert_eq!(
+            fractional,
+            Checkpoint::from_str(&fractional.to_string()).unwrap()
+        );
+
+        let other = Schedule::from_str("0 30 10 * * Mon-Fri").unwrap();
+        let Err(error) = other.resume(&restored, &Utc) else {
+            panic!("The checkpoint was taken from another schedule.");
+        };
+        assert_eq!(ErrorCode::CheckpointMismatch, error.code());
+        assert_eq!("E014", error.code().as_str());
+
+        for invalid in [
+            "",
+            "1792152000",
+            "x:0011223344556677",
+            "1.:0011223344556677",
+            "1:00",
+        ] {
+            let error = Checkpoint::from_str(invalid).unwrap_err();
+            assert_eq!(ErrorCode::Decode, error.code());
+        }
+
+        let once = Schedule::from_str("0 0 0 1 1 ? 2020").unwrap();
+        let mut exhausted = once.after(&start);
+        assert!(exhausted.next().is_none());
+        assert!(exhausted.checkpoint().is_none());
+    }
+
+    #[test]
+    fn test_tick_claiming() {
+        let schedule = Schedule::from_str("0 * * * * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let occurrences: Vec<_> = schedule.after(&start).take(1200).collect();
+
+        let mut counts = [0; 4];
+        for occurrence in &occurrences {
+            let owner = schedule.owner_of(occurrence, 4).unwrap();
+            assert_eq!(Some(owner), schedule.owner_of(occurrence, 4));
+            assert_eq!(
+                1,
+                (0..4)
+                    .filter(|worker| schedule.is_owned_by(occurrence, *worker, 4))
+                    .count()
+            );
+            counts[owner as usize] += 1;
+
+            // Adding a fourth worker only takes occurrences over from the first three.
+            let previous = schedule.owner_of(occurrence, 3).unwrap();
+            assert!(owner == previous || owner == 3);
+            assert_eq!(Some(0), schedule.owner_of(occurrence, 1));
+            // Without workers, nobody owns it.
+            assert_eq!(None, schedule.owner_of(occurrence, 0));
+            assert!(!schedule.is_owned_by(occurrence, 0, 0));
+        }
+        assert!(counts.iter().all(|count| (200..400).contains(count)));
+
+        // The owner is the same in every timezone, but depends on the schedule.
+        let occurrence = occurrences[0];
+        assert_eq!(
+            schedule.owner_of(&occurrence, 16),
+            schedule.owner_of(&occurrence.with_timezone(&Tz::Europe__Berlin), 16)
+        );
+        // Nor does it change between releases, as it doesn't depend on the binary encoding.
+        assert_eq!(Some(771), schedule.owner_of(&occurrence, 1000));
+        let other = Schedule::from_str("0 * * * * * 2026").unwrap();
+        assert!(occurrences
+            .iter()
+            .any(|occurrence| schedule.owner_of(occurrence, 16) != other.owner_of(occurrence, 16)));
+    }
+
+    #[test]
+    fn test_next_within() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        let schedule = Schedule::from_str("0 0 0 1 1 ?").unwrap();
+        let next = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(next, schedule.next_within(&start, next - start).unwrap());
+        let horizon = next - start - Duration::seconds(1);
+        let error = schedule.next_within(&start, horizon).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::NoUpcomingWithin(h) if *h == horizon));
+        assert!(error.to_string().contains("No upcoming time"));
+
+        // Rarely and never firing schedules fail alike within the horizon, but only the former
+        // fires given a long enough one.
+        let never = Schedule::from_str("0 0 0 30 2 ?").unwrap();
+        assert!(never
+            .next_within(&start, Duration::days(365 * 100))
+            .is_err());
+        let leap_day = Schedule::from_str("0 0 0 29 2 ?").unwrap();
+        assert!(leap_day.next_within(&start, Duration::days(365)).is_err());
+        assert!(leap_day
+            .next_within(&start, Duration::days(365 * 2))
+            .is_ok());
+
+        // Times excluded by calendars don't make the search go past the horizon.
+        let excluded = Schedule::from_str("0 * * * * ?").unwrap().with_calendar(
+            WeeklyCalendar::new()
+                .excluding(Weekday::Fri)
+                .excluding(Weekday::Sat),
+        );
+        assert!(excluded.next_within(&start, Duration::days(1)).is_err());
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap(),
+            excluded.next_within(&start, Duration::days(3)).unwrap()
+        );
+    }
 }